@@ -15,6 +15,7 @@ fn append_lorem(repeat: usize) -> usize {
                     key: Key::Char(c),
                     ctrl: false,
                     alt: false,
+                    shift: false,
                 });
                 term.draw_textarea(&textarea);
             }
@@ -23,6 +24,7 @@ fn append_lorem(repeat: usize) -> usize {
             key: Key::Enter,
             ctrl: false,
             alt: false,
+            shift: false,
         });
         term.draw_textarea(&textarea);
     }
@@ -45,6 +47,7 @@ fn random_lorem(repeat: usize) -> usize {
                 key: Key::Enter,
                 ctrl: false,
                 alt: false,
+                shift: false,
             });
             term.draw_textarea(&textarea);
 
@@ -53,6 +56,7 @@ fn random_lorem(repeat: usize) -> usize {
                     key: Key::Char(c),
                     ctrl: false,
                     alt: false,
+                    shift: false,
                 });
                 term.draw_textarea(&textarea);
             }
@@ -74,6 +78,7 @@ fn append_long_lorem(repeat: usize) -> usize {
                     key: Key::Char(c),
                     ctrl: false,
                     alt: false,
+                    shift: false,
                 });
                 term.draw_textarea(&textarea);
             }
@@ -83,6 +88,21 @@ fn append_long_lorem(repeat: usize) -> usize {
     textarea.lines().len()
 }
 
+#[inline]
+fn paste_lorem(repeat: usize) -> usize {
+    let mut textarea = TextArea::default();
+    let mut term = dummy_terminal();
+    let text = LOREM.join("\n");
+
+    for _ in 0..repeat {
+        textarea.insert_str(&text);
+        textarea.insert_newline();
+    }
+    term.draw_textarea(&textarea);
+
+    textarea.lines().len()
+}
+
 fn append(c: &mut Criterion) {
     c.bench_function("insert::append::1_lorem", |b| {
         b.iter(|| black_box(append_lorem(1)))
@@ -107,6 +127,19 @@ fn random(c: &mut Criterion) {
     });
 }
 
+// Pasting many lines at once with `TextArea::insert_str` should be done in linear time
+fn paste(c: &mut Criterion) {
+    c.bench_function("insert::paste::10_lorem", |b| {
+        b.iter(|| black_box(paste_lorem(10)))
+    });
+    c.bench_function("insert::paste::100_lorem", |b| {
+        b.iter(|| black_box(paste_lorem(100)))
+    });
+    c.bench_function("insert::paste::1000_lorem", |b| {
+        b.iter(|| black_box(paste_lorem(1000)))
+    });
+}
+
 // Inserting a long line is slower than multiple short lines into `TextArea`
 fn long(c: &mut Criterion) {
     c.bench_function("insert::long::1_lorem", |b| {
@@ -120,5 +153,5 @@ fn long(c: &mut Criterion) {
     });
 }
 
-criterion_group!(insert, append, random, long);
+criterion_group!(insert, append, random, long, paste);
 criterion_main!(insert);
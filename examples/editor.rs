@@ -1,4 +1,6 @@
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+};
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
@@ -157,7 +159,12 @@ impl<'a> Editor<'a> {
         }
         let mut stdout = io::stdout();
         enable_raw_mode()?;
-        crossterm::execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        crossterm::execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
         let backend = CrosstermBackend::new(stdout);
         let term = Terminal::new(backend)?;
         Ok(Self {
@@ -307,7 +314,15 @@ impl<'a> Editor<'a> {
                     }
                 }
             } else {
-                match crossterm::event::read()?.into() {
+                let event = crossterm::event::read()?;
+                if let Event::Paste(text) = &event {
+                    // Terminals may send newlines as `\r` in bracketed paste
+                    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+                    let buffer = &mut self.buffers[self.current];
+                    buffer.modified |= buffer.textarea.insert_str(text);
+                    continue;
+                }
+                match event.into() {
                     Input {
                         key: Key::Char('q'),
                         ctrl: true,
@@ -356,7 +371,8 @@ impl<'a> Drop for Editor<'a> {
         crossterm::execute!(
             self.term.backend_mut(),
             LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableMouseCapture,
+            DisableBracketedPaste
         )
         .unwrap();
     }
@@ -39,7 +39,7 @@ fn main() -> io::Result<()> {
     textarea.set_cursor_line_style(Style::default());
     textarea.set_placeholder_text("Enter a valid float (e.g. 1.56)");
     let layout =
-        Layout::default().constraints([Constraint::Length(3), Constraint::Min(1)].as_slice());
+        Layout::default().constraints([Constraint::Length(3), Constraint::Min(1)].as_ref());
     let mut is_valid = validate(&mut textarea);
 
     loop {
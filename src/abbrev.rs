@@ -0,0 +1,72 @@
+use crate::util::byte_offset;
+use crate::word::find_word_head;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default)]
+pub struct Abbreviations {
+    table: HashMap<String, String>,
+}
+
+impl Abbreviations {
+    pub fn insert(&mut self, abbr: String, expansion: String) {
+        self.table.insert(abbr, expansion);
+    }
+
+    pub fn remove(&mut self, abbr: &str) -> bool {
+        self.table.remove(abbr).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    pub fn get(&self, abbr: &str) -> Option<&str> {
+        self.table.get(abbr).map(String::as_str)
+    }
+
+    // Find the abbreviation which ends at `col` in the line. Returns the start column of the abbreviation and its
+    // expansion.
+    pub fn find(&self, line: &str, col: usize) -> Option<(usize, &str)> {
+        if self.table.is_empty() || col == 0 {
+            return None;
+        }
+
+        let start_col = find_word_head(line, col);
+        if start_col == col {
+            return None;
+        }
+
+        let start = byte_offset(line, start_col);
+        let end = byte_offset(line, col);
+        let expansion = self.get(&line[start..end])?;
+        Some((start_col, expansion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_abbreviation() {
+        let mut a = Abbreviations::default();
+        a.insert("teh".into(), "the".into());
+        a.insert("あい".into(), "愛".into());
+
+        let tests = [
+            ("teh", 3, Some((0, "the"))),
+            ("see teh", 7, Some((4, "the"))),
+            ("(teh", 4, Some((1, "the"))),
+            ("teh", 2, None),
+            ("steh", 4, None),
+            ("teh ", 4, None),
+            ("", 0, None),
+            ("あい", 2, Some((0, "愛"))),
+        ];
+
+        for test in tests {
+            let (line, col, want) = test;
+            assert_eq!(a.find(line, col), want, "{:?}", test);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::ratatui::layout::Alignment;
+use crate::width::{str_width, AmbiguousWidth};
+
+// Align the text of the line within the width by padding spaces before it. Whitespaces around the text are removed.
+// A line wider than the width is not padded, and a blank line becomes empty
+pub fn align_line(
+    line: &str,
+    alignment: Alignment,
+    width: usize,
+    ambiguous: AmbiguousWidth,
+) -> String {
+    let text = line.trim();
+    let rest = width.saturating_sub(str_width(text, ambiguous));
+    let pad = match alignment {
+        _ if text.is_empty() => 0,
+        Alignment::Left => 0,
+        Alignment::Center => rest / 2,
+        Alignment::Right => rest,
+    };
+    format!("{}{}", " ".repeat(pad), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn align_text() {
+        let tests = [
+            ("  foo  ", Alignment::Left, 10, "foo"),
+            ("foo", Alignment::Center, 10, "   foo"),
+            ("\tfoo bar ", Alignment::Center, 10, " foo bar"),
+            ("foo", Alignment::Right, 10, "       foo"),
+            ("  foo", Alignment::Right, 3, "foo"),
+            ("foobar", Alignment::Center, 3, "foobar"),
+            ("あい", Alignment::Right, 6, "  あい"),
+            ("   ", Alignment::Right, 10, ""),
+        ];
+        for (line, alignment, width, want) in tests {
+            assert_eq!(
+                align_line(line, alignment, width, AmbiguousWidth::Narrow),
+                want,
+                "{:?} {:?} {}",
+                line,
+                alignment,
+                width,
+            );
+        }
+    }
+}
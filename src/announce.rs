@@ -0,0 +1,185 @@
+use crate::word::is_word_char;
+use std::fmt;
+use std::sync::Arc;
+
+/// Semantic event of editor activity passed to the callback registered by
+/// [`TextArea::on_announcement`](crate::TextArea::on_announcement). Accessible frontends can announce it to assistive
+/// technology such as a screen reader. Positions are pairs of 0-base row and column, and [`fmt::Display`] formats the
+/// event as an English sentence with 1-base line and column numbers.
+///
+/// This type is marked as `#[non_exhaustive]` since more events may be added in the future.
+/// ```
+/// use tui_textarea::Announcement;
+///
+/// let event = Announcement::Inserted { text: "x".to_string(), pos: (2, 6) };
+/// assert_eq!(event.to_string(), "inserted 'x' at line 3 col 7");
+///
+/// let event = Announcement::Moved { from: (0, 0), to: (9, 0) };
+/// assert_eq!(event.to_string(), "moved to line 10");
+///
+/// let event = Announcement::Selected { chars: 20, words: 4 };
+/// assert_eq!(event.to_string(), "selected 4 words");
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Announcement {
+    /// The text was inserted at the position.
+    Inserted {
+        /// Inserted text. It may contain newlines
+        text: String,
+        /// Position where the text was inserted
+        pos: (usize, usize),
+    },
+    /// The text was deleted at the position.
+    Deleted {
+        /// Deleted text. It may contain newlines
+        text: String,
+        /// Start position of the deleted text
+        pos: (usize, usize),
+    },
+    /// The cursor moved without modifying the text.
+    Moved {
+        /// Position of the cursor before the move
+        from: (usize, usize),
+        /// Position of the cursor after the move
+        to: (usize, usize),
+    },
+    /// The selection was started or changed.
+    Selected {
+        /// Number of selected characters. A newline is counted as one character
+        chars: usize,
+        /// Number of words in the selection
+        words: usize,
+    },
+    /// The selection was cancelled.
+    SelectionCancelled,
+}
+
+fn quote(text: &str) -> String {
+    if text == "\n" {
+        "newline".to_string()
+    } else {
+        format!("'{}'", text.escape_debug())
+    }
+}
+
+fn plural(n: usize, unit: &str) -> String {
+    if n == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", n, unit)
+    }
+}
+
+impl fmt::Display for Announcement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inserted { text, pos } => {
+                write!(
+                    f,
+                    "inserted {} at line {} col {}",
+                    quote(text),
+                    pos.0 + 1,
+                    pos.1 + 1
+                )
+            }
+            Self::Deleted { text, pos } => {
+                write!(
+                    f,
+                    "deleted {} at line {} col {}",
+                    quote(text),
+                    pos.0 + 1,
+                    pos.1 + 1
+                )
+            }
+            Self::Moved { from, to } if from.0 != to.0 => write!(f, "moved to line {}", to.0 + 1),
+            Self::Moved { to, .. } => write!(f, "moved to col {}", to.1 + 1),
+            Self::Selected { words: 0, chars } => {
+                write!(f, "selected {}", plural(*chars, "character"))
+            }
+            Self::Selected { words, .. } => write!(f, "selected {}", plural(*words, "word")),
+            Self::SelectionCancelled => write!(f, "selection cancelled"),
+        }
+    }
+}
+
+// Count words as runs of word characters
+pub fn count_words(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        let word = is_word_char(c);
+        if word && !in_word {
+            count += 1;
+        }
+        in_word = word;
+    }
+    count
+}
+
+// Callback to receive announcements. The callback is shared between clones of the textarea
+#[derive(Clone)]
+pub struct Announcer<'a>(pub Arc<dyn Fn(&Announcement) + Send + Sync + 'a>);
+
+impl<'a> fmt::Debug for Announcer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Announcer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_announcement() {
+        let tests = [
+            (
+                Announcement::Deleted {
+                    text: "\n".to_string(),
+                    pos: (0, 3),
+                },
+                "deleted newline at line 1 col 4",
+            ),
+            (
+                Announcement::Inserted {
+                    text: "a\tb\nc".to_string(),
+                    pos: (1, 0),
+                },
+                "inserted 'a\\tb\\nc' at line 2 col 1",
+            ),
+            (
+                Announcement::Moved {
+                    from: (3, 0),
+                    to: (3, 4),
+                },
+                "moved to col 5",
+            ),
+            (
+                Announcement::Selected { chars: 1, words: 0 },
+                "selected 1 character",
+            ),
+            (
+                Announcement::Selected { chars: 3, words: 1 },
+                "selected 1 word",
+            ),
+            (Announcement::SelectionCancelled, "selection cancelled"),
+        ];
+        for (event, want) in tests {
+            assert_eq!(event.to_string(), want);
+        }
+    }
+
+    #[test]
+    fn count_words_in_text() {
+        for (text, want) in [
+            ("", 0),
+            ("  ", 0),
+            ("foo", 1),
+            ("foo, bar\nbaz ", 3),
+            ("a-b", 2),
+        ] {
+            assert_eq!(count_words(text), want, "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use unicode_bidi::{BidiInfo, Level};
+
+// Character indices of the line in the visual order by the Unicode bidirectional algorithm. The base direction is
+// left-to-right so that indentation stays at the start of line. `None` is returned when the line has no right-to-left
+// text.
+// https://unicode.org/reports/tr9/
+pub fn visual_order(line: &str) -> Option<Vec<usize>> {
+    if line.is_ascii() {
+        return None;
+    }
+    let info = BidiInfo::new(line, Some(Level::ltr()));
+    if !info.levels.iter().any(|l| l.is_rtl()) {
+        return None;
+    }
+
+    let mut order = Vec::with_capacity(line.len());
+    for para in &info.paragraphs {
+        let (levels, runs) = info.visual_runs(para, para.range.clone());
+        for run in runs {
+            let start = line[..run.start].chars().count();
+            let end = start + line[run.clone()].chars().count();
+            if levels[run.start].is_rtl() {
+                order.extend((start..end).rev());
+            } else {
+                order.extend(start..end);
+            }
+        }
+    }
+    Some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visual_order_of_line() {
+        let tests = [
+            ("abc", None),
+            ("café", None),
+            ("ab אבג", Some(vec![0, 1, 2, 5, 4, 3])),
+            ("abc אבג def", Some(vec![0, 1, 2, 3, 6, 5, 4, 7, 8, 9, 10])),
+            // Numbers in right-to-left text keep their order
+            ("אב 12", Some(vec![3, 4, 2, 1, 0])),
+            ("  שלום", Some(vec![0, 1, 5, 4, 3, 2])),
+        ];
+        for (line, want) in tests {
+            assert_eq!(visual_order(line), want, "{:?}", line);
+        }
+    }
+}
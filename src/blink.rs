@@ -0,0 +1,71 @@
+use std::time::{Duration, Instant};
+
+// State of the blinking cursor. The visibility is toggled every `interval` since `toggled_at`
+#[derive(Clone, Debug)]
+pub struct CursorBlink {
+    pub interval: Duration,
+    pub visible: bool,
+    pub toggled_at: Instant,
+}
+
+impl CursorBlink {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            visible: true,
+            toggled_at: now,
+        }
+    }
+
+    // Toggle the visibility when the interval has passed. Returns `true` when the visibility was changed
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if self.remaining(now) != Duration::ZERO {
+            return false;
+        }
+        self.visible = !self.visible;
+        self.toggled_at = now;
+        true
+    }
+
+    // Show the cursor and restart the blink. Returns `true` when the cursor was hidden
+    pub fn reset(&mut self, now: Instant) -> bool {
+        let hidden = !self.visible;
+        self.visible = true;
+        self.toggled_at = now;
+        hidden
+    }
+
+    // Time remaining until the next toggle
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.interval
+            .saturating_sub(now.saturating_duration_since(self.toggled_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_visibility() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(500);
+        let mut b = CursorBlink::new(interval, start);
+        assert!(b.visible);
+
+        let later = |ms| start + Duration::from_millis(ms);
+        assert!(!b.tick(later(499)));
+        assert_eq!(b.remaining(later(499)), Duration::from_millis(1));
+        assert!(b.tick(later(500)));
+        assert!(!b.visible);
+        assert!(!b.tick(later(900)));
+        assert!(b.tick(later(1000)));
+        assert!(b.visible);
+
+        assert!(b.tick(later(1600)));
+        assert!(b.reset(later(1700)));
+        assert!(b.visible);
+        assert_eq!(b.remaining(later(1700)), interval);
+        assert!(!b.reset(later(1800)));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+type Range = ((usize, usize), (usize, usize));
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+enum Connection {
+    NotOpened,
+    Opened(arboard::Clipboard),
+    // Opening the clipboard failed, for example in a headless environment. It is not retried
+    Failed,
+}
+
+// Primary selection on Linux (X11 and Wayland). The clipboard connection is shared between clones of the textarea
+// since the selection content is served while the connection is alive. On other platforms, which have no primary
+// selection, all operations do nothing
+#[derive(Clone)]
+pub struct PrimarySelection {
+    pub enabled: bool,
+    // The selection range synced last time to avoid copying the same selection on every key input
+    range: Option<Range>,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    clipboard: Arc<Mutex<Connection>>,
+}
+
+impl Default for PrimarySelection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            range: None,
+            clipboard: Arc::new(Mutex::new(Connection::NotOpened)),
+        }
+    }
+}
+
+impl fmt::Debug for PrimarySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrimarySelection")
+            .field("enabled", &self.enabled)
+            .field("range", &self.range)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PrimarySelection {
+    #[cfg(target_os = "linux")]
+    fn with_clipboard<T>(&self, f: impl FnOnce(&mut arboard::Clipboard) -> Option<T>) -> Option<T> {
+        let mut conn = self.clipboard.lock().ok()?;
+        if let Connection::NotOpened = *conn {
+            *conn = match arboard::Clipboard::new() {
+                Ok(clipboard) => Connection::Opened(clipboard),
+                Err(_) => Connection::Failed,
+            };
+        }
+        match &mut *conn {
+            Connection::Opened(clipboard) => f(clipboard),
+            _ => None,
+        }
+    }
+
+    // Remember the current selection range. Returns `true` when it differs from the range remembered last time
+    pub fn update_range(&mut self, range: Option<Range>) -> bool {
+        if self.range == range {
+            return false;
+        }
+        self.range = range;
+        true
+    }
+
+    // Copy the text to the primary selection. Returns `true` when the text was copied
+    #[cfg(target_os = "linux")]
+    pub fn set(&mut self, text: String) -> bool {
+        use arboard::{LinuxClipboardKind, SetExtLinux as _};
+
+        if !self.enabled || text.is_empty() {
+            return false;
+        }
+        self.with_clipboard(|c| {
+            c.set()
+                .clipboard(LinuxClipboardKind::Primary)
+                .text(text)
+                .ok()
+        })
+        .is_some()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn set(&mut self, _text: String) -> bool {
+        false
+    }
+
+    // Get the text in the primary selection
+    #[cfg(target_os = "linux")]
+    pub fn get(&self) -> Option<String> {
+        use arboard::{GetExtLinux as _, LinuxClipboardKind};
+
+        if !self.enabled {
+            return None;
+        }
+        self.with_clipboard(|c| c.get().clipboard(LinuxClipboardKind::Primary).text().ok())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn get(&self) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_range_only_when_changed() {
+        let mut p = PrimarySelection::default();
+        assert!(!p.enabled);
+        assert!(!p.update_range(None));
+        assert!(p.update_range(Some(((0, 0), (0, 3)))));
+        assert!(!p.update_range(Some(((0, 0), (0, 3)))));
+        assert!(p.update_range(Some(((0, 0), (1, 0)))));
+        assert!(p.update_range(None));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::util::byte_offset;
+
+// Conventional limits of git commit messages
+pub const SUBJECT_WIDTH: usize = 50;
+pub const BODY_WIDTH: usize = 72;
+
+pub fn is_comment(line: &str) -> bool {
+    line.starts_with('#')
+}
+
+// Row of the subject line. Comment lines and blank lines before it are skipped as git does
+pub fn subject_row(lines: &[String]) -> Option<usize> {
+    lines
+        .iter()
+        .position(|l| !is_comment(l) && !l.trim().is_empty())
+}
+
+// Message without comment lines, trailing spaces, and leading and trailing blank lines like `git commit --cleanup=strip`
+pub fn strip_message(lines: &[String]) -> String {
+    let mut message = String::new();
+    let mut blanks = 0;
+    for line in lines.iter().filter(|l| !is_comment(l)) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blanks += 1;
+            continue;
+        }
+        if !message.is_empty() {
+            // Consecutive blank lines are collapsed into one
+            message.push_str(if blanks > 0 { "\n\n" } else { "\n" });
+        }
+        message.push_str(line);
+        blanks = 0;
+    }
+    message
+}
+
+// Column to break the body line longer than the width. The line is broken at the last space which fits in the width.
+// `None` is returned when the line fits or has no space to break
+pub fn wrap_col(line: &str, width: usize, char_width: impl Fn(char) -> usize) -> Option<usize> {
+    let mut total = 0;
+    let mut space = None;
+    let mut text_seen = false;
+    for (col, c) in line.chars().enumerate() {
+        if c == ' ' {
+            if total <= width && text_seen {
+                space = Some(col);
+            }
+        } else {
+            text_seen = true;
+        }
+        total += char_width(c);
+    }
+    if total <= width {
+        return None;
+    }
+    space
+}
+
+#[derive(Clone, Debug)]
+pub struct CommitMessage {
+    pub enabled: bool,
+    pub subject_style: Style,
+    pub overflow_style: Style,
+    pub comment_style: Style,
+}
+
+impl Default for CommitMessage {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            subject_style: Style::default().add_modifier(Modifier::BOLD),
+            overflow_style: Style::default().fg(Color::Red),
+            comment_style: Style::default().fg(Color::DarkGray),
+        }
+    }
+}
+
+impl CommitMessage {
+    // Style of the entire line and the byte range of the subject exceeding the limit with its style
+    pub fn line_styles(
+        &self,
+        lines: &[String],
+        row: usize,
+    ) -> (Option<Style>, Option<(usize, usize, Style)>) {
+        let line = &lines[row];
+        if is_comment(line) {
+            return (Some(self.comment_style), None);
+        }
+        if subject_row(lines) != Some(row) {
+            return (None, None);
+        }
+        let start = byte_offset(line, SUBJECT_WIDTH);
+        let overflow = (start < line.len()).then(|| (start, line.len(), self.overflow_style));
+        (Some(self.subject_style), overflow)
+    }
+
+    // The line at the row is a body line which is wrapped on typing
+    pub fn is_body(&self, lines: &[String], row: usize) -> bool {
+        self.enabled && !is_comment(&lines[row]) && subject_row(lines).map_or(false, |s| s < row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn find_subject_row() {
+        assert_eq!(subject_row(&lines(["Fix bug", "", "body"])), Some(0));
+        assert_eq!(subject_row(&lines(["# comment", "", "Fix bug"])), Some(2));
+        assert_eq!(subject_row(&lines(["", "# comment"])), None);
+    }
+
+    #[test]
+    fn strip_commit_message() {
+        let tests: [(&[&str], &str); 4] = [
+            (
+                &["Fix bug  ", "", "Body", "# Please enter", ""],
+                "Fix bug\n\nBody",
+            ),
+            (
+                &["", "Subject", "", "", "", "a", "# x", "b"],
+                "Subject\n\na\nb",
+            ),
+            (&["# only comments"], ""),
+            (&["#not a subject", "Subject"], "Subject"),
+        ];
+        for (input, want) in tests {
+            assert_eq!(strip_message(&lines(input)), want, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn body_wrap_col() {
+        let width = |_: char| 1;
+        let tests = [
+            ("aaa bbb", 10, None),
+            ("aaa bbb ccc", 10, Some(7)),
+            ("aaa bbb ccc", 7, Some(7)),
+            ("aaa bbb ccc", 6, Some(3)),
+            ("aaaaaaaaaaaa", 10, None),
+            ("   aaaaaaaaaaaa", 10, None),
+            ("aaa bbbbbbbbbbbb", 10, Some(3)),
+        ];
+        for (line, w, want) in tests {
+            assert_eq!(wrap_col(line, w, width), want, "{:?} {}", line, w);
+        }
+    }
+
+    #[test]
+    fn subject_overflow() {
+        let c = CommitMessage::default();
+        let long = "x".repeat(55);
+        let lines = lines(["# comment", long.as_str(), "", "body"]);
+        assert_eq!(c.line_styles(&lines, 0), (Some(c.comment_style), None));
+        assert_eq!(
+            c.line_styles(&lines, 1),
+            (Some(c.subject_style), Some((50, 55, c.overflow_style))),
+        );
+        assert_eq!(c.line_styles(&lines, 3), (None, None));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::word::{find_word_head, is_word_char};
+use std::collections::HashSet;
+
+// Words in the buffer which start with the prefix. They are ordered by the distance from the cursor, scanning the
+// buffer forward from the cursor with wrapping around. The word at the cursor is excluded.
+fn collect_candidates(lines: &[String], cursor: (usize, usize), prefix: &str) -> Vec<String> {
+    let (row, col) = cursor;
+    let mut before = vec![];
+    let mut after = vec![];
+
+    for (r, line) in lines.iter().enumerate() {
+        // Start of the current word as a pair of the character index and the byte offset
+        let mut start = None;
+        let chars = line.char_indices().chain(Some((line.len(), ' ')));
+        for (c, (i, ch)) in chars.enumerate() {
+            match (start, is_word_char(ch)) {
+                (None, true) => start = Some((c, i)),
+                (Some((s, j)), false) => {
+                    start = None;
+                    if r == row && s <= col && col <= c {
+                        continue; // The word being completed
+                    }
+                    let word = &line[j..i];
+                    if word.len() > prefix.len() && word.starts_with(prefix) {
+                        if (r, s) < (row, col) {
+                            before.push(word);
+                        } else {
+                            after.push(word);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    after
+        .into_iter()
+        .chain(before)
+        .filter(|word| seen.insert(*word))
+        .map(String::from)
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct WordCompletion {
+    pub row: usize,
+    pub start_col: usize,
+    prefix: String,
+    candidates: Vec<String>,
+    index: Option<usize>, // `None` means the original prefix
+}
+
+impl WordCompletion {
+    pub fn start(lines: &[String], cursor: (usize, usize)) -> Option<Self> {
+        let (row, col) = cursor;
+        let start_col = find_word_head(&lines[row], col);
+        if start_col == col {
+            return None;
+        }
+        let prefix: String = lines[row]
+            .chars()
+            .skip(start_col)
+            .take(col - start_col)
+            .collect();
+        let candidates = collect_candidates(lines, cursor, &prefix);
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(Self {
+            row,
+            start_col,
+            prefix,
+            candidates,
+            index: None,
+        })
+    }
+
+    pub fn current(&self) -> &str {
+        match self.index {
+            Some(i) => &self.candidates[i],
+            None => &self.prefix,
+        }
+    }
+
+    // Check the completion is still ongoing at the cursor. When the text was modified or the cursor moved after the
+    // last completion, the completion is no longer valid.
+    pub fn is_active_at(&self, lines: &[String], cursor: (usize, usize)) -> bool {
+        let current = self.current();
+        let end_col = self.start_col + current.chars().count();
+        cursor == (self.row, end_col)
+            && lines.get(self.row).map_or(false, |line| {
+                line.chars()
+                    .skip(self.start_col)
+                    .take(end_col - self.start_col)
+                    .eq(current.chars())
+            })
+    }
+
+    pub fn next(&mut self) {
+        self.index = match self.index {
+            None => Some(0),
+            Some(i) if i + 1 < self.candidates.len() => Some(i + 1),
+            Some(_) => None,
+        };
+    }
+
+    pub fn prev(&mut self) {
+        self.index = match self.index {
+            None => Some(self.candidates.len() - 1),
+            Some(0) => None,
+            Some(i) => Some(i - 1),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn candidates_order() {
+        let tests = [
+            (
+                &["foo fob fo", "food foo"][..],
+                (0, 10),
+                &["food", "foo", "fob"][..],
+            ),
+            (
+                &["fo fob", "food foo fob"][..],
+                (0, 2),
+                &["fob", "food", "foo"][..],
+            ),
+            (&["food fo", "fob"][..], (0, 7), &["fob", "food"][..]),
+            (&["fo"][..], (0, 2), &[][..]),
+            (&["fo foo"][..], (0, 1), &["foo"][..]),
+        ];
+        for test in tests {
+            let (ls, cursor, want) = test;
+            let ls = lines(ls);
+            let (row, col) = cursor;
+            let prefix_start = ls[row][..col].rfind(' ').map(|i| i + 1).unwrap_or(0);
+            let prefix = &ls[row][prefix_start..col];
+            assert_eq!(collect_candidates(&ls, cursor, prefix), want, "{:?}", test);
+        }
+    }
+
+    #[test]
+    fn cycle_candidates() {
+        let ls = lines(["ab abc abd a"]);
+        let mut c = WordCompletion::start(&ls, (0, 12)).unwrap();
+        assert_eq!(c.current(), "a");
+        c.next();
+        assert_eq!(c.current(), "ab");
+        c.next();
+        c.next();
+        assert_eq!(c.current(), "abd");
+        c.next();
+        assert_eq!(c.current(), "a");
+        c.prev();
+        assert_eq!(c.current(), "abd");
+
+        assert!(WordCompletion::start(&ls, (0, 11)).is_none());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::ratatui::style::{Color, Style};
+use std::cell::{Ref, RefCell};
+use std::ops::Range;
+
+/// Region of a merge conflict left by VCS like Git. Each field is a row of a marker line. Find conflicts in the text with
+/// [`crate::TextArea::conflicts`].
+///
+/// ```text
+/// <<<<<<< HEAD        <- start
+/// our change
+/// ||||||| base        <- base (only in diff3 style)
+/// original text
+/// =======             <- separator
+/// their change
+/// >>>>>>> branch      <- end
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// Row of the `<<<<<<<` marker
+    pub start: usize,
+    /// Row of the `|||||||` marker of the common ancestor section. `None` when the section doesn't exist
+    pub base: Option<usize>,
+    /// Row of the `=======` marker
+    pub separator: usize,
+    /// Row of the `>>>>>>>` marker
+    pub end: usize,
+}
+
+impl Conflict {
+    /// Get the rows of our side of the conflict.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["<<<<<<< HEAD", "ours", "=======", "theirs", ">>>>>>> branch"]);
+    /// let conflict = textarea.conflicts()[0];
+    /// assert_eq!(conflict.ours(), 1..2);
+    /// assert_eq!(conflict.theirs(), 3..4);
+    /// ```
+    pub fn ours(&self) -> Range<usize> {
+        self.start + 1..self.base.unwrap_or(self.separator)
+    }
+
+    /// Get the rows of their side of the conflict.
+    pub fn theirs(&self) -> Range<usize> {
+        self.separator + 1..self.end
+    }
+}
+
+/// How to resolve a merge conflict with [`crate::TextArea::resolve_conflict`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConflictResolution {
+    /// Keep our side
+    Ours,
+    /// Keep their side
+    Theirs,
+    /// Keep our side followed by their side
+    Both,
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    match line.strip_prefix(marker) {
+        Some(rest) => rest.is_empty() || rest.starts_with(' '),
+        None => false,
+    }
+}
+
+// Find merge conflicts in the lines. Incomplete conflicts are ignored
+pub fn find_conflicts(lines: &[String]) -> Vec<Conflict> {
+    let mut conflicts = vec![];
+    let mut start = None;
+    let mut base = None;
+    let mut separator = None;
+    for (row, line) in lines.iter().enumerate() {
+        if is_marker(line, "<<<<<<<") {
+            start = Some(row);
+            base = None;
+            separator = None;
+        } else if start.is_none() {
+            continue;
+        } else if is_marker(line, "|||||||") && base.is_none() && separator.is_none() {
+            base = Some(row);
+        } else if is_marker(line, "=======") && separator.is_none() {
+            separator = Some(row);
+        } else if is_marker(line, ">>>>>>>") {
+            if let (Some(start), Some(separator)) = (start, separator) {
+                conflicts.push(Conflict {
+                    start,
+                    base,
+                    separator,
+                    end: row,
+                });
+            }
+            start = None;
+        }
+    }
+    conflicts
+}
+
+#[derive(Clone, Debug)]
+pub struct ConflictHighlight {
+    pub enabled: bool,
+    // Conflicts in the text. They are found on the first use after the text was modified
+    conflicts: RefCell<Option<Vec<Conflict>>>,
+    pub ours_style: Style,
+    pub theirs_style: Style,
+    pub marker_style: Style,
+}
+
+impl Default for ConflictHighlight {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            conflicts: RefCell::new(None),
+            ours_style: Style::default().bg(Color::Rgb(0x1f, 0x3d, 0x2b)),
+            theirs_style: Style::default().bg(Color::Rgb(0x1f, 0x2f, 0x4d)),
+            marker_style: Style::default().fg(Color::DarkGray),
+        }
+    }
+}
+
+impl ConflictHighlight {
+    // Discard the conflicts found in the text since the text was modified
+    pub fn clear(&mut self) {
+        *self.conflicts.get_mut() = None;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.conflicts.borrow().as_ref().map_or(0, Vec::capacity)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        if let Some(conflicts) = self.conflicts.get_mut() {
+            conflicts.shrink_to_fit();
+        }
+    }
+
+    fn conflicts(&self, lines: &[String]) -> Ref<'_, [Conflict]> {
+        if self.conflicts.borrow().is_none() {
+            *self.conflicts.borrow_mut() = Some(find_conflicts(lines));
+        }
+        Ref::map(self.conflicts.borrow(), |c| c.as_deref().unwrap_or(&[]))
+    }
+
+    // Style of the line at the row when it is in some conflict
+    pub fn line_style(&self, lines: &[String], row: usize) -> Option<Style> {
+        if !self.enabled {
+            return None;
+        }
+        let conflicts = self.conflicts(lines);
+        let i = conflicts.partition_point(|c| c.end < row);
+        let c = conflicts.get(i).filter(|c| c.start <= row)?;
+        if c.ours().contains(&row) {
+            Some(self.ours_style)
+        } else if c.theirs().contains(&row) {
+            Some(self.theirs_style)
+        } else {
+            Some(self.marker_style)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn find_conflict_regions() {
+        let text = "a
+<<<<<<< HEAD
+ours
+=======
+theirs
+>>>>>>> branch
+b
+<<<<<<< HEAD
+ours
+||||||| base
+base
+=======
+>>>>>>>
+<<<<<<<< not a marker
+=======
+>>>>>>> incomplete";
+        assert_eq!(
+            find_conflicts(&lines(text.lines())),
+            [
+                Conflict {
+                    start: 1,
+                    base: None,
+                    separator: 3,
+                    end: 5,
+                },
+                Conflict {
+                    start: 7,
+                    base: Some(9),
+                    separator: 11,
+                    end: 12,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn conflict_line_styles() {
+        let text = "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\nafter";
+        let ls = lines(text.lines());
+        let mut h = ConflictHighlight::default();
+        assert_eq!(h.line_style(&ls, 1), None);
+        assert_eq!(h.capacity(), 0);
+
+        h.enabled = true;
+        let want = [
+            Some(h.marker_style),
+            Some(h.ours_style),
+            Some(h.marker_style),
+            Some(h.theirs_style),
+            Some(h.marker_style),
+            None,
+        ];
+        for (row, want) in want.iter().enumerate() {
+            assert_eq!(h.line_style(&ls, row), *want, "row {}", row);
+        }
+
+        // Conflicts are found again after the text was modified
+        let ls = lines(["after"]);
+        assert_eq!(h.line_style(&ls, 0), Some(h.marker_style));
+        h.clear();
+        assert_eq!(h.line_style(&ls, 0), None);
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Notation to render control characters such as carriage returns, escape bytes and NUL. Control characters are not
+/// printable and writing them to a terminal as-is corrupts the layout, so they are always rendered with the notation
+/// and the style set by [`crate::TextArea::set_control_char_style`]. Tabs are not control characters in this context
+/// since they are expanded to spaces. C1 control characters (U+0080..U+009F) are rendered as their code like `<85>`
+/// in both notations. The replacement character U+FFFD, which replaces invalid UTF-8 sequences on loading (see
+/// [`crate::InvalidUtf8`]), is also rendered with the style.
+/// ```
+/// use tui_textarea::{ControlCharDisplay, TextArea};
+///
+/// let mut textarea = TextArea::from(["a\r", "\u{1b}[0m"]);
+/// assert_eq!(textarea.render_to_string(6, 2), "a^M   \n^[[0m ");
+///
+/// textarea.set_control_char_display(ControlCharDisplay::Picture);
+/// assert_eq!(textarea.render_to_string(6, 2), "a␍    \n␛[0m  ");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ControlCharDisplay {
+    /// Caret notation like `^M` for a carriage return and `^[` for an escape. This is the default notation
+    Caret,
+    /// Unicode control pictures like `␍` for a carriage return and `␛` for an escape
+    Picture,
+}
+
+impl Default for ControlCharDisplay {
+    fn default() -> Self {
+        Self::Caret
+    }
+}
+
+fn is_control_char(c: char) -> bool {
+    c != '\t' && c.is_control()
+}
+
+// Characters rendered with the style of control characters. The replacement character is included since it marks
+// invalid UTF-8 sequences on loading
+pub fn is_special_char(c: char) -> bool {
+    c == char::REPLACEMENT_CHARACTER || is_control_char(c)
+}
+
+// Text rendered for the control character. `None` is returned when the character is not a control character
+pub fn control_char_text(c: char, display: ControlCharDisplay) -> Option<String> {
+    if !is_control_char(c) {
+        return None;
+    }
+    let code = c as u32;
+    let text = match (code, display) {
+        (0x7f, ControlCharDisplay::Caret) => "^?".to_string(),
+        (0x7f, ControlCharDisplay::Picture) => "\u{2421}".to_string(),
+        (0x80..=0x9f, _) => format!("<{:02x}>", code),
+        (_, ControlCharDisplay::Caret) => format!("^{}", char::from(code as u8 + 0x40)),
+        (_, ControlCharDisplay::Picture) => char::from_u32(0x2400 + code)?.to_string(),
+    };
+    Some(text)
+}
+
+// Display width of the control character rendered with the notation
+pub fn control_char_width(c: char, display: ControlCharDisplay) -> Option<usize> {
+    if !is_control_char(c) {
+        return None;
+    }
+    let width = match (c as u32, display) {
+        (0x80..=0x9f, _) => 4,
+        (_, ControlCharDisplay::Caret) => 2,
+        (_, ControlCharDisplay::Picture) => 1,
+    };
+    Some(width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_width::UnicodeWidthStr as _;
+
+    #[test]
+    fn control_char_notation() {
+        let tests = [
+            ('\0', "^@", "␀"),
+            ('\r', "^M", "␍"),
+            ('\u{1b}', "^[", "␛"),
+            ('\u{1f}', "^_", "␟"),
+            ('\u{7f}', "^?", "␡"),
+            ('\u{85}', "<85>", "<85>"),
+        ];
+        for (c, caret, picture) in tests {
+            for (display, want) in [
+                (ControlCharDisplay::Caret, caret),
+                (ControlCharDisplay::Picture, picture),
+            ] {
+                assert_eq!(
+                    control_char_text(c, display).as_deref(),
+                    Some(want),
+                    "{:?}",
+                    c
+                );
+                assert_eq!(
+                    control_char_width(c, display),
+                    Some(want.width()),
+                    "{:?}",
+                    c
+                );
+            }
+        }
+
+        for c in ['\t', 'a', ' ', 'あ'] {
+            assert_eq!(
+                control_char_text(c, ControlCharDisplay::Caret),
+                None,
+                "{:?}",
+                c
+            );
+            assert_eq!(
+                control_char_width(c, ControlCharDisplay::Picture),
+                None,
+                "{:?}",
+                c
+            );
+        }
+    }
+}
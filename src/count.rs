@@ -0,0 +1,107 @@
+use crate::announce::count_words;
+use crate::op::EditOp;
+use std::ops::Range;
+
+// Numbers of characters and words in the text maintained on each edit. A newline is counted as one character. Words
+// are counted per line since no word spans lines
+#[derive(Clone, Debug, Default)]
+pub struct TextCounts {
+    pub chars: usize,
+    pub words: usize,
+    line_words: Vec<usize>,
+}
+
+impl TextCounts {
+    pub fn new(lines: &[String]) -> Self {
+        let mut counts = Self::default();
+        counts.recount(lines);
+        counts
+    }
+
+    pub fn recount(&mut self, lines: &[String]) {
+        self.line_words = lines.iter().map(|l| count_words(l)).collect();
+        self.words = self.line_words.iter().sum();
+        self.chars = lines.iter().map(|l| l.chars().count()).sum::<usize>() + lines.len() - 1;
+    }
+
+    // Update the counts by the edit. `lines` must be the text just after the edit was applied
+    pub fn update(&mut self, lines: &[String], op: &EditOp) {
+        let chars = op.text().chars().count();
+        let (start, end) = (op.pos().0, op.end().0);
+        // Rows of the edited lines before the edit and after the edit
+        let (old, new) = match op {
+            EditOp::Insert { .. } => {
+                self.chars += chars;
+                (start..start + 1, start..end + 1)
+            }
+            EditOp::Delete { .. } => {
+                self.chars = self.chars.saturating_sub(chars);
+                (start..end + 1, start..start + 1)
+            }
+        };
+        if self.line_words.len() < old.end || lines.len() < new.end {
+            // The edit does not match the counted text
+            self.recount(lines);
+            return;
+        }
+        self.splice(lines, old, new);
+    }
+
+    fn splice(&mut self, lines: &[String], old: Range<usize>, new: Range<usize>) {
+        let counts: Vec<_> = lines[new].iter().map(|l| count_words(l)).collect();
+        let removed: usize = self.line_words.splice(old, counts.iter().copied()).sum();
+        self.words = self.words + counts.iter().sum::<usize>() - removed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn count_text() {
+        let c = TextCounts::new(&lines(["foo bar", "", "あい"]));
+        assert_eq!((c.chars, c.words), (11, 3));
+        let c = TextCounts::new(&lines([""]));
+        assert_eq!((c.chars, c.words), (0, 0));
+    }
+
+    #[test]
+    fn update_on_edit() {
+        let mut c = TextCounts::new(&lines(["foo bar", "baz"]));
+
+        // Joining words: "foo bar" -> "foobar"
+        let op = EditOp::Delete {
+            pos: (0, 3),
+            text: " ".to_string(),
+        };
+        c.update(&lines(["foobar", "baz"]), &op);
+        assert_eq!((c.chars, c.words), (10, 2));
+
+        // Splitting lines: "foobar" -> "foo x", "y bar"
+        let op = EditOp::Insert {
+            pos: (0, 3),
+            text: " x\ny ".to_string(),
+        };
+        c.update(&lines(["foo x", "y bar", "baz"]), &op);
+        assert_eq!((c.chars, c.words), (15, 5));
+
+        // Joining lines: "y bar", "baz" -> "y bbaz"
+        let op = EditOp::Delete {
+            pos: (1, 3),
+            text: "ar\n".to_string(),
+        };
+        c.update(&lines(["foo x", "y bbaz"]), &op);
+        assert_eq!((c.chars, c.words), (12, 4));
+        assert_eq!(c.line_words, [2, 2]);
+
+        // Counted again when the edit does not match
+        let op = EditOp::Insert {
+            pos: (5, 0),
+            text: "\n".to_string(),
+        };
+        c.update(&lines(["a", "b"]), &op);
+        assert_eq!((c.chars, c.words), (3, 2));
+    }
+}
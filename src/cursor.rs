@@ -1,5 +1,8 @@
 use crate::widget::Viewport;
-use crate::word::{find_word_start_backward, find_word_start_forward};
+use crate::word::{
+    find_subword_start_backward, find_subword_start_forward, find_word_start_backward,
+    find_word_start_forward, WordBoundary,
+};
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
 use std::cmp;
@@ -105,8 +108,10 @@ pub enum CursorMove {
     /// ```
     Bottom,
     /// Move cursor forward by one word. Word boundary appears at spaces, punctuations, and others. For example
-    /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at the end of line, it moves to the
-    /// head of next line.
+    /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`. Words are split by the word boundaries of [Unicode
+    /// Standard Annex #29](https://www.unicode.org/reports/tr29/), so `don't` and `3.14` are one word. Text without
+    /// spaces such as Japanese is also split where the script changes. When the cursor is at the end of line, it moves
+    /// to the head of next line.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -119,8 +124,8 @@ pub enum CursorMove {
     /// ```
     WordForward,
     /// Move cursor backward by one word.  Word boundary appears at spaces, punctuations, and others. For example
-    /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`.When the cursor is at the head of line, it moves to
-    /// the end of previous line.
+    /// `fn foo(a)` consists of words `fn`, `foo`, `(`, `a`, `)`. Words are split in the same way as
+    /// [`CursorMove::WordForward`]. When the cursor is at the head of line, it moves to the end of previous line.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -135,6 +140,36 @@ pub enum CursorMove {
     /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
     WordBack,
+    /// Move cursor forward by one sub-word. In addition to the word boundaries of [`CursorMove::WordForward`], the
+    /// cursor stops at the starts of sub-words inside identifiers such as `Bar` in `fooBar` and `bar` in `foo_bar`.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fooBar bazQux"]);
+    ///
+    /// textarea.move_cursor(CursorMove::SubwordForward);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// textarea.move_cursor(CursorMove::SubwordForward);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.move_cursor(CursorMove::SubwordForward);
+    /// assert_eq!(textarea.cursor(), (0, 10));
+    /// ```
+    SubwordForward,
+    /// Move cursor backward by one sub-word. Sub-words are split in the same way as [`CursorMove::SubwordForward`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fooBar bazQux"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::SubwordBack);
+    /// assert_eq!(textarea.cursor(), (0, 10));
+    /// textarea.move_cursor(CursorMove::SubwordBack);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.move_cursor(CursorMove::SubwordBack);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    SubwordBack,
     /// Move cursor down by one paragraph. Paragraph is a chunk of non-empty lines. Cursor moves to the first line of paragraph.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
@@ -231,6 +266,7 @@ impl CursorMove {
         (row, col): (usize, usize),
         lines: &[String],
         viewport: &Viewport,
+        words: &WordBoundary,
     ) -> Option<(usize, usize)> {
         use CursorMove::*;
 
@@ -260,8 +296,12 @@ impl CursorMove {
                 let row = lines.len() - 1;
                 Some((row, fit_col(col, &lines[row])))
             }
-            WordForward => {
-                if let Some(col) = find_word_start_forward(&lines[row], col) {
+            WordForward | SubwordForward => {
+                let find = match self {
+                    SubwordForward => find_subword_start_forward,
+                    _ => find_word_start_forward,
+                };
+                if let Some(col) = find(&lines[row], col, words) {
                     Some((row, col))
                 } else if row + 1 < lines.len() {
                     Some((row + 1, 0))
@@ -269,8 +309,12 @@ impl CursorMove {
                     Some((row, lines[row].chars().count()))
                 }
             }
-            WordBack => {
-                if let Some(col) = find_word_start_backward(&lines[row], col) {
+            WordBack | SubwordBack => {
+                let find = match self {
+                    SubwordBack => find_subword_start_backward,
+                    _ => find_word_start_backward,
+                };
+                if let Some(col) = find(&lines[row], col, words) {
                     Some((row, col))
                 } else if row > 0 {
                     Some((row - 1, lines[row - 1].chars().count()))
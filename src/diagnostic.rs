@@ -0,0 +1,193 @@
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::util::byte_offset;
+
+/// Severity of [`Diagnostic`]. The order of the variants is from the least severe to the most severe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// Hint like a suggestion of refactoring
+    Hint,
+    /// Informational message
+    Info,
+    /// Warning
+    Warning,
+    /// Error
+    Error,
+}
+
+impl Severity {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A diagnostic message attached to a range of text in [`crate::TextArea`] such as an error reported by a linter or a
+/// language server. Set diagnostics with [`crate::TextArea::set_diagnostics`].
+///
+/// The range is a pair of the start position and the end position. Each position is a 0-base `(row, column)` pair and
+/// the end position is exclusive.
+/// ```
+/// use tui_textarea::{Diagnostic, Severity};
+///
+/// let d = Diagnostic::new((0, 4), (0, 7), Severity::Error, "unknown variable");
+/// assert_eq!(d.range, ((0, 4), (0, 7)));
+/// assert_eq!(d.severity, Severity::Error);
+/// assert_eq!(d.message, "unknown variable");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// Range of the text as the start position and the end position (exclusive)
+    pub range: ((usize, usize), (usize, usize)),
+    /// Severity of the diagnostic
+    pub severity: Severity,
+    /// Message of the diagnostic
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Create a new diagnostic from the start position, the end position (exclusive), the severity, and the message.
+    pub fn new(
+        start: (usize, usize),
+        end: (usize, usize),
+        severity: Severity,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            range: (start, end),
+            severity,
+            message: message.into(),
+        }
+    }
+
+    fn contains(&self, pos: (usize, usize)) -> bool {
+        let (start, end) = self.range;
+        start <= pos && (pos < end || start == end && pos == start)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Diagnostics {
+    pub items: Vec<Diagnostic>,
+    pub styles: [Style; 4],
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        let underline = |c| Style::default().fg(c).add_modifier(Modifier::UNDERLINED);
+        Self {
+            items: vec![],
+            styles: [
+                underline(Color::DarkGray),
+                underline(Color::Blue),
+                underline(Color::Yellow),
+                underline(Color::Red),
+            ],
+        }
+    }
+}
+
+impl Diagnostics {
+    pub fn style(&self, severity: Severity) -> Style {
+        self.styles[severity.index()]
+    }
+
+    pub fn set_style(&mut self, severity: Severity, style: Style) {
+        self.styles[severity.index()] = style;
+    }
+
+    // The most severe diagnostic which contains the position
+    pub fn at(&self, pos: (usize, usize)) -> Option<&Diagnostic> {
+        self.items
+            .iter()
+            .filter(|d| d.contains(pos))
+            .max_by_key(|d| d.severity)
+    }
+
+    // Byte offset ranges in the line and their styles. Empty range is extended to one character so that it is visible.
+    // Positions outside the line are clamped since diagnostics are not updated on editing the text.
+    pub fn line_ranges(&self, row: usize, line: &str) -> Vec<(usize, usize, Style)> {
+        let offset = |col: usize| byte_offset(line, col);
+
+        let mut ranges = vec![];
+        for d in &self.items {
+            let ((start_row, start_col), (end_row, end_col)) = d.range;
+            if row < start_row || end_row < row {
+                continue;
+            }
+            let start = if row == start_row {
+                offset(start_col)
+            } else {
+                0
+            };
+            let mut end = if row == end_row {
+                offset(end_col)
+            } else {
+                line.len()
+            };
+            if d.range.0 == d.range.1 {
+                end = line[start..]
+                    .chars()
+                    .next()
+                    .map_or(start, |c| start + c.len_utf8());
+            }
+            if start < end {
+                ranges.push((start, end, self.style(d.severity)));
+            }
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Pos = (usize, usize);
+
+    fn diags(items: &[(Pos, Pos, Severity)]) -> Diagnostics {
+        Diagnostics {
+            items: items
+                .iter()
+                .map(|&(s, e, sev)| Diagnostic::new(s, e, sev, ""))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diagnostic_at_position() {
+        let d = diags(&[
+            ((0, 0), (0, 5), Severity::Warning),
+            ((0, 2), (1, 1), Severity::Error),
+            ((2, 3), (2, 3), Severity::Hint),
+        ]);
+        let tests = [
+            ((0, 0), Some(Severity::Warning)),
+            ((0, 2), Some(Severity::Error)),
+            ((1, 0), Some(Severity::Error)),
+            ((1, 1), None),
+            ((2, 3), Some(Severity::Hint)),
+            ((2, 4), None),
+        ];
+        for (pos, want) in tests {
+            assert_eq!(d.at(pos).map(|d| d.severity), want, "{:?}", pos);
+        }
+    }
+
+    #[test]
+    fn ranges_in_line() {
+        let d = diags(&[
+            ((0, 1), (0, 3), Severity::Error),
+            ((0, 4), (2, 1), Severity::Info),
+            ((1, 1), (1, 1), Severity::Hint),
+            ((1, 10), (1, 10), Severity::Hint),
+        ]);
+        let err = d.style(Severity::Error);
+        let info = d.style(Severity::Info);
+        let hint = d.style(Severity::Hint);
+
+        assert_eq!(d.line_ranges(0, "abcdef"), [(1, 3, err), (4, 6, info)]);
+        assert_eq!(d.line_ranges(1, "あい"), [(0, 6, info), (3, 6, hint)]);
+        assert_eq!(d.line_ranges(2, "xyz"), [(0, 1, info)]);
+        assert_eq!(d.line_ranges(3, "xyz"), []);
+    }
+}
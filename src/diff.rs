@@ -0,0 +1,382 @@
+use std::cmp;
+
+// Give up finding the shortest edit script when the number of edits exceeds this value. The rest of the text is treated
+// as one big change. This bounds the memory and time to compute the diff of totally different texts.
+const MAX_EDITS: usize = 1000;
+
+/// Kind of change of a line compared with the diff base set by [`crate::TextArea::set_diff_base`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineChange {
+    /// The line was added
+    Added,
+    /// The line was modified
+    Modified,
+    /// Some lines were removed at the line
+    Removed,
+}
+
+impl LineChange {
+    pub(crate) fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// Changed chunks between `a` and `b` as (start in `a`, length in `a`, start in `b`, length in `b`)
+fn hunks(a: &[String], b: &[String]) -> Vec<(usize, usize, usize, usize)> {
+    let prefix = a.iter().zip(b).take_while(|(l, r)| l == r).count();
+    let (a_rest, b_rest) = (&a[prefix..], &b[prefix..]);
+    let suffix = a_rest
+        .iter()
+        .rev()
+        .zip(b_rest.iter().rev())
+        .take_while(|(l, r)| l == r)
+        .count();
+    let a_mid = &a_rest[..a_rest.len() - suffix];
+    let b_mid = &b_rest[..b_rest.len() - suffix];
+
+    if a_mid.is_empty() && b_mid.is_empty() {
+        return vec![];
+    }
+    if a_mid.is_empty() || b_mid.is_empty() {
+        return vec![(prefix, a_mid.len(), prefix, b_mid.len())];
+    }
+
+    let equals = match shortest_edit(a_mid, b_mid) {
+        Some(equals) => equals,
+        None => return vec![(prefix, a_mid.len(), prefix, b_mid.len())],
+    };
+
+    let mut hunks = vec![];
+    let (mut x, mut y) = (0, 0);
+    for (ex, ey) in equals.into_iter().chain(Some((a_mid.len(), b_mid.len()))) {
+        if x < ex || y < ey {
+            hunks.push((prefix + x, ex - x, prefix + y, ey - y));
+        }
+        x = ex + 1;
+        y = ey + 1;
+    }
+    hunks
+}
+
+// Myers' diff algorithm. Returns pairs of indices of equal lines in `a` and `b`. `None` is returned when the number of
+// edits exceeds `MAX_EDITS`.
+fn shortest_edit(a: &[String], b: &[String]) -> Option<Vec<(usize, usize)>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = cmp::min((n + m) as usize, MAX_EDITS) as isize;
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    // Snapshot of `v` in range of -d..=d before each step d
+    let mut trace: Vec<Vec<isize>> = vec![];
+
+    let mut found = false;
+    'outer: for d in 0..=max {
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        let mut k = -d;
+        while k <= d {
+            let i = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'outer;
+            }
+            k += 2;
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    let mut equals = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let get = |k: isize| v[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && get(k - 1) < get(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let (prev_x, prev_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let px = get(prev_k);
+            (px, px - prev_k)
+        };
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            equals.push((x as usize, y as usize));
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    equals.reverse();
+    Some(equals)
+}
+
+// Changes of lines compared with the base. Each element is the row and its change, sorted by the row.
+pub fn line_changes(base: &[String], lines: &[String]) -> Vec<(usize, LineChange)> {
+    let mut changes: Vec<(usize, LineChange)> = vec![];
+    for (_, base_len, start, len) in hunks(base, lines) {
+        if len == 0 {
+            let row = cmp::min(start, lines.len().saturating_sub(1));
+            if changes.last().map_or(true, |&(r, _)| r < row) {
+                changes.push((row, LineChange::Removed));
+            }
+            continue;
+        }
+        let modified = cmp::min(base_len, len);
+        for row in start..start + len {
+            let change = if row < start + modified {
+                LineChange::Modified
+            } else {
+                LineChange::Added
+            };
+            if let Some(last) = changes.last_mut() {
+                if last.0 == row {
+                    *last = (row, change);
+                    continue;
+                }
+            }
+            changes.push((row, change));
+        }
+    }
+    changes
+}
+
+// Rows of `a` and `b` aligned side by side. Equal lines are put in the same row. Changed lines in each hunk are paired
+// from the top and the rest is put next to an empty slot (`None`).
+pub fn align_rows(a: &[String], b: &[String]) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut rows = vec![];
+    let (mut x, mut y) = (0, 0);
+    let end = (a.len(), 0, b.len(), 0);
+    for (a_start, a_len, b_start, b_len) in hunks(a, b).into_iter().chain(Some(end)) {
+        while x < a_start {
+            rows.push((Some(x), Some(y)));
+            x += 1;
+            y += 1;
+        }
+        for i in 0..cmp::max(a_len, b_len) {
+            rows.push((
+                (i < a_len).then(|| a_start + i),
+                (i < b_len).then(|| b_start + i),
+            ));
+        }
+        x = a_start + a_len;
+        y = b_start + b_len;
+    }
+    rows
+}
+
+// Byte ranges of the changed parts of two lines. The common prefix and suffix are excluded
+pub fn changed_ranges(a: &str, b: &str) -> ((usize, usize), (usize, usize)) {
+    let prefix: usize = a
+        .chars()
+        .zip(b.chars())
+        .take_while(|(l, r)| l == r)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+    let (a_rest, b_rest) = (&a[prefix..], &b[prefix..]);
+    let suffix: usize = a_rest
+        .chars()
+        .rev()
+        .zip(b_rest.chars().rev())
+        .take_while(|(l, r)| l == r)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+    ((prefix, a.len() - suffix), (prefix, b.len() - suffix))
+}
+
+// Edits to change the text `a` into `b` as (position in `a`, deleted text, inserted text). Each changed hunk of lines
+// is narrowed to its changed characters. The edits are sorted from the end so that applying them in order doesn't move
+// the positions of the rest
+pub fn text_edits(a: &[String], b: &[String]) -> Vec<((usize, usize), String, String)> {
+    let lines_with_newline =
+        |ls: &[String]| ls.iter().map(|l| format!("{}\n", l)).collect::<String>();
+    let newline_with_lines =
+        |ls: &[String]| ls.iter().map(|l| format!("\n{}", l)).collect::<String>();
+
+    let mut edits = vec![];
+    for (a_start, a_len, b_start, b_len) in hunks(a, b).into_iter().rev() {
+        let (a_lines, b_lines) = (&a[a_start..a_start + a_len], &b[b_start..b_start + b_len]);
+        // Include the newline after each line. The hunk at the end of the text has no newline after it, so the newline
+        // before each line is included instead
+        let (pos, old, new) = if a_start + a_len < a.len() {
+            let (old, new) = (lines_with_newline(a_lines), lines_with_newline(b_lines));
+            ((a_start, 0), old, new)
+        } else if a_start > 0 {
+            let (old, new) = (newline_with_lines(a_lines), newline_with_lines(b_lines));
+            ((a_start - 1, a[a_start - 1].chars().count()), old, new)
+        } else {
+            ((0, 0), a.join("\n"), b.join("\n"))
+        };
+
+        let ((prefix, old_end), (_, new_end)) = changed_ranges(&old, &new);
+        let skipped = &old[..prefix];
+        let pos = match skipped.rfind('\n') {
+            Some(i) => (
+                pos.0 + skipped.matches('\n').count(),
+                skipped[i + 1..].chars().count(),
+            ),
+            None => (pos.0, pos.1 + skipped.chars().count()),
+        };
+        edits.push((
+            pos,
+            old[prefix..old_end].to_string(),
+            new[prefix..new_end].to_string(),
+        ));
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn diff_hunks() {
+        let tests = [
+            ("abc", "abc", &[][..]),
+            ("abc", "abxc", &[(2, 0, 2, 1)][..]),
+            ("abc", "ac", &[(1, 1, 1, 0)][..]),
+            ("abc", "axc", &[(1, 1, 1, 1)][..]),
+            (
+                "abcabba",
+                "cbabac",
+                &[(0, 2, 0, 0), (3, 0, 1, 1), (5, 1, 4, 0), (7, 0, 5, 1)][..],
+            ),
+            ("", "ab", &[(0, 0, 0, 2)][..]),
+            ("abcd", "xy", &[(0, 4, 0, 2)][..]),
+            (
+                "xaybz",
+                "aqb",
+                &[(0, 1, 0, 0), (2, 1, 1, 1), (4, 1, 3, 0)][..],
+            ),
+        ];
+        for test in tests {
+            let (a, b, want) = test;
+            assert_eq!(
+                hunks(&lines(a.chars()), &lines(b.chars())),
+                want,
+                "{:?}",
+                test
+            );
+        }
+    }
+
+    #[test]
+    fn changes_of_lines() {
+        use LineChange::*;
+        let tests = [
+            ("abc", "abc", &[][..]),
+            ("abc", "abxc", &[(2, Added)][..]),
+            ("abc", "ac", &[(1, Removed)][..]),
+            ("abc", "ab", &[(1, Removed)][..]),
+            ("abc", "axyc", &[(1, Modified), (2, Added)][..]),
+            ("axyc", "abc", &[(1, Modified)][..]),
+            ("", "", &[][..]),
+        ];
+        for test in tests {
+            let (a, b, want) = test;
+            assert_eq!(
+                line_changes(&lines(a.chars()), &lines(b.chars())),
+                want,
+                "{:?}",
+                test
+            );
+        }
+    }
+
+    #[test]
+    fn minimal_text_edits() {
+        let edit = |row, col, old: &str, new: &str| ((row, col), old.to_string(), new.to_string());
+        let tests = [
+            ("a\nb", "a\nb", vec![]),
+            ("foo\nbar", "foo\nbaz", vec![edit(1, 2, "r", "z")]),
+            ("a\nb\nc", "a\nx\nb\nc", vec![edit(1, 0, "", "x\n")]),
+            ("a\nb\nc", "a\nc", vec![edit(1, 0, "b\n", "")]),
+            ("a\nb", "a\nb\nc", vec![edit(1, 1, "", "\nc")]),
+            ("a\nb\nc", "a", vec![edit(0, 1, "\nb\nc", "")]),
+            ("abc", "xbz", vec![edit(0, 0, "abc", "xbz")]),
+            ("", "あい", vec![edit(0, 0, "", "あい")]),
+            (
+                "a\nfoo\nb\nbar",
+                "a\nfoo!\nb\nbaz",
+                vec![edit(3, 2, "r", "z"), edit(1, 3, "", "!")],
+            ),
+        ];
+        for test in tests {
+            let (a, b, want) = &test;
+            assert_eq!(
+                text_edits(&lines(a.split('\n')), &lines(b.split('\n'))),
+                *want,
+                "{:?}",
+                test
+            );
+        }
+    }
+
+    #[test]
+    fn align_rows_side_by_side() {
+        let tests = [
+            (
+                "abc",
+                "abc",
+                &[(Some(0), Some(0)), (Some(1), Some(1)), (Some(2), Some(2))][..],
+            ),
+            (
+                "abc",
+                "axyc",
+                &[
+                    (Some(0), Some(0)),
+                    (Some(1), Some(1)),
+                    (None, Some(2)),
+                    (Some(2), Some(3)),
+                ][..],
+            ),
+            (
+                "abc",
+                "ac",
+                &[(Some(0), Some(0)), (Some(1), None), (Some(2), Some(1))][..],
+            ),
+            ("", "a", &[(None, Some(0))][..]),
+            ("", "", &[][..]),
+        ];
+        for test in tests {
+            let (a, b, want) = test;
+            assert_eq!(
+                align_rows(&lines(a.chars()), &lines(b.chars())),
+                want,
+                "{:?}",
+                test
+            );
+        }
+    }
+
+    #[test]
+    fn changed_ranges_in_lines() {
+        let tests = [
+            ("abcd", "axyd", ((1, 3), (1, 3))),
+            ("ab", "abc", ((2, 2), (2, 3))),
+            ("aa", "a", ((1, 2), (1, 1))),
+            ("ab", "ab", ((2, 2), (2, 2))),
+            ("あいう", "あえう", ((3, 6), (3, 6))),
+        ];
+        for test in tests {
+            let (a, b, want) = test;
+            assert_eq!(changed_ranges(a, b), want, "{:?}", test);
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use crate::diff::{align_rows, changed_ranges};
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Color, Style};
+use crate::ratatui::text::{Span, Text};
+use crate::ratatui::widgets::{Paragraph, Widget};
+use crate::textarea::TextArea;
+use crate::util::num_digits;
+#[cfg(feature = "ratatui")]
+use ratatui::text::Line;
+use std::cell::{Ref, RefCell};
+use std::cmp;
+#[cfg(feature = "tuirs")]
+use tui::text::Spans as Line;
+
+// Pair of rows of the left and right textareas rendered in the same row. `None` is a filled row
+type AlignedRow = (Option<usize>, Option<usize>);
+
+/// Widget to render two textareas side by side as a diff. Lines are aligned so that equal lines are rendered in the
+/// same row, and rows missing on one side are filled with the filler character. Changed lines and the changed parts
+/// within them are highlighted.
+///
+/// Each side is rendered with the same pipeline as [`TextArea`], so the styles, line numbers, block, and other
+/// decorations of each textarea are used as they are. Both sides are always scrolled together.
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use ratatui::widgets::Widget;
+/// use tui_textarea::{DiffView, TextArea};
+///
+/// let old = TextArea::from(["a", "b", "c"]);
+/// let new = TextArea::from(["a", "x", "c", "d"]);
+/// let view = DiffView::new(old, new);
+///
+/// let area = Rect::new(0, 0, 21, 4);
+/// let mut buf = Buffer::empty(area);
+/// (&view).render(area, &mut buf);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DiffView<'a> {
+    left: TextArea<'a>,
+    right: TextArea<'a>,
+    // Aligned rows of both sides. Computed on the first use after either side was borrowed mutably
+    rows: RefCell<Option<Vec<AlignedRow>>>,
+    top: usize,
+    removed_styles: (Style, Style),
+    added_styles: (Style, Style),
+    filler: (char, Style),
+    separator: (char, Style),
+}
+
+impl<'a> DiffView<'a> {
+    /// Create a new diff view of the old text on the left side and the new text on the right side.
+    /// ```
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let view = DiffView::new(TextArea::from(["a"]), TextArea::from(["b"]));
+    /// assert_eq!(view.left().lines(), ["a"]);
+    /// assert_eq!(view.right().lines(), ["b"]);
+    /// ```
+    pub fn new(left: TextArea<'a>, right: TextArea<'a>) -> Self {
+        Self {
+            left,
+            right,
+            rows: RefCell::new(None),
+            top: 0,
+            removed_styles: (
+                Style::default().bg(Color::Rgb(0x3d, 0x1f, 0x1f)),
+                Style::default().bg(Color::Rgb(0x7a, 0x2e, 0x2e)),
+            ),
+            added_styles: (
+                Style::default().bg(Color::Rgb(0x1f, 0x3d, 0x2b)),
+                Style::default().bg(Color::Rgb(0x2e, 0x6b, 0x44)),
+            ),
+            filler: ('╱', Style::default().fg(Color::DarkGray)),
+            separator: ('│', Style::default()),
+        }
+    }
+
+    /// Get the textarea on the left side.
+    pub fn left(&self) -> &TextArea<'a> {
+        &self.left
+    }
+
+    /// Get the mutable textarea on the left side. The diff is updated on the next rendering.
+    /// ```
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let mut view = DiffView::new(TextArea::from(["a"]), TextArea::from(["a"]));
+    /// view.left_mut().insert_str("b\n");
+    /// assert_eq!(view.rows(), [(Some(0), None), (Some(1), Some(0))]);
+    /// ```
+    pub fn left_mut(&mut self) -> &mut TextArea<'a> {
+        *self.rows.get_mut() = None;
+        &mut self.left
+    }
+
+    /// Get the textarea on the right side.
+    pub fn right(&self) -> &TextArea<'a> {
+        &self.right
+    }
+
+    /// Get the mutable textarea on the right side. The diff is updated on the next rendering.
+    pub fn right_mut(&mut self) -> &mut TextArea<'a> {
+        *self.rows.get_mut() = None;
+        &mut self.right
+    }
+
+    /// Get the textareas on the left and right sides, consuming the view.
+    pub fn into_textareas(self) -> (TextArea<'a>, TextArea<'a>) {
+        (self.left, self.right)
+    }
+
+    /// Get the aligned rows of the view from the top. Each row is a pair of 0-base rows of the left and right
+    /// textareas rendered in it. `None` means that the side is filled since the line doesn't exist on the side.
+    /// ```
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let view = DiffView::new(TextArea::from(["a", "b", "c"]), TextArea::from(["a", "c", "d"]));
+    /// assert_eq!(
+    ///     view.rows(),
+    ///     [
+    ///         (Some(0), Some(0)),
+    ///         (Some(1), None),
+    ///         (Some(2), Some(1)),
+    ///         (None, Some(2)),
+    ///     ],
+    /// );
+    /// ```
+    pub fn rows(&self) -> Vec<(Option<usize>, Option<usize>)> {
+        self.aligned_rows().to_vec()
+    }
+
+    fn aligned_rows(&self) -> Ref<'_, [AlignedRow]> {
+        if self.rows.borrow().is_none() {
+            let rows = align_rows(self.left.lines(), self.right.lines());
+            *self.rows.borrow_mut() = Some(rows);
+        }
+        Ref::map(self.rows.borrow(), |r| r.as_deref().unwrap_or(&[]))
+    }
+
+    /// Scroll both sides of the view by the number of rows. A positive value scrolls down and a negative value scrolls
+    /// up. The scroll position is clamped to the aligned rows.
+    /// ```
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let mut view = DiffView::new(TextArea::from(["a", "b"]), TextArea::from(["a", "b", "c", "d"]));
+    /// view.scroll(2);
+    /// assert_eq!(view.scroll_top(), 2);
+    /// view.scroll(10);
+    /// assert_eq!(view.scroll_top(), 3);
+    /// view.scroll(-10);
+    /// assert_eq!(view.scroll_top(), 0);
+    /// ```
+    pub fn scroll(&mut self, rows: i16) {
+        let max = self.aligned_rows().len().saturating_sub(1);
+        let top = if rows >= 0 {
+            self.top.saturating_add(rows as usize)
+        } else {
+            self.top.saturating_sub(rows.unsigned_abs() as usize)
+        };
+        self.top = cmp::min(top, max);
+    }
+
+    /// Get the 0-base index of the aligned row at the top of the view. See [`DiffView::rows`].
+    pub fn scroll_top(&self) -> usize {
+        self.top
+    }
+
+    /// Scroll the view so that the row of the left textarea is at the top. The right side is scrolled to the aligned
+    /// row together. It returns `false` when the row doesn't exist.
+    /// ```
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let mut view = DiffView::new(TextArea::from(["a", "b"]), TextArea::from(["x", "a", "b"]));
+    /// assert!(view.scroll_to_left_row(1));
+    /// assert_eq!(view.scroll_top(), 2);
+    /// assert!(!view.scroll_to_left_row(2));
+    /// ```
+    pub fn scroll_to_left_row(&mut self, row: usize) -> bool {
+        self.scroll_to(|(l, _)| l == Some(row))
+    }
+
+    /// Scroll the view so that the row of the right textarea is at the top. The left side is scrolled to the aligned
+    /// row together. It returns `false` when the row doesn't exist.
+    pub fn scroll_to_right_row(&mut self, row: usize) -> bool {
+        self.scroll_to(|(_, r)| r == Some(row))
+    }
+
+    fn scroll_to(&mut self, pred: impl Fn(AlignedRow) -> bool) -> bool {
+        let found = self.aligned_rows().iter().copied().position(pred);
+        match found {
+            Some(i) => {
+                self.top = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the styles of removed lines on the left side. `line` is applied to the entire changed lines and `text` is
+    /// applied to the changed parts within them. The default styles are dark red and red backgrounds.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let mut view = DiffView::new(TextArea::default(), TextArea::default());
+    /// let line = Style::default().fg(Color::Red);
+    /// let text = Style::default().bg(Color::Red);
+    /// view.set_removed_styles(line, text);
+    /// assert_eq!(view.removed_styles(), (line, text));
+    /// ```
+    pub fn set_removed_styles(&mut self, line: Style, text: Style) {
+        self.removed_styles = (line, text);
+    }
+
+    /// Get the styles of removed lines and the changed parts within them. See [`DiffView::set_removed_styles`].
+    pub fn removed_styles(&self) -> (Style, Style) {
+        self.removed_styles
+    }
+
+    /// Set the styles of added lines on the right side. `line` is applied to the entire changed lines and `text` is
+    /// applied to the changed parts within them. The default styles are dark green and green backgrounds.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let mut view = DiffView::new(TextArea::default(), TextArea::default());
+    /// let line = Style::default().fg(Color::Green);
+    /// let text = Style::default().bg(Color::Green);
+    /// view.set_added_styles(line, text);
+    /// assert_eq!(view.added_styles(), (line, text));
+    /// ```
+    pub fn set_added_styles(&mut self, line: Style, text: Style) {
+        self.added_styles = (line, text);
+    }
+
+    /// Get the styles of added lines and the changed parts within them. See [`DiffView::set_added_styles`].
+    pub fn added_styles(&self) -> (Style, Style) {
+        self.added_styles
+    }
+
+    /// Set the character and the style to fill rows missing on one side. The default is `'╱'` in dark gray.
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let mut view = DiffView::new(TextArea::default(), TextArea::default());
+    /// view.set_filler(' ', Style::default());
+    /// assert_eq!(view.filler(), (' ', Style::default()));
+    /// ```
+    pub fn set_filler(&mut self, c: char, style: Style) {
+        self.filler = (c, style);
+    }
+
+    /// Get the character and the style to fill missing rows. See [`DiffView::set_filler`].
+    pub fn filler(&self) -> (char, Style) {
+        self.filler
+    }
+
+    /// Set the character and the style of the separator column between both sides. The default is `'│'`.
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::{DiffView, TextArea};
+    ///
+    /// let mut view = DiffView::new(TextArea::default(), TextArea::default());
+    /// view.set_separator('|', Style::default());
+    /// assert_eq!(view.separator(), ('|', Style::default()));
+    /// ```
+    pub fn set_separator(&mut self, c: char, style: Style) {
+        self.separator = (c, style);
+    }
+
+    /// Get the character and the style of the separator column. See [`DiffView::set_separator`].
+    pub fn separator(&self) -> (char, Style) {
+        self.separator
+    }
+
+    // Build the lines of one side. `other` is the textarea on the opposite side to find the changed parts
+    fn side_text<'b>(
+        &'b self,
+        textarea: &'b TextArea<'a>,
+        other: &'b TextArea<'a>,
+        rows: &[AlignedRow],
+        width: u16,
+        styles: (Style, Style),
+    ) -> Text<'b> {
+        let lnum_len = num_digits(textarea.lines().len());
+        let (fill, fill_style) = self.filler;
+        let mut lines = Vec::with_capacity(rows.len());
+        for &(row, other_row) in rows {
+            let row = match row {
+                Some(row) => row,
+                None => {
+                    let text: String = (0..width).map(|_| fill).collect();
+                    lines.push(Line::from(Span::styled(text, fill_style)));
+                    continue;
+                }
+            };
+            let line = textarea.lines()[row].as_str();
+            let mut hl = textarea.line_highlighter(line, row, lnum_len, None);
+            match other_row {
+                Some(r) if other.lines()[r] != line => {
+                    let (range, _) = changed_ranges(line, &other.lines()[r]);
+                    hl.line_style(styles.0);
+                    hl.changes(Some((range.0, range.1, styles.1)).into_iter());
+                }
+                Some(_) => {}
+                None => hl.line_style(styles.0),
+            }
+            lines.push(hl.into_spans());
+        }
+        Text::from(lines)
+    }
+
+    fn render_side(&self, left: bool, rows: &[AlignedRow], area: Rect, buf: &mut Buffer) {
+        let (textarea, other, styles) = if left {
+            (&self.left, &self.right, self.removed_styles)
+        } else {
+            (&self.right, &self.left, self.added_styles)
+        };
+        let inner = if let Some(b) = textarea.block() {
+            b.clone().render(area, buf);
+            b.inner(area)
+        } else {
+            area
+        };
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|&(l, r)| if left { (l, r) } else { (r, l) })
+            .take(inner.height as usize)
+            .collect();
+        let text = self.side_text(textarea, other, &rows, inner.width, styles);
+        Paragraph::new(text)
+            .style(textarea.style())
+            .render(inner, buf);
+    }
+}
+
+impl<'a, 'b> Widget for &'b DiffView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = self.aligned_rows();
+        let top = cmp::min(self.top, rows.len().saturating_sub(1));
+        let rows = &rows[top..];
+
+        // Split the area into both sides and the separator column between them
+        let width = area.width.saturating_sub(1) / 2;
+        let left = Rect { width, ..area };
+        let right = Rect {
+            x: area.x + width + 1,
+            width: area.width.saturating_sub(width + 1),
+            ..area
+        };
+        if area.width > width {
+            let (c, style) = self.separator;
+            let x = area.x + width;
+            for y in area.top()..area.bottom() {
+                buf.get_mut(x, y).set_char(c).set_style(style);
+            }
+        }
+
+        self.render_side(true, rows, left, buf);
+        self.render_side(false, rows, right, buf);
+    }
+}
+
+// Tests for buffer contents don't work with tui-rs
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+    use crate::ratatui::style::Modifier;
+
+    fn render(view: &DiffView, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf.get(x, y).symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn render_side_by_side() {
+        let mut view = DiffView::new(
+            TextArea::from(["a", "b", "c"]),
+            TextArea::from(["a", "xy", "c", "d"]),
+        );
+        assert_eq!(
+            render(&view, 9, 4),
+            ["a   │a   ", "b   │xy  ", "c   │c   ", "╱╱╱╱│d   "],
+        );
+
+        // Both sides are scrolled together
+        view.scroll(2);
+        assert_eq!(render(&view, 9, 3), ["c   │c   ", "╱╱╱╱│d   ", "    │    "]);
+    }
+
+    #[test]
+    fn highlight_changed_text() {
+        let line = Style::default().fg(Color::Red);
+        let text = Style::default().add_modifier(Modifier::BOLD);
+        let mut left = TextArea::from(["abcd"]);
+        left.move_cursor(crate::CursorMove::End);
+        let mut view = DiffView::new(left, TextArea::from(["axyd"]));
+        view.set_removed_styles(line, text);
+
+        let area = Rect::new(0, 0, 11, 1);
+        let mut buf = Buffer::empty(area);
+        (&view).render(area, &mut buf);
+        let styles: Vec<_> = (0..4).map(|x| buf.get(x, 0).style()).collect();
+        assert_eq!(styles[0].fg, Some(Color::Red));
+        assert!(!styles[0].add_modifier.contains(Modifier::BOLD));
+        assert!(styles[1].add_modifier.contains(Modifier::BOLD));
+        assert!(styles[2].add_modifier.contains(Modifier::BOLD));
+        assert_eq!(styles[3].fg, Some(Color::Red));
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+
+// Digraphs of RFC 1345 which are also the defaults of Vim. Only commonly used characters are included
+// https://www.rfc-editor.org/rfc/rfc1345
+const RFC1345: &[(&str, char)] = &[
+    // Latin-1 supplement
+    ("NS", '\u{a0}'),
+    ("!I", '¡'),
+    ("Ct", '¢'),
+    ("Pd", '£'),
+    ("Cu", '¤'),
+    ("Ye", '¥'),
+    ("BB", '¦'),
+    ("SE", '§'),
+    ("':", '¨'),
+    ("Co", '©'),
+    ("-a", 'ª'),
+    ("<<", '«'),
+    ("NO", '¬'),
+    ("--", '\u{ad}'),
+    ("Rg", '®'),
+    ("'m", '¯'),
+    ("DG", '°'),
+    ("+-", '±'),
+    ("2S", '²'),
+    ("3S", '³'),
+    ("''", '´'),
+    ("My", 'µ'),
+    ("PI", '¶'),
+    (".M", '·'),
+    ("',", '¸'),
+    ("1S", '¹'),
+    ("-o", 'º'),
+    (">>", '»'),
+    ("14", '¼'),
+    ("12", '½'),
+    ("34", '¾'),
+    ("?I", '¿'),
+    ("A!", 'À'),
+    ("A'", 'Á'),
+    ("A>", 'Â'),
+    ("A?", 'Ã'),
+    ("A:", 'Ä'),
+    ("AA", 'Å'),
+    ("AE", 'Æ'),
+    ("C,", 'Ç'),
+    ("E!", 'È'),
+    ("E'", 'É'),
+    ("E>", 'Ê'),
+    ("E:", 'Ë'),
+    ("I!", 'Ì'),
+    ("I'", 'Í'),
+    ("I>", 'Î'),
+    ("I:", 'Ï'),
+    ("D-", 'Ð'),
+    ("N?", 'Ñ'),
+    ("O!", 'Ò'),
+    ("O'", 'Ó'),
+    ("O>", 'Ô'),
+    ("O?", 'Õ'),
+    ("O:", 'Ö'),
+    ("*X", '×'),
+    ("O/", 'Ø'),
+    ("U!", 'Ù'),
+    ("U'", 'Ú'),
+    ("U>", 'Û'),
+    ("U:", 'Ü'),
+    ("Y'", 'Ý'),
+    ("TH", 'Þ'),
+    ("ss", 'ß'),
+    ("a!", 'à'),
+    ("a'", 'á'),
+    ("a>", 'â'),
+    ("a?", 'ã'),
+    ("a:", 'ä'),
+    ("aa", 'å'),
+    ("ae", 'æ'),
+    ("c,", 'ç'),
+    ("e!", 'è'),
+    ("e'", 'é'),
+    ("e>", 'ê'),
+    ("e:", 'ë'),
+    ("i!", 'ì'),
+    ("i'", 'í'),
+    ("i>", 'î'),
+    ("i:", 'ï'),
+    ("d-", 'ð'),
+    ("n?", 'ñ'),
+    ("o!", 'ò'),
+    ("o'", 'ó'),
+    ("o>", 'ô'),
+    ("o?", 'õ'),
+    ("o:", 'ö'),
+    ("-:", '÷'),
+    ("o/", 'ø'),
+    ("u!", 'ù'),
+    ("u'", 'ú'),
+    ("u>", 'û'),
+    ("u:", 'ü'),
+    ("y'", 'ý'),
+    ("th", 'þ'),
+    ("y:", 'ÿ'),
+    // Latin extended-A
+    ("A-", 'Ā'),
+    ("a-", 'ā'),
+    ("A(", 'Ă'),
+    ("a(", 'ă'),
+    ("A;", 'Ą'),
+    ("a;", 'ą'),
+    ("C'", 'Ć'),
+    ("c'", 'ć'),
+    ("C<", 'Č'),
+    ("c<", 'č'),
+    ("D<", 'Ď'),
+    ("d<", 'ď'),
+    ("E-", 'Ē'),
+    ("e-", 'ē'),
+    ("E;", 'Ę'),
+    ("e;", 'ę'),
+    ("E<", 'Ě'),
+    ("e<", 'ě'),
+    ("G(", 'Ğ'),
+    ("g(", 'ğ'),
+    ("I-", 'Ī'),
+    ("i-", 'ī'),
+    ("I.", 'İ'),
+    ("i.", 'ı'),
+    ("L/", 'Ł'),
+    ("l/", 'ł'),
+    ("N'", 'Ń'),
+    ("n'", 'ń'),
+    ("N<", 'Ň'),
+    ("n<", 'ň'),
+    ("O\"", 'Ő'),
+    ("o\"", 'ő'),
+    ("OE", 'Œ'),
+    ("oe", 'œ'),
+    ("R<", 'Ř'),
+    ("r<", 'ř'),
+    ("S'", 'Ś'),
+    ("s'", 'ś'),
+    ("S,", 'Ş'),
+    ("s,", 'ş'),
+    ("S<", 'Š'),
+    ("s<", 'š'),
+    ("T<", 'Ť'),
+    ("t<", 'ť'),
+    ("U0", 'Ů'),
+    ("u0", 'ů'),
+    ("U\"", 'Ű'),
+    ("u\"", 'ű'),
+    ("Y:", 'Ÿ'),
+    ("Z'", 'Ź'),
+    ("z'", 'ź'),
+    ("Z.", 'Ż'),
+    ("z.", 'ż'),
+    ("Z<", 'Ž'),
+    ("z<", 'ž'),
+    // Greek
+    ("A*", 'Α'),
+    ("B*", 'Β'),
+    ("G*", 'Γ'),
+    ("D*", 'Δ'),
+    ("E*", 'Ε'),
+    ("Z*", 'Ζ'),
+    ("Y*", 'Η'),
+    ("H*", 'Θ'),
+    ("I*", 'Ι'),
+    ("K*", 'Κ'),
+    ("L*", 'Λ'),
+    ("M*", 'Μ'),
+    ("N*", 'Ν'),
+    ("C*", 'Ξ'),
+    ("O*", 'Ο'),
+    ("P*", 'Π'),
+    ("R*", 'Ρ'),
+    ("S*", 'Σ'),
+    ("T*", 'Τ'),
+    ("U*", 'Υ'),
+    ("F*", 'Φ'),
+    ("X*", 'Χ'),
+    ("Q*", 'Ψ'),
+    ("W*", 'Ω'),
+    ("a*", 'α'),
+    ("b*", 'β'),
+    ("g*", 'γ'),
+    ("d*", 'δ'),
+    ("e*", 'ε'),
+    ("z*", 'ζ'),
+    ("y*", 'η'),
+    ("h*", 'θ'),
+    ("i*", 'ι'),
+    ("k*", 'κ'),
+    ("l*", 'λ'),
+    ("m*", 'μ'),
+    ("n*", 'ν'),
+    ("c*", 'ξ'),
+    ("o*", 'ο'),
+    ("p*", 'π'),
+    ("r*", 'ρ'),
+    ("*s", 'ς'),
+    ("s*", 'σ'),
+    ("t*", 'τ'),
+    ("u*", 'υ'),
+    ("f*", 'φ'),
+    ("x*", 'χ'),
+    ("q*", 'ψ'),
+    ("w*", 'ω'),
+    // Punctuation and symbols
+    ("-N", '–'),
+    ("-M", '—'),
+    ("'6", '‘'),
+    ("'9", '’'),
+    (".9", '‚'),
+    ("\"6", '“'),
+    ("\"9", '”'),
+    (":9", '„'),
+    ("/-", '†'),
+    ("/=", '‡'),
+    ("..", '‥'),
+    (",.", '…'),
+    ("%0", '‰'),
+    ("<1", '‹'),
+    (">1", '›'),
+    ("Eu", '€'),
+    ("oC", '℃'),
+    ("TM", '™'),
+    ("OK", '✓'),
+    ("XX", '✗'),
+    // Arrows
+    ("<-", '←'),
+    ("-!", '↑'),
+    ("->", '→'),
+    ("-v", '↓'),
+    ("<>", '↔'),
+    ("UD", '↕'),
+    ("<=", '⇐'),
+    ("=>", '⇒'),
+    ("==", '⇔'),
+    // Mathematical operators
+    ("FA", '∀'),
+    ("dP", '∂'),
+    ("TE", '∃'),
+    ("/0", '∅'),
+    ("DE", '∆'),
+    ("NB", '∇'),
+    ("(-", '∈'),
+    ("-)", '∋'),
+    ("*P", '∏'),
+    ("+Z", '∑'),
+    ("-2", '−'),
+    ("RT", '√'),
+    ("0(", '∝'),
+    ("00", '∞'),
+    ("AN", '∧'),
+    ("OR", '∨'),
+    ("(U", '∩'),
+    (")U", '∪'),
+    ("In", '∫'),
+    (".:", '∴'),
+    (":.", '∵'),
+    ("?2", '≈'),
+    ("!=", '≠'),
+    ("=3", '≡'),
+    ("=<", '≤'),
+    (">=", '≥'),
+    ("(C", '⊂'),
+    (")C", '⊃'),
+    ("(_", '⊆'),
+    (")_", '⊇'),
+    ("-T", '⊥'),
+];
+
+fn rfc1345(first: char, second: char) -> Option<char> {
+    RFC1345.iter().find_map(|&(d, c)| {
+        let mut chars = d.chars();
+        (chars.next() == Some(first) && chars.next() == Some(second)).then(|| c)
+    })
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Digraphs {
+    custom: HashMap<(char, char), char>,
+}
+
+impl Digraphs {
+    pub fn insert(&mut self, first: char, second: char, c: char) {
+        self.custom.insert((first, second), c);
+    }
+
+    // Get the character of the digraph. Digraphs registered by users take precedence over RFC 1345. Like Vim, the
+    // characters in reverse order are also tried when the digraph is not found
+    pub fn get(&self, first: char, second: char) -> Option<char> {
+        let find = |a, b| self.custom.get(&(a, b)).copied().or_else(|| rfc1345(a, b));
+        find(first, second).or_else(|| find(second, first))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc1345_digraphs_are_unique() {
+        for (i, (d, _)) in RFC1345.iter().enumerate() {
+            assert_eq!(d.chars().count(), 2, "{:?}", d);
+            assert!(RFC1345[i + 1..].iter().all(|(e, _)| e != d), "{:?}", d);
+        }
+    }
+
+    #[test]
+    fn get_digraph() {
+        let mut d = Digraphs::default();
+        d.insert('s', 'm', '☺');
+        d.insert('-', '>', '⟶');
+
+        let tests = [
+            ('a', ':', Some('ä')),
+            (':', 'a', Some('ä')),
+            ('e', '\'', Some('é')),
+            ('<', '-', Some('←')),
+            ('a', '*', Some('α')),
+            ('s', 'm', Some('☺')),
+            ('m', 's', Some('☺')),
+            ('-', '>', Some('⟶')),
+            ('q', 'q', None),
+        ];
+        for test in tests {
+            let (first, second, want) = test;
+            assert_eq!(d.get(first, second), want, "{:?}", test);
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use crate::newline::FinalNewline;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Settings of the textarea read from `.editorconfig` files. Properties which are not set are `None`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Settings {
+    pub hard_tab: Option<bool>,
+    pub tab_len: Option<u8>,
+    pub final_newline: Option<FinalNewline>,
+}
+
+impl Settings {
+    fn from_properties(props: &HashMap<String, String>) -> Self {
+        let hard_tab = match props.get("indent_style").map(String::as_str) {
+            Some("tab") => Some(true),
+            Some("space") => Some(false),
+            _ => None,
+        };
+        let width = |key: &str| {
+            props
+                .get(key)
+                .and_then(|v| v.parse::<u8>().ok())
+                .filter(|&w| w > 0)
+        };
+        // `indent_size = tab` means the width of `tab_width`
+        let tab_len = if hard_tab == Some(true) {
+            width("tab_width").or_else(|| width("indent_size"))
+        } else {
+            width("indent_size").or_else(|| width("tab_width"))
+        };
+        let final_newline = match props.get("insert_final_newline").map(String::as_str) {
+            Some("true") => Some(FinalNewline::Always),
+            Some("false") => Some(FinalNewline::Never),
+            _ => None,
+        };
+        Self {
+            hard_tab,
+            tab_len,
+            final_newline,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// Read `.editorconfig` files in the directories of the file and its ancestors until a file with `root = true`, and
+// return the settings for the file. Closer files take precedence
+pub fn load(path: &Path) -> io::Result<Settings> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    let mut configs = vec![];
+    let mut dir = path.parent();
+    while let Some(d) = dir {
+        match fs::read_to_string(d.join(".editorconfig")) {
+            Ok(content) => {
+                let root = is_root(&content);
+                configs.push((d, content));
+                if root {
+                    break;
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        dir = d.parent();
+    }
+
+    let mut props = HashMap::new();
+    for (dir, content) in configs.iter().rev() {
+        let rel = path.strip_prefix(dir).unwrap_or(&path);
+        let rel = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        apply_sections(content, &rel, &mut props);
+    }
+    Ok(Settings::from_properties(&props))
+}
+
+// Parse `key = value` line. Keys are case insensitive
+fn property(line: &str) -> Option<(String, &str)> {
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim().to_ascii_lowercase(), value.trim()))
+}
+
+fn is_comment(line: &str) -> bool {
+    line.is_empty() || line.starts_with('#') || line.starts_with(';')
+}
+
+// Check `root = true` in the preamble before the first section
+fn is_root(content: &str) -> bool {
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            break;
+        }
+        if is_comment(line) {
+            continue;
+        }
+        if let Some((key, value)) = property(line) {
+            if key == "root" && value.eq_ignore_ascii_case("true") {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// Apply the properties in the sections matching the path relative to the directory of the `.editorconfig` file.
+// Later sections override earlier ones and `unset` removes the property
+fn apply_sections(content: &str, rel: &str, props: &mut HashMap<String, String>) {
+    let name = rel.rsplit('/').next().unwrap_or(rel);
+    let mut matched = false;
+    for line in content.lines().map(str::trim) {
+        if is_comment(line) {
+            continue;
+        }
+        if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            // A glob without `/` matches the file name in any directory
+            matched = match glob.strip_prefix('/') {
+                Some(glob) => glob_match(glob, rel),
+                None if glob.contains('/') => glob_match(glob, rel),
+                None => glob_match(glob, name),
+            };
+            continue;
+        }
+        if !matched {
+            continue;
+        }
+        if let Some((key, value)) = property(line) {
+            let value = value.to_ascii_lowercase();
+            if value == "unset" {
+                props.remove(&key);
+            } else {
+                props.insert(key, value);
+            }
+        }
+    }
+}
+
+// Match the path with the glob of a section. `*` matches any characters except for `/`, `**` matches any
+// characters, `?` matches one character, `[abc]`, `[a-z]` and `[!abc]` match one character in (or not in) the set,
+// and `{a,b}` matches one of the alternatives
+fn glob_match(glob: &str, path: &str) -> bool {
+    let path = path.chars().collect::<Vec<_>>();
+    expand_braces(glob)
+        .iter()
+        .any(|g| match_chars(&g.chars().collect::<Vec<_>>(), &path))
+}
+
+// Expand `{a,b}` into separate globs. Braces without a comma are matched literally
+fn expand_braces(glob: &str) -> Vec<String> {
+    let chars = glob.chars().collect::<Vec<_>>();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut commas = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => {
+                if depth == 0 {
+                    start = i;
+                    commas.clear();
+                }
+                depth += 1;
+            }
+            ',' if depth == 1 => commas.push(i),
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 && !commas.is_empty() {
+                    let prefix = chars[..start].iter().collect::<String>();
+                    let suffix = chars[i + 1..].iter().collect::<String>();
+                    let mut bounds = vec![start];
+                    bounds.extend(&commas);
+                    bounds.push(i);
+                    return bounds
+                        .windows(2)
+                        .flat_map(|w| {
+                            let alt = chars[w[0] + 1..w[1]].iter().collect::<String>();
+                            expand_braces(&format!("{}{}{}", prefix, alt, suffix))
+                        })
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    vec![glob.to_string()]
+}
+
+fn match_chars(glob: &[char], path: &[char]) -> bool {
+    match glob {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `a/**/b` also matches `a/b`
+            if let ['/', after @ ..] = rest {
+                if match_chars(after, path) {
+                    return true;
+                }
+            }
+            (0..=path.len()).any(|i| match_chars(rest, &path[i..]))
+        }
+        ['*', rest @ ..] => {
+            for i in 0..=path.len() {
+                if match_chars(rest, &path[i..]) {
+                    return true;
+                }
+                if path.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        ['?', rest @ ..] => match path {
+            [c, tail @ ..] if *c != '/' => match_chars(rest, tail),
+            _ => false,
+        },
+        ['[', class @ ..] => {
+            let negated = class.first() == Some(&'!');
+            let set = if negated { &class[1..] } else { class };
+            match set.iter().skip(1).position(|&c| c == ']') {
+                Some(end) => {
+                    let (set, rest) = (&set[..end + 1], &set[end + 2..]);
+                    match path {
+                        [c, tail @ ..] if *c != '/' && in_class(set, *c) != negated => {
+                            match_chars(rest, tail)
+                        }
+                        _ => false,
+                    }
+                }
+                // Unclosed `[` is matched literally
+                None => path.first() == Some(&'[') && match_chars(class, &path[1..]),
+            }
+        }
+        ['\\', c, rest @ ..] | [c, rest @ ..] => {
+            path.first() == Some(c) && match_chars(rest, &path[1..])
+        }
+    }
+}
+
+fn in_class(set: &[char], c: char) -> bool {
+    let mut i = 0;
+    while i < set.len() {
+        if i + 2 < set.len() && set[i + 1] == '-' {
+            if set[i] <= c && c <= set[i + 2] {
+                return true;
+            }
+            i += 3;
+        } else {
+            if set[i] == c {
+                return true;
+            }
+            i += 1;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_section_glob() {
+        let tests = [
+            ("*", "main.rs", true),
+            ("*.rs", "main.rs", true),
+            ("*.rs", "src/main.rs", false),
+            ("src/*.rs", "src/main.rs", true),
+            ("**.rs", "src/a/main.rs", true),
+            ("src/**/main.rs", "src/main.rs", true),
+            ("src/**/main.rs", "src/a/b/main.rs", true),
+            ("?.md", "a.md", true),
+            ("?.md", "ab.md", false),
+            ("*.{js,ts}", "a.ts", true),
+            ("*.{js,ts}", "a.rs", false),
+            ("{a,b{c,d}}.txt", "bd.txt", true),
+            ("{a}.txt", "{a}.txt", true),
+            ("[Mm]akefile", "makefile", true),
+            ("[a-c].txt", "b.txt", true),
+            ("[!a-c].txt", "b.txt", false),
+            ("[!a-c].txt", "d.txt", true),
+            ("[ab", "[ab", true),
+            ("\\*.txt", "*.txt", true),
+            ("\\*.txt", "a.txt", false),
+        ];
+        for (glob, path, want) in tests {
+            assert_eq!(glob_match(glob, path), want, "{:?} {:?}", glob, path);
+        }
+    }
+
+    #[test]
+    fn settings_from_sections() {
+        let content = "
+root = true
+
+[*]
+indent_style = space
+indent_size = 4
+insert_final_newline = true
+
+# Makefile requires tabs
+[Makefile]
+indent_style = tab
+indent_size = tab
+tab_width = 8
+
+[*.md]
+insert_final_newline = unset
+";
+        assert!(is_root(content));
+        assert!(!is_root("[*]\nroot = true\n"));
+
+        let tests = [
+            (
+                "src/main.rs",
+                Settings {
+                    hard_tab: Some(false),
+                    tab_len: Some(4),
+                    final_newline: Some(FinalNewline::Always),
+                },
+            ),
+            (
+                "Makefile",
+                Settings {
+                    hard_tab: Some(true),
+                    tab_len: Some(8),
+                    final_newline: Some(FinalNewline::Always),
+                },
+            ),
+            (
+                "README.md",
+                Settings {
+                    hard_tab: Some(false),
+                    tab_len: Some(4),
+                    final_newline: None,
+                },
+            ),
+        ];
+        for (path, want) in tests {
+            let mut props = HashMap::new();
+            apply_sections(content, path, &mut props);
+            assert_eq!(Settings::from_properties(&props), want, "{:?}", path);
+        }
+        assert!(Settings::default().is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::width::{str_width, AmbiguousWidth};
+use std::cmp;
+
+// Display widths of the cells terminated by tabs in the line. The text after the last tab is not a cell
+fn cell_widths(line: &str, ambiguous: AmbiguousWidth) -> Vec<usize> {
+    let mut cells = line
+        .split('\t')
+        .map(|c| str_width(c, ambiguous))
+        .collect::<Vec<_>>();
+    cells.pop();
+    cells
+}
+
+// Cell widths of the lines until a line without tab
+fn tabbed_lines<'a>(
+    lines: impl Iterator<Item = &'a String>,
+    ambiguous: AmbiguousWidth,
+) -> Vec<Vec<usize>> {
+    lines
+        .map(|l| cell_widths(l, ambiguous))
+        .take_while(|c| !c.is_empty())
+        .collect()
+}
+
+// Display widths of the tabs in the line at the row with elastic tabstops. The cells at the same index in adjacent
+// lines form a column block, and the tabs in the block are expanded so that the cells are aligned. A column is at least
+// `min_width` wide and has at least one space after the widest cell.
+// https://nick-gravgaard.com/elastic-tabstops/
+pub fn tab_widths(
+    lines: &[String],
+    row: usize,
+    min_width: usize,
+    ambiguous: AmbiguousWidth,
+) -> Vec<usize> {
+    let cells = cell_widths(&lines[row], ambiguous);
+    if cells.is_empty() {
+        return cells;
+    }
+    let above = tabbed_lines(lines[..row].iter().rev(), ambiguous);
+    let below = tabbed_lines(lines[row + 1..].iter(), ambiguous);
+
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, &width)| {
+            let in_column = |c: &&Vec<usize>| c.len() > i;
+            let max = above
+                .iter()
+                .take_while(in_column)
+                .chain(below.iter().take_while(in_column))
+                .fold(width, |max, c| cmp::max(max, c[i]));
+            cmp::max(max + 1, min_width) - width
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elastic_tab_widths() {
+        let lines = [
+            "name\tage\tcity",
+            "alice\t30\tparis",
+            "bob\t4\tlondon",
+            "",
+            "x\ty",
+            "\tindented",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+
+        let tests = [
+            (0, vec![2, 1]),
+            (1, vec![1, 2]),
+            (2, vec![3, 3]),
+            (3, vec![]),
+            (4, vec![3]),
+            (5, vec![4]),
+        ];
+        for (row, want) in tests {
+            let widths = tab_widths(&lines, row, 4, AmbiguousWidth::Narrow);
+            assert_eq!(widths, want, "row {}", row);
+        }
+
+        // A column block ends at the line which doesn't have the cell
+        let lines = ["a\tb\tc", "aaaaaa\tb", "a\tbbbbbbbb\tc"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        let narrow = AmbiguousWidth::Narrow;
+        assert_eq!(tab_widths(&lines, 0, 2, narrow), vec![6, 1]);
+        assert_eq!(tab_widths(&lines, 2, 2, narrow), vec![6, 1]);
+
+        // Ambiguous characters are wider with the wide setting
+        let lines = ["○×\tx", "abc\ty"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(tab_widths(&lines, 0, 2, narrow), vec![2]);
+        assert_eq!(tab_widths(&lines, 0, 2, AmbiguousWidth::Wide), vec![1]);
+    }
+}
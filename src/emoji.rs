@@ -0,0 +1,158 @@
+use crate::util::byte_offset;
+use crate::word::is_word_char;
+use std::collections::HashMap;
+
+// Common shortcodes of GitHub and Slack sorted by the names
+const BUILTIN: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("angry", "😠"),
+    ("bug", "🐛"),
+    ("clap", "👏"),
+    ("cry", "😢"),
+    ("eyes", "👀"),
+    ("fire", "🔥"),
+    ("grin", "😁"),
+    ("heart", "❤️"),
+    ("joy", "😂"),
+    ("laughing", "😆"),
+    ("ok_hand", "👌"),
+    ("pray", "🙏"),
+    ("rocket", "🚀"),
+    ("slightly_smiling_face", "🙂"),
+    ("smile", "😄"),
+    ("sob", "😭"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("sunglasses", "😎"),
+    ("tada", "🎉"),
+    ("thinking", "🤔"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("warning", "⚠️"),
+    ("wave", "👋"),
+    ("white_check_mark", "✅"),
+    ("wink", "😉"),
+    ("x", "❌"),
+];
+
+fn builtin(name: &str) -> Option<&'static str> {
+    let i = BUILTIN.binary_search_by_key(&name, |&(n, _)| n).ok()?;
+    Some(BUILTIN[i].1)
+}
+
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-')
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Shortcodes {
+    // Shortcodes registered by users. `None` hides the built-in shortcode
+    custom: HashMap<String, Option<String>>,
+    without_builtin: bool,
+}
+
+impl Shortcodes {
+    pub fn insert(&mut self, name: String, emoji: String) {
+        self.custom.insert(name, Some(emoji));
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        let found = self.get(name).is_some();
+        if !self.without_builtin && builtin(name).is_some() {
+            self.custom.insert(name.to_string(), None);
+        } else {
+            self.custom.remove(name);
+        }
+        found
+    }
+
+    // Remove all shortcodes including the built-in ones
+    pub fn clear(&mut self) {
+        self.custom.clear();
+        self.without_builtin = true;
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        match self.custom.get(name) {
+            Some(emoji) => emoji.as_deref(),
+            None if self.without_builtin => None,
+            None => builtin(name),
+        }
+    }
+
+    // Find the shortcode like `:tada:` which ends at `col` in the line. Returns the start column of the shortcode and
+    // its emoji. A shortcode following a word character like `a:b:` is not matched.
+    pub fn find(&self, line: &str, col: usize) -> Option<(usize, &str)> {
+        let head = line[..byte_offset(line, col)].strip_suffix(':')?;
+        let start = head.rfind(':')?;
+        let name = &head[start + 1..];
+        if name.is_empty() || !name.chars().all(is_shortcode_char) {
+            return None;
+        }
+        if line[..start]
+            .chars()
+            .next_back()
+            .map_or(false, is_word_char)
+        {
+            return None;
+        }
+        let emoji = self.get(name)?;
+        Some((line[..start].chars().count(), emoji))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_shortcodes_are_sorted() {
+        for w in BUILTIN.windows(2) {
+            assert!(w[0].0 < w[1].0, "{:?}", w);
+        }
+    }
+
+    #[test]
+    fn find_shortcode() {
+        let mut s = Shortcodes::default();
+        s.insert("ferris".into(), "🦀".into());
+
+        let tests = [
+            (":tada:", 6, Some((0, "🎉"))),
+            ("yay :tada:", 10, Some((4, "🎉"))),
+            ("(:+1:", 5, Some((1, "👍"))),
+            ("あ :ferris:", 10, Some((2, "🦀"))),
+            (":tada:", 5, None),
+            (":tada", 5, None),
+            ("a:tada:", 7, None),
+            ("::", 2, None),
+            (":ta da:", 7, None),
+            (":unknown:", 9, None),
+        ];
+        for test in tests {
+            let (line, col, want) = test;
+            assert_eq!(s.find(line, col), want, "{:?}", test);
+        }
+    }
+
+    #[test]
+    fn customize_shortcodes() {
+        let mut s = Shortcodes::default();
+        assert_eq!(s.get("tada"), Some("🎉"));
+
+        s.insert("tada".into(), "🥳".into());
+        assert_eq!(s.get("tada"), Some("🥳"));
+        assert!(s.remove("tada"));
+        assert_eq!(s.get("tada"), None);
+        assert!(!s.remove("tada"));
+
+        s.insert("ferris".into(), "🦀".into());
+        s.clear();
+        assert_eq!(s.get("ferris"), None);
+        assert_eq!(s.get("smile"), None);
+        s.insert("smile".into(), ":)".into());
+        assert_eq!(s.get("smile"), Some(":)"));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::word::{find_word_range, WordBoundary};
+use std::cmp;
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+const QUOTES: [char; 3] = ['"', '\'', '`'];
+
+// Selection start and cursor of a selection
+type Selection = (Option<(usize, usize)>, (usize, usize));
+
+// Selections before they were expanded by `TextArea::expand_selection`. `expanded` is the selection made by the last
+// expansion to detect the selection was changed by other operations
+#[derive(Clone, Debug, Default)]
+pub struct ExpandHistory {
+    pub entries: Vec<Selection>,
+    pub expanded: Option<((usize, usize), (usize, usize))>,
+}
+
+fn len(lines: &[String], row: usize) -> usize {
+    lines[row].chars().count()
+}
+
+// Number of characters in the range. A newline is counted as one character
+fn span(lines: &[String], start: (usize, usize), end: (usize, usize)) -> usize {
+    if start.0 == end.0 {
+        return end.1 - start.1;
+    }
+    let middle: usize = (start.0 + 1..end.0).map(|r| len(lines, r) + 1).sum();
+    len(lines, start.0) - start.1 + 1 + middle + end.1
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+// Find the quoted strings in the line as pairs of the columns of the opening quote and the closing quote. A quote
+// escaped with a backslash is not counted
+fn quote_pairs(line: &str) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    for quote in QUOTES {
+        let mut open = None;
+        let mut escaped = false;
+        for (i, c) in line.chars().enumerate() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                match open.take() {
+                    Some(o) => pairs.push((o, i)),
+                    None => open = Some(i),
+                }
+            }
+        }
+    }
+    pairs
+}
+
+// Find the matching closing bracket of the opening bracket at the position
+fn find_closer(
+    lines: &[String],
+    pos: (usize, usize),
+    open: char,
+    close: char,
+) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    for (row, line) in lines.iter().enumerate().skip(pos.0) {
+        let skip = if row == pos.0 { pos.1 + 1 } else { 0 };
+        for (col, c) in line.chars().enumerate().skip(skip) {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                if depth == 0 {
+                    return Some((row, col));
+                }
+                depth -= 1;
+            }
+        }
+    }
+    None
+}
+
+// Find the innermost bracket pair enclosing the range. Returns the positions of the opening bracket and the closing
+// bracket
+fn enclosing_brackets(
+    lines: &[String],
+    start: (usize, usize),
+    end: (usize, usize),
+) -> Option<((usize, usize), (usize, usize))> {
+    // Closing brackets found while scanning backward. Brackets between them and their opening brackets are skipped
+    let mut closers = vec![];
+    for row in (0..=start.0).rev() {
+        let chars: Vec<char> = lines[row].chars().collect();
+        let upto = if row == start.0 {
+            cmp::min(start.1, chars.len())
+        } else {
+            chars.len()
+        };
+        for col in (0..upto).rev() {
+            let c = chars[col];
+            if BRACKETS.iter().any(|(_, close)| *close == c) {
+                closers.push(c);
+                continue;
+            }
+            let close = match BRACKETS.iter().find(|(open, _)| *open == c) {
+                Some((_, close)) => *close,
+                None => continue,
+            };
+            if closers.last() == Some(&close) {
+                closers.pop();
+            } else if closers.is_empty() {
+                match find_closer(lines, (row, col), c, close) {
+                    Some(closer) if closer >= end => return Some(((row, col), closer)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    None
+}
+
+// Find the smallest structural range which strictly contains the range. Candidates are a word, a quoted string, a
+// bracket pair (inner and outer), lines, a paragraph, and the entire text.
+pub fn expand_range(
+    lines: &[String],
+    start: (usize, usize),
+    end: (usize, usize),
+    words: &WordBoundary,
+) -> Option<((usize, usize), (usize, usize))> {
+    let mut candidates = vec![];
+
+    if start.0 == end.0 {
+        let line = &lines[start.0];
+        let (s, e) = find_word_range(line, start.1, words);
+        candidates.push(((start.0, s), (start.0, e)));
+        for (open, close) in quote_pairs(line) {
+            candidates.push(((start.0, open + 1), (start.0, close)));
+            candidates.push(((start.0, open), (start.0, close + 1)));
+        }
+    }
+
+    if let Some((open, close)) = enclosing_brackets(lines, start, end) {
+        candidates.push(((open.0, open.1 + 1), close));
+        candidates.push((open, (close.0, close.1 + 1)));
+    }
+
+    candidates.push(((start.0, 0), (end.0, len(lines, end.0))));
+
+    let mut first = start.0;
+    while first > 0 && !is_blank(&lines[first - 1]) {
+        first -= 1;
+    }
+    let mut last = end.0;
+    while last + 1 < lines.len() && !is_blank(&lines[last + 1]) {
+        last += 1;
+    }
+    candidates.push(((first, 0), (last, len(lines, last))));
+
+    let last = lines.len() - 1;
+    candidates.push(((0, 0), (last, len(lines, last))));
+
+    candidates
+        .into_iter()
+        .filter(|&(s, e)| s <= start && end <= e && (s, e) != (start, end))
+        .min_by_key(|&(s, e)| span(lines, s, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_structurally() {
+        let lines: Vec<String> = [
+            r#"fn f() {"#,
+            r#"    call(a, "hello world", [x])"#,
+            r#"}"#,
+            r#""#,
+            r#"end"#,
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let mut range = ((1, 19), (1, 19));
+        let mut expanded = vec![];
+        while let Some(r) = expand_range(&lines, range.0, range.1, &WordBoundary::default()) {
+            expanded.push(r);
+            range = r;
+        }
+
+        assert_eq!(
+            expanded,
+            [
+                ((1, 19), (1, 24)), // world
+                ((1, 13), (1, 24)), // hello world
+                ((1, 12), (1, 25)), // "hello world"
+                ((1, 9), (1, 30)),  // a, "hello world", [x]
+                ((1, 8), (1, 31)),  // (a, "hello world", [x])
+                ((1, 0), (1, 31)),  // line
+                ((0, 8), (2, 0)),   // inside of {}
+                ((0, 7), (2, 1)),   // {...}
+                ((0, 0), (2, 1)),   // paragraph
+                ((0, 0), (4, 3)),   // entire text
+            ],
+        );
+    }
+
+    #[test]
+    fn quoted_strings() {
+        assert_eq!(quote_pairs(r#"'a' "b\"c" `"#), [(4, 9), (0, 2)]);
+        assert!(quote_pairs("no quotes").is_empty());
+    }
+}
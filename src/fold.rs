@@ -0,0 +1,321 @@
+use crate::op::EditOp;
+use crate::ratatui::style::{Color, Style};
+use std::cmp;
+
+// Indentation width of the line. Tab is counted as `tab_len` spaces. `None` is returned for blank lines
+pub fn indent_width(line: &str, tab_len: u8) -> Option<usize> {
+    let mut width = 0;
+    for c in line.chars() {
+        match c {
+            ' ' => width += 1,
+            '\t' => width += cmp::max(tab_len as usize, 1),
+            c if c.is_whitespace() => width += 1,
+            _ => return Some(width),
+        }
+    }
+    None
+}
+
+// Find the range of the indented block following the row. The range starts at the row and ends at the last line which
+// is indented deeper than the row. Trailing blank lines are not included.
+pub fn indent_block(lines: &[String], row: usize, tab_len: u8) -> Option<(usize, usize)> {
+    let base = indent_width(&lines[row], tab_len).unwrap_or(0);
+    let mut end = row;
+    for (i, line) in lines.iter().enumerate().skip(row + 1) {
+        match indent_width(line, tab_len) {
+            Some(w) if w <= base => break,
+            Some(_) => end = i,
+            None => {}
+        }
+    }
+    (end > row).then(|| (row, end))
+}
+
+#[derive(Clone, Debug)]
+pub struct Folds {
+    // Non-overlapping row ranges sorted by the start rows. Each range is inclusive and the first row is visible as the
+    // summary line
+    ranges: Vec<(usize, usize)>,
+    // Number of lines the folds are tracking. Folds are invalidated when the number of lines changes without an edit
+    // moving them
+    lines_len: usize,
+    pub style: Style,
+    pub indicator: (char, Style),
+}
+
+impl Default for Folds {
+    fn default() -> Self {
+        Self {
+            ranges: vec![],
+            lines_len: 0,
+            style: Style::default().fg(Color::DarkGray),
+            indicator: ('▸', Style::default().fg(Color::DarkGray)),
+        }
+    }
+}
+
+impl Folds {
+    pub fn ranges(&self, lines_len: usize) -> &[(usize, usize)] {
+        if self.lines_len == lines_len {
+            &self.ranges
+        } else {
+            &[]
+        }
+    }
+
+    // Folds applied to the text. A fold which hides the cursor row is not applied so that the cursor is always visible
+    pub fn active(&self, lines_len: usize, cursor_row: usize) -> Vec<(usize, usize)> {
+        self.ranges(lines_len)
+            .iter()
+            .copied()
+            .filter(|&(s, e)| cursor_row <= s || e < cursor_row)
+            .collect()
+    }
+
+    // Remove all folds when the number of lines changed since the folds were created
+    pub fn update(&mut self, lines_len: usize) {
+        if self.lines_len != lines_len {
+            self.ranges.clear();
+            self.lines_len = lines_len;
+        }
+    }
+
+    // Move the folds along with the edit. Folds after the edited rows are shifted by the inserted or deleted lines and
+    // folds overlapping the edited rows are removed. When lines are inserted or deleted at the head of line, the line
+    // is moved rather than edited
+    pub fn shift(&mut self, op: &EditOp, lines_len: usize) {
+        let (start, end) = (op.pos(), op.end());
+        let (row, end_row) = (start.0, end.0);
+        let lines = end_row - row;
+        if lines == 0 {
+            return;
+        }
+        let before = match op {
+            EditOp::Insert { .. } => lines_len - lines,
+            EditOp::Delete { .. } => lines_len + lines,
+        };
+        self.update(before);
+        self.lines_len = lines_len;
+        let whole_lines = start.1 == 0 && end.1 == 0;
+        self.ranges = match op {
+            EditOp::Insert { .. } => {
+                let below = |s| if whole_lines { row <= s } else { row < s };
+                self.ranges
+                    .iter()
+                    .filter(|&&(s, e)| below(s) || e < row)
+                    .map(|&(s, e)| {
+                        if below(s) {
+                            (s + lines, e + lines)
+                        } else {
+                            (s, e)
+                        }
+                    })
+                    .collect()
+            }
+            EditOp::Delete { .. } => {
+                let last = if whole_lines { end_row - 1 } else { end_row };
+                self.ranges
+                    .iter()
+                    .filter(|&&(s, e)| last < s || e < row)
+                    .map(|&(s, e)| {
+                        if last < s {
+                            (s - lines, e - lines)
+                        } else {
+                            (s, e)
+                        }
+                    })
+                    .collect()
+            }
+        };
+    }
+
+    pub fn add(&mut self, start: usize, end: usize, lines_len: usize) -> bool {
+        if start >= end || end >= lines_len {
+            return false;
+        }
+        self.update(lines_len);
+        // Merge the overlapping folds into the new fold
+        let (mut start, mut end) = (start, end);
+        self.ranges.retain(|&(s, e)| {
+            let overlap = s <= end && start <= e;
+            if overlap {
+                start = cmp::min(start, s);
+                end = cmp::max(end, e);
+            }
+            !overlap
+        });
+        let i = self.ranges.partition_point(|&(s, _)| s < start);
+        self.ranges.insert(i, (start, end));
+        true
+    }
+
+    pub fn remove_at(&mut self, row: usize, lines_len: usize) -> bool {
+        let len = self.ranges(lines_len).len();
+        self.ranges.retain(|&(s, e)| row < s || e < row);
+        self.ranges.len() != len
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+}
+
+// The fold which hides the row. The first row of a fold is not hidden
+pub fn hiding_fold(folds: &[(usize, usize)], row: usize) -> Option<(usize, usize)> {
+    let i = folds.partition_point(|&(s, _)| s < row);
+    let &(s, e) = folds.get(i.checked_sub(1)?)?;
+    (row <= e).then(|| (s, e))
+}
+
+// Index of the row on screen, counting only visible rows. A hidden row is mapped to the first row of its fold
+pub fn visual_row(folds: &[(usize, usize)], row: usize) -> usize {
+    let row = hiding_fold(folds, row).map_or(row, |(s, _)| s);
+    let hidden: usize = folds
+        .iter()
+        .take_while(|&&(s, _)| s < row)
+        .map(|&(s, e)| e - s)
+        .sum();
+    row - hidden
+}
+
+// Inverse of `visual_row`
+pub fn buffer_row(folds: &[(usize, usize)], visual: usize) -> usize {
+    let mut row = visual;
+    for &(s, e) in folds {
+        if s >= row {
+            break;
+        }
+        row += e - s;
+    }
+    row
+}
+
+// Rows visible on screen from the top row
+pub fn visible_rows(
+    folds: &[(usize, usize)],
+    top_row: usize,
+    lines_len: usize,
+    height: usize,
+) -> Vec<usize> {
+    let mut rows = Vec::with_capacity(height);
+    let mut row = hiding_fold(folds, top_row).map_or(top_row, |(s, _)| s);
+    let first = row;
+    let mut folds = folds.iter().skip_while(|&&(s, _)| s < first).peekable();
+    while row < lines_len && rows.len() < height {
+        rows.push(row);
+        row = match folds.peek() {
+            Some(&&(s, e)) if s == row => {
+                folds.next();
+                e + 1
+            }
+            _ => row + 1,
+        };
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn find_indent_block() {
+        let ls = lines(["fn f() {", "    a", "", "\tb", "}", "", "x", "  y"]);
+        assert_eq!(indent_block(&ls, 0, 4), Some((0, 3)));
+        assert_eq!(indent_block(&ls, 1, 4), None);
+        assert_eq!(indent_block(&ls, 4, 4), None);
+        assert_eq!(indent_block(&ls, 6, 4), Some((6, 7)));
+    }
+
+    #[test]
+    fn add_and_remove_folds() {
+        let mut f = Folds::default();
+        assert!(!f.add(2, 2, 10));
+        assert!(!f.add(2, 10, 10));
+        assert!(f.add(5, 6, 10));
+        assert!(f.add(1, 2, 10));
+        assert_eq!(f.ranges(10), [(1, 2), (5, 6)]);
+        assert!(f.add(2, 5, 10)); // Merged
+        assert_eq!(f.ranges(10), [(1, 6)]);
+        assert_eq!(f.ranges(11), []);
+        f.update(10);
+        assert_eq!(f.ranges(10), [(1, 6)]);
+        f.update(11);
+        f.update(10);
+        assert_eq!(f.ranges(10), []);
+        assert!(f.add(1, 6, 10));
+        assert!(f.remove_at(3, 10));
+        assert!(!f.remove_at(3, 10));
+        assert_eq!(f.ranges(10), []);
+    }
+
+    #[test]
+    fn shift_folds() {
+        let insert = |pos, text: &str| EditOp::Insert {
+            pos,
+            text: text.to_string(),
+        };
+        let delete = |pos, text: &str| EditOp::Delete {
+            pos,
+            text: text.to_string(),
+        };
+        let tests = [
+            // Edits in a line don't move folds
+            (insert((4, 1), "x"), 10, vec![(3, 5)]),
+            // Above the fold
+            (insert((0, 1), "\n"), 11, vec![(4, 6)]),
+            (insert((3, 0), "x\n"), 11, vec![(4, 6)]),
+            (delete((1, 0), "b\n"), 9, vec![(2, 4)]),
+            (delete((1, 1), "\n"), 9, vec![(2, 4)]),
+            // Below the fold
+            (insert((6, 1), "\n"), 11, vec![(3, 5)]),
+            (delete((6, 1), "\nx"), 9, vec![(3, 5)]),
+            (delete((6, 0), "g\n"), 9, vec![(3, 5)]),
+            // Inside the fold
+            (insert((3, 0), "x\ny"), 11, vec![]),
+            (insert((5, 1), "\n"), 11, vec![]),
+            (delete((4, 0), "e\n"), 9, vec![]),
+            (delete((3, 0), "d\n"), 9, vec![]),
+        ];
+        for (op, lines_len, want) in tests {
+            let mut f = Folds::default();
+            assert!(f.add(3, 5, 10));
+            f.shift(&op, lines_len);
+            assert_eq!(f.ranges(lines_len), want, "{:?}", op);
+        }
+
+        // Folds are removed when the number of lines was changed without an edit
+        let mut f = Folds::default();
+        assert!(f.add(3, 5, 10));
+        f.shift(&insert((0, 0), "\n"), 12);
+        assert_eq!(f.ranges(12), []);
+    }
+
+    #[test]
+    fn map_rows() {
+        let folds = [(1, 3), (5, 6)];
+        let tests = [
+            (0, 0),
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (4, 2),
+            (5, 3),
+            (6, 3),
+            (7, 4),
+        ];
+        for (row, visual) in tests {
+            assert_eq!(visual_row(&folds, row), visual, "row={}", row);
+        }
+        for (visual, row) in [(0, 0), (1, 1), (2, 4), (3, 5), (4, 7)] {
+            assert_eq!(buffer_row(&folds, visual), row, "visual={}", visual);
+        }
+        assert_eq!(hiding_fold(&folds, 1), None);
+        assert_eq!(hiding_fold(&folds, 3), Some((1, 3)));
+        assert_eq!(hiding_fold(&folds, 4), None);
+        assert_eq!(visible_rows(&folds, 0, 9, 10), [0, 1, 4, 5, 7, 8]);
+        assert_eq!(visible_rows(&folds, 2, 9, 3), [1, 4, 5]);
+        assert_eq!(visible_rows(&[], 2, 4, 5), [2, 3]);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::ratatui::widgets::Widget;
+use crate::textarea::TextArea;
+
+/// Widget to render a textarea as a field of a form. A label is rendered above the textarea and a message line is
+/// rendered below it. The message line shows the validation error when it is set, otherwise the help message. The row
+/// of the message line is always reserved so that the layout doesn't move when an error is set.
+///
+/// The label is styled following the focus of the textarea set by [`TextArea::set_focused`]. While an error is set,
+/// the label, the border of the block of the textarea, and the message are rendered with the error style.
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use ratatui::widgets::Widget;
+/// use tui_textarea::{FormField, TextArea};
+///
+/// let mut field = FormField::new("Name", TextArea::default());
+/// field.set_help("Your full name");
+/// field.textarea_mut().insert_str("Alice");
+///
+/// let area = Rect::new(0, 0, 20, field.preferred_height());
+/// let mut buf = Buffer::empty(area);
+/// (&field).render(area, &mut buf);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FormField<'a> {
+    textarea: TextArea<'a>,
+    label: String,
+    help: Option<String>,
+    error: Option<String>,
+    label_styles: (Style, Style),
+    message_styles: (Style, Style),
+}
+
+impl<'a> FormField<'a> {
+    /// Create a new form field of the textarea with the label. An empty label doesn't occupy a row.
+    /// ```
+    /// use tui_textarea::{FormField, TextArea};
+    ///
+    /// let field = FormField::new("Name", TextArea::from(["Alice"]));
+    /// assert_eq!(field.label(), "Name");
+    /// assert_eq!(field.textarea().lines(), ["Alice"]);
+    /// ```
+    pub fn new(label: impl Into<String>, textarea: TextArea<'a>) -> Self {
+        Self {
+            textarea,
+            label: label.into(),
+            help: None,
+            error: None,
+            label_styles: (
+                Style::default().add_modifier(Modifier::BOLD),
+                Style::default(),
+            ),
+            message_styles: (
+                Style::default().fg(Color::DarkGray),
+                Style::default().fg(Color::Red),
+            ),
+        }
+    }
+
+    /// Get the textarea of the field.
+    pub fn textarea(&self) -> &TextArea<'a> {
+        &self.textarea
+    }
+
+    /// Get the mutable textarea of the field to handle inputs and to configure it.
+    pub fn textarea_mut(&mut self) -> &mut TextArea<'a> {
+        &mut self.textarea
+    }
+
+    /// Get the textarea of the field, consuming the field.
+    pub fn into_textarea(self) -> TextArea<'a> {
+        self.textarea
+    }
+
+    /// Set the label rendered above the textarea.
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
+    /// Get the label of the field.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Set the help message rendered below the textarea while no error is set.
+    /// ```
+    /// use tui_textarea::{FormField, TextArea};
+    ///
+    /// let mut field = FormField::new("Port", TextArea::default());
+    /// assert_eq!(field.help(), None);
+    ///
+    /// field.set_help("1 to 65535");
+    /// assert_eq!(field.help(), Some("1 to 65535"));
+    ///
+    /// field.remove_help();
+    /// assert_eq!(field.help(), None);
+    /// ```
+    pub fn set_help(&mut self, help: impl Into<String>) {
+        self.help = Some(help.into());
+    }
+
+    /// Remove the help message set by [`FormField::set_help`].
+    pub fn remove_help(&mut self) {
+        self.help = None;
+    }
+
+    /// Get the help message set by [`FormField::set_help`].
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Set the validation error message. The error is rendered instead of the help message until it is cleared by
+    /// [`FormField::clear_error`] or [`FormField::validate`].
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    /// Clear the validation error message set by [`FormField::set_error`].
+    pub fn clear_error(&mut self) {
+        self.error = None;
+    }
+
+    /// Get the validation error message of the field.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Validate the lines of the textarea with the function. The error returned from the function is set as the
+    /// error message of the field, and the error is cleared when the function returns `Ok`. This method returns if
+    /// the text is valid or not.
+    /// ```
+    /// use tui_textarea::{FormField, TextArea};
+    ///
+    /// let mut field = FormField::new("Port", TextArea::from(["http"]));
+    /// let port = |lines: &[String]| match lines[0].parse::<u16>() {
+    ///     Ok(p) if p > 0 => Ok(()),
+    ///     _ => Err("Port must be 1 to 65535".to_string()),
+    /// };
+    ///
+    /// assert!(!field.validate(port));
+    /// assert_eq!(field.error(), Some("Port must be 1 to 65535"));
+    ///
+    /// field.textarea_mut().select_all();
+    /// field.textarea_mut().insert_str("8080");
+    /// assert!(field.validate(port));
+    /// assert_eq!(field.error(), None);
+    /// ```
+    pub fn validate<F>(&mut self, f: F) -> bool
+    where
+        F: FnOnce(&[String]) -> Result<(), String>,
+    {
+        self.error = f(self.textarea.lines()).err();
+        self.error.is_none()
+    }
+
+    /// Set if the field is focused. This is the same as [`TextArea::set_focused`] of the textarea.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.textarea.set_focused(focused);
+    }
+
+    /// Get if the field is focused. See [`FormField::set_focused`].
+    pub fn is_focused(&self) -> bool {
+        self.textarea.is_focused()
+    }
+
+    /// Set the styles of the label when the field is focused and unfocused. The default styles are bold and the
+    /// default style.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{FormField, TextArea};
+    ///
+    /// let mut field = FormField::new("Name", TextArea::default());
+    /// let focused = Style::default().fg(Color::Yellow);
+    /// let unfocused = Style::default().fg(Color::Gray);
+    /// field.set_label_styles(focused, unfocused);
+    /// assert_eq!(field.label_styles(), (focused, unfocused));
+    /// ```
+    pub fn set_label_styles(&mut self, focused: Style, unfocused: Style) {
+        self.label_styles = (focused, unfocused);
+    }
+
+    /// Get the styles of the label when the field is focused and unfocused. See [`FormField::set_label_styles`].
+    pub fn label_styles(&self) -> (Style, Style) {
+        self.label_styles
+    }
+
+    /// Set the styles of the help message and the error message. The error style is also applied to the label and the
+    /// border of the block while an error is set. The default styles are dark gray and red.
+    /// ```
+    /// use ratatui::style::{Color, Style};
+    /// use tui_textarea::{FormField, TextArea};
+    ///
+    /// let mut field = FormField::new("Name", TextArea::default());
+    /// let help = Style::default().fg(Color::Gray);
+    /// let error = Style::default().fg(Color::LightRed);
+    /// field.set_message_styles(help, error);
+    /// assert_eq!(field.message_styles(), (help, error));
+    /// ```
+    pub fn set_message_styles(&mut self, help: Style, error: Style) {
+        self.message_styles = (help, error);
+    }
+
+    /// Get the styles of the help message and the error message. See [`FormField::set_message_styles`].
+    pub fn message_styles(&self) -> (Style, Style) {
+        self.message_styles
+    }
+
+    /// Get the height preferred to render the field. It is the height of the label, the preferred height of the
+    /// textarea returned from [`TextArea::preferred_height`], and the height of the message line.
+    /// ```
+    /// use tui_textarea::{FormField, TextArea};
+    ///
+    /// let field = FormField::new("Name", TextArea::from(["a", "b"]));
+    /// assert_eq!(field.preferred_height(), 4);
+    ///
+    /// let field = FormField::new("", TextArea::default());
+    /// assert_eq!(field.preferred_height(), 2);
+    /// ```
+    pub fn preferred_height(&self) -> u16 {
+        self.textarea
+            .preferred_height()
+            .saturating_add(self.label_height() + 1)
+    }
+
+    fn label_height(&self) -> u16 {
+        if self.label.is_empty() {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn label_style(&self) -> Style {
+        let (focused, unfocused) = self.label_styles;
+        let style = if self.is_focused() {
+            focused
+        } else {
+            unfocused
+        };
+        match &self.error {
+            Some(_) => style.patch(self.message_styles.1),
+            None => style,
+        }
+    }
+
+    // Render the error style on the cells of the block around the inner area of the textarea
+    fn render_error_border(&self, area: Rect, buf: &mut Buffer) {
+        let inner = match (&self.error, self.textarea.block()) {
+            (Some(_), Some(b)) => b.inner(area),
+            _ => return,
+        };
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let in_inner = inner.left() <= x
+                    && x < inner.right()
+                    && inner.top() <= y
+                    && y < inner.bottom();
+                if !in_inner {
+                    buf.get_mut(x, y).set_style(self.message_styles.1);
+                }
+            }
+        }
+    }
+}
+
+impl<'a, 'b> Widget for &'b FormField<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // The label and the message line take their rows before the textarea
+        let mut rest = area;
+        if self.label_height() > 0 && rest.height > 0 {
+            let width = rest.width as usize;
+            buf.set_stringn(rest.x, rest.y, &self.label, width, self.label_style());
+            rest.y += 1;
+            rest.height -= 1;
+        }
+        if rest.height > 0 {
+            let (message, style) = match (&self.error, &self.help) {
+                (Some(e), _) => (e.as_str(), self.message_styles.1),
+                (None, Some(h)) => (h.as_str(), self.message_styles.0),
+                (None, None) => ("", self.message_styles.0),
+            };
+            let y = rest.bottom() - 1;
+            buf.set_stringn(rest.x, y, message, rest.width as usize, style);
+            rest.height -= 1;
+        }
+
+        Widget::render(&self.textarea, rest, buf);
+        self.render_error_border(rest, buf);
+    }
+}
+
+// Tests for buffer contents don't work with tui-rs
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+    use crate::ratatui::widgets::{Block, Borders};
+
+    fn render(field: &FormField, width: u16, height: u16) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        field.render(area, &mut buf);
+        buf
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width)
+            .map(|x| buf.get(x, y).symbol())
+            .collect()
+    }
+
+    #[test]
+    fn render_label_and_message() {
+        let mut field = FormField::new("Name", TextArea::from(["ab"]));
+        field.set_help("help");
+        let buf = render(&field, 6, 3);
+        assert_eq!(row(&buf, 0), "Name  ");
+        assert_eq!(row(&buf, 1), "ab    ");
+        assert_eq!(row(&buf, 2), "help  ");
+        assert!(buf.get(0, 0).modifier.contains(Modifier::BOLD));
+        assert_eq!(buf.get(0, 2).fg, Color::DarkGray);
+
+        field.set_focused(false);
+        let buf = render(&field, 6, 3);
+        assert!(!buf.get(0, 0).modifier.contains(Modifier::BOLD));
+
+        // The row of the message line is kept without any message
+        field.remove_help();
+        field.set_label("");
+        let buf = render(&field, 6, 3);
+        assert_eq!(row(&buf, 0), "ab    ");
+        assert_eq!(row(&buf, 1), "      ");
+        assert_eq!(row(&buf, 2), "      ");
+    }
+
+    #[test]
+    fn render_error() {
+        let mut textarea = TextArea::from(["ab"]);
+        textarea.set_block(Block::default().borders(Borders::ALL));
+        let mut field = FormField::new("Name", textarea);
+        field.set_help("help");
+        field.set_error("error");
+        assert_eq!(field.preferred_height(), 5);
+
+        let buf = render(&field, 6, 5);
+        assert_eq!(row(&buf, 0), "Name  ");
+        assert_eq!(row(&buf, 2), "│ab  │");
+        assert_eq!(row(&buf, 4), "error ");
+        assert_eq!(buf.get(0, 0).fg, Color::Red);
+        assert_eq!(buf.get(0, 1).fg, Color::Red);
+        assert_eq!(buf.get(5, 3).fg, Color::Red);
+        assert_eq!(buf.get(1, 2).fg, Color::Reset);
+        assert_eq!(buf.get(0, 4).fg, Color::Red);
+
+        field.clear_error();
+        let buf = render(&field, 6, 5);
+        assert_eq!(row(&buf, 4), "help  ");
+        assert_eq!(buf.get(0, 1).fg, Color::Reset);
+    }
+}
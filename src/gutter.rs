@@ -0,0 +1,396 @@
+use crate::diff::{line_changes, LineChange};
+use crate::op::EditOp;
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Color, Style};
+use std::cell::{Ref, RefCell};
+use std::cmp;
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter;
+use std::mem;
+use unicode_width::UnicodeWidthStr as _;
+
+#[derive(Clone, Debug)]
+pub struct Gutter {
+    // Annotation of each line such as blame information. Rendered at the left of the other columns
+    pub blame: Vec<String>,
+    pub blame_style: Style,
+    pub blame_width: u16,
+    pub signs: BTreeMap<usize, (String, Style)>,
+    pub sign_width: u8,
+    diff_base: Option<Vec<String>>,
+    // Changes of lines compared with the diff base. Computed on the first use after the text or the diff base changed
+    line_changes: RefCell<Option<Vec<(usize, LineChange)>>>,
+    pub change_markers: [(String, Style); 3], // Indexed by `LineChange`
+    // Rows changed since the text was saved
+    pub modified_lines: BTreeSet<usize>,
+    pub modified_marker: Option<(String, Style)>,
+}
+
+impl Default for Gutter {
+    fn default() -> Self {
+        Self {
+            blame: vec![],
+            blame_style: Style::default().fg(Color::DarkGray),
+            blame_width: 0,
+            signs: BTreeMap::new(),
+            sign_width: 2,
+            diff_base: None,
+            line_changes: RefCell::new(None),
+            change_markers: [
+                ("▎".into(), Style::default().fg(Color::Green)),
+                ("▎".into(), Style::default().fg(Color::Yellow)),
+                ("▁".into(), Style::default().fg(Color::Red)),
+            ],
+            modified_lines: BTreeSet::new(),
+            modified_marker: None,
+        }
+    }
+}
+
+impl Gutter {
+    pub fn set_blame(&mut self, blame: Vec<String>) {
+        // One space is put between the annotations and the next column
+        self.blame_width = blame
+            .iter()
+            .map(|s| s.width() as u16 + 1)
+            .max()
+            .unwrap_or(0);
+        self.blame = blame;
+    }
+
+    pub fn set_diff_base(&mut self, base: Option<Vec<String>>) {
+        self.diff_base = base;
+        self.clear_line_changes();
+    }
+
+    // Changes of lines compared with the diff base. It is empty when the diff base is not set
+    pub fn line_changes(&self, lines: &[String]) -> Ref<'_, [(usize, LineChange)]> {
+        if self.line_changes.borrow().is_none() {
+            let changes = match &self.diff_base {
+                Some(base) => line_changes(base, lines),
+                None => vec![],
+            };
+            *self.line_changes.borrow_mut() = Some(changes);
+        }
+        Ref::map(self.line_changes.borrow(), |c| c.as_deref().unwrap_or(&[]))
+    }
+
+    // Discard the cached changes of lines since the text was modified
+    pub fn clear_line_changes(&mut self) {
+        *self.line_changes.get_mut() = None;
+    }
+
+    fn sign_column_width(&self) -> u16 {
+        if self.signs.is_empty() {
+            0
+        } else {
+            self.sign_width as u16
+        }
+    }
+
+    // Width of the gutter. The blame column is hidden when no annotation is set. The sign column is hidden when no sign
+    // is put. The change marker column is hidden when the diff base is not set. The modified marker column is hidden
+    // when no marker is set. The fold column is hidden when no fold is applied
+    pub fn width(&self, has_folds: bool) -> u16 {
+        self.blame_width
+            + self.sign_column_width()
+            + self.diff_base.is_some() as u16
+            + self.modified_marker.is_some() as u16
+            + has_folds as u16
+    }
+
+    // Mark the rows changed by the edit as modified. Rows after the edit are moved by the inserted or deleted lines
+    pub fn mark_modified(&mut self, op: &EditOp) {
+        let (row, end_row) = (op.pos().0, op.end().0);
+        let lines = end_row - row;
+        if lines == 0 {
+            self.modified_lines.insert(row);
+            return;
+        }
+        let rows = mem::take(&mut self.modified_lines).into_iter();
+        self.modified_lines = match op {
+            EditOp::Insert { .. } => rows
+                .map(|r| if r > row { r + lines } else { r })
+                .chain(row..=end_row)
+                .collect(),
+            EditOp::Delete { .. } => rows
+                .filter(|&r| r <= row || end_row < r)
+                .map(|r| if r > row { r - lines } else { r })
+                .chain(iter::once(row))
+                .collect(),
+        };
+    }
+
+    // Move the annotations along with the edit. Inserted lines have no annotation and deleted lines lose theirs. When
+    // the edit starts at the head of line, the line is moved rather than split
+    pub fn shift_blame(&mut self, op: &EditOp) {
+        let (start, end) = (op.pos(), op.end());
+        let (row, end_row) = (start.0, end.0);
+        if row == end_row || row >= self.blame.len() {
+            return;
+        }
+        match op {
+            EditOp::Insert { .. } => {
+                let at = if start.1 == 0 { row } else { row + 1 };
+                let rest = self.blame.split_off(at);
+                self.blame
+                    .extend(iter::repeat(String::new()).take(end_row - row));
+                self.blame.extend(rest);
+            }
+            EditOp::Delete { .. } => {
+                let (from, to) = if start.1 == 0 && end.1 == 0 {
+                    (row, end_row)
+                } else {
+                    (row + 1, end_row + 1)
+                };
+                let to = cmp::min(to, self.blame.len());
+                self.blame.drain(from..to);
+            }
+        }
+    }
+
+    // Render blame annotations, signs, change markers, and fold indicators in the area. `rows` are the rows of the text
+    // rendered in the area from the top
+    pub fn render(
+        &self,
+        area: Rect,
+        rows: &[usize],
+        lines: &[String],
+        folds: &[(usize, usize)],
+        fold_indicator: (char, Style),
+        buf: &mut Buffer,
+    ) {
+        let mut x = area.x;
+        let right = area.right();
+        let screen_rows = rows.iter().zip(area.y..area.bottom());
+
+        let blame_width = cmp::min(self.blame_width, right - x);
+        if blame_width > 0 {
+            for (row, y) in screen_rows.clone() {
+                if let Some(text) = self.blame.get(*row) {
+                    let width = cmp::min(blame_width, self.blame_width - 1) as usize;
+                    buf.set_stringn(x, y, text, width, self.blame_style);
+                }
+            }
+            x += blame_width;
+        }
+
+        let sign_width = cmp::min(self.sign_column_width(), right - x);
+        if sign_width > 0 {
+            for (row, y) in screen_rows.clone() {
+                if let Some((symbol, style)) = self.signs.get(row) {
+                    buf.set_stringn(x, y, symbol, sign_width as usize, *style);
+                }
+            }
+            x += sign_width;
+        }
+
+        if self.diff_base.is_some() {
+            if x >= right {
+                return;
+            }
+            let changes = self.line_changes(lines);
+            for (row, y) in screen_rows.clone() {
+                if let Ok(i) = changes.binary_search_by_key(row, |&(r, _)| r) {
+                    let (symbol, style) = &self.change_markers[changes[i].1.index()];
+                    buf.set_stringn(x, y, symbol, 1, *style);
+                }
+            }
+            x += 1;
+        }
+
+        if let Some((symbol, style)) = &self.modified_marker {
+            if x >= right {
+                return;
+            }
+            for (row, y) in screen_rows.clone() {
+                if self.modified_lines.contains(row) {
+                    buf.set_stringn(x, y, symbol, 1, *style);
+                }
+            }
+            x += 1;
+        }
+
+        if !folds.is_empty() && x < right {
+            let (c, style) = fold_indicator;
+            for (row, y) in screen_rows {
+                if folds.binary_search_by_key(row, |&(s, _)| s).is_ok() {
+                    buf.get_mut(x, y).set_char(c).set_style(style);
+                }
+            }
+        }
+    }
+}
+
+// Tests for buffer contents don't work with tui-rs
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_signs() {
+        let mut g = Gutter::default();
+        assert_eq!(g.width(false), 0);
+
+        g.signs.insert(1, ("●".into(), Style::default()));
+        g.signs.insert(3, ("!!!".into(), Style::default()));
+        g.signs.insert(5, ("x".into(), Style::default()));
+        assert_eq!(g.width(false), 2);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 3));
+        let indicator = ('>', Style::default());
+        g.render(
+            Rect::new(0, 0, 2, 3),
+            &[1, 2, 3],
+            &[],
+            &[],
+            indicator,
+            &mut buf,
+        );
+        let have: Vec<String> = (0..3)
+            .map(|y| (0..3).map(|x| buf.get(x, y).symbol()).collect())
+            .collect();
+        assert_eq!(have, ["●  ", "   ", "!! "]);
+    }
+
+    #[test]
+    fn cache_line_changes() {
+        let mut g = Gutter::default();
+        let lines = ["a".to_string(), "x".into()];
+        assert!(g.line_changes(&lines).is_empty());
+        g.set_diff_base(Some(vec!["a".into(), "b".into()]));
+        assert_eq!(*g.line_changes(&lines), [(1, LineChange::Modified)]);
+
+        // Cached until the text is modified
+        let lines = ["a".to_string(), "b".into()];
+        assert_eq!(*g.line_changes(&lines), [(1, LineChange::Modified)]);
+        g.clear_line_changes();
+        assert!(g.line_changes(&lines).is_empty());
+    }
+
+    #[test]
+    fn render_change_markers() {
+        let g = Gutter {
+            diff_base: Some(vec!["a".into(), "b".into(), "c".into()]),
+            ..Default::default()
+        };
+        assert_eq!(g.width(false), 1);
+        assert_eq!(g.width(true), 2);
+
+        let lines = ["a".to_string(), "x".into(), "y".into()];
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 3));
+        let indicator = ('>', Style::default());
+        let folds = [(0, 1)];
+        g.render(
+            Rect::new(0, 0, 2, 3),
+            &[0, 2],
+            &lines,
+            &folds,
+            indicator,
+            &mut buf,
+        );
+        let have: Vec<String> = (0..3)
+            .map(|y| (0..2).map(|x| buf.get(x, y).symbol()).collect())
+            .collect();
+        assert_eq!(have, [" >", "▎ ", "  "]);
+    }
+
+    #[test]
+    fn mark_modified_lines() {
+        let mut g = Gutter::default();
+        let insert = |row, text: &str| EditOp::Insert {
+            pos: (row, 1),
+            text: text.to_string(),
+        };
+        let delete = |row, text: &str| EditOp::Delete {
+            pos: (row, 1),
+            text: text.to_string(),
+        };
+        let rows = |g: &Gutter| g.modified_lines.iter().copied().collect::<Vec<_>>();
+
+        g.mark_modified(&insert(3, "a"));
+        assert_eq!(rows(&g), [3]);
+        g.mark_modified(&insert(1, "a\nb\n"));
+        assert_eq!(rows(&g), [1, 2, 3, 5]);
+        g.mark_modified(&delete(0, "a\nb\nc"));
+        assert_eq!(rows(&g), [0, 1, 3]);
+        g.mark_modified(&delete(3, "x"));
+        assert_eq!(rows(&g), [0, 1, 3]);
+    }
+
+    #[test]
+    fn render_modified_markers() {
+        let mut g = Gutter::default();
+        assert_eq!(g.width(false), 0);
+        g.modified_marker = Some(("┃".into(), Style::default()));
+        g.modified_lines.insert(1);
+        assert_eq!(g.width(false), 1);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 1, 3));
+        let indicator = ('>', Style::default());
+        g.render(
+            Rect::new(0, 0, 1, 3),
+            &[0, 1, 2],
+            &[],
+            &[],
+            indicator,
+            &mut buf,
+        );
+        let have: Vec<&str> = (0..3).map(|y| buf.get(0, y).symbol()).collect();
+        assert_eq!(have, [" ", "┃", " "]);
+    }
+
+    #[test]
+    fn render_blame_column() {
+        let mut g = Gutter::default();
+        g.set_blame(vec!["abc alice".into(), "".into(), "de bob".into()]);
+        g.signs.insert(1, ("●".into(), Style::default()));
+        assert_eq!(g.width(false), 12);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 3));
+        let indicator = ('>', Style::default());
+        g.render(
+            Rect::new(0, 0, 12, 3),
+            &[0, 1, 2],
+            &[],
+            &[],
+            indicator,
+            &mut buf,
+        );
+        let have: Vec<String> = (0..3)
+            .map(|y| (0..12).map(|x| buf.get(x, y).symbol()).collect())
+            .collect();
+        assert_eq!(have, ["abc alice   ", "          ● ", "de bob      "]);
+
+        g.set_blame(vec![]);
+        assert_eq!(g.width(false), 2);
+    }
+
+    #[test]
+    fn shift_blame_lines() {
+        let mut g = Gutter::default();
+        g.set_blame(vec!["a".into(), "b".into(), "c".into()]);
+        let op = |pos, text: &str, insert| {
+            let text = text.to_string();
+            if insert {
+                EditOp::Insert { pos, text }
+            } else {
+                EditOp::Delete { pos, text }
+            }
+        };
+
+        g.shift_blame(&op((0, 1), "x", true));
+        assert_eq!(g.blame, ["a", "b", "c"]);
+        g.shift_blame(&op((1, 0), "x\ny\n", true));
+        assert_eq!(g.blame, ["a", "", "", "b", "c"]);
+        g.shift_blame(&op((0, 1), "\nx\n", false));
+        assert_eq!(g.blame, ["a", "b", "c"]);
+        g.shift_blame(&op((1, 0), "b\n", false));
+        assert_eq!(g.blame, ["a", "c"]);
+        g.shift_blame(&op((0, 1), "x\n", true));
+        assert_eq!(g.blame, ["a", "", "c"]);
+        g.shift_blame(&op((9, 0), "\n", true));
+        assert_eq!(g.blame, ["a", "", "c"]);
+    }
+}
@@ -1,20 +1,31 @@
+use crate::control::{control_char_text, is_special_char, ControlCharDisplay};
 use crate::ratatui::style::Style;
 use crate::ratatui::text::Span;
-use crate::util::{num_digits, spaces};
+use crate::util::{byte_offset, num_digits, spaces, tab_width};
+use crate::width::{char_width, is_widened, pad_widened, str_width, AmbiguousWidth};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::iter;
+use std::mem;
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
-use unicode_width::UnicodeWidthChar as _;
 
 enum Boundary {
     Cursor(Style),
     Select(Style),
     #[cfg(feature = "search")]
     Search(Style),
+    RemoteCursor(Style),
+    RemoteSelect(Style),
+    Diagnostic(Style),
+    Change(Style),
+    Misspell(Style),
+    Occurrence(Style),
+    Link(Style),
+    #[cfg(feature = "search")]
+    Rule(Style),
     End,
 }
 
@@ -22,10 +33,19 @@ impl Boundary {
     fn cmp(&self, other: &Boundary) -> Ordering {
         fn rank(b: &Boundary) -> u8 {
             match b {
-                Boundary::Cursor(_) => 3,
+                Boundary::Cursor(_) => 11,
+                #[cfg(feature = "search")]
+                Boundary::Search(_) => 10,
+                Boundary::Select(_) => 9,
+                Boundary::RemoteCursor(_) => 8,
+                Boundary::RemoteSelect(_) => 7,
+                Boundary::Diagnostic(_) => 6,
+                Boundary::Change(_) => 5,
+                Boundary::Misspell(_) => 4,
+                Boundary::Occurrence(_) => 3,
+                Boundary::Link(_) => 2,
                 #[cfg(feature = "search")]
-                Boundary::Search(_) => 2,
-                Boundary::Select(_) => 1,
+                Boundary::Rule(_) => 1,
                 Boundary::End => 0,
             }
         }
@@ -38,15 +58,44 @@ impl Boundary {
             Boundary::Select(s) => Some(*s),
             #[cfg(feature = "search")]
             Boundary::Search(s) => Some(*s),
+            Boundary::RemoteCursor(s) => Some(*s),
+            Boundary::RemoteSelect(s) => Some(*s),
+            Boundary::Diagnostic(s) => Some(*s),
+            Boundary::Change(s) => Some(*s),
+            Boundary::Misspell(s) => Some(*s),
+            Boundary::Occurrence(s) => Some(*s),
+            Boundary::Link(s) => Some(*s),
+            #[cfg(feature = "search")]
+            Boundary::Rule(s) => Some(*s),
             Boundary::End => None,
         }
     }
 }
 
+// Kind of a run of characters rendered in the same way
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Run {
+    Text,
+    Whitespace,
+    Control,
+}
+
 struct DisplayTextBuilder {
     tab_len: u8,
     width: usize,
     mask: Option<char>,
+    // Styles of whitespaces and trailing whitespaces, and the byte offset where the trailing whitespaces start
+    whitespace: Option<(Style, Style, usize)>,
+    // Widths of the tabs in the line with elastic tabstops, and the number of tabs built so far
+    elastic_tabs: Vec<usize>,
+    tabs: usize,
+    tab_stops: Vec<usize>,
+    // Notation and style of control characters
+    control: Option<(ControlCharDisplay, Style)>,
+    ambiguous: AmbiguousWidth,
+    // Index of the first span of the text and the byte offsets of the characters in the spans after it. They are
+    // recorded to reorder the spans visually. Text is split into characters while recording
+    span_offsets: Option<(usize, Vec<usize>)>,
 }
 
 impl DisplayTextBuilder {
@@ -55,6 +104,142 @@ impl DisplayTextBuilder {
             tab_len,
             width: 0,
             mask,
+            whitespace: None,
+            elastic_tabs: vec![],
+            tabs: 0,
+            tab_stops: vec![],
+            control: None,
+            ambiguous: AmbiguousWidth::default(),
+            span_offsets: None,
+        }
+    }
+
+    fn record_offset(&mut self, offset: usize, spans: &[Span<'_>]) {
+        if let Some((first, offsets)) = &mut self.span_offsets {
+            offsets.resize(spans.len() - *first, offset);
+        }
+    }
+
+    // Display width of the next tab. Tabs after the elastic tabstops (e.g. in ghost text) stop at the tab stops
+    fn tab_width(&mut self) -> usize {
+        let width = match self.elastic_tabs.get(self.tabs) {
+            Some(&w) => w,
+            None => tab_width(self.width, self.tab_len, &self.tab_stops),
+        };
+        self.tabs += 1;
+        width
+    }
+
+    // Build the visible glyphs of spaces and tabs. A tab is rendered as `→` followed by padding spaces. `·` and `→`
+    // are ambiguous characters, so ASCII glyphs `.` and `>` are used instead when they are wide
+    fn build_whitespace(&mut self, s: &str) -> String {
+        let (space, tab) = match self.ambiguous {
+            AmbiguousWidth::Narrow => ('·', '→'),
+            AmbiguousWidth::Wide => ('.', '>'),
+        };
+        let mut buf = String::with_capacity(s.len());
+        for c in s.chars() {
+            if c == ' ' {
+                buf.push(space);
+                self.width += 1;
+            } else if self.tab_len > 0 {
+                let len = self.tab_width();
+                buf.push(tab);
+                buf.extend(iter::repeat(' ').take(len - 1));
+                self.width += len;
+            }
+        }
+        buf
+    }
+
+    // Build the notation of the control characters. Other special characters are rendered as they are
+    fn build_control(&mut self, s: &str, display: ControlCharDisplay) -> String {
+        let mut buf = String::new();
+        for c in s.chars() {
+            let text = control_char_text(c, display).unwrap_or_else(|| c.to_string());
+            self.width += str_width(&text, self.ambiguous);
+            buf.push_str(&text);
+        }
+        buf
+    }
+
+    // Push the span of the text in `start..end` of the line. Each character is pushed separately while recording the
+    // offsets of the spans
+    fn push_text<'a>(
+        &mut self,
+        line: &'a str,
+        start: usize,
+        end: usize,
+        style: Style,
+        spans: &mut Vec<Span<'a>>,
+    ) {
+        if self.span_offsets.is_none() {
+            self.push_run(line, start, end, style, spans);
+            return;
+        }
+        for (i, c) in line[start..end].char_indices() {
+            let offset = start + i;
+            self.push_run(line, offset, offset + c.len_utf8(), style, spans);
+            self.record_offset(offset, spans);
+        }
+    }
+
+    // Push the spans of the text in `start..end` of the line. When whitespaces are visualized, runs of spaces and tabs
+    // are split into separate spans. Runs of control characters are also split to render them with their notation
+    fn push_run<'a>(
+        &mut self,
+        line: &'a str,
+        start: usize,
+        end: usize,
+        style: Style,
+        spans: &mut Vec<Span<'a>>,
+    ) {
+        let control = self
+            .control
+            .filter(|_| line[start..end].contains(is_special_char));
+        let (ws_style, trailing_style, trailing) = match (self.whitespace, control) {
+            (Some(ws), _) => ws,
+            // Whitespaces are not visualized
+            (None, Some(_)) => (style, style, line.len()),
+            (None, None) => {
+                spans.push(Span::styled(self.build(&line[start..end]), style));
+                return;
+            }
+        };
+        let visualize_ws = self.whitespace.is_some();
+        let run = |c: char| {
+            if control.is_some() && is_special_char(c) {
+                Run::Control
+            } else if visualize_ws && (c == ' ' || c == '\t') {
+                Run::Whitespace
+            } else {
+                Run::Text
+            }
+        };
+        let mut i = start;
+        while i < end {
+            let rest = &line[i..end];
+            let kind = rest.chars().next().map_or(Run::Text, run);
+            let len = rest.find(|c: char| run(c) != kind).unwrap_or(rest.len());
+            let text = &rest[..len];
+            if kind == Run::Text {
+                spans.push(Span::styled(self.build(text), style));
+            } else if let (Run::Control, Some((display, control_style))) = (kind, control) {
+                let built = self.build_control(text, display);
+                spans.push(Span::styled(built, style.patch(control_style)));
+            } else {
+                // Trailing whitespaces start at a run boundary since they continue to the end of line
+                let s = if i >= trailing {
+                    trailing_style
+                } else {
+                    ws_style
+                };
+                let built = self.build_whitespace(text);
+                if !built.is_empty() {
+                    spans.push(Span::styled(built, style.patch(s)));
+                }
+            }
+            i += len;
         }
     }
 
@@ -65,7 +250,6 @@ impl DisplayTextBuilder {
             return Cow::Owned(masked);
         }
 
-        let tab = spaces(self.tab_len);
         let mut buf = String::new();
         for (i, c) in s.char_indices() {
             if c == '\t' {
@@ -74,15 +258,15 @@ impl DisplayTextBuilder {
                     buf.push_str(&s[..i]);
                 }
                 if self.tab_len > 0 {
-                    let len = self.tab_len as usize - (self.width % self.tab_len as usize);
-                    buf.push_str(&tab[..len]);
+                    let len = self.tab_width();
+                    buf.extend(iter::repeat(' ').take(len));
                     self.width += len;
                 }
             } else {
                 if !buf.is_empty() {
                     buf.push(c);
                 }
-                self.width += c.width().unwrap_or(0);
+                self.width += char_width(c, self.ambiguous).unwrap_or(0);
             }
         }
 
@@ -92,6 +276,153 @@ impl DisplayTextBuilder {
             Cow::Borrowed(s)
         }
     }
+
+    // Build the span of the text in `start..end` of the line. Inlay hints in the range are inserted before the
+    // characters at their offsets. `inlays` is sorted by the offsets and consumed from its head
+    fn build_segment<'a>(
+        &mut self,
+        line: &'a str,
+        mut start: usize,
+        end: usize,
+        style: Style,
+        spans: &mut Vec<Span<'a>>,
+        inlays: &mut &[(usize, Cow<'a, str>, Style)],
+    ) {
+        while let Some(((offset, text, inlay_style), rest)) = inlays.split_first() {
+            let offset = *offset;
+            if offset >= end {
+                break;
+            }
+            if start < offset {
+                self.push_text(line, start, offset, style, spans);
+                start = offset;
+            }
+            spans.push(Span::styled(text.clone(), *inlay_style));
+            self.record_offset(offset, spans);
+            *inlays = rest;
+        }
+        if start < end {
+            self.push_text(line, start, end, style, spans);
+        }
+    }
+
+    // Build spans of the line split at the boundaries of highlights
+    fn build_boundaries<'a>(
+        &mut self,
+        line: &'a str,
+        spans: &mut Vec<Span<'a>>,
+        mut boundaries: Vec<(Boundary, usize)>,
+        style_begin: Style,
+        mut inlays: &[(usize, Cow<'a, str>, Style)],
+    ) {
+        boundaries.sort_unstable_by(|(l, i), (r, j)| match i.cmp(j) {
+            Ordering::Equal => l.cmp(r),
+            o => o,
+        });
+
+        let mut style = style_begin;
+        let mut start = 0;
+        let mut stack = vec![];
+
+        for (next_boundary, end) in boundaries {
+            self.build_segment(line, start, end, style, spans, &mut inlays);
+
+            style = if let Some(s) = next_boundary.style() {
+                stack.push(style);
+                s
+            } else {
+                stack.pop().unwrap_or(style_begin)
+            };
+            start = end;
+        }
+
+        self.build_segment(line, start, line.len(), style, spans, &mut inlays);
+
+        // Inlay hints at the end of line
+        for (_, text, style) in inlays {
+            spans.push(Span::styled(text.clone(), *style));
+            self.record_offset(line.len(), spans);
+        }
+    }
+}
+
+// Display width of the indentation of the line
+fn indent_width(line: &str, tab_len: u8) -> usize {
+    let mut width = 0;
+    for c in line.chars() {
+        match c {
+            ' ' => width += 1,
+            '\t' => width += tab_len as usize - width % tab_len as usize,
+            _ => break,
+        }
+    }
+    width
+}
+
+// Replace spaces in the indentation with the guide character at every `step` columns. Only spaces in spans styled with
+// `base` are replaced so that guides don't hide the cursor or highlights. A wide guide character replaces two spaces
+fn draw_indent_guides<'a>(
+    spans: &mut Vec<Span<'a>>,
+    indent: usize,
+    step: usize,
+    guide: (char, Style),
+    base: Style,
+    ambiguous: AmbiguousWidth,
+) {
+    let (guide, guide_style) = guide;
+    let guide_style = base.patch(guide_style);
+    let wide = is_widened(guide, ambiguous);
+    let mut col = 0;
+    for span in mem::take(spans) {
+        if col >= indent || span.style != base {
+            col += str_width(&span.content, ambiguous);
+            spans.push(span);
+            continue;
+        }
+        let mut text = String::new();
+        let mut chars = span.content.chars().peekable();
+        while let Some(c) = chars.next() {
+            let fits = !wide || col + 1 < indent && chars.peek() == Some(&' ');
+            if c == ' ' && col < indent && col % step == 0 && fits {
+                if !text.is_empty() {
+                    spans.push(Span::styled(mem::take(&mut text), base));
+                }
+                spans.push(Span::styled(guide.to_string(), guide_style));
+                if wide {
+                    chars.next();
+                    col += 1;
+                }
+            } else {
+                text.push(c);
+            }
+            col += char_width(c, ambiguous).unwrap_or(0);
+        }
+        if !text.is_empty() {
+            spans.push(Span::styled(text, base));
+        }
+    }
+}
+
+// Reorder the spans of the text split into characters in the visual order. `offsets` are the byte offsets of the
+// characters in the spans after `first`. Inlay hints are put before the characters at their offsets
+fn reorder_spans(
+    line: &str,
+    order: &[usize],
+    offsets: &[usize],
+    spans: &mut Vec<Span<'_>>,
+    first: usize,
+) {
+    let mut rank = vec![order.len(); line.len() + 1];
+    let starts = line.char_indices().map(|(i, _)| i).collect::<Vec<_>>();
+    for (visual, &col) in order.iter().enumerate() {
+        rank[starts[col]] = visual;
+    }
+    let mut text = offsets
+        .iter()
+        .zip(spans.split_off(first))
+        .collect::<Vec<_>>();
+    text.sort_by_key(|(&offset, _)| rank[offset]);
+    spans.extend(text.into_iter().map(|(_, span)| span));
 }
 
 pub struct LineHighlighter<'a> {
@@ -100,11 +431,24 @@ pub struct LineHighlighter<'a> {
     boundaries: Vec<(Boundary, usize)>, // TODO: Consider smallvec
     style_begin: Style,
     cursor_at_end: bool,
+    virtual_cols: usize,
     cursor_style: Style,
     tab_len: u8,
     mask: Option<char>,
     select_at_end: bool,
     select_style: Style,
+    remote_at_end: Option<Style>,
+    ghost: Option<(&'a str, Style)>,
+    inlays: Vec<(usize, Cow<'a, str>, Style)>,
+    annotation: Option<(&'a str, Style)>,
+    fold_summary: Option<(String, Style)>,
+    indent_guide: Option<(char, Style)>,
+    whitespace: Option<(Style, Style)>,
+    elastic_tabs: Vec<usize>,
+    tab_stops: Vec<usize>,
+    control: Option<(ControlCharDisplay, Style)>,
+    ambiguous: AmbiguousWidth,
+    visual_order: Option<Vec<usize>>,
 }
 
 impl<'a> LineHighlighter<'a> {
@@ -121,11 +465,24 @@ impl<'a> LineHighlighter<'a> {
             boundaries: vec![],
             style_begin: Style::default(),
             cursor_at_end: false,
+            virtual_cols: 0,
             cursor_style,
             tab_len,
             mask,
             select_at_end: false,
             select_style,
+            remote_at_end: None,
+            ghost: None,
+            inlays: vec![],
+            annotation: None,
+            fold_summary: None,
+            indent_guide: None,
+            whitespace: None,
+            elastic_tabs: vec![],
+            tab_stops: vec![],
+            control: None,
+            ambiguous: AmbiguousWidth::default(),
+            visual_order: None,
         }
     }
 
@@ -136,7 +493,8 @@ impl<'a> LineHighlighter<'a> {
     }
 
     pub fn cursor_line(&mut self, cursor_col: usize, style: Style) {
-        if let Some((start, c)) = self.line.char_indices().nth(cursor_col) {
+        let start = byte_offset(self.line, cursor_col);
+        if let Some(c) = self.line[start..].chars().next() {
             self.boundaries
                 .push((Boundary::Cursor(self.cursor_style), start));
             self.boundaries.push((Boundary::End, start + c.len_utf8()));
@@ -146,6 +504,92 @@ impl<'a> LineHighlighter<'a> {
         self.style_begin = style;
     }
 
+    // Columns of virtual space between the end of line and the cursor. The cursor is rendered after them
+    pub fn virtual_space(&mut self, cols: usize) {
+        self.virtual_cols = cols;
+    }
+
+    // Widths of the tabs in the line with elastic tabstops
+    pub fn elastic_tabs(&mut self, widths: Vec<usize>) {
+        self.elastic_tabs = widths;
+    }
+
+    // Custom tab stop columns in display width
+    pub fn tab_stops(&mut self, stops: &[usize]) {
+        self.tab_stops = stops.to_vec();
+    }
+
+    // Notation and style to render control characters
+    pub fn control_chars(&mut self, display: ControlCharDisplay, style: Style) {
+        self.control = Some((display, style));
+    }
+
+    // Width of East Asian ambiguous characters on the terminal
+    pub fn ambiguous_width(&mut self, ambiguous: AmbiguousWidth) {
+        self.ambiguous = ambiguous;
+    }
+
+    // Character indices of the line in the visual order of bidirectional text
+    pub fn visual_order(&mut self, order: Vec<usize>) {
+        self.visual_order = Some(order);
+    }
+
+    // Style of the entire line. The cursor line style takes precedence over it
+    pub fn line_style(&mut self, style: Style) {
+        self.style_begin = style.patch(self.style_begin);
+    }
+
+    // Inlay hints as (byte offset, text, style). They must be sorted by the offsets
+    pub fn inlay_hints(&mut self, hints: impl Iterator<Item = (usize, &'a str, Style)>) {
+        self.inlays
+            .extend(hints.map(|(offset, text, style)| (offset, Cow::Borrowed(text), style)));
+        self.inlays.sort_by_key(|(offset, _, _)| *offset);
+    }
+
+    // Text deleted by tracked changes as (byte offset, text, style). It is rendered as virtual text like inlay hints
+    pub fn tracked_deletions(
+        &mut self,
+        deletions: impl Iterator<Item = (usize, Cow<'a, str>, Style)>,
+    ) {
+        self.inlays.extend(deletions);
+        self.inlays.sort_by_key(|(offset, _, _)| *offset);
+    }
+
+    // Ranges of changed text such as tracked insertions and changes within lines of side-by-side diff
+    pub fn changes(&mut self, ranges: impl Iterator<Item = (usize, usize, Style)>) {
+        for (start, end, style) in ranges {
+            if start != end {
+                self.boundaries.push((Boundary::Change(style), start));
+                self.boundaries.push((Boundary::End, end));
+            }
+        }
+    }
+
+    pub fn fold_summary(&mut self, hidden_lines: usize, style: Style) {
+        let text = if hidden_lines == 1 {
+            " ⋯ 1 line".to_string()
+        } else {
+            format!(" ⋯ {} lines", hidden_lines)
+        };
+        self.fold_summary = Some((text, style));
+    }
+
+    pub fn indent_guide(&mut self, c: char, style: Style) {
+        self.indent_guide = Some((c, style));
+    }
+
+    pub fn whitespace(&mut self, style: Style, trailing_style: Style) {
+        self.whitespace = Some((style, trailing_style));
+    }
+
+    pub fn annotation(&mut self, text: &'a str, style: Style) {
+        self.annotation = Some((text, style));
+    }
+
+    pub fn ghost_text(&mut self, text: &'a str, style: Style) {
+        self.ghost = Some((text, style));
+    }
+
     #[cfg(feature = "search")]
     pub fn search(&mut self, matches: impl Iterator<Item = (usize, usize)>, style: Style) {
         for (start, end) in matches {
@@ -156,6 +600,44 @@ impl<'a> LineHighlighter<'a> {
         }
     }
 
+    pub fn misspellings(&mut self, ranges: impl Iterator<Item = (usize, usize)>, style: Style) {
+        for (start, end) in ranges {
+            self.boundaries.push((Boundary::Misspell(style), start));
+            self.boundaries.push((Boundary::End, end));
+        }
+    }
+
+    pub fn diagnostics(&mut self, ranges: impl Iterator<Item = (usize, usize, Style)>) {
+        for (start, end, style) in ranges {
+            self.boundaries.push((Boundary::Diagnostic(style), start));
+            self.boundaries.push((Boundary::End, end));
+        }
+    }
+
+    #[cfg(feature = "search")]
+    pub fn highlight_rules(&mut self, ranges: impl Iterator<Item = (usize, usize, Style)>) {
+        for (start, end, style) in ranges {
+            self.boundaries.push((Boundary::Rule(style), start));
+            self.boundaries.push((Boundary::End, end));
+        }
+    }
+
+    pub fn occurrences(&mut self, ranges: impl Iterator<Item = (usize, usize)>, style: Style) {
+        for (start, end) in ranges {
+            self.boundaries.push((Boundary::Occurrence(style), start));
+            self.boundaries.push((Boundary::End, end));
+        }
+    }
+
+    pub fn hyperlinks(&mut self, ranges: impl Iterator<Item = (usize, usize, Style)>) {
+        for (start, end, style) in ranges {
+            if start != end {
+                self.boundaries.push((Boundary::Link(style), start));
+                self.boundaries.push((Boundary::End, end));
+            }
+        }
+    }
+
     pub fn selection(
         &mut self,
         current_row: usize,
@@ -186,65 +668,145 @@ impl<'a> LineHighlighter<'a> {
         }
     }
 
+    // Remote cursors as (byte offset, style) and remote selections as (start offset, end offset, style). The end offset
+    // is `None` when the selection continues to the next line. A cursor at the end of line or a selection continuing
+    // to the next line is rendered after the line
+    pub fn remote_cursors(
+        &mut self,
+        cursors: impl Iterator<Item = (usize, Style)>,
+        selections: impl Iterator<Item = (usize, Option<usize>, Style)>,
+    ) {
+        for (start, end, style) in selections {
+            if end.is_none() {
+                self.remote_at_end = Some(style);
+            }
+            let end = end.unwrap_or(self.line.len());
+            if start < end {
+                self.boundaries.push((Boundary::RemoteSelect(style), start));
+                self.boundaries.push((Boundary::End, end));
+            }
+        }
+        for (offset, style) in cursors {
+            if let Some(c) = self.line[offset..].chars().next() {
+                self.boundaries
+                    .push((Boundary::RemoteCursor(style), offset));
+                self.boundaries.push((Boundary::End, offset + c.len_utf8()));
+            } else {
+                self.remote_at_end = Some(style);
+            }
+        }
+    }
+
     pub fn into_spans(self) -> Line<'a> {
         let Self {
             line,
             mut spans,
-            mut boundaries,
+            boundaries,
             tab_len,
             style_begin,
             cursor_style,
             cursor_at_end,
+            virtual_cols,
             mask,
             select_at_end,
             select_style,
+            remote_at_end,
+            ghost,
+            inlays,
+            annotation,
+            fold_summary,
+            indent_guide,
+            whitespace,
+            elastic_tabs,
+            tab_stops,
+            control,
+            ambiguous,
+            visual_order,
         } = self;
         let mut builder = DisplayTextBuilder::new(tab_len, mask);
+        builder.elastic_tabs = elastic_tabs;
+        builder.tab_stops = tab_stops;
+        builder.ambiguous = ambiguous;
+        let text_start = spans.len();
+
+        if let Some((style, trailing_style)) = whitespace {
+            if mask.is_none() {
+                let trailing = line.trim_end_matches(&[' ', '\t'][..]).len();
+                builder.whitespace = Some((style, trailing_style, trailing));
+            }
+        }
 
-        if boundaries.is_empty() {
+        if mask.is_none() {
+            builder.control = control;
+        }
+        let has_control = builder.control.is_some() && line.contains(is_special_char);
+        if visual_order.is_some() {
+            builder.span_offsets = Some((text_start, vec![]));
+        }
+
+        if boundaries.is_empty()
+            && inlays.is_empty()
+            && builder.whitespace.is_none()
+            && !has_control
+            && visual_order.is_none()
+        {
             let built = builder.build(line);
             if !built.is_empty() {
                 spans.push(Span::styled(built, style_begin));
             }
-            if cursor_at_end {
-                spans.push(Span::styled(" ", cursor_style));
-            } else if select_at_end {
-                spans.push(Span::styled(" ", select_style));
-            }
-            return Line::from(spans);
+        } else {
+            builder.build_boundaries(line, &mut spans, boundaries, style_begin, &inlays);
         }
 
-        boundaries.sort_unstable_by(|(l, i), (r, j)| match i.cmp(j) {
-            Ordering::Equal => l.cmp(r),
-            o => o,
-        });
+        if let (Some(order), Some((_, offsets))) = (visual_order, builder.span_offsets.take()) {
+            reorder_spans(line, &order, &offsets, &mut spans, text_start);
+        }
 
-        let mut style = style_begin;
-        let mut start = 0;
-        let mut stack = vec![];
+        if let Some(guide) = indent_guide {
+            if mask.is_none() && tab_len > 0 {
+                let indent = indent_width(line, tab_len);
+                let mut text = spans.split_off(text_start);
+                let step = tab_len as usize;
+                draw_indent_guides(&mut text, indent, step, guide, style_begin, ambiguous);
+                spans.extend(text);
+            }
+        }
 
-        for (next_boundary, end) in boundaries {
-            if start < end {
-                spans.push(Span::styled(builder.build(&line[start..end]), style));
+        if cursor_at_end && virtual_cols > 0 {
+            spans.push(Span::styled(" ".repeat(virtual_cols), style_begin));
+            spans.push(Span::styled(" ", cursor_style));
+        } else if cursor_at_end {
+            // Ghost text is only shown at the end of line. The cursor is put on its first character
+            match ghost {
+                Some((text, style)) if !text.is_empty() => {
+                    let built = builder.build(text).into_owned();
+                    let len = built.chars().next().map_or(0, char::len_utf8);
+                    let (head, rest) = built.split_at(len);
+                    spans.push(Span::styled(head.to_string(), cursor_style));
+                    if !rest.is_empty() {
+                        spans.push(Span::styled(rest.to_string(), style));
+                    }
+                }
+                _ => spans.push(Span::styled(" ", cursor_style)),
             }
+        } else if select_at_end {
+            spans.push(Span::styled(" ", select_style));
+        } else if let Some(style) = remote_at_end {
+            spans.push(Span::styled(" ", style));
+        }
 
-            style = if let Some(s) = next_boundary.style() {
-                stack.push(style);
-                s
-            } else {
-                stack.pop().unwrap_or(style_begin)
-            };
-            start = end;
+        if let Some((text, style)) = fold_summary {
+            spans.push(Span::styled(text, style));
         }
 
-        if start != line.len() {
-            spans.push(Span::styled(builder.build(&line[start..]), style));
+        if let Some((text, style)) = annotation {
+            spans.push(Span::styled(text, style));
         }
 
-        if cursor_at_end {
-            spans.push(Span::styled(" ", cursor_style));
-        } else if select_at_end {
-            spans.push(Span::styled(" ", select_style));
+        if ambiguous == AmbiguousWidth::Wide {
+            for span in spans.iter_mut() {
+                span.content = pad_widened(mem::take(&mut span.content), ambiguous);
+            }
         }
 
         Line::from(spans)
@@ -402,6 +964,299 @@ mod tests {
         }
     }
 
+    #[test]
+    fn into_spans_virtual_space() {
+        let mut lh = LineHighlighter::new("ab", CUR, 4, None, SEL);
+        lh.cursor_line(2, LINE);
+        lh.virtual_space(3);
+        lh.ghost_text("c", DEFAULT);
+        assert_spans(lh, &[("ab", LINE), ("   ", LINE), (" ", CUR)], "ab");
+
+        // Virtual space is ignored when the cursor is not at the end of line
+        let mut lh = LineHighlighter::new("ab", CUR, 4, None, SEL);
+        lh.cursor_line(1, LINE);
+        lh.virtual_space(3);
+        assert_spans(lh, &[("a", LINE), ("b", CUR)], "a");
+    }
+
+    #[test]
+    fn into_spans_control_chars() {
+        const CTRL: Style = Style::new().fg(Color::Blue);
+        let mut lh = LineHighlighter::new("a\r\u{1b}b\0", CUR, 4, None, SEL);
+        lh.control_chars(ControlCharDisplay::Caret, CTRL);
+        assert_spans(
+            lh,
+            &[("a", DEFAULT), ("^M^[", CTRL), ("b", DEFAULT), ("^@", CTRL)],
+            "caret",
+        );
+
+        let mut lh = LineHighlighter::new("\r\t", CUR, 4, None, SEL);
+        lh.cursor_line(0, LINE);
+        lh.control_chars(ControlCharDisplay::Picture, CTRL);
+        lh.whitespace(DEFAULT, DEFAULT);
+        assert_spans(lh, &[("␍", CUR.patch(CTRL)), ("→  ", LINE)], "picture");
+
+        // Replacement characters of invalid UTF-8 sequences are marked
+        let mut lh = LineHighlighter::new("a\u{fffd}b", CUR, 4, None, SEL);
+        lh.control_chars(ControlCharDisplay::Caret, CTRL);
+        assert_spans(
+            lh,
+            &[("a", DEFAULT), ("\u{fffd}", CTRL), ("b", DEFAULT)],
+            "replacement",
+        );
+
+        // Not rendered when masked
+        let mut lh = LineHighlighter::new("a\r", CUR, 4, Some('*'), SEL);
+        lh.control_chars(ControlCharDisplay::Caret, CTRL);
+        assert_spans(lh, &[("**", DEFAULT)], "masked");
+    }
+
+    #[test]
+    fn into_spans_tab_stops() {
+        let mut lh = LineHighlighter::new("a\tb\tc\td", CUR, 4, None, SEL);
+        lh.tab_stops(&[6, 8]);
+        assert_spans(lh, &[("a     b c   d", DEFAULT)], "a");
+    }
+
+    #[test]
+    fn into_spans_elastic_tabs() {
+        let mut lh = LineHighlighter::new("a\tb\tc", CUR, 4, None, SEL);
+        lh.elastic_tabs(vec![6, 1]);
+        assert_spans(lh, &[("a      b c", DEFAULT)], "a");
+
+        // Tabs in ghost text are not elastic
+        let mut lh = LineHighlighter::new("a\t", CUR, 4, None, SEL);
+        lh.cursor_line(2, LINE);
+        lh.elastic_tabs(vec![6]);
+        lh.ghost_text("\tb", DEFAULT);
+        assert_spans(lh, &[("a      ", LINE), (" ", CUR), ("b", DEFAULT)], "a");
+    }
+
+    #[test]
+    fn into_spans_ghost_text() {
+        const GHOST: Style = Style::new().fg(Color::DarkGray);
+        let tests = [
+            ("", 0, "abc", &[("a", CUR), ("bc", GHOST)][..]),
+            ("ab", 2, "c", &[("ab", LINE), ("c", CUR)][..]),
+            ("a", 0, "bc", &[("a", CUR)][..]), // Not shown when cursor is not at end of line
+            (
+                "ab",
+                2,
+                "\tc",
+                &[("ab", LINE), (" ", CUR), (" c", GHOST)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (line, col, ghost, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.cursor_line(col, LINE);
+            lh.ghost_text(ghost, GHOST);
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[test]
+    fn into_spans_inlay_hints() {
+        const HINT: Style = Style::new().fg(Color::DarkGray);
+        let tests = [
+            (
+                "ab",
+                0,
+                &[(1, ": x")][..],
+                &[("a", CUR), (": x", HINT), ("b", LINE)][..],
+            ),
+            (
+                "ab",
+                1,
+                &[(1, ": x")][..],
+                &[("a", LINE), (": x", HINT), ("b", CUR)][..],
+            ),
+            (
+                "ab",
+                2,
+                &[(0, "<"), (2, ">")][..],
+                &[("<", HINT), ("ab", LINE), (">", HINT), (" ", CUR)][..],
+            ),
+            (
+                "a\tb",
+                0,
+                &[(1, "!"), (1, "?")][..],
+                &[("a", CUR), ("!", HINT), ("?", HINT), ("   b", LINE)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (line, col, hints, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.cursor_line(col, LINE);
+            lh.inlay_hints(hints.iter().map(|&(o, t)| (o, t, HINT)));
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[test]
+    fn into_spans_annotation() {
+        const NOTE: Style = Style::new().fg(Color::Magenta);
+        let tests = [
+            ("ab", None, &[("ab", DEFAULT), (" <- x", NOTE)][..]),
+            (
+                "ab",
+                Some(2),
+                &[("ab", LINE), (" ", CUR), (" <- x", NOTE)][..],
+            ),
+            ("", None, &[(" <- x", NOTE)][..]),
+        ];
+
+        for test in tests {
+            let (line, cursor, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            if let Some(col) = cursor {
+                lh.cursor_line(col, LINE);
+            }
+            lh.annotation(" <- x", NOTE);
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[test]
+    fn into_spans_whitespace() {
+        const WS: Style = Style::new().fg(Color::DarkGray);
+        const TRAIL: Style = Style::new().fg(Color::Red);
+        let tests = [
+            (
+                "a b",
+                None,
+                &[("a", DEFAULT), ("·", WS), ("b", DEFAULT)][..],
+            ),
+            (
+                "\ta\t ",
+                None,
+                &[("→   ", WS), ("a", DEFAULT), ("→  ·", TRAIL)][..],
+            ),
+            ("  ", None, &[("··", TRAIL)][..]),
+            (
+                "a  ",
+                Some(1),
+                &[
+                    ("a", LINE),
+                    ("·", CUR.fg(Color::Red)),
+                    ("·", LINE.fg(Color::Red)),
+                ][..],
+            ),
+        ];
+
+        for test in tests {
+            let (line, cursor, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            if let Some(col) = cursor {
+                lh.cursor_line(col, LINE);
+            }
+            lh.whitespace(WS, TRAIL);
+            assert_spans(lh, want, test);
+        }
+
+        // Whitespaces are not visualized in masked text
+        let mut lh = LineHighlighter::new("a b", CUR, 4, Some('*'), SEL);
+        lh.whitespace(WS, TRAIL);
+        assert_spans(lh, &[("***", DEFAULT)], "masked");
+    }
+
+    #[test]
+    fn into_spans_indent_guides() {
+        const GUIDE: Style = Style::new().fg(Color::DarkGray);
+        const LINE_GUIDE: Style = LINE.fg(Color::DarkGray);
+        let tests = [
+            ("    a", None, &[("│", GUIDE), ("   a", DEFAULT)][..]),
+            (
+                "\t\ta",
+                None,
+                &[
+                    ("│", GUIDE),
+                    ("   ", DEFAULT),
+                    ("│", GUIDE),
+                    ("   a", DEFAULT),
+                ][..],
+            ),
+            ("  a", None, &[("│", GUIDE), (" a", DEFAULT)][..]),
+            ("a  b", None, &[("a  b", DEFAULT)][..]),
+            (
+                "      a",
+                Some(4),
+                &[("│", LINE_GUIDE), ("   ", LINE), (" ", CUR), (" a", LINE)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (line, cursor, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            if let Some(col) = cursor {
+                lh.cursor_line(col, LINE);
+            }
+            lh.indent_guide('│', GUIDE);
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[test]
+    fn into_spans_visual_order() {
+        const HINT: Style = Style::new().fg(Color::DarkGray);
+        let mut lh = LineHighlighter::new("ab אבג", CUR, 4, None, SEL);
+        lh.cursor_line(3, LINE);
+        lh.visual_order(vec![0, 1, 2, 5, 4, 3]);
+        assert_spans(
+            lh,
+            &[
+                ("a", LINE),
+                ("b", LINE),
+                (" ", LINE),
+                ("ג", LINE),
+                ("ב", LINE),
+                ("א", CUR),
+            ],
+            "cursor",
+        );
+
+        // Inlay hints are put before the characters at their offsets
+        let mut lh = LineHighlighter::new("אב", CUR, 4, None, SEL);
+        lh.inlay_hints([(0, ":", HINT), (4, "!", HINT)].into_iter());
+        lh.visual_order(vec![1, 0]);
+        assert_spans(
+            lh,
+            &[("ב", DEFAULT), (":", HINT), ("א", DEFAULT), ("!", HINT)],
+            "inlay hints",
+        );
+    }
+
+    #[test]
+    fn into_spans_ambiguous_width() {
+        const GUIDE: Style = Style::new().fg(Color::DarkGray);
+        let wide = AmbiguousWidth::Wide;
+
+        // A padding cell is inserted after a wide ambiguous character
+        let mut lh = LineHighlighter::new("○\tb", CUR, 4, None, SEL);
+        lh.cursor_line(0, LINE);
+        lh.ambiguous_width(wide);
+        assert_spans(lh, &[("○ ", CUR), ("  b", LINE)], "tab");
+
+        let lh = LineHighlighter::new("○\tb", CUR, 4, None, SEL);
+        assert_spans(lh, &[("○   b", DEFAULT)], "narrow");
+
+        let mut lh = LineHighlighter::new(" \ta", CUR, 4, None, SEL);
+        lh.ambiguous_width(wide);
+        lh.whitespace(DEFAULT, DEFAULT);
+        assert_spans(lh, &[(".>  ", DEFAULT), ("a", DEFAULT)], "whitespace");
+
+        let mut lh = LineHighlighter::new("    a", CUR, 4, None, SEL);
+        lh.ambiguous_width(wide);
+        lh.indent_guide('│', GUIDE);
+        assert_spans(lh, &[("│ ", GUIDE), ("  a", DEFAULT)], "indent guide");
+
+        let mut lh = LineHighlighter::new("ab", CUR, 4, Some('●'), SEL);
+        lh.ambiguous_width(wide);
+        assert_spans(lh, &[("● ● ", DEFAULT)], "mask");
+    }
+
     #[test]
     fn into_spans_line_number() {
         let tests = [
@@ -482,6 +1337,120 @@ mod tests {
         }
     }
 
+    #[test]
+    fn into_spans_misspellings() {
+        const BAD: Style = Style::new().fg(Color::Red);
+        let tests = [
+            (
+                "abc def",
+                &[(0, 3)][..],
+                &[("abc", BAD), (" def", DEFAULT)][..],
+            ),
+            (
+                "abc\tdef",
+                &[(4, 7)][..],
+                &[("abc ", DEFAULT), ("def", BAD)][..],
+            ),
+            ("abc", &[][..], &[("abc", DEFAULT)][..]),
+        ];
+
+        for test in tests {
+            let (line, ranges, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.misspellings(ranges.iter().copied(), BAD);
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[test]
+    fn into_spans_diagnostics() {
+        const ERR: Style = Style::new().fg(Color::Red);
+        const WARN: Style = Style::new().fg(Color::Yellow);
+        let tests = [
+            (
+                "abcdef",
+                &[(1, 3, ERR), (4, 5, WARN)][..],
+                &[
+                    ("a", DEFAULT),
+                    ("bc", ERR),
+                    ("d", DEFAULT),
+                    ("e", WARN),
+                    ("f", DEFAULT),
+                ][..],
+            ),
+            ("abc", &[][..], &[("abc", DEFAULT)][..]),
+        ];
+
+        for test in tests {
+            let (line, ranges, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.diagnostics(ranges.iter().copied());
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[cfg(feature = "search")]
+    #[test]
+    fn into_spans_highlight_rules() {
+        const TAG: Style = Style::new().fg(Color::Green);
+        const TODO: Style = Style::new().fg(Color::Yellow);
+        let tests = [
+            (
+                "TODO #tag",
+                &[(0, 4, TODO), (5, 9, TAG)][..],
+                &[("TODO", TODO), (" ", DEFAULT), ("#tag", TAG)][..],
+            ),
+            ("abc", &[][..], &[("abc", DEFAULT)][..]),
+        ];
+
+        for test in tests {
+            let (line, ranges, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.highlight_rules(ranges.iter().copied());
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[test]
+    fn into_spans_occurrences() {
+        const OCC: Style = Style::new().fg(Color::Cyan);
+        let tests = [
+            (
+                "x = x + 1",
+                &[(4, 5)][..],
+                &[("x = ", DEFAULT), ("x", OCC), (" + 1", DEFAULT)][..],
+            ),
+            ("abc", &[][..], &[("abc", DEFAULT)][..]),
+        ];
+
+        for test in tests {
+            let (line, ranges, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.occurrences(ranges.iter().copied(), OCC);
+            assert_spans(lh, want, test);
+        }
+    }
+
+    #[test]
+    fn into_spans_hyperlinks() {
+        const LINK: Style = Style::new().fg(Color::Blue);
+        let tests = [
+            (
+                "see https://example.com",
+                &[(4, 23, LINK)][..],
+                &[("see ", DEFAULT), ("https://example.com", LINK)][..],
+            ),
+            ("abc", &[(1, 1, LINK)][..], &[("abc", DEFAULT)][..]),
+        ];
+
+        for test in tests {
+            let (line, ranges, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.hyperlinks(ranges.iter().copied());
+            assert_spans(lh, want, test);
+        }
+    }
+
     #[test]
     fn into_spans_selection() {
         let tests = [
@@ -522,6 +1491,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn into_spans_remote_cursors() {
+        const REMOTE: Style = Style::new().bg(Color::Magenta);
+        const REMOTE_SEL: Style = Style::new().bg(Color::LightMagenta);
+
+        let tests = [
+            // (line, cursors, selections, want)
+            (
+                "abc",
+                &[(1, REMOTE)][..],
+                &[][..],
+                &[("a", DEFAULT), ("b", REMOTE), ("c", DEFAULT)][..],
+            ),
+            (
+                "abc",
+                &[(3, REMOTE)][..],
+                &[][..],
+                &[("abc", DEFAULT), (" ", REMOTE)][..],
+            ),
+            (
+                "abcd",
+                &[(3, REMOTE)][..],
+                &[(1, Some(3), REMOTE_SEL)][..],
+                &[("a", DEFAULT), ("bc", REMOTE_SEL), ("d", REMOTE)][..],
+            ),
+            (
+                "abc",
+                &[][..],
+                &[(1, None, REMOTE_SEL)][..],
+                &[("a", DEFAULT), ("bc", REMOTE_SEL), (" ", REMOTE_SEL)][..],
+            ),
+            (
+                "abc",
+                &[][..],
+                &[(1, Some(3), REMOTE_SEL)][..],
+                &[("a", DEFAULT), ("bc", REMOTE_SEL)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (line, cursors, selections, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.remote_cursors(cursors.iter().copied(), selections.iter().copied());
+            assert_spans(lh, want, test);
+        }
+
+        // The local cursor and selection take precedence over remote ones
+        let mut lh = LineHighlighter::new("abcd", CUR, 4, None, SEL);
+        lh.cursor_line(1, LINE);
+        lh.selection(0, 0, 2, 0, 3);
+        lh.remote_cursors(
+            [(1, REMOTE)].into_iter(),
+            [(0, Some(4), REMOTE_SEL)].into_iter(),
+        );
+        assert_spans(
+            lh,
+            &[("a", REMOTE_SEL), ("b", CUR), ("c", SEL), ("d", REMOTE_SEL)],
+            "local and remote",
+        );
+    }
+
+    #[test]
+    fn into_spans_line_style() {
+        const CONFLICT: Style = Style::new().bg(Color::Cyan);
+
+        let mut lh = LineHighlighter::new("abc", CUR, 4, None, SEL);
+        lh.line_style(CONFLICT);
+        assert_spans(lh, &[("abc", CONFLICT)], "line style");
+
+        let mut lh = LineHighlighter::new("abc", CUR, 4, None, SEL);
+        lh.cursor_line(1, LINE);
+        lh.line_style(CONFLICT);
+        assert_spans(
+            lh,
+            &[("a", LINE), ("b", CUR), ("c", LINE)],
+            "line style under cursor line",
+        );
+    }
+
+    #[test]
+    fn into_spans_tracked_changes() {
+        const INS: Style = Style::new().bg(Color::Green);
+        const DEL: Style = Style::new().bg(Color::Red);
+
+        let mut lh = LineHighlighter::new("abcd", CUR, 4, None, SEL);
+        lh.changes([(1, 3, INS)].into_iter());
+        lh.tracked_deletions(
+            [
+                (4, Cow::Borrowed("x"), DEL),
+                (1, Cow::Owned("y↵".to_string()), DEL),
+            ]
+            .into_iter(),
+        );
+        lh.inlay_hints([(3, "z", LNUM)].into_iter());
+        assert_spans(
+            lh,
+            &[
+                ("a", DEFAULT),
+                ("y↵", DEL),
+                ("bc", INS),
+                ("z", LNUM),
+                ("d", DEFAULT),
+                ("x", DEL),
+            ],
+            "tracked changes",
+        );
+    }
+
     #[test]
     fn into_spans_mixed_highlights() {
         let tests = [
@@ -1,7 +1,46 @@
+use crate::memory::strings_size;
+use crate::op::EditOp;
 use crate::util::Pos;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::mem;
+use std::time::{Duration, Instant};
+
+/// Policy to group edits into undo steps. One call of [`crate::TextArea::undo`] reverts one group of edits. Regardless
+/// of the policy, [`crate::TextArea::commit_undo_group`] ends the current group explicitly.
+/// ```
+/// use std::time::Duration;
+/// use tui_textarea::{TextArea, UndoGrouping};
+///
+/// let mut textarea = TextArea::default();
+///
+/// // Edits within 500 milliseconds are undone at once
+/// textarea.set_undo_grouping(UndoGrouping::TimeGap(Duration::from_millis(500)));
+/// assert_eq!(textarea.undo_grouping(), UndoGrouping::TimeGap(Duration::from_millis(500)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UndoGrouping {
+    /// Every edit is a separate undo step. This is the default policy
+    Keystroke,
+    /// Consecutive insertions or deletions of characters are grouped by words. A word and the whitespaces following it
+    /// are one group. Moving the cursor or other kinds of edits start a new group
+    Word,
+    /// Edits are grouped while the interval between them is shorter than the duration
+    TimeGap(Duration),
+    /// All edits are grouped until [`crate::TextArea::commit_undo_group`] is called
+    Explicit,
+}
+
+impl Default for UndoGrouping {
+    fn default() -> Self {
+        Self::Keystroke
+    }
+}
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EditKind {
     InsertChar(char),
     DeleteChar(char),
@@ -44,16 +83,19 @@ impl EditKind {
 
                 // Handle first line of chunk
                 let first_line = &mut lines[before.row];
-                let mut last_line = first_line.drain(before.offset..).as_str().to_string();
+                let rest = first_line.split_off(before.offset);
                 first_line.push_str(&c[0]);
 
                 // Handle last line of chunk
-                let next_row = before.row + 1;
-                last_line.insert_str(0, c.last().unwrap());
-                lines.insert(next_row, last_line);
+                let mut last_line = String::with_capacity(c[c.len() - 1].len() + rest.len());
+                last_line.push_str(&c[c.len() - 1]);
+                last_line.push_str(&rest);
 
-                // Handle middle lines of chunk
-                lines.splice(next_row..next_row, c[1..c.len() - 1].iter().cloned());
+                // Insert middle lines and last line of chunk at once. Inserting them one by one shifts the following
+                // lines for each insertion, which is slow when pasting many lines
+                let next_row = before.row + 1;
+                let inserted = c[1..c.len() - 1].iter().cloned().chain([last_line]);
+                lines.splice(next_row..next_row, inserted);
             }
             EditKind::DeleteChunk(c) => {
                 debug_assert!(c.len() > 1, "Chunk size must be > 1: {:?}", c);
@@ -61,7 +103,7 @@ impl EditKind {
                 // Remove middle lines of chunk
                 let mut last_line = lines
                     .drain(after.row + 1..after.row + c.len())
-                    .last()
+                    .next_back()
                     .unwrap();
                 // Remove last line of chunk
                 last_line.drain(..c[c.len() - 1].len());
@@ -90,10 +132,13 @@ impl EditKind {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Edit {
     kind: EditKind,
     before: Pos,
     after: Pos,
+    // Whether this edit is the first edit of an undo group
+    group_start: bool,
 }
 
 impl Edit {
@@ -102,6 +147,7 @@ impl Edit {
             kind,
             before,
             after,
+            group_start: true,
         }
     }
 
@@ -120,13 +166,45 @@ impl Edit {
     pub fn cursor_after(&self) -> (usize, usize) {
         (self.after.row, self.after.col)
     }
+
+    // Convert the edit into the operation on text. When `undo` is true, the operation reverts the edit. Deleted text
+    // always starts at the cursor position after the deletion
+    pub fn op(&self, undo: bool) -> EditOp {
+        use EditKind::*;
+        let (insert, text) = match &self.kind {
+            InsertChar(c) => (true, c.to_string()),
+            DeleteChar(c) => (false, c.to_string()),
+            InsertNewline => (true, "\n".to_string()),
+            DeleteNewline => (false, "\n".to_string()),
+            InsertStr(s) => (true, s.clone()),
+            DeleteStr(s) => (false, s.clone()),
+            InsertChunk(c) => (true, c.join("\n")),
+            DeleteChunk(c) => (false, c.join("\n")),
+        };
+        let pos = if insert {
+            self.cursor_before()
+        } else {
+            self.cursor_after()
+        };
+        if insert != undo {
+            EditOp::Insert { pos, text }
+        } else {
+            EditOp::Delete { pos, text }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct History {
     index: usize,
     max_items: usize,
     edits: VecDeque<Edit>,
+    grouping: UndoGrouping,
+    // Whether the next edit must start a new undo group
+    commit: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_pushed: Option<Instant>,
 }
 
 impl History {
@@ -135,17 +213,57 @@ impl History {
             index: 0,
             max_items,
             edits: VecDeque::new(),
+            grouping: UndoGrouping::default(),
+            commit: false,
+            last_pushed: None,
         }
     }
 
-    pub fn push(&mut self, edit: Edit) {
+    // Check if the edit continues the word edited by the previous edit
+    fn continues_word(prev: &Edit, edit: &Edit) -> bool {
+        if prev.cursor_after() != edit.cursor_before() {
+            return false;
+        }
+        match (&prev.kind, &edit.kind) {
+            (EditKind::InsertChar(p), EditKind::InsertChar(c)) => {
+                !p.is_whitespace() || c.is_whitespace()
+            }
+            (EditKind::DeleteChar(p), EditKind::DeleteChar(c)) => {
+                p.is_whitespace() || !c.is_whitespace()
+            }
+            _ => false,
+        }
+    }
+
+    pub fn push(&mut self, mut edit: Edit) {
         if self.max_items == 0 {
             return;
         }
 
+        let now = Instant::now();
+        let prev = self.index.checked_sub(1).map(|i| &self.edits[i]);
+        edit.group_start = match prev {
+            _ if self.commit || self.index < self.edits.len() => true,
+            None => true,
+            Some(prev) => match self.grouping {
+                UndoGrouping::Keystroke => true,
+                UndoGrouping::Word => !Self::continues_word(prev, &edit),
+                UndoGrouping::TimeGap(gap) => self
+                    .last_pushed
+                    .map_or(true, |at| now.duration_since(at) > gap),
+                UndoGrouping::Explicit => false,
+            },
+        };
+        self.commit = false;
+        self.last_pushed = Some(now);
+
         if self.edits.len() == self.max_items {
             self.edits.pop_front();
             self.index = self.index.saturating_sub(1);
+            // The rest of the oldest group is undone as one group
+            if let Some(front) = self.edits.front_mut() {
+                front.group_start = true;
+            }
         }
 
         if self.index < self.edits.len() {
@@ -160,22 +278,202 @@ impl History {
         if self.index == self.edits.len() {
             return None;
         }
-        let edit = &self.edits[self.index];
-        edit.redo(lines);
-        self.index += 1;
-        Some(edit.cursor_after())
+        self.commit = true;
+        loop {
+            let edit = &self.edits[self.index];
+            edit.redo(lines);
+            self.index += 1;
+            if self.edits.get(self.index).map_or(true, |e| e.group_start) {
+                return Some(edit.cursor_after());
+            }
+        }
     }
 
     pub fn undo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
-        self.index = self.index.checked_sub(1)?;
-        let edit = &self.edits[self.index];
-        edit.undo(lines);
-        Some(edit.cursor_before())
+        self.index.checked_sub(1)?;
+        self.commit = true;
+        loop {
+            self.index -= 1;
+            let edit = &self.edits[self.index];
+            edit.undo(lines);
+            if edit.group_start || self.index == 0 {
+                return Some(edit.cursor_before());
+            }
+        }
+    }
+
+    // End the current undo group. The next edit starts a new group
+    pub fn commit(&mut self) {
+        self.commit = true;
+    }
+
+    pub fn grouping(&self) -> UndoGrouping {
+        self.grouping
+    }
+
+    pub fn set_grouping(&mut self, grouping: UndoGrouping) {
+        self.grouping = grouping;
+        self.commit = true;
+    }
+
+    // Bytes held by the edits including the text in them
+    pub fn memory_usage(&self) -> usize {
+        let texts: usize = self
+            .edits
+            .iter()
+            .map(|e| match &e.kind {
+                EditKind::InsertStr(s) | EditKind::DeleteStr(s) => s.capacity(),
+                EditKind::InsertChunk(c) | EditKind::DeleteChunk(c) => strings_size(c),
+                _ => 0,
+            })
+            .sum();
+        self.edits.capacity() * mem::size_of::<Edit>() + texts
     }
 
     pub fn max_items(&self) -> usize {
         self.max_items
     }
+
+    // Index of the next edit to be redone. Edits before the index are applied to the text
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn edit(&self, index: usize) -> &Edit {
+        &self.edits[index]
+    }
+
+    // Rebase the edits on the operation which was applied to the current text by someone else so that undo and redo
+    // keep working. `offset` is the byte offset of the position of the operation in its line. The operation is
+    // transformed backward through the applied edits and forward through the undone edits, and each edit is moved by
+    // it. When the operation touches the text of some edit, the edits are not changed and `false` is returned
+    pub fn rebase(&mut self, op: &EditOp, offset: usize) -> bool {
+        fn overlaps(
+            a: ((usize, usize), (usize, usize)),
+            b: ((usize, usize), (usize, usize)),
+        ) -> bool {
+            a.0 <= b.1 && b.0 <= a.1
+        }
+
+        // Offset of the position where the edit happens. Deleted text starts at the cursor after the deletion
+        fn op_offset(edit: &Edit, op: &EditOp) -> usize {
+            match op {
+                EditOp::Insert { .. } => edit.before.offset,
+                EditOp::Delete { .. } => edit.after.offset,
+            }
+        }
+
+        // Move the operation by the edit. Returns the operation and its offset
+        fn shift_op(
+            edit: &EditOp,
+            edit_offset: usize,
+            op: &EditOp,
+            offset: usize,
+        ) -> (EditOp, usize) {
+            let (row, col) = op.pos();
+            let p = edit.shift_pos(edit_offset, &Pos::new(row, col, offset));
+            let op = match op.clone() {
+                EditOp::Insert { text, .. } => EditOp::Insert {
+                    pos: (p.row, p.col),
+                    text,
+                },
+                EditOp::Delete { text, .. } => EditOp::Delete {
+                    pos: (p.row, p.col),
+                    text,
+                },
+            };
+            (op, p.offset)
+        }
+
+        let mut edits = self.edits.clone();
+
+        // The operation is applied after the applied edits
+        let (mut r, mut r_offset) = (op.clone(), offset);
+        for edit in edits.range_mut(..self.index).rev() {
+            let e = edit.op(false);
+            if overlaps(r.range_before(), e.range_after()) {
+                return false;
+            }
+            let e_offset = op_offset(edit, &e);
+            edit.after = r.shift_pos(r_offset, &edit.after);
+            // Move the operation to the text before the edit
+            let shifted = shift_op(&e.invert(), e_offset, &r, r_offset);
+            r = shifted.0;
+            r_offset = shifted.1;
+            edit.before = r.shift_pos(r_offset, &edit.before);
+        }
+
+        // The undone edits are applied after the operation
+        let (mut r, mut r_offset) = (op.clone(), offset);
+        for edit in edits.range_mut(self.index..) {
+            let e = edit.op(false);
+            if overlaps(r.range_before(), e.range_before()) {
+                return false;
+            }
+            let e_offset = op_offset(edit, &e);
+            edit.before = r.shift_pos(r_offset, &edit.before);
+            let shifted = shift_op(&e, e_offset, &r, r_offset);
+            r = shifted.0;
+            r_offset = shifted.1;
+            edit.after = r.shift_pos(r_offset, &edit.after);
+        }
+
+        self.edits = edits;
+        self.commit = true;
+        true
+    }
+}
+
+/// Snapshot of the undo/redo history of [`crate::TextArea`]. It is taken by [`crate::TextArea::undo_history`] and
+/// restored by [`crate::TextArea::set_undo_history`]. When `serde` feature is enabled, this type can be serialized so
+/// that an application can save the history with the document and undo past a restart.
+///
+/// The snapshot remembers a fingerprint of the text when it was taken. It can be restored only to the same text.
+/// ```
+/// use tui_textarea::TextArea;
+///
+/// let mut textarea = TextArea::default();
+/// textarea.insert_str("hello");
+/// let history = textarea.undo_history();
+///
+/// // Reload the document and the history
+/// let mut textarea = TextArea::from(textarea.lines().to_vec());
+/// assert!(textarea.set_undo_history(history));
+/// textarea.undo();
+/// assert_eq!(textarea.lines(), [""]);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UndoHistory {
+    history: History,
+    fingerprint: u64,
+}
+
+impl UndoHistory {
+    pub(crate) fn new(history: History, lines: &[String]) -> Self {
+        Self {
+            history,
+            fingerprint: fingerprint(lines),
+        }
+    }
+
+    pub(crate) fn restore(self, lines: &[String]) -> Option<History> {
+        (self.fingerprint == fingerprint(lines)).then(|| self.history)
+    }
+}
+
+// FNV-1a hash of the lines. `std::hash::DefaultHasher` is not used since its algorithm may change between Rust
+// versions and the fingerprint is persisted
+fn fingerprint(lines: &[String]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for (i, line) in lines.iter().enumerate() {
+        let newline = if i == 0 { &b""[..] } else { &b"\n"[..] };
+        for b in newline.iter().chain(line.as_bytes()) {
+            hash ^= *b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
 }
 
 #[cfg(test)]
@@ -647,4 +945,84 @@ mod tests {
             assert_eq!(&lines, &before, "{test:?}");
         }
     }
+
+    fn insert_chars(history: &mut History, lines: &mut [String], s: &str) {
+        for c in s.chars() {
+            let col = lines[0].chars().count();
+            let offset = lines[0].len();
+            lines[0].push(c);
+            let edit = Edit::new(
+                EditKind::InsertChar(c),
+                Pos::new(0, col, offset),
+                Pos::new(0, col + 1, offset + c.len_utf8()),
+            );
+            history.push(edit);
+        }
+    }
+
+    #[test]
+    fn restore_undo_history() {
+        let mut history = History::new(50);
+        let mut lines = vec![String::new()];
+        insert_chars(&mut history, &mut lines, "ab");
+
+        let snapshot = UndoHistory::new(history, &lines);
+        assert!(snapshot.clone().restore(&["a".to_string()]).is_none());
+        assert!(snapshot
+            .clone()
+            .restore(&["a".to_string(), "b".to_string()])
+            .is_none());
+        let mut history = snapshot.restore(&lines).unwrap();
+        history.undo(&mut lines);
+        assert_eq!(lines, ["a"]);
+    }
+
+    #[test]
+    fn undo_grouping() {
+        let undo_all = |history: &mut History, lines: &mut Vec<String>| {
+            let mut texts = vec![];
+            while history.undo(lines).is_some() {
+                texts.push(lines[0].clone());
+            }
+            texts
+        };
+
+        let mut history = History::new(50);
+        let mut lines = vec![String::new()];
+        insert_chars(&mut history, &mut lines, "ab");
+        assert_eq!(undo_all(&mut history, &mut lines), ["a", ""]);
+
+        let mut history = History::new(50);
+        history.set_grouping(UndoGrouping::Word);
+        let mut lines = vec![String::new()];
+        insert_chars(&mut history, &mut lines, "foo bar  baz");
+        assert_eq!(
+            undo_all(&mut history, &mut lines),
+            ["foo bar  ", "foo ", ""],
+        );
+        // Redo restores the groups
+        assert_eq!(history.redo(&mut lines), Some((0, 4)));
+        assert_eq!(lines, ["foo "]);
+
+        let mut history = History::new(50);
+        history.set_grouping(UndoGrouping::Explicit);
+        let mut lines = vec![String::new()];
+        insert_chars(&mut history, &mut lines, "ab");
+        history.commit();
+        insert_chars(&mut history, &mut lines, "cd");
+        assert_eq!(undo_all(&mut history, &mut lines), ["ab", ""]);
+
+        let mut history = History::new(50);
+        history.set_grouping(UndoGrouping::TimeGap(Duration::from_secs(60)));
+        let mut lines = vec![String::new()];
+        insert_chars(&mut history, &mut lines, "abc");
+        assert_eq!(undo_all(&mut history, &mut lines), [""]);
+
+        // The oldest group is broken when it exceeds the max number of edits
+        let mut history = History::new(3);
+        history.set_grouping(UndoGrouping::Explicit);
+        let mut lines = vec![String::new()];
+        insert_chars(&mut history, &mut lines, "abcd");
+        assert_eq!(undo_all(&mut history, &mut lines), ["a"]);
+    }
 }
@@ -0,0 +1,32 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type Callback<'a> = Arc<dyn Fn(&[String]) + Send + Sync + 'a>;
+
+// Callback invoked with the current lines after edits stop for `interval`. `changed_at` is the time of the last edit
+// which has not been notified to the callback yet
+#[derive(Clone)]
+pub struct IdleChange<'a> {
+    pub interval: Duration,
+    pub callback: Callback<'a>,
+    pub changed_at: Option<Instant>,
+}
+
+impl<'a> fmt::Debug for IdleChange<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleChange")
+            .field("interval", &self.interval)
+            .field("callback", &"..")
+            .field("changed_at", &self.changed_at)
+            .finish()
+    }
+}
+
+impl<'a> IdleChange<'a> {
+    // Time remaining until the callback should be invoked. `None` when no edit is waiting for the callback
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        let at = self.changed_at?;
+        Some(self.interval.saturating_sub(now.duration_since(at)))
+    }
+}
@@ -0,0 +1,293 @@
+use crate::fold::indent_width;
+use crate::word::is_word_char;
+use std::cmp;
+
+// Re-indent the lines except for the first one so that the least indented line has the base indentation. Relative
+// indentation between the lines is preserved. The first line is not changed since it is inserted after the cursor.
+// Blank lines are not changed
+pub fn reindent(lines: &mut [String], base: &str, tab_len: u8) {
+    let rest = match lines.get_mut(1..) {
+        Some(rest) if !rest.is_empty() => rest,
+        _ => return,
+    };
+    let min = match rest.iter().filter_map(|l| indent_width(l, tab_len)).min() {
+        Some(min) => min,
+        None => return,
+    };
+
+    for line in rest.iter_mut() {
+        if indent_width(line, tab_len).is_none() {
+            continue;
+        }
+        // Remove the indentation of `min` width. When a tab crosses the width, the excess is filled with spaces
+        let mut width = 0;
+        let mut offset = 0;
+        for (i, c) in line.char_indices() {
+            if width >= min {
+                offset = i;
+                break;
+            }
+            width += if c == '\t' {
+                cmp::max(tab_len as usize, 1)
+            } else {
+                1
+            };
+            offset = i + c.len_utf8();
+        }
+        let mut indented = String::with_capacity(base.len() + line.len());
+        indented.push_str(base);
+        indented.extend(std::iter::repeat(' ').take(width - min));
+        indented.push_str(&line[offset..]);
+        *line = indented;
+    }
+}
+
+// Indentation used in the text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indent {
+    Tabs,
+    Spaces(u8),
+}
+
+// Detect the indentation used by the lines. Hard tabs are detected when more lines are indented with tabs than with
+// spaces. The width of spaces is the most frequent difference of indentation between adjacent lines. `None` is
+// returned when the lines are not indented
+pub fn detect_indent(lines: &[String]) -> Option<Indent> {
+    let (mut tabs, mut spaces) = (0, 0);
+    let mut deltas = [0usize; 9];
+    let mut prev = 0;
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with('\t') {
+            tabs += 1;
+            continue;
+        }
+        let width = line.chars().take_while(|&c| c == ' ').count();
+        if width > 0 {
+            spaces += 1;
+        }
+        let delta = if width > prev {
+            width - prev
+        } else {
+            prev - width
+        };
+        if (2..deltas.len()).contains(&delta) {
+            deltas[delta] += 1;
+        }
+        prev = width;
+    }
+
+    if tabs == 0 && spaces == 0 {
+        return None;
+    }
+    if tabs > spaces {
+        return Some(Indent::Tabs);
+    }
+    let mut width = 0;
+    for w in 2..deltas.len() {
+        if deltas[w] > deltas[width] {
+            width = w;
+        }
+    }
+    (width > 0).then(|| Indent::Spaces(width as u8))
+}
+
+/// Rules to decide the indentation of lines for auto-indent and re-indentation. See
+/// [`crate::TextArea::set_indent_rules`].
+///
+/// A line is indented one level deeper than the previous non-blank line when the previous line ends with one of
+/// `increase_after`, and one level shallower when the line itself starts with one of `decrease_on`. A word pattern like
+/// `else` matches only the whole word. Presets for languages with braces, Python, and YAML are available. The default
+/// rules have no pattern so that lines keep the indentation of the previous line.
+/// ```
+/// use tui_textarea::IndentRules;
+///
+/// let mut rules = IndentRules::braces();
+/// assert!(rules.increases("fn main() {"));
+/// assert!(rules.decreases("    } else {"));
+///
+/// // Add your own pattern
+/// rules.increase_after.push("=>".to_string());
+/// assert!(rules.increases("Some(x) =>"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IndentRules {
+    /// Line endings after which the next line is indented deeper, like `{`
+    pub increase_after: Vec<String>,
+    /// Line beginnings which make the line indented shallower, like `}`
+    pub decrease_on: Vec<String>,
+}
+
+impl IndentRules {
+    fn new(increase_after: &[&str], decrease_on: &[&str]) -> Self {
+        Self {
+            increase_after: increase_after.iter().map(|s| s.to_string()).collect(),
+            decrease_on: decrease_on.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Rules for languages with braces such as Rust, C, Java, and JavaScript. Lines after `{`, `[`, and `(` are
+    /// indented, and lines starting with `}`, `]`, and `)` are dedented.
+    pub fn braces() -> Self {
+        Self::new(&["{", "[", "("], &["}", "]", ")"])
+    }
+
+    /// Rules for Python. Lines after `:` and opening brackets are indented, and lines starting with `else`, `elif`,
+    /// `except`, `finally`, and closing brackets are dedented.
+    /// ```
+    /// use tui_textarea::IndentRules;
+    ///
+    /// let rules = IndentRules::python();
+    /// assert!(rules.increases("if x:"));
+    /// assert!(rules.decreases("else:"));
+    /// assert!(!rules.decreases("elsewhere = 1"));
+    /// ```
+    pub fn python() -> Self {
+        Self::new(
+            &[":", "{", "[", "("],
+            &["else", "elif", "except", "finally", "}", "]", ")"],
+        )
+    }
+
+    /// Rules for YAML. Lines after a mapping key ending with `:` and block scalar indicators like `|` and `>` are
+    /// indented.
+    pub fn yaml() -> Self {
+        Self::new(&[":", "|", "|-", "|+", ">", ">-", ">+"], &[])
+    }
+
+    /// Return if the line following this line should be indented deeper.
+    pub fn increases(&self, line: &str) -> bool {
+        let line = line.trim_end();
+        self.increase_after
+            .iter()
+            .any(|p| !p.is_empty() && line.ends_with(p.as_str()))
+    }
+
+    /// Return if this line should be indented shallower than the previous line.
+    pub fn decreases(&self, line: &str) -> bool {
+        let line = line.trim_start();
+        self.decrease_on
+            .iter()
+            .any(|p| match line.strip_prefix(p.as_str()) {
+                Some(rest) if !p.is_empty() => {
+                    !(p.ends_with(is_word_char) && rest.starts_with(is_word_char))
+                }
+                _ => false,
+            })
+    }
+}
+
+// Indentation width of the line at the row decided by the rules. The width is relative to the previous non-blank line
+pub fn rule_indent(lines: &[String], row: usize, rules: &IndentRules, tab_len: u8) -> usize {
+    let unit = tab_len as usize;
+    let prev = lines[..row]
+        .iter()
+        .rev()
+        .find_map(|l| indent_width(l, tab_len).map(|w| (l, w)));
+    let width = match prev {
+        Some((line, w)) if rules.increases(line) => w + unit,
+        Some((_, w)) => w,
+        None => 0,
+    };
+    if rules.decreases(&lines[row]) {
+        width.saturating_sub(unit)
+    } else {
+        width
+    }
+}
+
+// Indentation of the width. Hard tabs are used as many as possible when `hard_tab` is true
+pub fn indent_string(width: usize, tab_len: u8, hard_tab: bool) -> String {
+    if hard_tab && tab_len > 0 {
+        let tab_len = tab_len as usize;
+        "\t".repeat(width / tab_len) + &" ".repeat(width % tab_len)
+    } else {
+        " ".repeat(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn reindent_lines() {
+        let tests = [
+            (
+                &["if x {", "        a", "", "    }"][..],
+                "  ",
+                &["if x {", "      a", "", "  }"][..],
+            ),
+            (
+                &["f(", "\ta,", "\t\tb", ")"][..],
+                "    ",
+                &["f(", "    \ta,", "    \t\tb", "    )"][..],
+            ),
+            (&["x", "\t  a", "    b"][..], "", &["x", "  a", "b"][..]),
+            (&["x", "  a"][..], "\t", &["x", "\ta"][..]),
+            (&["only one line"][..], "  ", &["only one line"][..]),
+            (&["x", "   "][..], "  ", &["x", "   "][..]),
+        ];
+
+        for (input, base, want) in tests {
+            let mut ls = lines(input);
+            reindent(&mut ls, base, 4);
+            assert_eq!(ls, want, "{:?} with {:?}", input, base);
+        }
+    }
+
+    #[test]
+    fn detect_indent_of_lines() {
+        let tests = [
+            (
+                &["fn f() {", "    a", "    if x {", "        b", "    }", "}"][..],
+                Some(Indent::Spaces(4)),
+            ),
+            (
+                &["a:", "  b:", "    c: 1", "  d: 2", "", "e: 3"][..],
+                Some(Indent::Spaces(2)),
+            ),
+            (
+                &["f() {", "\ta", "\t\tb", "  c", "}"][..],
+                Some(Indent::Tabs),
+            ),
+            (&["/*", " * comment", " */"][..], None),
+            (&["a", "", "b"][..], None),
+            (&[][..], None),
+        ];
+
+        for (input, want) in tests {
+            assert_eq!(detect_indent(&lines(input)), want, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn indent_by_rules() {
+        let rules = IndentRules::braces();
+        let lines = lines(["fn f() {", "if x {", "", "a", "}", "    } else {", "b"]);
+        let want = [0, 4, 4, 4, 0, 0, 8];
+        for (row, want) in want.iter().enumerate() {
+            assert_eq!(rule_indent(&lines, row, &rules, 4), *want, "row={}", row);
+        }
+
+        let default = IndentRules::default();
+        assert_eq!(rule_indent(&lines, 6, &default, 4), 4);
+        assert_eq!(rule_indent(&lines, 1, &default, 4), 0);
+
+        let yaml = IndentRules::yaml();
+        let lines = self::lines(["a:", "  b: |", "x"]);
+        assert_eq!(rule_indent(&lines, 1, &yaml, 2), 2);
+        assert_eq!(rule_indent(&lines, 2, &yaml, 2), 4);
+    }
+
+    #[test]
+    fn indent_string_of_width() {
+        assert_eq!(indent_string(6, 4, false), "      ");
+        assert_eq!(indent_string(6, 4, true), "\t  ");
+        assert_eq!(indent_string(8, 4, true), "\t\t");
+        assert_eq!(indent_string(3, 0, true), "   ");
+    }
+}
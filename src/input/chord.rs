@@ -0,0 +1,410 @@
+use super::{Input, Key};
+use std::time::{Duration, Instant};
+
+/// Output of [`KeyChords`]. A key input is either resolved into an action bound to a key sequence, or passed through
+/// as-is.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Chord<A> {
+    /// Key sequence bound to the action was input.
+    Action(A),
+    /// Key input which is not a part of any key sequence.
+    Input(Input),
+}
+
+/// Small state machine to handle multi-key bindings such as `jk` or Emacs-like `C-x C-s`.
+///
+/// Each key input is fed with [`KeyChords::feed`]. While the inputs so far are a prefix of some bound key sequence,
+/// they are held as pending and nothing is returned. When a key sequence is completed, its action is returned. When
+/// the pending inputs turn out not to be a part of any key sequence, they are returned as they are so that they can
+/// be passed to [`TextArea::input`](crate::TextArea::input).
+///
+/// ```
+/// use tui_textarea::{Chord, Input, Key, KeyChords, TextArea};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Action {
+///     LeaveInsertMode,
+/// }
+///
+/// let mut chords = KeyChords::default();
+/// let j = Input { key: Key::Char('j'), ..Default::default() };
+/// let k = Input { key: Key::Char('k'), ..Default::default() };
+/// chords.bind([j.clone(), k.clone()], Action::LeaveInsertMode);
+///
+/// // 'j' is pending since it may be followed by 'k'
+/// assert!(chords.feed(j.clone()).is_empty());
+/// assert_eq!(chords.feed(k), [Chord::Action(Action::LeaveInsertMode)]);
+///
+/// // 'j' followed by 'a' is not bound. Both inputs are passed through
+/// let mut textarea = TextArea::default();
+/// chords.feed(j);
+/// for chord in chords.feed(Input { key: Key::Char('a'), ..Default::default() }) {
+///     if let Chord::Input(input) = chord {
+///         textarea.input(input);
+///     }
+/// }
+/// assert_eq!(textarea.lines(), ["ja"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyChords<A> {
+    bindings: Vec<(Vec<Input>, A)>,
+    pending: Vec<Input>,
+    timeout: Option<Duration>,
+    last_input: Option<Instant>,
+}
+
+impl<A> Default for KeyChords<A> {
+    fn default() -> Self {
+        Self {
+            bindings: vec![],
+            pending: vec![],
+            timeout: Some(Duration::from_secs(1)),
+            last_input: None,
+        }
+    }
+}
+
+impl<A: Clone> KeyChords<A> {
+    /// Bind the key sequence to the action. When the same key sequence is already bound, its action is replaced.
+    /// Empty key sequence is ignored.
+    /// ```
+    /// use tui_textarea::{Chord, Input, Key, KeyChords};
+    ///
+    /// let mut chords = KeyChords::default();
+    /// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+    /// chords.bind([ctrl('x'), ctrl('s')], "save");
+    ///
+    /// assert!(chords.feed(ctrl('x')).is_empty());
+    /// assert_eq!(chords.feed(ctrl('s')), [Chord::Action("save")]);
+    /// ```
+    pub fn bind(&mut self, keys: impl IntoIterator<Item = Input>, action: A) {
+        let keys: Vec<_> = keys.into_iter().collect();
+        if keys.is_empty() {
+            return;
+        }
+        if let Some((_, a)) = self.bindings.iter_mut().find(|(k, _)| *k == keys) {
+            *a = action;
+        } else {
+            self.bindings.push((keys, action));
+        }
+    }
+
+    /// Remove the binding of the key sequence. Returns the action which was bound to the key sequence.
+    pub fn unbind(&mut self, keys: &[Input]) -> Option<A> {
+        let idx = self.bindings.iter().position(|(k, _)| k == keys)?;
+        Some(self.bindings.remove(idx).1)
+    }
+
+    /// Remove all bindings. Pending inputs are discarded.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.pending.clear();
+        self.last_input = None;
+    }
+
+    /// Set the timeout to wait for the next key of a key sequence. When the timeout passes, the pending inputs are
+    /// resolved by [`KeyChords::feed`] or [`KeyChords::poll`]. `None` means waiting forever. The default value is 1
+    /// second.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Get the timeout to wait for the next key of a key sequence.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Feed the key input to the state machine and return the resolved actions and inputs in order. Empty result
+    /// means the input is pending. When the pending inputs no longer continue any key sequence, they are resolved in
+    /// the same way as [`KeyChords::flush`]. [`Key::Null`] inputs are always passed through. [`Key::Esc`] input cancels
+    /// the pending inputs unless it continues some key sequence.
+    pub fn feed(&mut self, input: impl Into<Input>) -> Vec<Chord<A>> {
+        let input = input.into();
+        let mut resolved = if self.is_timed_out() {
+            self.flush()
+        } else {
+            vec![]
+        };
+
+        if input.key == Key::Null {
+            resolved.push(Chord::Input(input));
+            return resolved;
+        }
+
+        self.pending.push(input);
+
+        if self.pending.len() > 1
+            && self.pending.last().map(|i| i.key) == Some(Key::Esc)
+            && !self.is_prefix(&self.pending)
+            && self.action(&self.pending).is_none()
+        {
+            self.cancel_pending();
+            return resolved;
+        }
+
+        while !self.pending.is_empty() {
+            if self.is_prefix(&self.pending) {
+                self.last_input = Some(Instant::now());
+                return resolved;
+            }
+            resolved.push(self.resolve_head());
+        }
+        self.last_input = None;
+        resolved
+    }
+
+    /// Get the inputs currently pending as a prefix of some key sequence. This is useful to show the partially typed
+    /// key sequence like Vim's `showcmd`.
+    /// ```
+    /// use tui_textarea::{Input, Key, KeyChords};
+    ///
+    /// let mut chords = KeyChords::default();
+    /// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+    /// chords.bind([ctrl('x'), ctrl('s')], "save");
+    ///
+    /// chords.feed(ctrl('x'));
+    /// assert_eq!(chords.pending(), [ctrl('x')]);
+    /// ```
+    pub fn pending(&self) -> &[Input] {
+        &self.pending
+    }
+
+    /// Discard the pending inputs without resolving them. Returns `true` when some inputs were pending. Feeding
+    /// [`Key::Esc`] while some inputs are pending also cancels them.
+    /// ```
+    /// use tui_textarea::{Input, Key, KeyChords};
+    ///
+    /// let mut chords = KeyChords::default();
+    /// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+    /// chords.bind([ctrl('x'), ctrl('s')], "save");
+    ///
+    /// chords.feed(ctrl('x'));
+    /// assert!(chords.cancel_pending());
+    /// assert!(chords.pending().is_empty());
+    /// assert!(chords.flush().is_empty());
+    ///
+    /// chords.feed(ctrl('x'));
+    /// assert!(chords.feed(Input { key: Key::Esc, ..Default::default() }).is_empty());
+    /// assert!(chords.pending().is_empty());
+    /// ```
+    pub fn cancel_pending(&mut self) -> bool {
+        self.last_input = None;
+        if self.pending.is_empty() {
+            return false;
+        }
+        self.pending.clear();
+        true
+    }
+
+    /// Resolve the pending inputs when the timeout has passed since the last input. This method should be called
+    /// periodically (e.g. when polling terminal events timed out) so that pending inputs are not held forever.
+    pub fn poll(&mut self) -> Vec<Chord<A>> {
+        if self.is_timed_out() {
+            self.flush()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Resolve the pending inputs immediately regardless of the timeout. The longest key sequence matching to the
+    /// pending inputs is resolved into its action. Other inputs are passed through.
+    pub fn flush(&mut self) -> Vec<Chord<A>> {
+        let mut resolved = vec![];
+        while !self.pending.is_empty() {
+            resolved.push(self.resolve_head());
+        }
+        self.last_input = None;
+        resolved
+    }
+
+    // Resolve the longest key sequence at the head of the pending inputs into its action, or pass through the first
+    // input when no key sequence matches
+    fn resolve_head(&mut self) -> Chord<A> {
+        let matched = (1..=self.pending.len())
+            .rev()
+            .find_map(|len| Some((len, self.action(&self.pending[..len])?)));
+        if let Some((len, action)) = matched {
+            self.pending.drain(..len);
+            Chord::Action(action)
+        } else {
+            Chord::Input(self.pending.remove(0))
+        }
+    }
+
+    fn is_prefix(&self, inputs: &[Input]) -> bool {
+        self.bindings
+            .iter()
+            .any(|(k, _)| k.len() > inputs.len() && k.starts_with(inputs))
+    }
+
+    fn action(&self, inputs: &[Input]) -> Option<A> {
+        self.bindings
+            .iter()
+            .find(|(k, _)| k == inputs)
+            .map(|(_, a)| a.clone())
+    }
+
+    fn is_timed_out(&self) -> bool {
+        match (self.timeout, self.last_input) {
+            (Some(timeout), Some(last)) => last.elapsed() >= timeout,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::tests::input;
+
+    fn ch(c: char) -> Input {
+        input(Key::Char(c), false, false, false)
+    }
+
+    fn ctrl(c: char) -> Input {
+        input(Key::Char(c), true, false, false)
+    }
+
+    #[test]
+    fn resolve_sequences() {
+        let mut chords = KeyChords::default();
+        chords.bind([ch('j'), ch('k')], 1);
+        chords.bind([ctrl('x'), ctrl('s')], 2);
+        chords.bind([ctrl('x'), ctrl('f')], 3);
+        chords.bind([ch('g'), ch('g'), ch('g')], 4);
+
+        assert_eq!(chords.feed(ch('a')), [Chord::Input(ch('a'))]);
+        assert!(chords.feed(ch('j')).is_empty());
+        assert_eq!(chords.feed(ch('k')), [Chord::Action(1)]);
+        assert!(chords.feed(ctrl('x')).is_empty());
+        assert_eq!(chords.feed(ctrl('f')), [Chord::Action(3)]);
+
+        // Unbound sequence passes through the inputs
+        assert!(chords.feed(ch('j')).is_empty());
+        assert_eq!(
+            chords.feed(ch('a')),
+            [Chord::Input(ch('j')), Chord::Input(ch('a'))],
+        );
+
+        // The last input can start a new sequence
+        assert!(chords.feed(ch('j')).is_empty());
+        assert_eq!(chords.feed(ch('j')), [Chord::Input(ch('j'))]);
+        assert_eq!(chords.feed(ch('k')), [Chord::Action(1)]);
+
+        // Inputs after the first one are retried
+        assert!(chords.feed(ch('g')).is_empty());
+        assert_eq!(chords.feed(ch('j')), [Chord::Input(ch('g'))]);
+        assert_eq!(chords.feed(ch('k')), [Chord::Action(1)]);
+
+        // Null key does not break pending inputs
+        let null = input(Key::Null, false, false, false);
+        assert!(chords.feed(ctrl('x')).is_empty());
+        assert_eq!(chords.feed(null.clone()), [Chord::Input(null)]);
+        assert_eq!(chords.feed(ctrl('s')), [Chord::Action(2)]);
+    }
+
+    #[test]
+    fn flush_pending_inputs() {
+        let mut chords = KeyChords::default();
+        chords.bind([ch('g')], 1);
+        chords.bind([ch('g'), ch('g'), ch('g')], 2);
+
+        assert!(chords.feed(ch('g')).is_empty());
+        assert!(chords.feed(ch('g')).is_empty());
+        assert_eq!(chords.flush(), [Chord::Action(1), Chord::Action(1)]);
+        assert!(chords.flush().is_empty());
+
+        assert!(chords.feed(ch('g')).is_empty());
+        assert!(chords.feed(ch('g')).is_empty());
+        assert_eq!(chords.feed(ch('g')), [Chord::Action(2)]);
+    }
+
+    #[test]
+    fn resolve_longest_match_on_mismatch() {
+        let mut chords = KeyChords::default();
+        chords.bind([ch('g')], 1);
+        chords.bind([ch('g'), ch('g')], 2);
+
+        assert!(chords.feed(ch('g')).is_empty());
+        assert_eq!(
+            chords.feed(ch('x')),
+            [Chord::Action(1), Chord::Input(ch('x'))],
+        );
+
+        chords.bind([ch('g'), ch('g'), ch('g')], 3);
+        assert!(chords.feed(ch('g')).is_empty());
+        assert!(chords.feed(ch('g')).is_empty());
+        assert_eq!(
+            chords.feed(ch('x')),
+            [Chord::Action(2), Chord::Input(ch('x'))],
+        );
+    }
+
+    #[test]
+    fn timeout() {
+        let mut chords = KeyChords::default();
+        chords.bind([ch('j'), ch('k')], 1);
+
+        chords.set_timeout(None);
+        assert!(chords.feed(ch('j')).is_empty());
+        assert!(chords.poll().is_empty());
+        assert_eq!(chords.feed(ch('k')), [Chord::Action(1)]);
+
+        chords.set_timeout(Some(Duration::ZERO));
+        assert!(chords.feed(ch('j')).is_empty());
+        assert_eq!(chords.poll(), [Chord::Input(ch('j'))]);
+        assert!(chords.feed(ch('j')).is_empty());
+        assert_eq!(
+            chords.feed(ch('j')),
+            [Chord::Input(ch('j'))],
+            "timed out input is resolved before the new input",
+        );
+        assert_eq!(chords.poll(), [Chord::Input(ch('j'))]);
+    }
+
+    #[test]
+    fn pending_inputs() {
+        let esc = input(Key::Esc, false, false, false);
+        let mut chords = KeyChords::default();
+        chords.bind([ctrl('x'), ctrl('s')], 1);
+        chords.bind([ch('g'), esc.clone()], 2);
+
+        assert!(chords.pending().is_empty());
+        assert!(chords.feed(ctrl('x')).is_empty());
+        assert_eq!(chords.pending(), [ctrl('x')]);
+        assert_eq!(chords.feed(ctrl('s')), [Chord::Action(1)]);
+        assert!(chords.pending().is_empty());
+
+        assert!(chords.feed(ctrl('x')).is_empty());
+        assert!(chords.feed(esc.clone()).is_empty());
+        assert!(chords.pending().is_empty());
+        assert!(!chords.cancel_pending());
+
+        // Esc is passed through when nothing is pending
+        assert_eq!(chords.feed(esc.clone()), [Chord::Input(esc.clone())]);
+
+        // Esc can be a part of key sequence
+        assert!(chords.feed(ch('g')).is_empty());
+        assert_eq!(chords.feed(esc), [Chord::Action(2)]);
+
+        assert!(chords.feed(ctrl('x')).is_empty());
+        assert!(chords.cancel_pending());
+        assert!(chords.pending().is_empty());
+    }
+
+    #[test]
+    fn bind_and_unbind() {
+        let mut chords = KeyChords::default();
+        chords.bind([], 0);
+        chords.bind([ch('j'), ch('k')], 1);
+        chords.bind([ch('j'), ch('k')], 2);
+        assert_eq!(chords.unbind(&[ch('j'), ch('k')]), Some(2));
+        assert_eq!(chords.unbind(&[ch('j'), ch('k')]), None);
+        assert_eq!(chords.feed(ch('j')), [Chord::Input(ch('j'))]);
+
+        chords.bind([ch('j'), ch('k')], 1);
+        assert!(chords.feed(ch('j')).is_empty());
+        chords.clear();
+        assert!(chords.flush().is_empty());
+    }
+}
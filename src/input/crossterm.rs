@@ -25,13 +25,15 @@ impl From<KeyCode> for Key {
             KeyCode::Right => Key::Right,
             KeyCode::Up => Key::Up,
             KeyCode::Down => Key::Down,
-            KeyCode::Tab => Key::Tab,
+            KeyCode::Tab | KeyCode::BackTab => Key::Tab,
             KeyCode::Delete => Key::Delete,
             KeyCode::Home => Key::Home,
             KeyCode::End => Key::End,
             KeyCode::PageUp => Key::PageUp,
             KeyCode::PageDown => Key::PageDown,
             KeyCode::Esc => Key::Esc,
+            KeyCode::Insert => Key::Insert,
+            KeyCode::KeypadBegin => Key::KeypadBegin,
             KeyCode::F(x) => Key::F(x),
             _ => Key::Null,
         }
@@ -39,9 +41,30 @@ impl From<KeyCode> for Key {
 }
 
 impl From<KeyEvent> for Input {
-    /// Convert [`crossterm::event::KeyEvent`] into [`Input`].
+    /// Convert [`crossterm::event::KeyEvent`] into [`Input`]. Key release events are converted into `Key::Null` so
+    /// that they are ignored. Key repeat events are handled as key press events. To ignore key repeat events, use
+    /// [`Input::from_key_event`].
     fn from(key: KeyEvent) -> Self {
-        if key.kind == KeyEventKind::Release {
+        Self::from_key_event(key, true)
+    }
+}
+
+impl Input {
+    /// Convert [`crossterm::event::KeyEvent`] into [`Input`] considering its kind. Key release events are always
+    /// converted into `Key::Null`. When `repeat` is `false`, key repeat events are also converted into `Key::Null`.
+    /// Key repeat events are reported on Windows or when `crossterm::event::PushKeyboardEnhancementFlags` is set.
+    /// ```
+    /// use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    /// use tui_textarea::{Input, Key};
+    ///
+    /// let mut event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+    /// event.kind = KeyEventKind::Repeat;
+    ///
+    /// assert_eq!(Input::from_key_event(event, true).key, Key::Char('a'));
+    /// assert_eq!(Input::from_key_event(event, false).key, Key::Null);
+    /// ```
+    pub fn from_key_event(key: KeyEvent, repeat: bool) -> Self {
+        if key.kind == KeyEventKind::Release || !repeat && key.kind == KeyEventKind::Repeat {
             // On Windows or when `crossterm::event::PushKeyboardEnhancementFlags` is set,
             // key release event can be reported. Ignore it. (#14)
             return Self::default();
@@ -49,7 +72,8 @@ impl From<KeyEvent> for Input {
 
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         let alt = key.modifiers.contains(KeyModifiers::ALT);
-        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        // Shift+Tab is reported as `BackTab` without the shift modifier on some terminals
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT) || key.code == KeyCode::BackTab;
         let key = Key::from(key.code);
 
         Self {
@@ -142,6 +166,27 @@ mod tests {
                 ),
                 input(Key::F(1), true, true, true),
             ),
+            (
+                key_event(KeyCode::Insert, KeyModifiers::empty()),
+                input(Key::Insert, false, false, false),
+            ),
+            (
+                key_event(KeyCode::KeypadBegin, KeyModifiers::empty()),
+                input(Key::KeypadBegin, false, false, false),
+            ),
+            (
+                key_event(KeyCode::BackTab, KeyModifiers::empty()),
+                input(Key::Tab, false, false, true),
+            ),
+            // Keys disambiguated by the kitty keyboard protocol
+            (
+                key_event(KeyCode::Enter, KeyModifiers::SHIFT),
+                input(Key::Enter, false, false, true),
+            ),
+            (
+                key_event(KeyCode::Char('i'), KeyModifiers::CONTROL),
+                input(Key::Char('i'), true, false, false),
+            ),
             (
                 key_event(KeyCode::NumLock, KeyModifiers::CONTROL),
                 input(Key::Null, true, false, false),
@@ -214,4 +259,23 @@ mod tests {
         let to = input(Key::Null, false, false, false);
         assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
     }
+
+    #[test]
+    fn key_repeat_event() {
+        let mut from = key_event(KeyCode::Char('a'), KeyModifiers::empty());
+        from.kind = KeyEventKind::Repeat;
+        assert_eq!(
+            Input::from(from),
+            input(Key::Char('a'), false, false, false)
+        );
+        assert_eq!(
+            Input::from_key_event(from, false),
+            input(Key::Null, false, false, false),
+        );
+        from.kind = KeyEventKind::Release;
+        assert_eq!(
+            Input::from_key_event(from, true),
+            input(Key::Null, false, false, false),
+        );
+    }
 }
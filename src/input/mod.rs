@@ -1,5 +1,7 @@
+mod chord;
 #[cfg(any(feature = "crossterm", feature = "tuirs-crossterm"))]
 mod crossterm;
+mod record;
 #[cfg(any(feature = "termion", feature = "tuirs-termion"))]
 mod termion;
 #[cfg(feature = "termwiz")]
@@ -8,6 +10,9 @@ mod termwiz;
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
 
+pub use chord::{Chord, KeyChords};
+pub use record::InputLog;
+
 /// Backend-agnostic key input kind.
 ///
 /// This type is marked as `#[non_exhaustive]` since more keys may be supported in the future.
@@ -45,6 +50,10 @@ pub enum Key {
     PageDown,
     /// Escape key
     Esc,
+    /// Insert key
+    Insert,
+    /// Begin key on keypad. This is the '5' key of numeric keypad when NumLock is off
+    KeypadBegin,
     /// Copy key. This key is supported by termwiz only
     Copy,
     /// Cut key. This key is supported by termwiz only
@@ -85,6 +94,12 @@ impl Default for Key {
 /// }
 /// ```
 ///
+/// When the terminal supports [the kitty keyboard protocol](https://sw.kovidgoyal.net/kitty/keyboard-protocol/) and it
+/// is enabled with `crossterm::event::PushKeyboardEnhancementFlags`, keys which are indistinguishable on legacy
+/// terminals are reported with their modifiers. For example, Shift+Enter is converted into `Key::Enter` with `shift`,
+/// and Ctrl+I is converted into `Key::Char('i')` with `ctrl` instead of `Key::Tab`. Default key mappings of
+/// [`TextArea::input`](crate::TextArea::input) handle both forms.
+///
 /// Creating `Input` instance directly can cause backend-agnostic input as follows.
 ///
 /// ```
@@ -0,0 +1,229 @@
+use super::{Input, Key};
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+const KEY_NAMES: &[(Key, &str)] = &[
+    (Key::Backspace, "Backspace"),
+    (Key::Enter, "Enter"),
+    (Key::Left, "Left"),
+    (Key::Right, "Right"),
+    (Key::Up, "Up"),
+    (Key::Down, "Down"),
+    (Key::Tab, "Tab"),
+    (Key::Delete, "Delete"),
+    (Key::Home, "Home"),
+    (Key::End, "End"),
+    (Key::PageUp, "PageUp"),
+    (Key::PageDown, "PageDown"),
+    (Key::Esc, "Esc"),
+    (Key::Insert, "Insert"),
+    (Key::KeypadBegin, "KeypadBegin"),
+    (Key::Copy, "Copy"),
+    (Key::Cut, "Cut"),
+    (Key::Paste, "Paste"),
+    (Key::MouseScrollDown, "MouseScrollDown"),
+    (Key::MouseScrollUp, "MouseScrollUp"),
+    (Key::Null, "Null"),
+];
+
+/// Key inputs recorded by [`TextArea::start_recording`](crate::TextArea::start_recording) with the time elapsed since
+/// the recording started. The log can be replayed by [`TextArea::replay`](crate::TextArea::replay) to reproduce the
+/// same editing.
+///
+/// The log can be written as text and read back, so that users can attach it to a bug report. Each input is written
+/// as one line of the elapsed milliseconds, the modifiers (`C` for Ctrl, `A` for Alt, `S` for Shift, or `-` for none)
+/// and the key. A character key is quoted like `'a'`.
+/// ```
+/// use std::time::Duration;
+/// use tui_textarea::{Input, InputLog, Key};
+///
+/// let mut log = InputLog::default();
+/// log.push(Duration::from_millis(0), Input { key: Key::Char('a'), ..Default::default() });
+/// log.push(Duration::from_millis(120), Input { key: Key::Enter, ctrl: true, ..Default::default() });
+///
+/// let mut text = vec![];
+/// log.write(&mut text).unwrap();
+/// assert_eq!(text, b"0 - 'a'\n120 C Enter\n");
+///
+/// let read = InputLog::read(&text[..]).unwrap();
+/// assert_eq!(read, log);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputLog {
+    inputs: Vec<(Duration, Input)>,
+}
+
+impl InputLog {
+    /// Append the input which happened at the time elapsed since the recording started.
+    pub fn push(&mut self, elapsed: Duration, input: Input) {
+        self.inputs.push((elapsed, input));
+    }
+
+    /// Get the recorded inputs with their elapsed times.
+    pub fn inputs(&self) -> &[(Duration, Input)] {
+        &self.inputs
+    }
+
+    /// Get the number of recorded inputs.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Return if no input is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Write the log as text to the writer. Each input is written as one line.
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        for (elapsed, input) in &self.inputs {
+            writeln!(writer, "{} {}", elapsed.as_millis(), encode(input))?;
+        }
+        writer.flush()
+    }
+
+    /// Read the log written by [`InputLog::write`]. Empty lines are ignored. When a line is malformed, an error with
+    /// [`std::io::ErrorKind::InvalidData`] is returned.
+    pub fn read(reader: impl BufRead) -> io::Result<Self> {
+        let mut log = Self::default();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                continue;
+            }
+            let (elapsed, input) = decode(line).ok_or_else(|| {
+                let msg = format!("invalid input log at line {}: {:?}", i + 1, line);
+                io::Error::new(io::ErrorKind::InvalidData, msg)
+            })?;
+            log.push(elapsed, input);
+        }
+        Ok(log)
+    }
+}
+
+// Encode the modifiers and the key like `C 'a'`
+fn encode(input: &Input) -> String {
+    let mut s = String::new();
+    for (pressed, c) in [(input.ctrl, 'C'), (input.alt, 'A'), (input.shift, 'S')] {
+        if pressed {
+            s.push(c);
+        }
+    }
+    if s.is_empty() {
+        s.push('-');
+    }
+    s.push(' ');
+    match input.key {
+        Key::Char(c) => {
+            s.push('\'');
+            match c {
+                '\\' => s.push_str("\\\\"),
+                '\n' => s.push_str("\\n"),
+                '\r' => s.push_str("\\r"),
+                '\t' => s.push_str("\\t"),
+                c => s.push(c),
+            }
+            s.push('\'');
+        }
+        Key::F(n) => s.push_str(&format!("F{}", n)),
+        key => {
+            let name = KEY_NAMES.iter().find(|(k, _)| *k == key).map(|(_, n)| *n);
+            s.push_str(name.unwrap_or("Null"));
+        }
+    }
+    s
+}
+
+fn decode(line: &str) -> Option<(Duration, Input)> {
+    let mut fields = line.splitn(3, ' ');
+    let elapsed = Duration::from_millis(fields.next()?.parse().ok()?);
+    let mods = fields.next()?;
+    let name = fields.next()?;
+
+    let mut input = Input::default();
+    if mods != "-" {
+        for c in mods.chars() {
+            match c {
+                'C' => input.ctrl = true,
+                'A' => input.alt = true,
+                'S' => input.shift = true,
+                _ => return None,
+            }
+        }
+    }
+
+    input.key = if let Some(quoted) = name.strip_prefix('\'') {
+        let mut chars = quoted.strip_suffix('\'')?.chars();
+        let c = match chars.next()? {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                _ => return None,
+            },
+            c => c,
+        };
+        if chars.next().is_some() {
+            return None;
+        }
+        Key::Char(c)
+    } else if let Some(n) = name.strip_prefix('F').and_then(|n| n.parse().ok()) {
+        Key::F(n)
+    } else {
+        KEY_NAMES.iter().find(|(_, n)| *n == name)?.0
+    };
+    Some((elapsed, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_input() {
+        let input = |key, ctrl, alt, shift| Input {
+            key,
+            ctrl,
+            alt,
+            shift,
+        };
+        let tests = [
+            (input(Key::Char('a'), false, false, false), "- 'a'"),
+            (input(Key::Char(' '), true, true, true), "CAS ' '"),
+            (input(Key::Char('\''), false, true, false), "A '''"),
+            (input(Key::Char('\\'), false, false, false), "- '\\\\'"),
+            (input(Key::Char('\n'), false, false, false), "- '\\n'"),
+            (input(Key::Char('🐶'), false, false, true), "S '🐶'"),
+            (input(Key::F(12), true, false, false), "C F12"),
+            (input(Key::PageDown, false, false, false), "- PageDown"),
+            (input(Key::Null, false, false, false), "- Null"),
+        ];
+        for (input, encoded) in tests {
+            assert_eq!(encode(&input), encoded);
+            let line = format!("42 {}", encoded);
+            assert_eq!(decode(&line), Some((Duration::from_millis(42), input)));
+        }
+
+        for invalid in [
+            "",
+            "x - 'a'",
+            "0 X 'a'",
+            "0 - 'ab'",
+            "0 - 'a",
+            "0 - '\\x'",
+            "0 - Foo",
+            "0 -",
+        ] {
+            assert_eq!(decode(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn read_invalid_log() {
+        let err = InputLog::read(&b"0 - 'a'\n\n10 - Oops\n"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 3"), "{}", err);
+    }
+}
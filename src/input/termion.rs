@@ -40,6 +40,7 @@ impl From<KeyEvent> for Input {
             KeyEvent::BackTab => Key::Tab,
             KeyEvent::Delete => Key::Delete,
             KeyEvent::Esc => Key::Esc,
+            KeyEvent::Insert => Key::Insert,
             KeyEvent::F(x) => Key::F(x),
             _ => Key::Null,
         };
@@ -105,6 +106,7 @@ mod tests {
             (KeyEvent::Char('\r'), input(Key::Enter, false, false, false)),
             (KeyEvent::F(1), input(Key::F(1), false, false, false)),
             (KeyEvent::BackTab, input(Key::Tab, false, false, false)),
+            (KeyEvent::Insert, input(Key::Insert, false, false, false)),
             (KeyEvent::Null, input(Key::Null, false, false, false)),
         ] {
             assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
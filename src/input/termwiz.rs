@@ -24,15 +24,34 @@ impl From<KeyCode> for Key {
             KeyCode::Tab => Key::Tab,
             KeyCode::Enter => Key::Enter,
             KeyCode::Escape => Key::Esc,
-            KeyCode::PageUp => Key::PageUp,
-            KeyCode::PageDown => Key::PageDown,
-            KeyCode::End => Key::End,
-            KeyCode::Home => Key::Home,
-            KeyCode::LeftArrow => Key::Left,
-            KeyCode::RightArrow => Key::Right,
-            KeyCode::UpArrow => Key::Up,
-            KeyCode::DownArrow => Key::Down,
+            // Keys on keypad and arrow keys in the application cursor mode are the same as normal keys
+            KeyCode::PageUp | KeyCode::KeyPadPageUp => Key::PageUp,
+            KeyCode::PageDown | KeyCode::KeyPadPageDown => Key::PageDown,
+            KeyCode::End | KeyCode::KeyPadEnd => Key::End,
+            KeyCode::Home | KeyCode::KeyPadHome => Key::Home,
+            KeyCode::LeftArrow | KeyCode::ApplicationLeftArrow => Key::Left,
+            KeyCode::RightArrow | KeyCode::ApplicationRightArrow => Key::Right,
+            KeyCode::UpArrow | KeyCode::ApplicationUpArrow => Key::Up,
+            KeyCode::DownArrow | KeyCode::ApplicationDownArrow => Key::Down,
             KeyCode::Delete => Key::Delete,
+            KeyCode::Insert => Key::Insert,
+            KeyCode::KeyPadBegin => Key::KeypadBegin,
+            // Numeric keypad inputs the same characters as normal keys
+            KeyCode::Numpad0 => Key::Char('0'),
+            KeyCode::Numpad1 => Key::Char('1'),
+            KeyCode::Numpad2 => Key::Char('2'),
+            KeyCode::Numpad3 => Key::Char('3'),
+            KeyCode::Numpad4 => Key::Char('4'),
+            KeyCode::Numpad5 => Key::Char('5'),
+            KeyCode::Numpad6 => Key::Char('6'),
+            KeyCode::Numpad7 => Key::Char('7'),
+            KeyCode::Numpad8 => Key::Char('8'),
+            KeyCode::Numpad9 => Key::Char('9'),
+            KeyCode::Multiply => Key::Char('*'),
+            KeyCode::Add => Key::Char('+'),
+            KeyCode::Subtract => Key::Char('-'),
+            KeyCode::Decimal => Key::Char('.'),
+            KeyCode::Divide => Key::Char('/'),
             KeyCode::Function(x) => Key::F(x),
             KeyCode::Copy => Key::Copy,
             KeyCode::Cut => Key::Cut,
@@ -177,6 +196,30 @@ mod tests {
                 ),
                 input(Key::F(1), true, true, true),
             ),
+            (
+                key_event(KeyCode::ApplicationUpArrow, Modifiers::SHIFT),
+                input(Key::Up, false, false, true),
+            ),
+            (
+                key_event(KeyCode::KeyPadPageDown, Modifiers::empty()),
+                input(Key::PageDown, false, false, false),
+            ),
+            (
+                key_event(KeyCode::Insert, Modifiers::empty()),
+                input(Key::Insert, false, false, false),
+            ),
+            (
+                key_event(KeyCode::KeyPadBegin, Modifiers::empty()),
+                input(Key::KeypadBegin, false, false, false),
+            ),
+            (
+                key_event(KeyCode::Numpad7, Modifiers::empty()),
+                input(Key::Char('7'), false, false, false),
+            ),
+            (
+                key_event(KeyCode::Add, Modifiers::empty()),
+                input(Key::Char('+'), false, false, false),
+            ),
             (
                 key_event(KeyCode::NumLock, Modifiers::CTRL),
                 input(Key::Null, true, false, false),
@@ -0,0 +1,86 @@
+use crate::op::EditOp;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+struct Sink {
+    writer: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+}
+
+// Destination to append operations of edits. The writer is shared between clones of the textarea
+#[derive(Clone)]
+pub struct Journal {
+    sink: Arc<Mutex<Sink>>,
+}
+
+impl fmt::Debug for Journal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Journal").finish_non_exhaustive()
+    }
+}
+
+impl Journal {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            sink: Arc::new(Mutex::new(Sink {
+                writer: Box::new(writer),
+                error: None,
+            })),
+        }
+    }
+
+    // Append the operation and flush it immediately so that it survives a crash. The first error is kept until it is
+    // taken
+    pub fn append(&self, op: &EditOp) {
+        let mut sink = match self.sink.lock() {
+            Ok(sink) => sink,
+            Err(_) => return,
+        };
+        let mut line = op.encode();
+        line.push('\n');
+        let result = sink
+            .writer
+            .write_all(line.as_bytes())
+            .and_then(|_| sink.writer.flush());
+        if let Err(err) = result {
+            sink.error.get_or_insert(err);
+        }
+    }
+
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.sink.lock().ok()?.error.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_to_journal() {
+        let buf = Arc::new(Mutex::new(vec![]));
+        struct Shared(Arc<Mutex<Vec<u8>>>);
+        impl Write for Shared {
+            fn write(&mut self, b: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(b)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let journal = Journal::new(Shared(buf.clone()));
+        let pos = (0, 1);
+        journal.append(&EditOp::Insert {
+            pos,
+            text: "ab".to_string(),
+        });
+        journal.append(&EditOp::Delete {
+            pos,
+            text: "b".to_string(),
+        });
+        assert_eq!(&*buf.lock().unwrap(), b"+0:1:ab\n-0:1:b\n");
+        assert!(journal.take_error().is_none());
+    }
+}
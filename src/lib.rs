@@ -7,16 +7,64 @@
 #[cfg(all(feature = "ratatui", feature = "tuirs"))]
 compile_error!("ratatui support and tui-rs support are exclusive. only one of them can be enabled at the same time. see https://github.com/rhysd/tui-textarea#installation");
 
+mod abbrev;
+mod align;
+mod announce;
+#[cfg(feature = "bidi")]
+mod bidi;
+mod blink;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod commit;
+mod complete;
+mod conflict;
+mod control;
+mod count;
 mod cursor;
+mod diagnostic;
+mod diff;
+mod diffview;
+mod digraph;
+#[cfg(feature = "editorconfig")]
+mod editorconfig;
+mod elastic;
+mod emoji;
+mod expand;
+mod fold;
+mod form;
+mod gutter;
 mod highlight;
 mod history;
+mod idle;
+mod indent;
 mod input;
+mod journal;
+mod limit;
+mod link;
+mod list;
+mod loader;
+mod markdown;
+mod memory;
+mod newline;
+mod op;
+mod popup;
+mod protect;
+mod remote;
 mod scroll;
 #[cfg(feature = "search")]
 mod search;
+mod spell;
+mod status;
+mod template;
 mod textarea;
+mod textarea_set;
+mod theme;
+mod track;
 mod util;
+mod validate;
+mod virtual_text;
 mod widget;
+mod width;
 mod word;
 
 #[cfg(feature = "ratatui")]
@@ -31,7 +79,33 @@ use crossterm;
 #[cfg(feature = "tuirs-crossterm")]
 use crossterm_025 as crossterm;
 
+pub use announce::Announcement;
+pub use conflict::{Conflict, ConflictResolution};
+pub use control::ControlCharDisplay;
 pub use cursor::CursorMove;
-pub use input::{Input, Key};
+pub use diagnostic::{Diagnostic, Severity};
+pub use diff::LineChange;
+pub use diffview::DiffView;
+pub use form::FormField;
+pub use history::{UndoGrouping, UndoHistory};
+pub use indent::IndentRules;
+pub use input::{Chord, Input, InputLog, Key, KeyChords};
+pub use link::Hyperlink;
+pub use loader::InvalidUtf8;
+pub use memory::MemoryUsage;
+pub use newline::FinalNewline;
+pub use op::EditOp;
+pub use remote::RemoteCursor;
 pub use scroll::Scrolling;
+#[cfg(feature = "search")]
+pub use search::{ReplaceAnswer, SearchCase};
+pub use spell::SpellChecker;
+pub use status::{Mode, Status};
 pub use textarea::TextArea;
+pub use textarea_set::TextAreaSet;
+pub use theme::Theme;
+pub use track::{ChangeKind, TrackedChange};
+pub use validate::InvariantError;
+pub use virtual_text::InlayHint;
+pub use widget::TextAreaState;
+pub use width::AmbiguousWidth;
@@ -0,0 +1,93 @@
+use crate::ratatui::style::{Color, Style};
+use crate::util::byte_offset;
+
+// Maximum number of characters in a line. Characters exceeding the limit are rendered with the style, and insertion
+// making a line longer than the limit is rejected when `block` is true
+#[derive(Clone, Debug)]
+pub struct LineLimit {
+    pub max: Option<usize>,
+    pub style: Style,
+    pub block: bool,
+}
+
+impl Default for LineLimit {
+    fn default() -> Self {
+        Self {
+            max: None,
+            style: Style::default().bg(Color::Red),
+            block: false,
+        }
+    }
+}
+
+impl LineLimit {
+    // Byte range of the characters exceeding the limit in the line with its style
+    pub fn overflow(&self, line: &str) -> Option<(usize, usize, Style)> {
+        let start = byte_offset(line, self.max?);
+        (start < line.len()).then(|| (start, line.len(), self.style))
+    }
+
+    // Check all lines made by inserting the lines of `inserted` characters keep within the limit. `before` and `after`
+    // are the numbers of characters before and after the insertion point in the line
+    pub fn allows(&self, before: usize, after: usize, inserted: &[usize]) -> bool {
+        let max = match self.max {
+            Some(max) if self.block => max,
+            _ => return true,
+        };
+        match inserted {
+            [] => true,
+            [len] => before + len + after <= max,
+            [first, middle @ .., last] => {
+                before + first <= max && middle.iter().all(|&l| l <= max) && last + after <= max
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_range() {
+        let mut l = LineLimit::default();
+        assert_eq!(l.overflow("abcdef"), None);
+        l.max = Some(4);
+        assert_eq!(l.overflow("abcdef"), Some((4, 6, l.style)));
+        assert_eq!(l.overflow("あいうえお"), Some((12, 15, l.style)));
+        assert_eq!(l.overflow("abcd"), None);
+        assert_eq!(l.overflow(""), None);
+    }
+
+    #[test]
+    fn allow_insertion() {
+        let mut l = LineLimit {
+            max: Some(5),
+            ..Default::default()
+        };
+        // Insertion is not blocked by default
+        assert!(l.allows(3, 3, &[1]));
+
+        l.block = true;
+        let tests: [(usize, usize, &[usize], bool); 8] = [
+            (2, 2, &[1], true),
+            (2, 2, &[2], false),
+            (6, 0, &[0], false),
+            (2, 2, &[], true),
+            (3, 4, &[2, 1], true),
+            (4, 4, &[2, 1], false),
+            (3, 4, &[2, 6, 1], false),
+            (3, 4, &[2, 5, 2], false),
+        ];
+        for (before, after, inserted, want) in tests {
+            assert_eq!(
+                l.allows(before, after, inserted),
+                want,
+                "{} {} {:?}",
+                before,
+                after,
+                inserted,
+            );
+        }
+    }
+}
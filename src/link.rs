@@ -0,0 +1,132 @@
+use crate::ratatui::style::Style;
+use std::cmp;
+
+/// A hyperlink attached to a range of text in a line of [`crate::TextArea`]. The text in the range is rendered with the
+/// link style and can be made clickable in terminals supporting OSC 8 escape sequences. Set hyperlinks with
+/// [`crate::TextArea::set_hyperlinks`].
+///
+/// The range is a pair of the start column and the end column (exclusive) in the row.
+/// ```
+/// use ratatui::style::{Style, Color};
+/// use tui_textarea::Hyperlink;
+///
+/// let link = Hyperlink::new(0, (4, 11), "https://example.com");
+/// assert_eq!(link.row, 0);
+/// assert_eq!(link.range, (4, 11));
+/// assert_eq!(link.url, "https://example.com");
+/// assert_eq!(link.style, None); // Rendered with `TextArea::hyperlink_style`
+///
+/// let style = Style::default().fg(Color::Green);
+/// let link = Hyperlink::new(0, (4, 11), "https://example.com").style(style);
+/// assert_eq!(link.style, Some(style));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hyperlink {
+    /// Row of the link text
+    pub row: usize,
+    /// Range of the link text as the start column and the end column (exclusive)
+    pub range: (usize, usize),
+    /// URL which the link points to
+    pub url: String,
+    /// Style of the link text. When `None`, the style set by [`crate::TextArea::set_hyperlink_style`] is used
+    pub style: Option<Style>,
+}
+
+impl Hyperlink {
+    /// Create a new hyperlink to the URL on the range of columns in the row.
+    pub fn new(row: usize, range: (usize, usize), url: impl Into<String>) -> Self {
+        Self {
+            row,
+            range,
+            url: url.into(),
+            style: None,
+        }
+    }
+
+    /// Set the style of the link text.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+// Find http(s) URLs in the line. Returns the ranges of the columns and the URLs. Trailing punctuations and unbalanced
+// closing parentheses are not included in URLs since they are usually a part of the sentence.
+pub fn find_urls(line: &str) -> Vec<((usize, usize), &str)> {
+    let mut urls = vec![];
+    let mut i = 0;
+    while let Some(found) = line[i..].find("http") {
+        let start = i + found;
+        let rest = &line[start..];
+        let scheme = ["https://", "http://"]
+            .iter()
+            .find(|s| rest.starts_with(*s))
+            .map(|s| s.len());
+        let preceded_by_word = line[..start]
+            .chars()
+            .next_back()
+            .map_or(false, |c| c.is_alphanumeric());
+        let scheme_len = match scheme {
+            Some(len) if !preceded_by_word => len,
+            _ => {
+                i = start + "http".len();
+                continue;
+            }
+        };
+
+        let len = rest
+            .find(|c: char| c.is_whitespace() || "<>\"'`".contains(c))
+            .unwrap_or(rest.len());
+        let mut url = &rest[..len];
+        loop {
+            let trimmed = url.trim_end_matches(&['.', ',', ';', ':', '!', '?'][..]);
+            let trimmed = match trimmed.strip_suffix(')') {
+                Some(s) if s.matches('(').count() <= s.matches(')').count() => s,
+                _ => trimmed,
+            };
+            if trimmed.len() == url.len() {
+                break;
+            }
+            url = trimmed;
+        }
+
+        if url.len() > scheme_len {
+            let start_col = line[..start].chars().count();
+            let end_col = start_col + url.chars().count();
+            urls.push(((start_col, end_col), url));
+        }
+        i = start + cmp::max(url.len(), scheme_len);
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_urls_in_line() {
+        let tests = [
+            ("", &[][..]),
+            ("no links", &[][..]),
+            (
+                "https://example.com",
+                &[((0, 19), "https://example.com")][..],
+            ),
+            (
+                "see http://a.b/c?d=e#f.",
+                &[((4, 22), "http://a.b/c?d=e#f")][..],
+            ),
+            (
+                "(https://a.b/c_(d)) and <https://x.y>",
+                &[((1, 18), "https://a.b/c_(d)"), ((25, 36), "https://x.y")][..],
+            ),
+            ("xhttps://a.b https:// http", &[][..]),
+            ("リンク https://a.b", &[((4, 15), "https://a.b")][..]),
+        ];
+
+        for (line, want) in tests {
+            assert_eq!(find_urls(line), want, "{:?}", line);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// Item of a Markdown list like `- foo`, `* foo`, `+ foo`, `1. foo` or `1) foo`. Task list items like `- [x] foo` are
+// also recognized
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListItem {
+    // Number of characters of the indentation, the marker and the space following it
+    pub prefix_len: usize,
+    // Prefix of the next item. The number of an ordered list is incremented and a task is unchecked
+    pub next_prefix: String,
+    // The item has no text after the marker
+    pub empty: bool,
+}
+
+pub fn list_item(line: &str) -> Option<ListItem> {
+    let rest = line.trim_start_matches(|c: char| c == ' ' || c == '\t');
+    let indent = &line[..line.len() - rest.len()];
+
+    let (marker, after) = match rest.chars().next()? {
+        c @ ('-' | '*' | '+') => (c.to_string(), &rest[1..]),
+        _ => {
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            // CommonMark allows at most 9 digits
+            if digits == 0 || digits > 9 {
+                return None;
+            }
+            let delim = rest[digits..].chars().next()?;
+            if delim != '.' && delim != ')' {
+                return None;
+            }
+            let n: u64 = rest[..digits].parse().ok()?;
+            (format!("{}{}", n + 1, delim), &rest[digits + 1..])
+        }
+    };
+    let text = after.strip_prefix(' ')?;
+
+    let (task, text) = match text.get(..4) {
+        Some("[ ] " | "[x] " | "[X] ") => ("[ ] ", &text[4..]),
+        _ => ("", text),
+    };
+
+    Some(ListItem {
+        prefix_len: line.len() - text.len(),
+        next_prefix: format!("{}{} {}", indent, marker, task),
+        empty: text.trim().is_empty(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_item() {
+        let tests = [
+            ("- foo", Some((2, "- ", false))),
+            ("* foo", Some((2, "* ", false))),
+            ("+ foo", Some((2, "+ ", false))),
+            ("  - foo", Some((4, "  - ", false))),
+            ("\t* foo", Some((3, "\t* ", false))),
+            ("1. foo", Some((3, "2. ", false))),
+            ("9) foo", Some((3, "10) ", false))),
+            ("  41. foo", Some((6, "  42. ", false))),
+            ("- [x] done", Some((6, "- [ ] ", false))),
+            ("- [ ] ", Some((6, "- [ ] ", true))),
+            ("- ", Some((2, "- ", true))),
+            ("3.  ", Some((3, "4. ", true))),
+            ("-foo", None),
+            ("---", None),
+            ("1.foo", None),
+            ("a. foo", None),
+            ("1234567890. foo", None),
+            ("foo", None),
+            ("", None),
+        ];
+        for (line, want) in tests {
+            let want = want.map(|(prefix_len, next_prefix, empty)| ListItem {
+                prefix_len,
+                next_prefix: next_prefix.to_string(),
+                empty,
+            });
+            assert_eq!(list_item(line), want, "{:?}", line);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::str;
+use std::sync::{Arc, Mutex};
+
+/// How invalid UTF-8 sequences are decoded on loading text with [`crate::TextArea::start_loading`]. Real-world files
+/// such as logs may contain broken bytes, and loading them never fails.
+/// ```
+/// use tui_textarea::{InvalidUtf8, TextArea};
+///
+/// let content = b"ok \xff\xfe!";
+///
+/// let mut textarea = TextArea::default();
+/// textarea.start_loading(&content[..], None);
+/// while !textarea.load_chunk(1024).unwrap() {}
+/// assert_eq!(textarea.lines(), ["ok \u{fffd}\u{fffd}!"]);
+///
+/// let mut textarea = TextArea::default();
+/// textarea.set_invalid_utf8(InvalidUtf8::Hex);
+/// textarea.start_loading(&content[..], None);
+/// while !textarea.load_chunk(1024).unwrap() {}
+/// assert_eq!(textarea.lines(), ["ok <ff><fe>!"]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidUtf8 {
+    /// Replace each invalid sequence with the replacement character U+FFFD. The replacement characters are rendered
+    /// with the style of [`crate::TextArea::set_control_char_style`] so that they stand out. This is the default
+    Replace,
+    /// Replace each invalid byte with its hex code like `<ff>` so that the original bytes can be inspected. Note that
+    /// the code is inserted into the text as-is
+    Hex,
+}
+
+impl Default for InvalidUtf8 {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
+// Decode the bytes as UTF-8 replacing invalid sequences as specified
+fn decode(bytes: &[u8], invalid: InvalidUtf8) -> String {
+    if invalid == InvalidUtf8::Replace {
+        return String::from_utf8_lossy(bytes).into_owned();
+    }
+    let mut decoded = String::with_capacity(bytes.len());
+    let mut rest = bytes;
+    loop {
+        match str::from_utf8(rest) {
+            Ok(s) => {
+                decoded.push_str(s);
+                return decoded;
+            }
+            Err(err) => {
+                let (valid, after) = rest.split_at(err.valid_up_to());
+                decoded.push_str(str::from_utf8(valid).unwrap_or_default());
+                let len = err.error_len().unwrap_or(after.len());
+                for b in &after[..len] {
+                    decoded.push_str(&format!("<{:02x}>", b));
+                }
+                rest = &after[len..];
+            }
+        }
+    }
+}
+
+struct Source {
+    reader: Box<dyn Read + Send>,
+    // Bytes of the last line which is not terminated by a newline yet
+    pending: Vec<u8>,
+    // Whether the last byte read so far is a newline
+    ends_with_newline: bool,
+    loaded: u64,
+    total: Option<u64>,
+    invalid: InvalidUtf8,
+}
+
+// Reader to load text incrementally. The reader is shared between clones of the textarea
+#[derive(Clone)]
+pub struct Loader {
+    source: Arc<Mutex<Source>>,
+}
+
+impl fmt::Debug for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Loader").finish_non_exhaustive()
+    }
+}
+
+impl Loader {
+    pub fn new(
+        reader: impl Read + Send + 'static,
+        total: Option<u64>,
+        invalid: InvalidUtf8,
+    ) -> Self {
+        Self {
+            source: Arc::new(Mutex::new(Source {
+                reader: Box::new(reader),
+                pending: vec![],
+                ends_with_newline: false,
+                loaded: 0,
+                total,
+                invalid,
+            })),
+        }
+    }
+
+    // Read at most `max_bytes` bytes and return the lines completed by them. The second element is `true` when the
+    // reader reached EOF. Then the last line is returned even if it is not terminated by a newline
+    pub fn read_lines(&self, max_bytes: usize) -> io::Result<(Vec<String>, bool)> {
+        let mut source = self
+            .source
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "loader was poisoned"))?;
+        let source = &mut *source;
+
+        let mut buf = vec![0; max_bytes];
+        let len = loop {
+            match source.reader.read(&mut buf) {
+                Ok(len) => break len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        };
+        source.loaded += len as u64;
+        if len > 0 {
+            source.ends_with_newline = buf[len - 1] == b'\n';
+        }
+
+        let eof = len == 0 && max_bytes > 0;
+        let mut lines = vec![];
+        let mut start = 0;
+        for (i, &b) in buf[..len].iter().enumerate() {
+            if b == b'\n' {
+                source.pending.extend_from_slice(&buf[start..i]);
+                lines.push(decode(&source.pending, source.invalid));
+                source.pending.clear();
+                start = i + 1;
+            }
+        }
+        source.pending.extend_from_slice(&buf[start..len]);
+        if eof && !source.pending.is_empty() {
+            lines.push(decode(&source.pending, source.invalid));
+            source.pending.clear();
+        }
+        Ok((lines, eof))
+    }
+
+    // Number of bytes loaded so far and the total number of bytes if known
+    pub fn progress(&self) -> (u64, Option<u64>) {
+        match self.source.lock() {
+            Ok(source) => (source.loaded, source.total),
+            Err(_) => (0, None),
+        }
+    }
+
+    // Whether the content read so far ends with a newline
+    pub fn ends_with_newline(&self) -> bool {
+        self.source.lock().map_or(false, |s| s.ends_with_newline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_lines_in_chunks() {
+        let loader = Loader::new(&b"ab\ncd\r\n\nef"[..], Some(10), InvalidUtf8::Replace);
+        assert_eq!(
+            loader.read_lines(4).unwrap(),
+            (vec!["ab".to_string()], false)
+        );
+        assert_eq!(loader.progress(), (4, Some(10)));
+        assert_eq!(
+            loader.read_lines(4).unwrap(),
+            (vec!["cd\r".to_string(), "".to_string()], false),
+        );
+        assert!(loader.ends_with_newline());
+        assert_eq!(loader.read_lines(4).unwrap(), (vec![], false));
+        assert_eq!(
+            loader.read_lines(4).unwrap(),
+            (vec!["ef".to_string()], true)
+        );
+        assert_eq!(loader.progress(), (10, Some(10)));
+        assert!(!loader.ends_with_newline());
+    }
+
+    #[test]
+    fn decode_invalid_utf8() {
+        let tests = [
+            (&b"abc"[..], "abc", "abc"),
+            (&b"a\xffb"[..], "a\u{fffd}b", "a<ff>b"),
+            (&b"\xe3\x81"[..], "\u{fffd}", "<e3><81>"),
+            (&b"\xe3\x81\x82\xc0"[..], "あ\u{fffd}", "あ<c0>"),
+        ];
+        for (bytes, replaced, hex) in tests {
+            assert_eq!(decode(bytes, InvalidUtf8::Replace), replaced, "{:?}", bytes);
+            assert_eq!(decode(bytes, InvalidUtf8::Hex), hex, "{:?}", bytes);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use crate::util::byte_offset;
+use crate::width::{str_width, AmbiguousWidth};
+use std::cmp;
+
+// Number of the marker characters at the end of the text
+pub fn run_before(text: &str, marker: char) -> usize {
+    text.chars().rev().take_while(|&c| c == marker).count()
+}
+
+// Number of the marker characters at the start of the text
+pub fn run_after(text: &str, marker: char) -> usize {
+    text.chars().take_while(|&c| c == marker).count()
+}
+
+// Check the run of marker characters enclosing text contains the inline markup of `len` markers. A run of 3 asterisks
+// is both bold and italic like `***foo***`, and a run of 2 asterisks is not italic
+pub fn has_markup(run: usize, len: usize) -> bool {
+    matches!((len, run), (1, 1) | (1, 3) | (2, 2) | (2, 3))
+}
+
+// Prefix of the ATX heading like `## ` in the line and the prefix of the next level. The level cycles from a plain line
+// to `#` through `######` then goes back to a plain line
+pub fn next_heading(line: &str) -> (usize, String) {
+    let level = run_after(line, '#');
+    let rest = &line[level..];
+    let (level, prefix_len) = if level > 6 {
+        (0, 0)
+    } else if rest.is_empty() {
+        (level, level)
+    } else if rest.starts_with(' ') {
+        (level, level + 1)
+    } else {
+        (0, 0)
+    };
+    let next = match level {
+        6 => String::new(),
+        l => format!("{} ", "#".repeat(l + 1)),
+    };
+    (prefix_len, next)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Align {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+// Byte offsets of the pipes separating cells. Escaped pipes like `\|` are not separators
+fn pipe_offsets(line: &str) -> Vec<usize> {
+    let mut offsets = vec![];
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if c == '|' && !escaped {
+            offsets.push(i);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    offsets
+}
+
+// Split the row of a pipe table into the trimmed cells. The pipes at the start and end of the row are optional
+fn split_row(line: &str) -> Vec<&str> {
+    let row = line.trim();
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let mut cells = vec![];
+    let mut start = 0;
+    for i in pipe_offsets(row) {
+        cells.push(row[start..i].trim());
+        start = i + 1;
+    }
+    let last = row[start..].trim();
+    if !last.is_empty() || cells.is_empty() {
+        cells.push(last);
+    }
+    cells
+}
+
+// Alignment of the column specified by the cell of the delimiter row like `:---:`
+fn alignment(cell: &str) -> Option<Align> {
+    let (left, rest) = match cell.strip_prefix(':') {
+        Some(rest) => (true, rest),
+        None => (false, cell),
+    };
+    let (right, dashes) = match rest.strip_suffix(':') {
+        Some(dashes) => (true, dashes),
+        None => (false, rest),
+    };
+    if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+        return None;
+    }
+    Some(match (left, right) {
+        (false, false) => Align::None,
+        (true, false) => Align::Left,
+        (true, true) => Align::Center,
+        (false, true) => Align::Right,
+    })
+}
+
+fn is_delimiter_row(line: &str) -> bool {
+    line.contains('-') && split_row(line).iter().all(|c| alignment(c).is_some())
+}
+
+// Range of the rows of the pipe table containing the row. A table starts with the header row followed by the
+// delimiter row and continues while rows contain pipes
+pub fn table_range(lines: &[String], row: usize) -> Option<(usize, usize)> {
+    if !lines[row].contains('|') {
+        return None;
+    }
+    let is_row = |r: &usize| lines[*r].contains('|');
+    let first = (0..row).rev().take_while(is_row).last().unwrap_or(row);
+    let end = (row + 1..lines.len())
+        .take_while(is_row)
+        .last()
+        .map_or(row + 1, |r| r + 1);
+    let delimiter = (first + 1..end).find(|&r| is_delimiter_row(&lines[r]))?;
+    let start = delimiter - 1;
+    (start <= row).then(|| (start, end))
+}
+
+// Realign the rows of the table. Cells are padded to the width of the widest cell in the column following the
+// alignment of the column, and the delimiter row is rebuilt to the same widths
+pub fn align_table(lines: &[String], ambiguous: AmbiguousWidth) -> Vec<String> {
+    let first = &lines[0];
+    let indent = &first[..first.len() - first.trim_start().len()];
+    let rows: Vec<_> = lines.iter().map(|l| split_row(l)).collect();
+    let aligns: Vec<_> = rows[1]
+        .iter()
+        .map(|c| alignment(c).unwrap_or(Align::None))
+        .collect();
+    let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    // The delimiter row needs at least 3 characters like `:-:`
+    let mut widths = vec![3; cols];
+    for (i, row) in rows.iter().enumerate() {
+        if i == 1 {
+            continue;
+        }
+        for (w, cell) in widths.iter_mut().zip(row.iter()) {
+            *w = cmp::max(*w, str_width(cell, ambiguous));
+        }
+    }
+
+    let mut aligned = Vec::with_capacity(rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<_> = widths
+            .iter()
+            .enumerate()
+            .map(|(c, &w)| {
+                let align = aligns.get(c).copied().unwrap_or(Align::None);
+                if i == 1 {
+                    return match align {
+                        Align::None => "-".repeat(w),
+                        Align::Left => format!(":{}", "-".repeat(w - 1)),
+                        Align::Center => format!(":{}:", "-".repeat(w - 2)),
+                        Align::Right => format!("{}:", "-".repeat(w - 1)),
+                    };
+                }
+                let cell = row.get(c).copied().unwrap_or("");
+                let pad = w - str_width(cell, ambiguous);
+                let (l, r) = match align {
+                    Align::None | Align::Left => (0, pad),
+                    Align::Center => (pad / 2, pad - pad / 2),
+                    Align::Right => (pad, 0),
+                };
+                format!("{}{}{}", " ".repeat(l), cell, " ".repeat(r))
+            })
+            .collect();
+        aligned.push(format!("{}| {} |", indent, cells.join(" | ")));
+    }
+    aligned
+}
+
+// Index of the cell at the column in the row of a table
+pub fn table_cell(line: &str, col: usize) -> usize {
+    let offset = byte_offset(line, col);
+    let pipes = pipe_offsets(line)
+        .into_iter()
+        .filter(|&i| i < offset)
+        .count();
+    if line.trim_start().starts_with('|') {
+        pipes.saturating_sub(1)
+    } else {
+        pipes
+    }
+}
+
+// Column of the start of the cell text in the row aligned by `align_table`
+pub fn table_cell_col(line: &str, cell: usize) -> usize {
+    let pipes = pipe_offsets(line);
+    let i = match pipes.len() {
+        0 => return 0,
+        n => pipes[cmp::min(cell, n.saturating_sub(2))],
+    };
+    cmp::min(line[..i].chars().count() + 2, line.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markup_runs() {
+        assert_eq!(run_before("foo **", '*'), 2);
+        assert_eq!(run_before("foo", '*'), 0);
+        assert_eq!(run_after("*bar", '*'), 1);
+        assert_eq!(run_after("`x`", '`'), 1);
+
+        let tests = [
+            (0, 1, false),
+            (1, 1, true),
+            (2, 1, false),
+            (3, 1, true),
+            (1, 2, false),
+            (2, 2, true),
+            (3, 2, true),
+            (4, 2, false),
+        ];
+        for (run, len, want) in tests {
+            assert_eq!(has_markup(run, len), want, "run={} len={}", run, len);
+        }
+    }
+
+    #[test]
+    fn heading_levels() {
+        let tests = [
+            ("foo", 0, "# "),
+            ("", 0, "# "),
+            ("# foo", 2, "## "),
+            ("#", 1, "## "),
+            ("### foo", 4, "#### "),
+            ("###### foo", 7, ""),
+            ("####### foo", 0, "# "),
+            ("#hashtag", 0, "# "),
+        ];
+        for (line, len, next) in tests {
+            assert_eq!(next_heading(line), (len, next.to_string()), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn split_table_row() {
+        let tests: [(&str, &[&str]); 6] = [
+            ("| a | b |", &["a", "b"]),
+            ("a|b", &["a", "b"]),
+            ("  |a|  |c", &["a", "", "c"]),
+            ("| a \\| b |", &["a \\| b"]),
+            ("|", &[""]),
+            ("| :-- | --: |", &[":--", "--:"]),
+        ];
+        for (line, want) in tests {
+            assert_eq!(split_row(line), want, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn find_table_range() {
+        let lines: Vec<String> = ["text", "a | b", "|-|-|", "| 1 | 2 |", "", "x | y", "z"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let tests = [
+            (0, None),
+            (1, Some((1, 4))),
+            (2, Some((1, 4))),
+            (3, Some((1, 4))),
+            (4, None),
+            (5, None),
+        ];
+        for (row, want) in tests {
+            assert_eq!(table_range(&lines, row), want, "row={}", row);
+        }
+    }
+
+    #[test]
+    fn align_table_rows() {
+        let lines: Vec<String> = [
+            "  |Name|Qty|Note|",
+            "|:-|-:|:-:|",
+            "|apple|3|",
+            "| banana | 12 | ok | extra |",
+            "|りんご|1|x|",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let want = [
+            "  | Name   | Qty | Note |       |",
+            "  | :----- | --: | :--: | ----- |",
+            "  | apple  |   3 |      |       |",
+            "  | banana |  12 |  ok  | extra |",
+            "  | りんご |   1 |  x   |       |",
+        ];
+        assert_eq!(align_table(&lines, AmbiguousWidth::Narrow), want);
+
+        assert_eq!(table_cell(&lines[0], 0), 0);
+        assert_eq!(table_cell(&lines[0], 8), 1);
+        assert_eq!(table_cell("a | b", 4), 1);
+        assert_eq!(table_cell_col(want[3], 0), 4);
+        assert_eq!(table_cell_col(want[3], 2), 19);
+        assert_eq!(table_cell_col(want[3], 9), 26);
+    }
+}
@@ -0,0 +1,47 @@
+use std::mem;
+
+/// Approximate number of bytes held by a [`crate::TextArea`], returned from [`crate::TextArea::memory_usage`]. Sizes
+/// include the capacity allocated on heap, so they may be larger than the text itself. Memory held by the allocator or
+/// by objects shared with the application such as journal writers is not counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Bytes held by the lines of the text
+    pub lines: usize,
+    /// Bytes held by the undo/redo history
+    pub history: usize,
+    /// Bytes held by data which can be dropped without changing the text, such as the yank ring, the search history,
+    /// and the selection expansion history
+    pub caches: usize,
+}
+
+impl MemoryUsage {
+    /// Get the total number of bytes.
+    /// ```
+    /// use tui_textarea::MemoryUsage;
+    ///
+    /// let usage = MemoryUsage { lines: 10, history: 20, caches: 5 };
+    /// assert_eq!(usage.total(), 35);
+    /// ```
+    pub fn total(&self) -> usize {
+        self.lines + self.history + self.caches
+    }
+}
+
+// Bytes held by the vector of strings including the strings' buffers
+pub fn strings_size(v: &Vec<String>) -> usize {
+    v.capacity() * mem::size_of::<String>() + v.iter().map(String::capacity).sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_of_strings() {
+        let mut v = Vec::with_capacity(4);
+        v.push(String::with_capacity(10));
+        v.push("abc".to_string());
+        let want = 4 * mem::size_of::<String>() + 10 + v[1].capacity();
+        assert_eq!(strings_size(&v), want);
+    }
+}
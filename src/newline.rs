@@ -0,0 +1,74 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Policy for the newline at the end of the text exported by [`crate::TextArea::text`]. POSIX tools expect a text
+/// file to end with a newline, so an editor should not add or remove it silently.
+/// ```
+/// use tui_textarea::{FinalNewline, TextArea};
+///
+/// let mut textarea = TextArea::from(["hello", "world"]);
+/// assert_eq!(textarea.text(), "hello\nworld");
+///
+/// textarea.set_final_newline(FinalNewline::Always);
+/// assert_eq!(textarea.text(), "hello\nworld\n");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FinalNewline {
+    /// Keep the final newline as the original content. This is the default policy. See
+    /// [`crate::TextArea::ends_with_newline`]
+    Preserve,
+    /// Always end the text with a newline unless the text is empty
+    Always,
+    /// Never end the text with a newline
+    Never,
+}
+
+impl Default for FinalNewline {
+    fn default() -> Self {
+        Self::Preserve
+    }
+}
+
+// Join the lines with newlines and append the final newline following the policy
+pub fn join_lines(lines: &[String], policy: FinalNewline, ends_with_newline: bool) -> String {
+    let mut text = lines.join("\n");
+    let newline = match policy {
+        FinalNewline::Preserve => ends_with_newline,
+        FinalNewline::Always => !text.is_empty(),
+        FinalNewline::Never => false,
+    };
+    if newline {
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn join_lines_with_final_newline() {
+        let tests = [
+            (lines(["a", "b"]), FinalNewline::Preserve, false, "a\nb"),
+            (lines(["a", "b"]), FinalNewline::Preserve, true, "a\nb\n"),
+            (lines(["a", ""]), FinalNewline::Preserve, true, "a\n\n"),
+            (lines([""]), FinalNewline::Preserve, true, "\n"),
+            (lines(["a", "b"]), FinalNewline::Always, false, "a\nb\n"),
+            (lines([""]), FinalNewline::Always, true, ""),
+            (lines(["a", "b"]), FinalNewline::Never, true, "a\nb"),
+        ];
+        for (lines, policy, ends_with_newline, want) in tests {
+            assert_eq!(
+                join_lines(&lines, policy, ends_with_newline),
+                want,
+                "{:?} {:?} {}",
+                lines,
+                policy,
+                ends_with_newline,
+            );
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::util::Pos;
+
+/// An edit of text as an operation stamped with its position. Local edits can be taken with
+/// [`crate::TextArea::take_edit_ops`] and sent to other peers, and edits of other peers can be applied with
+/// [`crate::TextArea::apply_remote_edit`]. This is the building block of a collaborative editor based on CRDT or OT.
+///
+/// A position is a pair of row and column. Text may contain newlines.
+/// ```
+/// use tui_textarea::EditOp;
+///
+/// let op = EditOp::Insert { pos: (0, 5), text: ", world\nbye".to_string() };
+/// assert_eq!(op.pos(), (0, 5));
+/// assert_eq!(op.end(), (1, 3));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EditOp {
+    /// Insert the text at the position
+    Insert {
+        /// Position where the text is inserted
+        pos: (usize, usize),
+        /// Inserted text
+        text: String,
+    },
+    /// Delete the text starting at the position
+    Delete {
+        /// Start position of the deleted text
+        pos: (usize, usize),
+        /// Deleted text
+        text: String,
+    },
+}
+
+impl EditOp {
+    /// Get the position where the text is inserted or deleted.
+    pub fn pos(&self) -> (usize, usize) {
+        match self {
+            Self::Insert { pos, .. } | Self::Delete { pos, .. } => *pos,
+        }
+    }
+
+    /// Get the inserted or deleted text.
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Insert { text, .. } | Self::Delete { text, .. } => text,
+        }
+    }
+
+    /// Get the end position of the inserted text after insertion or the deleted text before deletion.
+    pub fn end(&self) -> (usize, usize) {
+        text_end(self.pos(), self.text())
+    }
+
+    // Move the position by this edit. Positions after the edited text are shifted. A position inside the deleted text
+    // moves to the start of the deletion. A position at the inserted position stays before the inserted text
+    pub(crate) fn shift(&self, p: (usize, usize)) -> (usize, usize) {
+        let (start, end) = (self.pos(), self.end());
+        match self {
+            Self::Insert { .. } if p <= start => p,
+            Self::Insert { .. } if p.0 == start.0 => (end.0, end.1 + p.1 - start.1),
+            Self::Insert { .. } => (p.0 + end.0 - start.0, p.1),
+            Self::Delete { .. } if p <= start => p,
+            Self::Delete { .. } if p <= end => start,
+            Self::Delete { .. } if p.0 == end.0 => (start.0, start.1 + p.1 - end.1),
+            Self::Delete { .. } => (p.0 - (end.0 - start.0), p.1),
+        }
+    }
+
+    // Same as `shift` but the byte offset of the position is also moved. `offset` is the byte offset of the position
+    // of this edit in its line
+    pub(crate) fn shift_pos(&self, offset: usize, p: &Pos) -> Pos {
+        let (start, end) = (self.pos(), self.end());
+        let text = self.text();
+        // Byte offset of the end position of the inserted or deleted text
+        let end_offset = match text.rfind('\n') {
+            Some(i) => text.len() - i - 1,
+            None => offset + text.len(),
+        };
+        let (row, col) = self.shift((p.row, p.col));
+        let offset = match self {
+            _ if (p.row, p.col) <= start => p.offset,
+            Self::Insert { .. } if p.row == start.0 => end_offset + p.offset - offset,
+            Self::Insert { .. } => p.offset,
+            Self::Delete { .. } if (p.row, p.col) <= end => offset,
+            Self::Delete { .. } if p.row == end.0 => offset + p.offset - end_offset,
+            Self::Delete { .. } => p.offset,
+        };
+        Pos::new(row, col, offset)
+    }
+
+    // Range of the text edited by this operation before the edit. It is empty for insertion
+    pub(crate) fn range_before(&self) -> ((usize, usize), (usize, usize)) {
+        match self {
+            Self::Insert { pos, .. } => (*pos, *pos),
+            Self::Delete { pos, .. } => (*pos, self.end()),
+        }
+    }
+
+    // Range of the text edited by this operation after the edit. It is empty for deletion
+    pub(crate) fn range_after(&self) -> ((usize, usize), (usize, usize)) {
+        match self {
+            Self::Insert { pos, .. } => (*pos, self.end()),
+            Self::Delete { pos, .. } => (*pos, *pos),
+        }
+    }
+
+    pub(crate) fn invert(&self) -> Self {
+        match self.clone() {
+            Self::Insert { pos, text } => Self::Delete { pos, text },
+            Self::Delete { pos, text } => Self::Insert { pos, text },
+        }
+    }
+
+    // Encode the operation as one line like `+1:2:hello\nworld`. Backslashes and newlines in the text are escaped
+    pub(crate) fn encode(&self) -> String {
+        let (row, col) = self.pos();
+        let sign = match self {
+            Self::Insert { .. } => '+',
+            Self::Delete { .. } => '-',
+        };
+        let mut line = format!("{}{}:{}:", sign, row, col);
+        for c in self.text().chars() {
+            match c {
+                '\\' => line.push_str("\\\\"),
+                '\n' => line.push_str("\\n"),
+                '\r' => line.push_str("\\r"),
+                c => line.push(c),
+            }
+        }
+        line
+    }
+
+    pub(crate) fn decode(line: &str) -> Option<Self> {
+        let mut chars = line.chars();
+        let insert = match chars.next()? {
+            '+' => true,
+            '-' => false,
+            _ => return None,
+        };
+        let mut fields = chars.as_str().splitn(3, ':');
+        let row = fields.next()?.parse().ok()?;
+        let col = fields.next()?.parse().ok()?;
+        let mut text = String::new();
+        let mut escaped = fields.next()?.chars();
+        while let Some(c) = escaped.next() {
+            if c != '\\' {
+                text.push(c);
+                continue;
+            }
+            match escaped.next()? {
+                '\\' => text.push('\\'),
+                'n' => text.push('\n'),
+                'r' => text.push('\r'),
+                _ => return None,
+            }
+        }
+        let pos = (row, col);
+        Some(if insert {
+            Self::Insert { pos, text }
+        } else {
+            Self::Delete { pos, text }
+        })
+    }
+}
+
+// End position of the text which starts at the position
+pub(crate) fn text_end(pos: (usize, usize), text: &str) -> (usize, usize) {
+    let (row, col) = pos;
+    let mut lines = text.split('\n');
+    let first = lines.next().unwrap_or("").chars().count();
+    match lines.enumerate().last() {
+        Some((i, last)) => (row + i + 1, last.chars().count()),
+        None => (row, col + first),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_op() {
+        let tests = [
+            (true, (0, 0), "", "+0:0:"),
+            (true, (1, 2), "hello\nworld", "+1:2:hello\\nworld"),
+            (false, (3, 4), "a:b\\c\r\n", "-3:4:a:b\\\\c\\r\\n"),
+            (true, (0, 1), "🐶", "+0:1:🐶"),
+        ];
+        for (insert, pos, text, encoded) in tests {
+            let text = text.to_string();
+            let op = if insert {
+                EditOp::Insert { pos, text }
+            } else {
+                EditOp::Delete { pos, text }
+            };
+            assert_eq!(op.encode(), encoded);
+            assert_eq!(EditOp::decode(encoded), Some(op));
+        }
+
+        for invalid in ["", "*0:0:a", "+0:a", "+x:0:a", "+0:0:\\x", "+0:0:\\"] {
+            assert_eq!(EditOp::decode(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn shift_position() {
+        let insert = EditOp::Insert {
+            pos: (1, 2),
+            text: "ab\ncde".to_string(),
+        };
+        let delete = EditOp::Delete {
+            pos: (1, 2),
+            text: "ab\ncde".to_string(),
+        };
+        let tests = [
+            ((0, 5), (0, 5), (0, 5)),
+            ((1, 1), (1, 1), (1, 1)),
+            ((1, 2), (1, 2), (1, 2)),
+            ((1, 4), (2, 5), (1, 2)),
+            ((2, 3), (3, 3), (1, 2)),
+            ((2, 5), (3, 5), (1, 4)),
+            ((4, 1), (5, 1), (3, 1)),
+        ];
+        for (pos, inserted, deleted) in tests {
+            assert_eq!(insert.shift(pos), inserted, "insert at {:?}", pos);
+            assert_eq!(delete.shift(pos), deleted, "delete at {:?}", pos);
+        }
+    }
+
+    #[test]
+    fn shift_position_with_offset() {
+        // Edits at the column 1 of the line "あいう", which is the byte offset 3
+        let insert = EditOp::Insert {
+            pos: (0, 1),
+            text: "え\nおx".to_string(),
+        };
+        let delete = EditOp::Delete {
+            pos: (0, 1),
+            text: "い".to_string(),
+        };
+        let tests = [
+            ((0, 0, 0), (0, 0, 0), (0, 0, 0)),
+            ((0, 1, 3), (0, 1, 3), (0, 1, 3)),
+            ((0, 2, 6), (1, 3, 7), (0, 1, 3)),
+            ((0, 3, 9), (1, 4, 10), (0, 2, 6)),
+            ((1, 2, 2), (2, 2, 2), (1, 2, 2)),
+        ];
+        for (pos, inserted, deleted) in tests {
+            let p = Pos::new(pos.0, pos.1, pos.2);
+            let i = insert.shift_pos(3, &p);
+            assert_eq!((i.row, i.col, i.offset), inserted, "insert at {:?}", pos);
+            let d = delete.shift_pos(3, &p);
+            assert_eq!((d.row, d.col, d.offset), deleted, "delete at {:?}", pos);
+        }
+    }
+}
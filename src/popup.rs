@@ -0,0 +1,171 @@
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::style::{Color, Modifier, Style};
+use std::cmp;
+use unicode_width::UnicodeWidthStr as _;
+
+const MAX_ROWS: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct CompletionPopup {
+    pub items: Vec<String>,
+    pub selected: usize,
+    pub style: Style,
+    pub selected_style: Style,
+}
+
+impl Default for CompletionPopup {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            selected: 0,
+            style: Style::default().bg(Color::DarkGray),
+            selected_style: Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::REVERSED),
+        }
+    }
+}
+
+impl CompletionPopup {
+    pub fn open(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.selected = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.items.clear();
+        self.selected = 0;
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.items.is_empty()
+    }
+
+    pub fn selected_item(&self) -> Option<&str> {
+        self.items.get(self.selected).map(String::as_str)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.is_open() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.is_open() {
+            self.selected = self.selected.checked_sub(1).unwrap_or(self.items.len() - 1);
+        }
+    }
+
+    // Render the popup just below the cursor cell at `(x, y)`. When there is no space below the cursor, the popup is
+    // rendered above the cursor.
+    pub fn render(&self, (x, y): (u16, u16), buf: &mut Buffer) {
+        if !self.is_open() {
+            return;
+        }
+
+        let area = buf.area;
+        let rows = cmp::min(self.items.len(), MAX_ROWS) as u16;
+        let width = self.items.iter().map(|s| s.width()).max().unwrap_or(0) as u16 + 2;
+        let width = cmp::min(width, area.width);
+
+        let below = y.saturating_add(1);
+        let top = if below.saturating_add(rows) <= area.bottom() {
+            below
+        } else if y.saturating_sub(area.y) >= rows {
+            y - rows
+        } else {
+            below
+        };
+        let left = cmp::min(x, area.right().saturating_sub(width));
+        let popup = Rect {
+            x: left,
+            y: top,
+            width,
+            height: cmp::min(rows, area.bottom().saturating_sub(top)),
+        };
+        if popup.area() == 0 {
+            return;
+        }
+
+        // Scroll the items so that the selected item is always visible
+        let rows = popup.height as usize;
+        let scroll = (self.selected + 1).saturating_sub(rows);
+        buf.set_style(popup, self.style);
+        for (i, item) in self.items.iter().skip(scroll).take(rows).enumerate() {
+            let y = popup.y + i as u16;
+            let style = if scroll + i == self.selected {
+                self.selected_style
+            } else {
+                self.style
+            };
+            buf.set_style(Rect::new(popup.x, y, popup.width, 1), style);
+            buf.set_stringn(
+                popup.x + 1,
+                y,
+                item,
+                popup.width.saturating_sub(2) as usize,
+                style,
+            );
+        }
+    }
+}
+
+// Tests for buffer contents don't work with tui-rs
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use super::*;
+
+    fn rendered(buf: &Buffer) -> Vec<String> {
+        let area = buf.area;
+        (area.y..area.bottom())
+            .map(|y| {
+                (area.x..area.right())
+                    .map(|x| buf.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn select_items() {
+        let mut p = CompletionPopup::default();
+        assert!(!p.is_open());
+        assert_eq!(p.selected_item(), None);
+        p.select_next(); // Does nothing when closed
+
+        p.open(vec!["a".into(), "b".into(), "c".into()]);
+        assert_eq!(p.selected_item(), Some("a"));
+        p.select_prev();
+        assert_eq!(p.selected_item(), Some("c"));
+        p.select_next();
+        p.select_next();
+        assert_eq!(p.selected_item(), Some("b"));
+
+        p.close();
+        assert!(!p.is_open());
+    }
+
+    #[test]
+    fn render_position() {
+        let mut p = CompletionPopup::default();
+        p.open(vec!["foo".into(), "foobar".into()]);
+
+        // Below the cursor
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 4));
+        p.render((1, 0), &mut buf);
+        assert_eq!(
+            rendered(&buf),
+            ["          ", "  foo     ", "  foobar  ", "          "],
+        );
+
+        // Above the cursor since no space below
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 4));
+        p.render((6, 3), &mut buf);
+        assert_eq!(
+            rendered(&buf),
+            ["          ", "   foo    ", "   foobar ", "          "],
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::op::EditOp;
+
+// Read-only range of text. A range of characters protects only the characters between its start and end positions,
+// so text can be inserted just before or after it. A range of lines protects entire lines including their boundaries
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub lines: bool,
+}
+
+impl Region {
+    fn allows_insert(&self, pos: (usize, usize)) -> bool {
+        if self.lines {
+            pos < self.start || self.end < pos
+        } else {
+            pos <= self.start || self.end <= pos
+        }
+    }
+
+    fn allows_delete(&self, start: (usize, usize), end: (usize, usize)) -> bool {
+        if self.lines {
+            end < self.start || self.end < start
+        } else {
+            end <= self.start || self.end <= start
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Protection {
+    pub regions: Vec<Region>,
+    // Whether the protection is temporarily disabled to apply edits which already happened (e.g. remote edits)
+    pub suspended: bool,
+    // Whether some edit was rejected by the protection
+    pub rejected: bool,
+}
+
+impl Protection {
+    // Check if the text in the range can be edited. An empty range means an insertion at the position. When the edit
+    // is not allowed, it is remembered as rejected
+    pub fn allows(&mut self, start: (usize, usize), end: (usize, usize)) -> bool {
+        if self.suspended {
+            return true;
+        }
+        let allowed = self.is_editable(start, end);
+        self.rejected |= !allowed;
+        allowed
+    }
+
+    pub fn is_editable(&self, start: (usize, usize), end: (usize, usize)) -> bool {
+        if start == end {
+            self.regions.iter().all(|r| r.allows_insert(start))
+        } else {
+            self.regions.iter().all(|r| r.allows_delete(start, end))
+        }
+    }
+
+    // Move the regions along with the edit. Text inserted at the start of a range of characters is put outside of it
+    pub fn shift(&mut self, op: &EditOp) {
+        for r in &mut self.regions {
+            r.start = match op {
+                EditOp::Insert { pos, .. } if *pos == r.start && !r.lines => op.end(),
+                _ => op.shift(r.start),
+            };
+            r.end = op.shift(r.end);
+        }
+        self.regions.retain(|r| r.lines || r.start < r.end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protect_regions() {
+        let mut p = Protection::default();
+        p.regions.push(Region {
+            start: (0, 2),
+            end: (0, 5),
+            lines: false,
+        });
+        p.regions.push(Region {
+            start: (2, 0),
+            end: (3, 4),
+            lines: true,
+        });
+
+        let tests = [
+            ((0, 1), (0, 1), true),
+            ((0, 2), (0, 2), true),
+            ((0, 3), (0, 3), false),
+            ((0, 5), (0, 5), true),
+            ((0, 0), (0, 2), true),
+            ((0, 1), (0, 3), false),
+            ((0, 5), (1, 0), true),
+            ((1, 0), (1, 3), true),
+            ((1, 3), (2, 0), false), // Joining lines
+            ((2, 0), (2, 0), false),
+            ((3, 4), (3, 4), false),
+            ((3, 4), (4, 0), false),
+            ((4, 0), (4, 0), true),
+        ];
+        for (start, end, want) in tests {
+            assert_eq!(p.allows(start, end), want, "{:?}", (start, end));
+        }
+        assert!(p.rejected);
+
+        p.suspended = true;
+        assert!(p.allows((0, 3), (0, 3)));
+    }
+
+    #[test]
+    fn shift_regions() {
+        let mut p = Protection::default();
+        p.regions.push(Region {
+            start: (0, 2),
+            end: (0, 5),
+            lines: false,
+        });
+
+        let text = "ab".to_string();
+        p.shift(&EditOp::Insert { pos: (0, 2), text });
+        assert_eq!((p.regions[0].start, p.regions[0].end), ((0, 4), (0, 7)));
+
+        let text = "\n".to_string();
+        p.shift(&EditOp::Insert { pos: (0, 7), text });
+        assert_eq!((p.regions[0].start, p.regions[0].end), ((0, 4), (0, 7)));
+
+        let text = "xab".to_string();
+        p.shift(&EditOp::Delete { pos: (0, 1), text });
+        assert_eq!((p.regions[0].start, p.regions[0].end), ((0, 1), (0, 4)));
+    }
+}
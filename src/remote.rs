@@ -0,0 +1,95 @@
+use crate::ratatui::style::Style;
+
+/// Cursor and selection of a remote collaborator rendered in [`crate::TextArea`] alongside the local cursor, for
+/// pair-programming or shared-editing applications. Each collaborator is identified by its name. Register remote
+/// cursors with [`crate::TextArea::set_remote_cursor`].
+///
+/// Positions are 0-base `(row, column)` pairs. The selection is the range between the selection start and the cursor
+/// position.
+/// ```
+/// use ratatui::style::{Style, Color};
+/// use tui_textarea::RemoteCursor;
+///
+/// let style = Style::default().bg(Color::Magenta);
+/// let cursor = RemoteCursor::new("alice", (0, 5), style);
+/// assert_eq!(cursor.name, "alice");
+/// assert_eq!(cursor.position, (0, 5));
+/// assert_eq!(cursor.selection_start, None);
+///
+/// let select_style = Style::default().bg(Color::LightMagenta);
+/// let cursor = RemoteCursor::new("alice", (0, 5), style).selection((0, 2), select_style);
+/// assert_eq!(cursor.selection_start, Some((0, 2)));
+/// assert_eq!(cursor.selection_style, select_style);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteCursor {
+    /// Name of the collaborator
+    pub name: String,
+    /// Position of the cursor
+    pub position: (usize, usize),
+    /// Style of the cursor
+    pub style: Style,
+    /// Start position of the selection. `None` when nothing is selected
+    pub selection_start: Option<(usize, usize)>,
+    /// Style of the selection
+    pub selection_style: Style,
+}
+
+impl RemoteCursor {
+    /// Create a new remote cursor of the collaborator at the position.
+    pub fn new(name: impl Into<String>, position: (usize, usize), style: Style) -> Self {
+        Self {
+            name: name.into(),
+            position,
+            style,
+            selection_start: None,
+            selection_style: Style::default(),
+        }
+    }
+
+    /// Set the selection from the start position to the cursor position.
+    pub fn selection(mut self, start: (usize, usize), style: Style) -> Self {
+        self.selection_start = Some(start);
+        self.selection_style = style;
+        self
+    }
+
+    // Selected columns in the row as a pair of start and end columns. The end column is `None` when the selection
+    // continues to the next line
+    pub(crate) fn selection_in_row(&self, row: usize) -> Option<(usize, Option<usize>)> {
+        let start = self.selection_start?;
+        let (start, end) = if start <= self.position {
+            (start, self.position)
+        } else {
+            (self.position, start)
+        };
+        if row < start.0 || end.0 < row {
+            return None;
+        }
+        let from = if row == start.0 { start.1 } else { 0 };
+        let to = if row == end.0 { Some(end.1) } else { None };
+        Some((from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_in_row() {
+        let style = Style::default();
+        let cursor = RemoteCursor::new("a", (1, 2), style);
+        assert_eq!(cursor.selection_in_row(1), None);
+
+        let cursor = cursor.selection((3, 1), style);
+        assert_eq!(cursor.selection_in_row(0), None);
+        assert_eq!(cursor.selection_in_row(1), Some((2, None)));
+        assert_eq!(cursor.selection_in_row(2), Some((0, None)));
+        assert_eq!(cursor.selection_in_row(3), Some((0, Some(1))));
+        assert_eq!(cursor.selection_in_row(4), None);
+
+        let cursor = RemoteCursor::new("a", (0, 4), style).selection((0, 1), style);
+        assert_eq!(cursor.selection_in_row(0), Some((1, Some(4))));
+    }
+}
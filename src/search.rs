@@ -1,17 +1,45 @@
+use crate::op::EditOp;
 use crate::ratatui::style::{Color, Style};
-use regex::Regex;
+use crate::util::byte_offset;
+use regex::{Regex, RegexBuilder};
+
+/// Case sensitivity of text search. See [`crate::TextArea::set_search_case`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SearchCase {
+    /// Match case sensitively
+    Sensitive,
+    /// Match case insensitively
+    Insensitive,
+    /// Match case insensitively unless the pattern contains some uppercase character, like Vim's `smartcase`
+    Smart,
+}
+
+impl Default for SearchCase {
+    fn default() -> Self {
+        Self::Sensitive
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Search {
     pub pat: Option<Regex>,
+    pub query: String,
     pub style: Style,
+    pub case: SearchCase,
+    pub whole_word: bool,
+    // Start and end (exclusive) positions of the range where matches are searched. `None` means the whole text
+    pub scope: Option<((usize, usize), (usize, usize))>,
 }
 
 impl Default for Search {
     fn default() -> Self {
         Self {
             pat: None,
+            query: String::new(),
             style: Style::default().bg(Color::Blue),
+            case: SearchCase::default(),
+            whole_word: false,
+            scope: None,
         }
     }
 }
@@ -20,27 +48,203 @@ impl Search {
     pub fn matches<'a>(
         &'a self,
         line: &'a str,
+        row: usize,
     ) -> Option<impl Iterator<Item = (usize, usize)> + 'a> {
         let pat = self.pat.as_ref()?;
-        let matches = pat.find_iter(line).map(|m| (m.start(), m.end()));
+        let (lo, hi) = self.scope_offsets(line, row)?;
+        let matches = pat
+            .find_iter(line)
+            .map(|m| (m.start(), m.end()))
+            .filter(move |&(s, e)| lo <= s && e <= hi);
         Some(matches)
     }
 
+    // Byte range of the line in the scope. `None` when the line is out of the scope
+    fn scope_offsets(&self, line: &str, row: usize) -> Option<(usize, usize)> {
+        let (start, end) = match self.scope {
+            Some(scope) => scope,
+            None => return Some((0, line.len())),
+        };
+        if row < start.0 || end.0 < row {
+            return None;
+        }
+        let lo = if row == start.0 {
+            byte_offset(line, start.1)
+        } else {
+            0
+        };
+        let hi = if row == end.0 {
+            byte_offset(line, end.1)
+        } else {
+            line.len()
+        };
+        Some((lo, hi))
+    }
+
+    fn in_scope(&self, start: (usize, usize), end: (usize, usize)) -> bool {
+        self.scope.map_or(true, |(s, e)| s <= start && end <= e)
+    }
+
+    // Move the scope along with the edit. Text inserted at the end of the scope is put inside of it so that replacing
+    // the last match in the scope keeps the replaced text in the scope
+    pub fn shift_scope(&mut self, op: &EditOp) {
+        if let Some((start, end)) = &mut self.scope {
+            *start = op.shift(*start);
+            *end = match op {
+                EditOp::Insert { pos, .. } if pos == end => op.end(),
+                _ => op.shift(*end),
+            };
+        }
+    }
+
     pub fn set_pattern(&mut self, query: &str) -> Result<(), regex::Error> {
         match &self.pat {
-            Some(r) if r.as_str() == query => {}
-            _ if query.is_empty() => self.pat = None,
-            _ => self.pat = Some(Regex::new(query)?),
+            Some(_) if self.query == query => {}
+            _ if query.is_empty() => {
+                self.pat = None;
+                self.query.clear();
+            }
+            _ => {
+                self.pat = Some(self.build(query)?);
+                self.query = query.to_string();
+            }
         }
         Ok(())
     }
 
+    pub fn set_case(&mut self, case: SearchCase) {
+        self.case = case;
+        self.rebuild();
+    }
+
+    pub fn set_whole_word(&mut self, enabled: bool) {
+        self.whole_word = enabled;
+        self.rebuild();
+    }
+
+    fn build(&self, query: &str) -> Result<Regex, regex::Error> {
+        let insensitive = match self.case {
+            SearchCase::Sensitive => false,
+            SearchCase::Insensitive => true,
+            SearchCase::Smart => !query.chars().any(char::is_uppercase),
+        };
+        let pattern = if self.whole_word {
+            format!(r"\b(?:{})\b", query)
+        } else {
+            query.to_string()
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(insensitive)
+            .build()
+    }
+
+    // Compile the current query again after the options were changed
+    fn rebuild(&mut self) {
+        if self.pat.is_some() {
+            // The query was already compiled successfully so it is still valid with the new options
+            self.pat = self.build(&self.query).ok();
+        }
+    }
+
+    // Find the first non-empty match at or after the position without wrapping around. Returns the row, the start
+    // column, and the end column of the match
+    pub fn find_from(
+        &self,
+        lines: &[String],
+        pos: (usize, usize),
+    ) -> Option<(usize, usize, usize)> {
+        let pat = self.pat.as_ref()?;
+        let (row, col) = pos;
+        for (i, line) in lines.iter().enumerate().skip(row) {
+            let offset = if i == row { byte_offset(line, col) } else { 0 };
+            let found = pat
+                .find_iter(line)
+                .filter(|m| m.start() >= offset && m.start() != m.end())
+                .map(|m| {
+                    let start = line[..m.start()].chars().count();
+                    (start, start + line[m.start()..m.end()].chars().count())
+                })
+                .find(|&(start, end)| self.in_scope((i, start), (i, end)));
+            if let Some((start, end)) = found {
+                return Some((i, start, end));
+            }
+        }
+        None
+    }
+
+    // Enumerate all non-empty matches in the lines as pairs of the start position and the end position (exclusive)
+    pub fn all_matches<'a>(
+        &'a self,
+        lines: &'a [String],
+    ) -> impl Iterator<Item = ((usize, usize), (usize, usize))> + 'a {
+        self.pat
+            .iter()
+            .flat_map(move |pat| {
+                lines.iter().enumerate().flat_map(move |(row, line)| {
+                    let (mut offset, mut col) = (0, 0);
+                    pat.find_iter(line)
+                        .filter(|m| m.start() != m.end())
+                        .map(move |m| {
+                            col += line[offset..m.start()].chars().count();
+                            let start = col;
+                            col += line[m.start()..m.end()].chars().count();
+                            offset = m.end();
+                            ((row, start), (row, col))
+                        })
+                })
+            })
+            .filter(move |&(start, end)| self.in_scope(start, end))
+    }
+
+    // Search the start of the next match in the scope. The search wraps around the scope
+    fn next_in_scope(
+        &self,
+        lines: &[String],
+        cursor: (usize, usize),
+        match_cursor: bool,
+        forward: bool,
+    ) -> Option<(usize, usize)> {
+        let starts: Vec<_> = self.all_matches(lines).map(|(start, _)| start).collect();
+        if forward {
+            starts
+                .iter()
+                .find(|&&s| s > cursor || (match_cursor && s == cursor))
+                .or_else(|| starts.first())
+                .copied()
+        } else {
+            starts
+                .iter()
+                .rev()
+                .find(|&&s| s < cursor || (match_cursor && s == cursor))
+                .or_else(|| starts.last())
+                .copied()
+        }
+    }
+
+    // Expand the replacement for the match starting at the byte offset in the line. `$1` or `${name}` in the
+    // replacement is replaced with the captured group
+    pub fn expand(&self, line: &str, offset: usize, replacement: &str) -> String {
+        let mut dst = String::new();
+        let caps = self.pat.as_ref().and_then(|pat| {
+            pat.captures_iter(line)
+                .find(|c| c.get(0).map_or(false, |m| m.start() == offset))
+        });
+        match caps {
+            Some(caps) => caps.expand(replacement, &mut dst),
+            None => dst.push_str(replacement),
+        }
+        dst
+    }
+
     pub fn forward(
         &mut self,
         lines: &[String],
         cursor: (usize, usize),
         match_cursor: bool,
     ) -> Option<(usize, usize)> {
+        if self.scope.is_some() {
+            return self.next_in_scope(lines, cursor, match_cursor, true);
+        }
         let pat = if let Some(pat) = &self.pat {
             pat
         } else {
@@ -51,7 +255,8 @@ impl Search {
 
         // Search current line after cursor
         let start_col = if match_cursor { col } else { col + 1 };
-        if let Some((i, _)) = current_line.char_indices().nth(start_col) {
+        let i = byte_offset(current_line, start_col);
+        if i < current_line.len() {
             if let Some(m) = pat.find_at(current_line, i) {
                 let col = start_col + current_line[i..m.start()].chars().count();
                 return Some((row, col));
@@ -75,11 +280,7 @@ impl Search {
         }
 
         // Search current line before cursor
-        let col_idx = current_line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(current_line.len());
+        let col_idx = byte_offset(current_line, col);
         if let Some(m) = pat.find(current_line) {
             let i = m.start();
             if i <= col_idx {
@@ -97,6 +298,9 @@ impl Search {
         cursor: (usize, usize),
         match_cursor: bool,
     ) -> Option<(usize, usize)> {
+        if self.scope.is_some() {
+            return self.next_in_scope(lines, cursor, match_cursor, false);
+        }
         let pat = if let Some(pat) = &self.pat {
             pat
         } else {
@@ -108,7 +312,8 @@ impl Search {
         // Search current line before cursor
         if col > 0 || match_cursor {
             let start_col = if match_cursor { col } else { col - 1 };
-            if let Some((i, _)) = current_line.char_indices().nth(start_col) {
+            let i = byte_offset(current_line, start_col);
+            if i < current_line.len() {
                 if let Some(m) = pat
                     .find_iter(current_line)
                     .take_while(|m| m.start() <= i)
@@ -137,7 +342,8 @@ impl Search {
         }
 
         // Search current line after cursor
-        if let Some((i, _)) = current_line.char_indices().nth(col) {
+        let i = byte_offset(current_line, col);
+        if i < current_line.len() {
             if let Some(m) = pat
                 .find_iter(current_line)
                 .skip_while(|m| m.start() < i)
@@ -151,3 +357,281 @@ impl Search {
         None
     }
 }
+
+/// Answer for each match in the interactive replace. See [`crate::TextArea::replace_start`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReplaceAnswer {
+    /// Replace the current match and go to the next match
+    Yes,
+    /// Skip the current match and go to the next match
+    No,
+    /// Replace the current match and all the remaining matches
+    All,
+    /// Stop the interactive replace without replacing the current match
+    Quit,
+}
+
+// State of the ongoing interactive replace. The current match is at `row` in `start..end` columns
+#[derive(Clone, Debug)]
+pub struct InteractiveReplace {
+    pub replacement: String,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+// State of the ongoing incremental search
+#[derive(Clone, Debug)]
+pub struct IncrementalSearch {
+    pub query: String,
+    // Cursor position and search query before the incremental search started. They are restored on canceling
+    pub origin: (usize, usize),
+    pub prev_query: String,
+    // Index of the pattern recalled from the search history and the query typed before browsing the history
+    pub history_index: Option<usize>,
+    pub draft: String,
+}
+
+// History of search patterns. Older patterns come first and the number of patterns is limited to `max`
+#[derive(Clone, Debug)]
+pub struct SearchHistory {
+    pub entries: Vec<String>,
+    pub max: usize,
+}
+
+impl Default for SearchHistory {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            max: 100,
+        }
+    }
+}
+
+impl SearchHistory {
+    // Add the pattern as the newest entry. The same pattern in older entries is removed to avoid duplicates
+    pub fn push(&mut self, pat: &str) {
+        if pat.is_empty() {
+            return;
+        }
+        self.entries.retain(|e| e != pat);
+        self.entries.push(pat.to_string());
+        self.truncate();
+    }
+
+    pub fn truncate(&mut self) {
+        if self.entries.len() > self.max {
+            let excess = self.entries.len() - self.max;
+            self.entries.drain(..excess);
+        }
+    }
+}
+
+// Regular expressions to highlight matched text with styles
+#[derive(Clone, Debug, Default)]
+pub struct HighlightRules {
+    pub rules: Vec<(Regex, Style)>,
+}
+
+impl HighlightRules {
+    pub fn add(&mut self, pattern: &str, style: Style) -> Result<(), regex::Error> {
+        let re = Regex::new(pattern)?;
+        self.rules.push((re, style));
+        Ok(())
+    }
+
+    pub fn remove(&mut self, pattern: &str) -> bool {
+        let len = self.rules.len();
+        self.rules.retain(|(re, _)| re.as_str() != pattern);
+        self.rules.len() != len
+    }
+
+    // Byte offset ranges of the matches in the line and their styles. Empty matches are skipped.
+    pub fn matches(&self, line: &str) -> Vec<(usize, usize, Style)> {
+        let mut ranges = vec![];
+        for (re, style) in &self.rules {
+            for m in re.find_iter(line) {
+                if m.start() != m.end() {
+                    ranges.push((m.start(), m.end(), *style));
+                }
+            }
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_history() {
+        let mut history = SearchHistory {
+            entries: vec![],
+            max: 3,
+        };
+        for pat in ["a", "b", "", "a", "c", "d"] {
+            history.push(pat);
+        }
+        // The duplicate "a" is moved to the newest position before the oldest entry "b" is dropped
+        assert_eq!(history.entries, ["a", "c", "d"]);
+        history.max = 1;
+        history.truncate();
+        assert_eq!(history.entries, ["d"]);
+    }
+
+    #[test]
+    fn find_and_expand() {
+        let lines = [
+            "foo = 1".to_string(),
+            "".to_string(),
+            "bar = foo".to_string(),
+        ];
+        let mut search = Search::default();
+        search.set_pattern(r"(\w+) = (\w+)").unwrap();
+        assert_eq!(search.find_from(&lines, (0, 0)), Some((0, 0, 7)));
+        assert_eq!(search.find_from(&lines, (0, 1)), Some((2, 0, 9)));
+        assert_eq!(search.find_from(&lines, (2, 1)), None);
+        assert_eq!(search.expand(&lines[2], 0, "$2 = $1"), "foo = bar");
+
+        search.set_pattern("x*").unwrap();
+        assert_eq!(search.find_from(&lines, (0, 0)), None);
+        assert_eq!(search.all_matches(&lines).count(), 0);
+    }
+
+    #[test]
+    fn enumerate_all_matches() {
+        let lines = ["あaいa".to_string(), "".to_string(), "aa".to_string()];
+        let mut search = Search::default();
+        assert_eq!(search.all_matches(&lines).count(), 0);
+
+        search.set_pattern("a").unwrap();
+        let matches: Vec<_> = search.all_matches(&lines).collect();
+        assert_eq!(
+            matches,
+            [
+                ((0, 1), (0, 2)),
+                ((0, 3), (0, 4)),
+                ((2, 0), (2, 1)),
+                ((2, 1), (2, 2)),
+            ],
+        );
+    }
+
+    #[test]
+    fn search_in_scope() {
+        let lines = ["ab ab".to_string(), "ab".to_string(), "ab ab".to_string()];
+        let mut search = Search::default();
+        search.set_pattern("ab").unwrap();
+        search.scope = Some(((0, 1), (2, 2)));
+
+        let matches: Vec<_> = search.all_matches(&lines).collect();
+        assert_eq!(
+            matches,
+            [((0, 3), (0, 5)), ((1, 0), (1, 2)), ((2, 0), (2, 2))]
+        );
+        assert_eq!(search.find_from(&lines, (0, 0)), Some((0, 3, 5)));
+        assert_eq!(search.find_from(&lines, (2, 1)), None);
+
+        // Search wraps around the scope
+        assert_eq!(search.forward(&lines, (2, 0), false), Some((0, 3)));
+        assert_eq!(search.forward(&lines, (2, 0), true), Some((2, 0)));
+        assert_eq!(search.back(&lines, (0, 3), false), Some((2, 0)));
+        assert_eq!(search.back(&lines, (1, 1), false), Some((1, 0)));
+
+        let highlighted = |row: usize| {
+            search
+                .matches(lines[row].as_str(), row)
+                .map(|m| m.collect::<Vec<_>>())
+        };
+        assert_eq!(highlighted(0), Some(vec![(3, 5)]));
+        assert_eq!(highlighted(2), Some(vec![(0, 2)]));
+
+        // The scope follows edits
+        search.shift_scope(&EditOp::Insert {
+            pos: (0, 0),
+            text: "x\n".to_string(),
+        });
+        assert_eq!(search.scope, Some(((1, 1), (3, 2))));
+        search.shift_scope(&EditOp::Insert {
+            pos: (3, 2),
+            text: "cd".to_string(),
+        });
+        assert_eq!(search.scope, Some(((1, 1), (3, 4))));
+    }
+
+    #[test]
+    fn search_options() {
+        let tests = [
+            // (query, case, whole_word, line, want)
+            (
+                "foo",
+                SearchCase::Sensitive,
+                false,
+                "Foo foo food",
+                &[(4, 7), (8, 11)][..],
+            ),
+            (
+                "foo",
+                SearchCase::Insensitive,
+                false,
+                "Foo foo",
+                &[(0, 3), (4, 7)][..],
+            ),
+            (
+                "foo",
+                SearchCase::Smart,
+                false,
+                "Foo foo",
+                &[(0, 3), (4, 7)][..],
+            ),
+            ("Foo", SearchCase::Smart, false, "Foo foo", &[(0, 3)][..]),
+            (
+                "foo",
+                SearchCase::Sensitive,
+                true,
+                "foo food _foo foo",
+                &[(0, 3), (14, 17)][..],
+            ),
+            (
+                "a|b",
+                SearchCase::Sensitive,
+                true,
+                "a ab b",
+                &[(0, 1), (5, 6)][..],
+            ),
+        ];
+
+        for test in tests {
+            let (query, case, whole_word, line, want) = test;
+            let mut search = Search::default();
+            search.set_pattern(query).unwrap();
+            search.set_case(case);
+            search.set_whole_word(whole_word);
+            let matches: Vec<_> = search.matches(line, 0).unwrap().collect();
+            assert_eq!(matches, want, "{:?}", test);
+            assert_eq!(search.query, query, "{:?}", test);
+        }
+    }
+
+    #[test]
+    fn highlight_rule_matches() {
+        let mention = Style::default().fg(Color::Blue);
+        let todo = Style::default().fg(Color::Yellow);
+        let mut rules = HighlightRules::default();
+        rules.add(r"@\w+", mention).unwrap();
+        rules.add(r"\bTODO\b", todo).unwrap();
+        rules.add(r"x*", todo).unwrap();
+        assert!(rules.add(r"(", todo).is_err());
+
+        assert_eq!(
+            rules.matches("TODO: ask @alice and @bob"),
+            [(10, 16, mention), (21, 25, mention), (0, 4, todo)],
+        );
+        assert!(rules.matches("nothing").is_empty());
+
+        assert!(rules.remove(r"x*"));
+        assert!(!rules.remove(r"x*"));
+        assert_eq!(rules.rules.len(), 2);
+    }
+}
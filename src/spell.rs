@@ -0,0 +1,197 @@
+use crate::ratatui::style::{Color, Modifier, Style};
+use crate::word::is_word_char;
+use std::fmt;
+use std::sync::Arc;
+
+/// A trait to check spelling of words in [`crate::TextArea`]. Set the checker with
+/// [`crate::TextArea::set_spell_checker`]. Words are split at whitespaces and ASCII punctuations.
+///
+/// This trait is implemented for closures which receive a word and return `true` when the word is misspelled.
+/// ```
+/// use tui_textarea::SpellChecker;
+///
+/// struct Dictionary(Vec<&'static str>);
+///
+/// impl SpellChecker for Dictionary {
+///     fn is_misspelled(&self, word: &str) -> bool {
+///         !self.0.contains(&word.to_lowercase().as_str())
+///     }
+/// }
+///
+/// let dict = Dictionary(vec!["hello", "world"]);
+/// assert!(!dict.is_misspelled("Hello"));
+/// assert!(dict.is_misspelled("wrold"));
+///
+/// let checker = |word: &str| word == "teh";
+/// assert!(checker.is_misspelled("teh"));
+/// ```
+pub trait SpellChecker {
+    /// Return `true` when the word is misspelled.
+    fn is_misspelled(&self, word: &str) -> bool;
+}
+
+impl<F: Fn(&str) -> bool> SpellChecker for F {
+    fn is_misspelled(&self, word: &str) -> bool {
+        self(word)
+    }
+}
+
+// Split the line into words. Each word is represented as (start column, start byte offset, end byte offset).
+fn words(line: &str) -> Vec<(usize, usize, usize)> {
+    let mut words = vec![];
+    let mut start = None;
+    for (col, (i, c)) in line.char_indices().enumerate() {
+        match (start, is_word_char(c)) {
+            (None, true) => start = Some((col, i)),
+            (Some((s, j)), false) => {
+                words.push((s, j, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((s, j)) = start {
+        words.push((s, j, line.len()));
+    }
+    words
+}
+
+#[derive(Clone)]
+pub struct SpellCheck<'a> {
+    pub checker: Option<Arc<dyn SpellChecker + Send + Sync + 'a>>,
+    pub style: Style,
+}
+
+impl<'a> Default for SpellCheck<'a> {
+    fn default() -> Self {
+        Self {
+            checker: None,
+            style: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::UNDERLINED),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for SpellCheck<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpellCheck")
+            .field("checker", &self.checker.as_ref().map(|_| ".."))
+            .field("style", &self.style)
+            .finish()
+    }
+}
+
+impl<'a> SpellCheck<'a> {
+    // Misspelled words in the line. Each word is represented as the same tuple as `words` function
+    fn misspelled_words(&self, line: &str) -> Vec<(usize, usize, usize)> {
+        let checker = match &self.checker {
+            Some(c) => c,
+            None => return vec![],
+        };
+        let mut words = words(line);
+        words.retain(|&(_, s, e)| checker.is_misspelled(&line[s..e]));
+        words
+    }
+
+    // Byte offset ranges of misspelled words in the line
+    pub fn misspellings(&self, line: &str) -> Option<Vec<(usize, usize)>> {
+        self.checker.as_ref()?;
+        let ranges = self.misspelled_words(line);
+        Some(ranges.into_iter().map(|(_, s, e)| (s, e)).collect())
+    }
+
+    // Start columns of misspelled words in the line
+    fn misspelled_cols(&self, line: &str) -> Vec<usize> {
+        let words = self.misspelled_words(line);
+        words.into_iter().map(|(c, _, _)| c).collect()
+    }
+
+    pub fn forward(&self, lines: &[String], cursor: (usize, usize)) -> Option<(usize, usize)> {
+        let (row, col) = cursor;
+        if let Some(c) = self
+            .misspelled_cols(&lines[row])
+            .into_iter()
+            .find(|&c| c > col)
+        {
+            return Some((row, c));
+        }
+        // Search lines after the cursor, then wrap around to lines before the cursor
+        for r in (row + 1..lines.len()).chain(0..row) {
+            if let Some(&c) = self.misspelled_cols(&lines[r]).first() {
+                return Some((r, c));
+            }
+        }
+        let c = *self.misspelled_cols(&lines[row]).first()?;
+        (c < col).then(|| (row, c))
+    }
+
+    pub fn back(&self, lines: &[String], cursor: (usize, usize)) -> Option<(usize, usize)> {
+        let (row, col) = cursor;
+        if let Some(c) = self
+            .misspelled_cols(&lines[row])
+            .into_iter()
+            .rev()
+            .find(|&c| c < col)
+        {
+            return Some((row, c));
+        }
+        // Search lines before the cursor, then wrap around to lines after the cursor
+        for r in (0..row).rev().chain((row + 1..lines.len()).rev()) {
+            if let Some(&c) = self.misspelled_cols(&lines[r]).last() {
+                return Some((r, c));
+            }
+        }
+        let c = *self.misspelled_cols(&lines[row]).last()?;
+        (c > col).then(|| (row, c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    fn checker() -> SpellCheck<'static> {
+        SpellCheck {
+            checker: Some(Arc::new(|w: &str| w.starts_with('x'))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn split_words() {
+        assert_eq!(words(""), []);
+        assert_eq!(words("  "), []);
+        assert_eq!(words("a bc"), [(0, 0, 1), (2, 2, 4)]);
+        assert_eq!(words("(foo)"), [(1, 1, 4)]);
+        assert_eq!(words("あい う"), [(0, 0, 6), (3, 7, 10)]);
+    }
+
+    #[test]
+    fn find_misspellings() {
+        let s = checker();
+        assert_eq!(s.misspellings("a xb c xd"), Some(vec![(2, 4), (7, 9)]));
+        assert_eq!(s.misspellings("a b"), Some(vec![]));
+        assert_eq!(SpellCheck::default().misspellings("xa"), None);
+    }
+
+    #[test]
+    fn move_forward_back() {
+        let s = checker();
+        let ls = lines(["xa b xc", "d", "e xf"]);
+        assert_eq!(s.forward(&ls, (0, 0)), Some((0, 5)));
+        assert_eq!(s.forward(&ls, (0, 5)), Some((2, 2)));
+        assert_eq!(s.forward(&ls, (2, 2)), Some((0, 0)));
+        assert_eq!(s.back(&ls, (0, 0)), Some((2, 2)));
+        assert_eq!(s.back(&ls, (2, 2)), Some((0, 5)));
+        assert_eq!(s.back(&ls, (0, 3)), Some((0, 0)));
+
+        // Only one misspelling at the cursor
+        let ls = lines(["a xb"]);
+        assert_eq!(s.forward(&ls, (0, 2)), None);
+        assert_eq!(s.back(&ls, (0, 2)), None);
+        assert_eq!(s.forward(&ls, (0, 3)), Some((0, 2)));
+        assert_eq!(s.back(&ls, (0, 0)), Some((0, 2)));
+    }
+}
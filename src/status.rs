@@ -0,0 +1,47 @@
+/// Editing mode of [`crate::TextArea`] reported by [`Status`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Normal editing. Characters are inserted at the cursor
+    Insert,
+    /// Text selection is ongoing. See [`crate::TextArea::start_selection`]
+    Select,
+}
+
+/// Snapshot of the state of [`crate::TextArea`] to render a status bar of an editor. Get the status with
+/// [`crate::TextArea::status`].
+/// ```
+/// use tui_textarea::{TextArea, CursorMove, Mode};
+///
+/// let mut textarea = TextArea::from(["abc", "def", "ghi", "jkl"]);
+/// textarea.move_cursor(CursorMove::Down);
+///
+/// let status = textarea.status();
+/// assert_eq!((status.row, status.col), (2, 1));
+/// assert_eq!(status.lines, 4);
+/// assert_eq!(status.percent, 50);
+/// assert_eq!(status.mode, Mode::Insert);
+/// assert!(!status.modified);
+///
+/// // Status bar text like "2:1 50%"
+/// let text = format!("{}:{} {}%", status.row, status.col, status.percent);
+/// assert_eq!(text, "2:1 50%");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Status {
+    /// 1-based row of the cursor
+    pub row: usize,
+    /// 1-based column of the cursor in characters
+    pub col: usize,
+    /// Number of lines in the textarea
+    pub lines: usize,
+    /// Position of the cursor line through the text in percent (0..=100)
+    pub percent: u8,
+    /// Current editing mode
+    pub mode: Mode,
+    /// Whether the text was modified. See [`crate::TextArea::is_modified`]
+    pub modified: bool,
+    /// Number of selected characters. A newline is counted as one character
+    pub selected_chars: usize,
+    /// Number of lines which the selection spans. This is 0 when nothing is selected
+    pub selected_lines: usize,
+}
@@ -0,0 +1,119 @@
+use crate::util::byte_offset;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+type Resolve<'a> = Arc<dyn Fn(&str) -> Option<String> + Send + Sync + 'a>;
+
+// Callback to resolve the name of a template token like `{{date}}` into its text. The callback is shared between
+// clones of the textarea
+#[derive(Clone)]
+pub struct TemplateResolver<'a>(pub Resolve<'a>);
+
+impl<'a> fmt::Debug for TemplateResolver<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplateResolver").finish_non_exhaustive()
+    }
+}
+
+// Name of the token between `{{` and `}}`. Spaces around the name are ignored like `{{ date }}`
+fn token_name(inner: &str) -> Option<&str> {
+    let name = inner.trim();
+    let valid = !name.is_empty() && !name.contains(|c| matches!(c, '{' | '}' | '\n'));
+    valid.then(|| name)
+}
+
+impl<'a> TemplateResolver<'a> {
+    fn resolve(&self, inner: &str) -> Option<String> {
+        (self.0)(token_name(inner)?)
+    }
+
+    // Find the token which ends at `col` in the line. Returns the start column of the token and its resolved text
+    pub fn find(&self, line: &str, col: usize) -> Option<(usize, String)> {
+        let head = line[..byte_offset(line, col)].strip_suffix("}}")?;
+        let start = head.rfind("{{")?;
+        let text = self.resolve(&head[start + 2..])?;
+        Some((line[..start].chars().count(), text))
+    }
+
+    // Replace all tokens in the text with their resolved texts. Tokens which are not resolved are kept as they are
+    pub fn expand<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut expanded = String::new();
+        let mut copied = 0;
+        let mut i = 0;
+        while let Some(s) = text[i..].find("{{") {
+            let start = i + s;
+            let end = match text[start + 2..].find("}}") {
+                Some(e) => start + 2 + e,
+                None => break,
+            };
+            match self.resolve(&text[start + 2..end]) {
+                Some(resolved) => {
+                    expanded.push_str(&text[copied..start]);
+                    expanded.push_str(&resolved);
+                    copied = end + 2;
+                    i = copied;
+                }
+                // The inner `{{` may start a token like `{{{date}}`
+                None => i = start + 1,
+            }
+        }
+        if copied == 0 {
+            return Cow::Borrowed(text);
+        }
+        expanded.push_str(&text[copied..]);
+        Cow::Owned(expanded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> TemplateResolver<'static> {
+        TemplateResolver(Arc::new(|name| match name {
+            "date" => Some("2024-01-02".to_string()),
+            "empty" => Some(String::new()),
+            _ => None,
+        }))
+    }
+
+    #[test]
+    fn find_token() {
+        let r = resolver();
+        let tests = [
+            ("{{date}}", 8, Some((0, "2024-01-02"))),
+            ("on {{ date }}", 13, Some((3, "2024-01-02"))),
+            ("{{{date}}", 9, Some((1, "2024-01-02"))),
+            ("あ{{empty}}", 10, Some((1, ""))),
+            ("{{date}}", 7, None),
+            ("{{date}", 7, None),
+            ("{{}}", 4, None),
+            ("{{unknown}}", 11, None),
+        ];
+        for test in tests {
+            let (line, col, want) = test;
+            let want = want.map(|(c, s)| (c, s.to_string()));
+            assert_eq!(r.find(line, col), want, "{:?}", test);
+        }
+    }
+
+    #[test]
+    fn expand_tokens() {
+        let r = resolver();
+        let tests = [
+            ("no tokens", "no tokens"),
+            ("# {{date}}", "# 2024-01-02"),
+            ("{{date}}..{{ date }}", "2024-01-02..2024-01-02"),
+            ("{{unknown}} {{date}}", "{{unknown}} 2024-01-02"),
+            ("{{{date}}}", "{2024-01-02}"),
+            ("a{{empty}}b", "ab"),
+            ("{{date", "{{date"),
+            ("{{da\nte}}", "{{da\nte}}"),
+        ];
+        for (text, want) in tests {
+            assert_eq!(r.expand(text), want, "{:?}", text);
+        }
+        assert!(matches!(r.expand("{{unknown}}"), Cow::Borrowed(_)));
+    }
+}
@@ -1,22 +1,85 @@
+use crate::abbrev::Abbreviations;
+use crate::align::align_line;
+use crate::announce::{count_words, Announcement, Announcer};
+#[cfg(feature = "bidi")]
+use crate::bidi;
+use crate::blink::CursorBlink;
+#[cfg(feature = "clipboard")]
+use crate::clipboard::PrimarySelection;
+use crate::commit::{strip_message, wrap_col, CommitMessage, BODY_WIDTH};
+use crate::complete::WordCompletion;
+use crate::conflict::{find_conflicts, Conflict, ConflictHighlight, ConflictResolution};
+use crate::control::{control_char_width, ControlCharDisplay};
+use crate::count::TextCounts;
 use crate::cursor::CursorMove;
+use crate::diagnostic::{Diagnostic, Diagnostics, Severity};
+use crate::diff::{text_edits, LineChange};
+use crate::digraph::Digraphs;
+#[cfg(feature = "editorconfig")]
+use crate::editorconfig;
+use crate::elastic::tab_widths;
+use crate::emoji::Shortcodes;
+use crate::expand::{expand_range, ExpandHistory};
+use crate::fold::{hiding_fold, indent_block, visual_row, Folds};
+use crate::gutter::Gutter;
 use crate::highlight::LineHighlighter;
-use crate::history::{Edit, EditKind, History};
-use crate::input::{Input, Key};
-use crate::ratatui::layout::Alignment;
+use crate::history::{Edit, EditKind, History, UndoGrouping, UndoHistory};
+use crate::idle::IdleChange;
+use crate::indent::{detect_indent, indent_string, reindent, rule_indent, Indent, IndentRules};
+use crate::input::{Input, InputLog, Key};
+use crate::journal::Journal;
+use crate::limit::LineLimit;
+use crate::link::{find_urls, Hyperlink};
+use crate::list::list_item;
+use crate::loader::{InvalidUtf8, Loader};
+use crate::markdown::{
+    align_table, has_markup, next_heading, run_after, run_before, table_cell, table_cell_col,
+    table_range,
+};
+use crate::memory::{strings_size, MemoryUsage};
+use crate::newline::{join_lines, FinalNewline};
+use crate::op::EditOp;
+use crate::popup::CompletionPopup;
+use crate::protect::{Protection, Region};
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::{Alignment, Rect};
 use crate::ratatui::style::{Color, Modifier, Style};
-use crate::ratatui::widgets::{Block, Widget};
+use crate::ratatui::widgets::{Block, StatefulWidget, Widget};
+use crate::remote::RemoteCursor;
 use crate::scroll::Scrolling;
 #[cfg(feature = "search")]
-use crate::search::Search;
-use crate::util::{spaces, Pos};
-use crate::widget::{Renderer, Viewport};
-use crate::word::{find_word_end_forward, find_word_start_backward};
+use crate::search::{
+    HighlightRules, IncrementalSearch, InteractiveReplace, ReplaceAnswer, Search, SearchCase,
+    SearchHistory,
+};
+use crate::spell::{SpellCheck, SpellChecker};
+use crate::status::{Mode, Status};
+use crate::template::TemplateResolver;
+use crate::theme::Theme;
+use crate::track::{ChangeKind, TrackedChange, TrackedChanges};
+use crate::util::{byte_offset, grapheme_range, shift_in_row, spaces, tab_width, Pos};
+use crate::validate::{validate, InvariantError};
+use crate::virtual_text::InlayHint;
+use crate::widget::{buffer_to_string, Renderer, TextAreaState, Viewport};
+use crate::width::{char_width, AmbiguousWidth};
+use crate::word::{
+    find_ident_at, find_ident_occurrences, find_word_end_forward, find_word_head, find_word_range,
+    find_word_start_backward, is_word_char, WordBoundary,
+};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
-use std::cmp::Ordering;
+use std::borrow::Cow;
+use std::cmp::{self, Ordering};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
-use unicode_width::UnicodeWidthChar as _;
+#[cfg(feature = "normalization")]
+use unicode_normalization::char::compose;
+#[cfg(feature = "normalization")]
+use unicode_normalization::{is_nfc, UnicodeNormalization as _};
 
 #[derive(Debug, Clone)]
 enum YankText {
@@ -45,15 +108,77 @@ impl From<Vec<String>> for YankText {
     }
 }
 
-impl ToString for YankText {
-    fn to_string(&self) -> String {
+impl fmt::Display for YankText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Piece(s) => s.clone(),
-            Self::Chunk(ss) => ss.join("\n"),
+            Self::Piece(s) => write!(f, "{}", s),
+            Self::Chunk(ss) => write!(f, "{}", ss.join("\n")),
         }
     }
 }
 
+// Start and end positions of the last pasted text and its index in the yank ring
+#[derive(Debug, Clone, Copy)]
+struct LastPaste {
+    start: (usize, usize),
+    end: (usize, usize),
+    index: usize,
+}
+
+// Recently yanked texts. The newest text comes last and it is pasted by `TextArea::paste`. Older texts are kept up to
+// `max` so that they can be pasted by cycling with `TextArea::paste_cycle`
+#[derive(Debug, Clone)]
+struct YankRing {
+    entries: Vec<YankText>,
+    max: usize,
+}
+
+impl Default for YankRing {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            max: 20,
+        }
+    }
+}
+
+impl YankRing {
+    fn push(&mut self, text: YankText) {
+        self.entries.push(text);
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        let max = cmp::max(self.max, 1);
+        if self.entries.len() > max {
+            let excess = self.entries.len() - max;
+            self.entries.drain(..excess);
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        let texts: usize = self
+            .entries
+            .iter()
+            .map(|t| match t {
+                YankText::Piece(s) => s.capacity(),
+                YankText::Chunk(c) => strings_size(c),
+            })
+            .sum();
+        self.entries.capacity() * std::mem::size_of::<YankText>() + texts
+    }
+
+    // Get the yanked text. 0 is the newest one
+    fn get(&self, index: usize) -> YankText {
+        self.entries
+            .iter()
+            .rev()
+            .nth(index)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
 /// A type to manage state of textarea.
 ///
 /// [`TextArea::default`] creates an empty textarea. [`TextArea::new`] creates a textarea with given text lines.
@@ -77,25 +202,126 @@ impl ToString for YankText {
 #[derive(Clone, Debug)]
 pub struct TextArea<'a> {
     lines: Vec<String>,
+    counts: TextCounts,
+    // Max number of characters shown in the counter on the block and the styles within and over the max
+    char_counter: Option<usize>,
+    char_counter_styles: (Style, Style),
     block: Option<Block<'a>>,
+    focused: bool,
+    // Border styles of the block when focused and unfocused
+    focus_styles: Option<(Style, Style)>,
+    // Min and max numbers of rows reported by `preferred_height`
+    min_rows: u16,
+    max_rows: Option<u16>,
     style: Style,
     cursor: (usize, usize), // 0-base
     tab_len: u8,
     hard_tab_indent: bool,
+    words: WordBoundary,
+    subword_motion: bool,
+    virtual_space: bool,
+    // Position of the cursor beyond the end of line in virtual space mode. The real cursor is at the end of line
+    virtual_pos: Option<(usize, usize)>,
     history: History,
     cursor_line_style: Style,
     line_number_style: Option<Style>,
     pub(crate) viewport: Viewport,
     cursor_style: Style,
-    yank: YankText,
+    cursor_blink: Option<CursorBlink>,
+    yank: YankRing,
+    last_paste: Option<LastPaste>,
+    paste_reindent: bool,
     #[cfg(feature = "search")]
     search: Search,
+    #[cfg(feature = "search")]
+    highlight_rules: HighlightRules,
+    #[cfg(feature = "search")]
+    incremental_search: Option<IncrementalSearch>,
+    #[cfg(feature = "search")]
+    search_history: SearchHistory,
+    #[cfg(feature = "search")]
+    replace: Option<InteractiveReplace>,
     alignment: Alignment,
+    align_width: usize,
     pub(crate) placeholder: String,
     pub(crate) placeholder_style: Style,
     mask: Option<char>,
     selection_start: Option<(usize, usize)>,
     select_style: Style,
+    expand_history: ExpandHistory,
+    #[cfg(feature = "clipboard")]
+    primary_selection: PrimarySelection,
+    abbreviations: Abbreviations,
+    emoji_shortcodes: Shortcodes,
+    emoji_expansion: bool,
+    template_resolver: Option<TemplateResolver<'a>>,
+    list_continuation: bool,
+    quote_next: bool,
+    // Hex digits typed after starting the code point entry
+    codepoint: Option<String>,
+    digraphs: Digraphs,
+    digraph_input: bool,
+    // The first character of the digraph being entered. `Some(None)` while waiting for the first character
+    digraph: Option<Option<char>>,
+    word_completion: bool,
+    completion: Option<WordCompletion>,
+    pub(crate) completion_popup: CompletionPopup,
+    ghost_text: String,
+    ghost_text_style: Style,
+    spell: SpellCheck<'a>,
+    idle_change: Option<IdleChange<'a>>,
+    announcer: Option<Announcer<'a>>,
+    journal: Option<Journal>,
+    recording: Option<(Instant, InputLog)>,
+    // Operations of local edits recorded for collaborative editing. `None` when recording is disabled
+    edit_ops: Option<Vec<EditOp>>,
+    remote_cursors: Vec<RemoteCursor>,
+    tracked: TrackedChanges,
+    protection: Protection,
+    conflict_highlight: ConflictHighlight,
+    commit_message: CommitMessage,
+    line_limit: LineLimit,
+    line_limit_rejected: bool,
+    max_lines: Option<usize>,
+    max_lines_rejected: bool,
+    follow: bool,
+    typewriter: bool,
+    loader: Option<Loader>,
+    invalid_utf8: InvalidUtf8,
+    #[cfg(feature = "normalization")]
+    normalization: bool,
+    final_newline: FinalNewline,
+    ends_with_newline: bool,
+    indent_detection: bool,
+    auto_indent: bool,
+    indent_rules: IndentRules,
+    elastic_tabstops: bool,
+    tab_stops: Vec<usize>,
+    control_char_display: ControlCharDisplay,
+    control_char_style: Style,
+    ambiguous_width: AmbiguousWidth,
+    #[cfg(feature = "bidi")]
+    bidi: bool,
+    diagnostics: Diagnostics,
+    pub(crate) gutter: Gutter,
+    inlay_hints: Vec<InlayHint>,
+    inlay_hint_style: Style,
+    hyperlinks: Vec<Hyperlink>,
+    hyperlink_style: Style,
+    osc8_hyperlinks: bool,
+    detect_urls: bool,
+    line_annotations: BTreeMap<usize, (String, Style)>,
+    show_line_annotations: bool,
+    folds: Folds,
+    indent_guide: Option<(char, Style)>,
+    show_whitespace: bool,
+    whitespace_style: Style,
+    trailing_whitespace_style: Style,
+    word_highlight: bool,
+    word_highlight_style: Style,
+    modified: bool,
+    click_interval: Duration,
+    last_click: Option<(Instant, (usize, usize), u8)>,
 }
 
 /// Convert any iterator whose elements can be converted into [`String`] into [`TextArea`]. Each [`String`] element is
@@ -180,27 +406,130 @@ impl<'a> TextArea<'a> {
             lines.push(String::new());
         }
 
+        let counts = TextCounts::new(&lines);
         Self {
             lines,
+            counts,
             block: None,
+            focused: true,
+            focus_styles: None,
+            min_rows: 1,
+            max_rows: None,
+            char_counter: None,
+            char_counter_styles: (
+                Style::default().fg(Color::DarkGray),
+                Style::default().fg(Color::Red),
+            ),
             style: Style::default(),
             cursor: (0, 0),
             tab_len: 4,
             hard_tab_indent: false,
+            words: WordBoundary::default(),
+            subword_motion: false,
+            virtual_space: false,
+            virtual_pos: None,
             history: History::new(50),
             cursor_line_style: Style::default().add_modifier(Modifier::UNDERLINED),
             line_number_style: None,
             viewport: Viewport::default(),
             cursor_style: Style::default().add_modifier(Modifier::REVERSED),
-            yank: YankText::default(),
+            cursor_blink: None,
+            yank: YankRing::default(),
+            last_paste: None,
+            paste_reindent: false,
             #[cfg(feature = "search")]
             search: Search::default(),
+            #[cfg(feature = "search")]
+            highlight_rules: HighlightRules::default(),
+            #[cfg(feature = "search")]
+            incremental_search: None,
+            #[cfg(feature = "search")]
+            search_history: SearchHistory::default(),
+            #[cfg(feature = "search")]
+            replace: None,
             alignment: Alignment::Left,
+            align_width: 80,
             placeholder: String::new(),
             placeholder_style: Style::default().fg(Color::DarkGray),
             mask: None,
             selection_start: None,
             select_style: Style::default().bg(Color::LightBlue),
+            expand_history: ExpandHistory::default(),
+            #[cfg(feature = "clipboard")]
+            primary_selection: PrimarySelection::default(),
+            abbreviations: Abbreviations::default(),
+            emoji_shortcodes: Shortcodes::default(),
+            emoji_expansion: false,
+            template_resolver: None,
+            list_continuation: false,
+            quote_next: false,
+            codepoint: None,
+            digraphs: Digraphs::default(),
+            digraph_input: false,
+            digraph: None,
+            word_completion: false,
+            completion: None,
+            completion_popup: CompletionPopup::default(),
+            ghost_text: String::new(),
+            ghost_text_style: Style::default().fg(Color::DarkGray),
+            spell: SpellCheck::default(),
+            idle_change: None,
+            announcer: None,
+            journal: None,
+            recording: None,
+            edit_ops: None,
+            remote_cursors: vec![],
+            tracked: TrackedChanges::default(),
+            protection: Protection::default(),
+            conflict_highlight: ConflictHighlight::default(),
+            commit_message: CommitMessage::default(),
+            line_limit: LineLimit::default(),
+            line_limit_rejected: false,
+            max_lines: None,
+            max_lines_rejected: false,
+            follow: false,
+            typewriter: false,
+            loader: None,
+            invalid_utf8: InvalidUtf8::default(),
+            #[cfg(feature = "normalization")]
+            normalization: false,
+            final_newline: FinalNewline::default(),
+            ends_with_newline: false,
+            indent_detection: false,
+            auto_indent: false,
+            indent_rules: IndentRules::default(),
+            elastic_tabstops: false,
+            tab_stops: vec![],
+            control_char_display: ControlCharDisplay::default(),
+            control_char_style: Style::default().fg(Color::Blue),
+            ambiguous_width: AmbiguousWidth::default(),
+            #[cfg(feature = "bidi")]
+            bidi: false,
+            diagnostics: Diagnostics::default(),
+            gutter: Gutter::default(),
+            inlay_hints: vec![],
+            inlay_hint_style: Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+            hyperlinks: vec![],
+            hyperlink_style: Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::UNDERLINED),
+            osc8_hyperlinks: false,
+            detect_urls: false,
+            line_annotations: BTreeMap::new(),
+            show_line_annotations: true,
+            folds: Folds::default(),
+            indent_guide: None,
+            show_whitespace: false,
+            whitespace_style: Style::default().fg(Color::DarkGray),
+            trailing_whitespace_style: Style::default().fg(Color::Red),
+            word_highlight: false,
+            word_highlight_style: Style::default()
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            modified: false,
+            click_interval: Duration::from_millis(500),
+            last_click: None,
         }
     }
 
@@ -242,7 +571,85 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn input(&mut self, input: impl Into<Input>) -> bool {
         let input = input.into();
+        self.reset_cursor_blink();
+        if let Some((start, log)) = &mut self.recording {
+            log.push(start.elapsed(), input.clone());
+        }
+        let (cursor, selection_start) = (self.cursor, self.selection_start);
+        let modified = self.handle_input(input);
+        // Edits are announced by `record_op`
+        if !modified {
+            self.announce_move(cursor, selection_start);
+        }
+        modified
+    }
+
+    fn handle_input(&mut self, input: Input) -> bool {
+        #[cfg(feature = "search")]
+        if self.incremental_search.is_some() {
+            self.search_input(input);
+            return false;
+        }
+        if self.completion_popup.is_open() {
+            if let Some(modified) = self.input_completion_popup(&input) {
+                return modified;
+            }
+        }
+        if self.codepoint.is_some() {
+            if let Some(modified) = self.input_codepoint(&input) {
+                return modified;
+            }
+        }
+        if let Some(first) = self.digraph {
+            if let Some(modified) = self.input_digraph(first, &input) {
+                return modified;
+            }
+        }
+
+        if !self.ghost_text.is_empty() {
+            match input {
+                Input {
+                    key: Key::Right | Key::Tab,
+                    ctrl: false,
+                    alt: false,
+                    ..
+                } if self.cursor.1 == self.lines[self.cursor.0].chars().count() => {
+                    return self.accept_ghost_text();
+                }
+                Input { key: Key::Null, .. } => {}
+                _ => self.ghost_text.clear(),
+            }
+        }
+
+        self.protection.rejected = false;
+        self.line_limit_rejected = false;
+        self.max_lines_rejected = false;
+        let quoted = std::mem::replace(&mut self.quote_next, false);
         let modified = match input {
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } if quoted => {
+                self.insert_char(c);
+                true
+            }
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                ..
+            } if quoted => {
+                self.insert_char('\t');
+                true
+            }
+            Input {
+                key: Key::Enter, ..
+            } if quoted => {
+                self.insert_newline();
+                true
+            }
             Input {
                 key: Key::Char('m'),
                 ctrl: true,
@@ -258,7 +665,14 @@ impl<'a> TextArea<'a> {
             | Input {
                 key: Key::Enter, ..
             } => {
-                self.insert_newline();
+                self.expand_at_terminator();
+                if !(self.list_continuation && self.continue_list()) {
+                    if self.auto_indent {
+                        self.insert_newline_and_indent();
+                    } else {
+                        self.insert_newline();
+                    }
+                }
                 true
             }
             Input {
@@ -267,15 +681,45 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 ..
             } => {
+                if !is_word_char(c) {
+                    self.expand_at_terminator();
+                }
                 self.insert_char(c);
+                if c == '}' {
+                    self.expand_template();
+                }
+                if c != ' ' {
+                    self.wrap_commit_body();
+                }
+                if self.auto_indent && !is_word_char(c) {
+                    self.indent_on_typing();
+                }
                 true
             }
             Input {
+                key: Key::Char('i'),
+                ctrl: true,
+                alt: false,
+                ..
+            }
+            | Input {
                 key: Key::Tab,
                 ctrl: false,
                 alt: false,
+                shift: false,
+            } => {
+                let expanded = self.expand_at_terminator();
+                self.insert_tab() || expanded
+            }
+            Input {
+                key: Key::Char('q'),
+                ctrl: true,
+                alt: false,
                 ..
-            } => self.insert_tab(),
+            } => {
+                self.quote_next = true;
+                false
+            }
             Input {
                 key: Key::Char('h'),
                 ctrl: true,
@@ -300,6 +744,15 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 ..
             } => self.delete_next_char(),
+            Input {
+                key: Key::Char('k'),
+                ctrl: true,
+                alt: false,
+                ..
+            } if self.digraph_input => {
+                self.start_digraph();
+                false
+            }
             Input {
                 key: Key::Char('k'),
                 ctrl: true,
@@ -342,6 +795,18 @@ impl<'a> TextArea<'a> {
                 alt: true,
                 ..
             } => self.delete_next_word(),
+            Input {
+                key: Key::Char('n'),
+                ctrl: true,
+                alt: false,
+                ..
+            } if self.word_completion => self.complete_word_forward(),
+            Input {
+                key: Key::Char('p'),
+                ctrl: true,
+                alt: false,
+                ..
+            } if self.word_completion => self.complete_word_back(),
             Input {
                 key: Key::Char('n'),
                 ctrl: true,
@@ -484,7 +949,12 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::WordForward, shift);
+                let m = if self.subword_motion {
+                    CursorMove::SubwordForward
+                } else {
+                    CursorMove::WordForward
+                };
+                self.move_cursor_with_shift(m, shift);
                 false
             }
             Input {
@@ -499,7 +969,12 @@ impl<'a> TextArea<'a> {
                 alt: false,
                 shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::WordBack, shift);
+                let m = if self.subword_motion {
+                    CursorMove::SubwordBack
+                } else {
+                    CursorMove::WordBack
+                };
+                self.move_cursor_with_shift(m, shift);
                 false
             }
             Input {
@@ -544,6 +1019,15 @@ impl<'a> TextArea<'a> {
                 self.move_cursor_with_shift(CursorMove::ParagraphBack, shift);
                 false
             }
+            Input {
+                key: Key::Char('u' | 'U'),
+                ctrl: true,
+                alt: false,
+                shift: true,
+            } => {
+                self.start_codepoint_entry();
+                false
+            }
             Input {
                 key: Key::Char('u'),
                 ctrl: true,
@@ -565,6 +1049,12 @@ impl<'a> TextArea<'a> {
             | Input {
                 key: Key::Paste, ..
             } => self.paste(),
+            Input {
+                key: Key::Char('y'),
+                ctrl: false,
+                alt: true,
+                ..
+            } => self.paste_cycle(),
             Input {
                 key: Key::Char('x'),
                 ctrl: true,
@@ -628,25 +1118,19 @@ impl<'a> TextArea<'a> {
             }
             _ => false,
         };
+        // An edit rejected by protected regions, by the maximum line length, or by the maximum number of lines doesn't
+        // modify the text
+        let modified = modified && !self.rejected();
+
+        #[cfg(feature = "clipboard")]
+        self.sync_primary_selection();
 
         // Check invariants
-        debug_assert!(!self.lines.is_empty(), "no line after {:?}", input);
-        let (r, c) = self.cursor;
-        debug_assert!(
-            self.lines.len() > r,
-            "cursor {:?} exceeds max lines {} after {:?}",
-            self.cursor,
-            self.lines.len(),
-            input,
-        );
-        debug_assert!(
-            self.lines[r].chars().count() >= c,
-            "cursor {:?} exceeds max col {} at line {:?} after {:?}",
-            self.cursor,
-            self.lines[r].chars().count(),
-            self.lines[r],
-            input,
-        );
+        if cfg!(debug_assertions) {
+            if let Err(err) = self.validate() {
+                panic!("{} after {:?}", err, input);
+            }
+        }
 
         modified
     }
@@ -664,22 +1148,41 @@ impl<'a> TextArea<'a> {
     /// This method is useful when you want to define your own key mappings and don't want default key mappings.
     /// See 'Define your own key mappings' section in [the module document](./index.html).
     pub fn input_without_shortcuts(&mut self, input: impl Into<Input>) -> bool {
-        match input.into() {
+        self.reset_cursor_blink();
+        self.protection.rejected = false;
+        self.line_limit_rejected = false;
+        self.max_lines_rejected = false;
+        let modified = match input.into() {
             Input {
                 key: Key::Char(c),
                 ctrl: false,
                 alt: false,
                 ..
             } => {
+                if !is_word_char(c) {
+                    self.expand_at_terminator();
+                }
                 self.insert_char(c);
+                if c == '}' {
+                    self.expand_template();
+                }
+                if c != ' ' {
+                    self.wrap_commit_body();
+                }
+                if self.auto_indent && !is_word_char(c) {
+                    self.indent_on_typing();
+                }
                 true
             }
             Input {
                 key: Key::Tab,
                 ctrl: false,
                 alt: false,
-                ..
-            } => self.insert_tab(),
+                shift: false,
+            } => {
+                let expanded = self.expand_at_terminator();
+                self.insert_tab() || expanded
+            }
             Input {
                 key: Key::Backspace,
                 ..
@@ -690,7 +1193,14 @@ impl<'a> TextArea<'a> {
             Input {
                 key: Key::Enter, ..
             } => {
-                self.insert_newline();
+                self.expand_at_terminator();
+                if !(self.list_continuation && self.continue_list()) {
+                    if self.auto_indent {
+                        self.insert_newline_and_indent();
+                    } else {
+                        self.insert_newline();
+                    }
+                }
                 true
             }
             Input {
@@ -708,14 +1218,221 @@ impl<'a> TextArea<'a> {
                 false
             }
             _ => false,
+        };
+        modified && !self.rejected()
+    }
+
+    fn input_completion_popup(&mut self, input: &Input) -> Option<bool> {
+        match input {
+            Input {
+                key: Key::Char('n'),
+                ctrl: true,
+                alt: false,
+                ..
+            }
+            | Input { key: Key::Down, .. } => {
+                self.completion_popup.select_next();
+                Some(false)
+            }
+            Input {
+                key: Key::Char('p'),
+                ctrl: true,
+                alt: false,
+                ..
+            }
+            | Input { key: Key::Up, .. } => {
+                self.completion_popup.select_prev();
+                Some(false)
+            }
+            Input {
+                key: Key::Enter | Key::Tab,
+                ctrl: false,
+                alt: false,
+                ..
+            } => Some(self.accept_completion()),
+            Input { key: Key::Esc, .. } => {
+                self.completion_popup.close();
+                Some(false)
+            }
+            Input { key: Key::Null, .. } => Some(false),
+            _ => {
+                self.completion_popup.close();
+                None
+            }
+        }
+    }
+
+    // Handle the input while entering a code point. Inputs other than hex digits, editing keys and `Space`/`Enter`
+    // cancel the entry and are handled as usual
+    fn input_codepoint(&mut self, input: &Input) -> Option<bool> {
+        match input {
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } if c.is_ascii_hexdigit() => {
+                if let Some(digits) = &mut self.codepoint {
+                    if digits.len() < 6 {
+                        digits.push(*c);
+                    }
+                }
+                Some(false)
+            }
+            Input {
+                key: Key::Backspace,
+                ctrl: false,
+                alt: false,
+                ..
+            } => {
+                if let Some(digits) = &mut self.codepoint {
+                    digits.pop();
+                }
+                Some(false)
+            }
+            Input {
+                key: Key::Enter | Key::Char(' '),
+                ctrl: false,
+                alt: false,
+                ..
+            } => {
+                let digits = self.codepoint.take().unwrap_or_default();
+                Some(self.insert_codepoint(&digits))
+            }
+            Input { key: Key::Esc, .. } => {
+                self.codepoint = None;
+                Some(false)
+            }
+            Input { key: Key::Null, .. } => Some(false),
+            _ => {
+                self.codepoint = None;
+                None
+            }
+        }
+    }
+
+    // Handle the input while entering a digraph. When the two characters are not a digraph, the second character is
+    // inserted as Vim does. Inputs other than characters cancel the entry and are handled as usual
+    fn input_digraph(&mut self, first: Option<char>, input: &Input) -> Option<bool> {
+        let c = match input {
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } => *c,
+            Input { key: Key::Null, .. } => return Some(false),
+            Input { key: Key::Esc, .. } => {
+                self.digraph = None;
+                return Some(false);
+            }
+            _ => {
+                self.digraph = None;
+                return None;
+            }
+        };
+        let first = match first {
+            Some(first) => first,
+            None => {
+                self.digraph = Some(Some(c));
+                return Some(false);
+            }
+        };
+        self.digraph = None;
+        if !self.insert_digraph(first, c) {
+            self.insert_char(c);
         }
+        Some(true)
     }
 
     fn push_history(&mut self, kind: EditKind, before: Pos, after_offset: usize) {
         let (row, col) = self.cursor;
         let after = Pos::new(row, col, after_offset);
         let edit = Edit::new(kind, before, after);
+        self.record_op(edit.op(false));
         self.history.push(edit);
+        self.modified = true;
+        self.mark_idle_change();
+    }
+
+    fn record_op(&mut self, op: EditOp) {
+        self.virtual_pos = None;
+        self.counts.update(&self.lines, &op);
+        if let Some(announcer) = &self.announcer {
+            let (text, pos) = (op.text().to_string(), op.pos());
+            (announcer.0)(&match op {
+                EditOp::Insert { .. } => Announcement::Inserted { text, pos },
+                EditOp::Delete { .. } => Announcement::Deleted { text, pos },
+            });
+        }
+        for cursor in &mut self.remote_cursors {
+            cursor.position = op.shift(cursor.position);
+            cursor.selection_start = cursor.selection_start.map(|pos| op.shift(pos));
+        }
+        if self.tracked.author.is_some() || !self.tracked.changes.is_empty() {
+            self.tracked.apply(&op);
+        }
+        if !self.protection.regions.is_empty() {
+            self.protection.shift(&op);
+        }
+        #[cfg(feature = "search")]
+        self.search.shift_scope(&op);
+        self.gutter.mark_modified(&op);
+        self.gutter.clear_line_changes();
+        self.gutter.shift_blame(&op);
+        self.conflict_highlight.clear();
+        self.folds.shift(&op, self.lines.len());
+        if let Some(journal) = &self.journal {
+            journal.append(&op);
+        }
+        if let Some(ops) = &mut self.edit_ops {
+            ops.push(op);
+        }
+    }
+
+    // Announce the move of the cursor or the change of the selection by an input which didn't modify the text
+    fn announce_move(&self, cursor: (usize, usize), selection_start: Option<(usize, usize)>) {
+        let announcer = match &self.announcer {
+            Some(announcer) => announcer,
+            None => return,
+        };
+        let event = match (selection_start, self.selection_range()) {
+            (_, Some((start, end)))
+                if (selection_start, cursor) != (self.selection_start, self.cursor) =>
+            {
+                let mut text = String::new();
+                for row in start.row..=end.row {
+                    let line = &self.lines[row];
+                    let s = if row == start.row { start.offset } else { 0 };
+                    let e = if row == end.row {
+                        end.offset
+                    } else {
+                        line.len()
+                    };
+                    if row > start.row {
+                        text.push('\n');
+                    }
+                    text.push_str(&line[s..e]);
+                }
+                Announcement::Selected {
+                    chars: text.chars().count(),
+                    words: count_words(&text),
+                }
+            }
+            (Some(_), _) if self.selection_start.is_none() => Announcement::SelectionCancelled,
+            _ if cursor != self.cursor => Announcement::Moved {
+                from: cursor,
+                to: self.cursor,
+            },
+            _ => return,
+        };
+        (announcer.0)(&event);
+    }
+
+    fn mark_idle_change(&mut self) {
+        if let Some(idle) = &mut self.idle_change {
+            idle.changed_at = Some(Instant::now());
+        }
     }
 
     /// Insert a single character at current cursor position.
@@ -734,13 +1451,38 @@ impl<'a> TextArea<'a> {
         }
 
         self.delete_selection(false);
+        if !self.fits_line_limit(&[1]) {
+            return;
+        }
+        if self.virtual_col() > 0 {
+            self.insert_piece(c.to_string());
+            return;
+        }
         let (row, col) = self.cursor;
+        if !self.protection.allows(self.cursor, self.cursor) {
+            return;
+        }
+        #[cfg(feature = "normalization")]
+        if self.normalization && col > 0 {
+            // Compose the character with the previous one like `e` + U+0301 into `é`
+            let line = &self.lines[row];
+            let (start, end) = (byte_offset(line, col - 1), byte_offset(line, col));
+            let composed = line[start..end]
+                .chars()
+                .next()
+                .and_then(|prev| compose(prev, c));
+            if let Some(composed) = composed {
+                let (start, end) = (Pos::new(row, col - 1, start), Pos::new(row, col, end));
+                self.undo_group(|t| {
+                    if t.delete_range(start, end, false) {
+                        t.insert_char(composed);
+                    }
+                });
+                return;
+            }
+        }
         let line = &mut self.lines[row];
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
+        let i = byte_offset(line, col);
         line.insert(i, c);
         self.cursor.1 += 1;
         self.push_history(
@@ -752,6 +1494,9 @@ impl<'a> TextArea<'a> {
 
     /// Insert a string at current cursor position. This method returns if some text was inserted or not in the textarea.
     /// Both `\n` and `\r\n` are recognized as newlines but `\r` isn't.
+    ///
+    /// The string is inserted as a single edit by splicing all lines at once. It is much faster than calling
+    /// [`TextArea::insert_char`] for each character, so use this method to handle bracketed paste of large text.
     /// ```
     /// use tui_textarea::TextArea;
     ///
@@ -765,11 +1510,15 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn insert_str<S: AsRef<str>>(&mut self, s: S) -> bool {
         let modified = self.delete_selection(false);
-        let mut lines: Vec<_> = s
-            .as_ref()
+        let mut lines: Vec<_> = self
+            .normalized(s.as_ref())
             .split('\n')
             .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
             .collect();
+        let lens: Vec<_> = lines.iter().map(|l| l.chars().count()).collect();
+        if !self.fits_line_limit(&lens) || !self.fits_max_lines(lines.len() - 1) {
+            return modified;
+        }
         match lines.len() {
             0 => modified,
             1 => self.insert_piece(lines.remove(0)),
@@ -777,37 +1526,144 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    fn insert_chunk(&mut self, chunk: Vec<String>) -> bool {
-        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
-
-        let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
-        let before = Pos::new(row, col, i);
-
-        let (row, col) = (
-            row + chunk.len() - 1,
-            chunk[chunk.len() - 1].chars().count(),
-        );
-        self.cursor = (row, col);
-
-        let end_offset = chunk.last().unwrap().len();
+    /// Enable or disable Unicode normalization (NFC). When enabled, inserted strings and search patterns are
+    /// normalized into the composed form, and a combining character inserted by [`TextArea::insert_char`] is composed
+    /// with the character before the cursor. So the composed and decomposed forms of the same accented character
+    /// behave consistently. The existing text is not changed. Use [`TextArea::normalize_text`] to normalize it. This
+    /// is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_normalization(true);
+    /// assert!(textarea.normalization());
+    ///
+    /// // `e` followed by the combining acute accent
+    /// textarea.insert_char('e');
+    /// textarea.insert_char('\u{301}');
+    /// assert_eq!(textarea.lines(), ["\u{e9}"]);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    ///
+    /// textarea.insert_str(" cafe\u{301}");
+    /// assert_eq!(textarea.lines(), ["\u{e9} caf\u{e9}"]);
+    /// ```
+    #[cfg(feature = "normalization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+    pub fn set_normalization(&mut self, enabled: bool) {
+        self.normalization = enabled;
+    }
 
-        let edit = EditKind::InsertChunk(chunk);
-        edit.apply(&mut self.lines, &before, &Pos::new(row, col, end_offset));
+    /// Get if Unicode normalization is enabled. See [`TextArea::set_normalization`].
+    #[cfg(feature = "normalization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+    pub fn normalization(&self) -> bool {
+        self.normalization
+    }
 
-        self.push_history(edit, before, end_offset);
+    /// Normalize the whole text into NFC. Each line which is not normalized is replaced as an edit so that it can be
+    /// undone. Lines protected by [`TextArea::protect_lines`] are not changed. This method returns if some line was
+    /// changed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["cafe\u{301}", "ok"]);
+    /// assert!(textarea.normalize_text());
+    /// assert_eq!(textarea.lines(), ["caf\u{e9}", "ok"]);
+    /// assert!(!textarea.normalize_text());
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["cafe\u{301}", "ok"]);
+    /// ```
+    #[cfg(feature = "normalization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "normalization")))]
+    pub fn normalize_text(&mut self) -> bool {
+        let (cursor_row, cursor_col) = self.cursor;
+        let modified = self.undo_group(|t| {
+            let mut modified = false;
+            for row in 0..t.lines.len() {
+                let line = &t.lines[row];
+                if is_nfc(line) {
+                    continue;
+                }
+                let normalized = line.nfc().collect::<String>();
+                let end = Pos::new(row, line.chars().count(), line.len());
+                t.cancel_selection();
+                if t.delete_range(Pos::new(row, 0, 0), end, false) {
+                    t.insert_piece(normalized);
+                    modified = true;
+                }
+            }
+            modified
+        });
+        let len = self.lines[cursor_row].chars().count();
+        self.cursor = (cursor_row, cmp::min(cursor_col, len));
+        modified
+    }
+
+    // Make the edits by the function one undo step. The step is not merged with the preceding edits unless the undo
+    // groups are managed explicitly
+    fn undo_group<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let grouping = self.history.grouping();
+        if grouping != UndoGrouping::Explicit {
+            self.history.commit();
+        }
+        self.history.set_grouping(UndoGrouping::Explicit);
+        let ret = f(self);
+        self.history.set_grouping(grouping);
+        ret
+    }
+
+    // Normalize the string into NFC when the normalization is enabled
+    #[cfg(feature = "normalization")]
+    fn normalized<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        if self.normalization && !is_nfc(s) {
+            Cow::Owned(s.nfc().collect())
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+
+    #[cfg(not(feature = "normalization"))]
+    fn normalized<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        Cow::Borrowed(s)
+    }
+
+    fn insert_chunk(&mut self, mut chunk: Vec<String>) -> bool {
+        debug_assert!(chunk.len() > 1, "Chunk size must be > 1: {:?}", chunk);
+
+        let (row, col) = self.cursor;
+        if !self.protection.allows(self.cursor, self.cursor) {
+            return false;
+        }
+        chunk[0].insert_str(0, &" ".repeat(self.virtual_col()));
+        let line = &mut self.lines[row];
+        let i = byte_offset(line, col);
+        let before = Pos::new(row, col, i);
+
+        let (row, col) = (
+            row + chunk.len() - 1,
+            chunk[chunk.len() - 1].chars().count(),
+        );
+        self.cursor = (row, col);
+
+        let end_offset = chunk.last().unwrap().len();
+
+        let edit = EditKind::InsertChunk(chunk);
+        edit.apply(&mut self.lines, &before, &Pos::new(row, col, end_offset));
+
+        self.push_history(edit, before, end_offset);
         true
     }
 
     fn insert_piece(&mut self, s: String) -> bool {
-        if s.is_empty() {
+        if s.is_empty() || !self.protection.allows(self.cursor, self.cursor) {
             return false;
         }
+        // Fill the virtual space before the cursor with spaces
+        let s = match self.virtual_col() {
+            0 => s,
+            n => " ".repeat(n) + &s,
+        };
 
         let (row, col) = self.cursor;
         let line = &mut self.lines[row];
@@ -817,11 +1673,7 @@ impl<'a> TextArea<'a> {
             line,
         );
 
-        let i = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
+        let i = byte_offset(line, col);
         line.insert_str(i, &s);
         let end_offset = i + s.len();
 
@@ -830,7 +1682,13 @@ impl<'a> TextArea<'a> {
         true
     }
 
-    fn delete_range(&mut self, start: Pos, end: Pos, should_yank: bool) {
+    fn delete_range(&mut self, start: Pos, end: Pos, should_yank: bool) -> bool {
+        if !self
+            .protection
+            .allows((start.row, start.col), (end.row, end.col))
+        {
+            return false;
+        }
         self.cursor = (start.row, start.col);
 
         if start.row == end.row {
@@ -839,10 +1697,10 @@ impl<'a> TextArea<'a> {
                 .as_str()
                 .to_string();
             if should_yank {
-                self.yank = removed.clone().into();
+                self.yank.push(removed.clone().into());
             }
             self.push_history(EditKind::DeleteStr(removed), end, start.offset);
-            return;
+            return true;
         }
 
         let mut deleted = vec![self.lines[start.row]
@@ -858,7 +1716,7 @@ impl<'a> TextArea<'a> {
         }
 
         if should_yank {
-            self.yank = YankText::Chunk(deleted.clone());
+            self.yank.push(YankText::Chunk(deleted.clone()));
         }
 
         let edit = if deleted.len() == 1 {
@@ -867,6 +1725,7 @@ impl<'a> TextArea<'a> {
             EditKind::DeleteChunk(deleted)
         };
         self.push_history(edit, end, start.offset);
+        true
     }
 
     /// Delete a string from the current cursor position. The `chars` parameter means number of characters, not a byte
@@ -916,22 +1775,23 @@ impl<'a> TextArea<'a> {
         };
 
         let line = &self.lines[start_row];
-        let start_offset = {
-            line.char_indices()
-                .nth(start_col)
-                .map(|(i, _)| i)
-                .unwrap_or(line.len())
-        };
+        let start_offset = byte_offset(line, start_col);
 
         // First line
         if let Some((offset_delta, col_delta)) = find_end(&line[start_offset..]) {
             let end_offset = start_offset + offset_delta;
             let end_col = start_col + col_delta;
+            if !self
+                .protection
+                .allows((start_row, start_col), (start_row, end_col))
+            {
+                return false;
+            }
             let removed = self.lines[start_row]
                 .drain(start_offset..end_offset)
                 .as_str()
                 .to_string();
-            self.yank = removed.clone().into();
+            self.yank.push(removed.clone().into());
             self.push_history(
                 EditKind::DeleteStr(removed),
                 Pos::new(start_row, end_col, end_offset),
@@ -956,8 +1816,7 @@ impl<'a> TextArea<'a> {
 
         let start = Pos::new(start_row, start_col, start_offset);
         let end = Pos::new(r, col, offset);
-        self.delete_range(start, end, true);
-        true
+        self.delete_range(start, end, true)
     }
 
     fn delete_piece(&mut self, col: usize, chars: usize) -> bool {
@@ -979,10 +1838,14 @@ impl<'a> TextArea<'a> {
         }
 
         let (row, _) = self.cursor;
-        let line = &mut self.lines[row];
-        if let Some((i, _)) = line.char_indices().nth(col) {
+        let line = &self.lines[row];
+        let i = byte_offset(line, col);
+        if i < line.len() {
             let (bytes, chars) = bytes_and_chars(chars, &line[i..]);
-            let removed = line.drain(i..i + bytes).as_str().to_string();
+            if !self.protection.allows((row, col), (row, col + chars)) {
+                return false;
+            }
+            let removed = self.lines[row].drain(i..i + bytes).as_str().to_string();
 
             self.cursor = (row, col);
             self.push_history(
@@ -990,7 +1853,7 @@ impl<'a> TextArea<'a> {
                 Pos::new(row, col + chars, i + bytes),
                 i,
             );
-            self.yank = removed.into();
+            self.yank.push(removed.into());
             true
         } else {
             false
@@ -1018,18 +1881,186 @@ impl<'a> TextArea<'a> {
         }
 
         if self.hard_tab_indent {
+            if !self.fits_line_limit(&[1]) {
+                return modified;
+            }
             self.insert_char('\t');
             return true;
         }
 
         let (row, col) = self.cursor;
-        let width: usize = self.lines[row]
-            .chars()
-            .take(col)
-            .map(|c| c.width().unwrap_or(0))
-            .sum();
-        let len = self.tab_len - (width % self.tab_len as usize) as u8;
-        self.insert_piece(spaces(len).to_string())
+        let width = self.display_width(row, col) + self.virtual_col();
+        let len = tab_width(width, self.tab_len, &self.tab_stops);
+        if !self.fits_line_limit(&[len]) {
+            return modified;
+        }
+        self.insert_piece(" ".repeat(len))
+    }
+
+    /// Insert the character of the Unicode code point written in hex digits such as `"1f389"` for 🎉. An optional `U+`
+    /// prefix is accepted. This method returns `false` and inserts nothing when the code point is invalid.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(textarea.insert_codepoint("1f389"));
+    /// assert!(textarea.insert_codepoint("U+00E9"));
+    /// assert!(!textarea.insert_codepoint("d800")); // Surrogate
+    /// assert!(!textarea.insert_codepoint("xyz"));
+    /// assert_eq!(textarea.lines(), ["🎉é"]);
+    /// ```
+    pub fn insert_codepoint(&mut self, hex: &str) -> bool {
+        let hex = hex
+            .strip_prefix("U+")
+            .or_else(|| hex.strip_prefix("u+"))
+            .unwrap_or(hex);
+        if hex.is_empty() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return false;
+        }
+        match u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
+            Some(c) => {
+                self.insert_char(c);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Start entering a Unicode code point in hex digits. Hex digits typed via [`TextArea::input`] after this are
+    /// collected, and `Space` or `Enter` inserts the character of the code point. `Backspace` deletes the last digit
+    /// and `Esc` cancels the entry. Other keys cancel the entry and are handled as usual. [`TextArea::input`] starts
+    /// the entry with `Ctrl+Shift+U`.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.start_codepoint_entry();
+    ///
+    /// for c in "1f389".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ctrl: false, alt: false, shift: false });
+    /// }
+    /// assert_eq!(textarea.codepoint_entry(), Some("1f389"));
+    ///
+    /// textarea.input(Input { key: Key::Enter, ctrl: false, alt: false, shift: false });
+    /// assert_eq!(textarea.lines(), ["🎉"]);
+    /// assert_eq!(textarea.codepoint_entry(), None);
+    /// ```
+    pub fn start_codepoint_entry(&mut self) {
+        self.codepoint = Some(String::new());
+    }
+
+    /// Cancel the code point entry started by [`TextArea::start_codepoint_entry`].
+    pub fn cancel_codepoint_entry(&mut self) {
+        self.codepoint = None;
+    }
+
+    /// Get the hex digits typed in the ongoing code point entry. `None` is returned when no entry is ongoing. This is
+    /// useful to show the entry in a status bar.
+    pub fn codepoint_entry(&self) -> Option<&str> {
+        self.codepoint.as_deref()
+    }
+
+    /// Enable entering digraphs with `Ctrl+K` in [`TextArea::input`]. When enabled, `Ctrl+K` followed by two
+    /// characters inserts the special character of the digraph like Vim, instead of deleting the text until the end
+    /// of line. For example, `a:` is `ä`, `e'` is `é`, `->` is `→`, and `a*` is `α`. Digraphs are defined by
+    /// [RFC 1345](https://www.rfc-editor.org/rfc/rfc1345) and can be added with [`TextArea::add_digraph`]. The two
+    /// characters can be typed in reverse order. When they are not a digraph, the second character is inserted.
+    /// `Esc` cancels the entry. Digraph input is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_digraph_input(true);
+    ///
+    /// textarea.input(Input { key: Key::Char('k'), ctrl: true, alt: false, shift: false });
+    /// textarea.input(Input { key: Key::Char('a'), ctrl: false, alt: false, shift: false });
+    /// textarea.input(Input { key: Key::Char(':'), ctrl: false, alt: false, shift: false });
+    /// assert_eq!(textarea.lines(), ["ä"]);
+    /// ```
+    pub fn set_digraph_input(&mut self, enabled: bool) {
+        self.digraph_input = enabled;
+    }
+
+    /// Get if entering digraphs with `Ctrl+K` is enabled or not.
+    pub fn digraph_input(&self) -> bool {
+        self.digraph_input
+    }
+
+    /// Start entering a digraph. The next two characters typed via [`TextArea::input`] are inserted as the character
+    /// of the digraph. This method is useful when you define your own key mapping for digraphs. See
+    /// [`TextArea::set_digraph_input`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.start_digraph();
+    /// assert!(textarea.is_entering_digraph());
+    /// textarea.input(Input { key: Key::Char('-'), ctrl: false, alt: false, shift: false });
+    /// textarea.input(Input { key: Key::Char('>'), ctrl: false, alt: false, shift: false });
+    /// assert_eq!(textarea.lines(), ["→"]);
+    /// assert!(!textarea.is_entering_digraph());
+    /// ```
+    pub fn start_digraph(&mut self) {
+        self.digraph = Some(None);
+    }
+
+    /// Cancel entering the digraph started by [`TextArea::start_digraph`].
+    pub fn cancel_digraph(&mut self) {
+        self.digraph = None;
+    }
+
+    /// Get if a digraph is being entered or not.
+    pub fn is_entering_digraph(&self) -> bool {
+        self.digraph.is_some()
+    }
+
+    /// Insert the character of the digraph of the two characters. This method returns `false` and inserts nothing when
+    /// they are not a digraph.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(textarea.insert_digraph('E', 'u'));
+    /// assert!(!textarea.insert_digraph('q', 'q'));
+    /// assert_eq!(textarea.lines(), ["€"]);
+    /// ```
+    pub fn insert_digraph(&mut self, first: char, second: char) -> bool {
+        match self.digraphs.get(first, second) {
+            Some(c) => {
+                self.insert_char(c);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Register a digraph. Registering the digraph of RFC 1345 overwrites it.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.add_digraph('s', 'm', '☺');
+    /// assert_eq!(textarea.digraph('s', 'm'), Some('☺'));
+    /// ```
+    pub fn add_digraph(&mut self, first: char, second: char, c: char) {
+        self.digraphs.insert(first, second, c);
+    }
+
+    /// Get the character of the digraph. `None` is returned when the two characters are not a digraph.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.digraph('a', ':'), Some('ä'));
+    /// assert_eq!(textarea.digraph(':', 'a'), Some('ä'));
+    /// assert_eq!(textarea.digraph('q', 'q'), None);
+    /// ```
+    pub fn digraph(&self, first: char, second: char) -> Option<char> {
+        self.digraphs.get(first, second)
     }
 
     /// Insert a newline at current cursor position.
@@ -1046,12 +2077,11 @@ impl<'a> TextArea<'a> {
         self.delete_selection(false);
 
         let (row, col) = self.cursor;
+        if !self.fits_max_lines(1) || !self.protection.allows(self.cursor, self.cursor) {
+            return;
+        }
         let line = &mut self.lines[row];
-        let offset = line
-            .char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len());
+        let offset = byte_offset(line, col);
         let next_line = line[offset..].to_string();
         line.truncate(offset);
 
@@ -1060,6 +2090,305 @@ impl<'a> TextArea<'a> {
         self.push_history(EditKind::InsertNewline, Pos::new(row, col, offset), 0);
     }
 
+    /// Enable continuing Markdown lists on `Enter` in [`TextArea::input`]. When enabled, pressing `Enter` on a list
+    /// item such as `- foo`, `* foo` or `1. foo` calls [`TextArea::continue_list`] to start the next item with the same
+    /// marker and indentation. Numbers of ordered lists are incremented. Pressing `Enter` on an empty item terminates
+    /// the list instead. List continuation is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_list_continuation(true);
+    ///
+    /// let enter = Input { key: Key::Enter, ctrl: false, alt: false, shift: false };
+    /// textarea.insert_str("1. foo");
+    /// textarea.input(enter.clone());
+    /// assert_eq!(textarea.lines(), ["1. foo", "2. "]);
+    ///
+    /// textarea.input(enter);
+    /// assert_eq!(textarea.lines(), ["1. foo", ""]);
+    /// ```
+    pub fn set_list_continuation(&mut self, enabled: bool) {
+        self.list_continuation = enabled;
+    }
+
+    /// Get if Markdown lists are continued on `Enter` or not.
+    pub fn list_continuation(&self) -> bool {
+        self.list_continuation
+    }
+
+    /// Insert a newline followed by the marker of the next list item when the cursor is on a Markdown list item. The
+    /// indentation and the marker are copied from the current item, the number of an ordered list is incremented and
+    /// a checked task `- [x]` continues as an unchecked task `- [ ]`. When the current item is empty, the marker is
+    /// removed to terminate the list instead of inserting a newline. This method returns `false` and does nothing when
+    /// the cursor is not after the marker of a list item or when text is selected.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["  - [x] foo"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.continue_list());
+    /// assert_eq!(textarea.lines(), ["  - [x] foo", "  - [ ] "]);
+    /// assert_eq!(textarea.cursor(), (1, 8));
+    ///
+    /// // Terminate the list on the empty item
+    /// assert!(textarea.continue_list());
+    /// assert_eq!(textarea.lines(), ["  - [x] foo", ""]);
+    ///
+    /// // Not a list item
+    /// assert!(!textarea.continue_list());
+    /// ```
+    pub fn continue_list(&mut self) -> bool {
+        if self.selection_start.is_some() {
+            return false;
+        }
+        let (row, col) = self.cursor;
+        let item = match list_item(&self.lines[row]) {
+            Some(item) if col >= item.prefix_len => item,
+            _ => return false,
+        };
+
+        if item.empty {
+            let line = &self.lines[row];
+            let end = Pos::new(row, line.chars().count(), line.len());
+            return self.delete_range(Pos::new(row, 0, 0), end, false);
+        }
+
+        self.undo_group(|t| {
+            t.insert_newline();
+            if t.cursor.0 == row + 1 {
+                t.insert_str(&item.next_prefix);
+            }
+        });
+        self.cursor.0 == row + 1
+    }
+
+    /// Toggle Markdown bold markup `**` around the selected text. When the selected text is already enclosed with
+    /// `**`, the markers are removed. Otherwise the selected text is wrapped with `**` and stays selected. Without
+    /// selection, an empty pair `****` is inserted with the cursor between them, or removed when the cursor is already
+    /// between the pair. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    ///
+    /// assert!(textarea.toggle_bold());
+    /// assert_eq!(textarea.lines(), ["**hello** world"]);
+    ///
+    /// assert!(textarea.toggle_bold());
+    /// assert_eq!(textarea.lines(), ["hello world"]);
+    /// ```
+    pub fn toggle_bold(&mut self) -> bool {
+        self.toggle_markup("**")
+    }
+
+    /// Toggle Markdown italic markup `*` around the selected text. Italic text inside bold text like `***foo***` is
+    /// also recognized. See [`TextArea::toggle_bold`] for the details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["**hello**"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 7));
+    ///
+    /// assert!(textarea.toggle_italic());
+    /// assert_eq!(textarea.lines(), ["***hello***"]);
+    ///
+    /// assert!(textarea.toggle_italic());
+    /// assert_eq!(textarea.lines(), ["**hello**"]);
+    /// ```
+    pub fn toggle_italic(&mut self) -> bool {
+        self.toggle_markup("*")
+    }
+
+    /// Toggle Markdown inline code markup `` ` `` around the selected text. See [`TextArea::toggle_bold`] for the
+    /// details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["run cargo"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.toggle_code());
+    /// assert_eq!(textarea.lines(), ["run `cargo`"]);
+    /// ```
+    pub fn toggle_code(&mut self) -> bool {
+        self.toggle_markup("`")
+    }
+
+    // Toggle the inline markup. The markers are removed when they enclose the selection or are at both ends of the
+    // selected text. The selection is kept on the text inside the markers after toggling
+    fn toggle_markup(&mut self, marker: &'static str) -> bool {
+        let m = marker.len();
+        let c = marker.chars().next().unwrap();
+        let range = self.selection_range();
+        let selecting = range.is_some();
+        let ((sr, sc, so), (er, ec, eo)) = match range {
+            Some((s, e)) => ((s.row, s.col, s.offset), (e.row, e.col, e.offset)),
+            None => {
+                let (row, col) = self.cursor;
+                let p = (row, col, self.line_offset(row, col));
+                (p, p)
+            }
+        };
+        let same_row = sr == er;
+
+        let outer = has_markup(
+            cmp::min(
+                run_before(&self.lines[sr][..so], c),
+                run_after(&self.lines[er][eo..], c),
+            ),
+            m,
+        );
+        let inner = selecting && !outer && {
+            let (head, tail) = if same_row {
+                let s = &self.lines[sr][so..eo];
+                (s, s)
+            } else {
+                (&self.lines[sr][so..], &self.lines[er][..eo])
+            };
+            (!same_row || head.len() >= 2 * m)
+                && has_markup(cmp::min(run_after(head, c), run_before(tail, c)), m)
+        };
+
+        let (start, end) = if outer {
+            ((sr, sc - m), (er, ec + m))
+        } else {
+            ((sr, sc), (er, ec))
+        };
+        if !self.protection.allows(start, end) {
+            return false;
+        }
+        self.cancel_selection();
+
+        let (start, end) = self.undo_group(|t| {
+            if outer || inner {
+                // Remove the closing marker first so that the column of the opening marker does not change
+                let (sc, ec) = if outer { (sc - m, ec) } else { (sc, ec - m) };
+                let (so, eo) = (t.line_offset(sr, sc), t.line_offset(er, ec));
+                t.delete_range(Pos::new(er, ec, eo), Pos::new(er, ec + m, eo + m), false);
+                t.delete_range(Pos::new(sr, sc, so), Pos::new(sr, sc + m, so + m), false);
+                let ec = if same_row { ec - m } else { ec };
+                ((sr, sc), (er, ec))
+            } else {
+                t.cursor = (er, ec);
+                t.insert_piece(marker.to_string());
+                t.cursor = (sr, sc);
+                t.insert_piece(marker.to_string());
+                let ec = if same_row { ec + m } else { ec };
+                ((sr, sc + m), (er, ec))
+            }
+        });
+
+        if selecting {
+            self.selection_start = Some(start);
+        }
+        self.cursor = end;
+        true
+    }
+
+    /// Cycle the Markdown heading level of the current line. A plain line becomes a `#` heading, then each call
+    /// increases the level until `######`, and the next call turns it back to a plain line. The cursor stays on the
+    /// same character of the line. This method returns if the line was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["Title"]);
+    ///
+    /// assert!(textarea.cycle_heading());
+    /// assert_eq!(textarea.lines(), ["# Title"]);
+    /// assert!(textarea.cycle_heading());
+    /// assert_eq!(textarea.lines(), ["## Title"]);
+    ///
+    /// let mut textarea = TextArea::from(["###### Title"]);
+    /// assert!(textarea.cycle_heading());
+    /// assert_eq!(textarea.lines(), ["Title"]);
+    /// ```
+    pub fn cycle_heading(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        let (len, next) = next_heading(&self.lines[row]);
+        if !self.protection.allows((row, 0), (row, len)) {
+            return false;
+        }
+        self.cancel_selection();
+
+        let next_len = next.len();
+        self.undo_group(|t| {
+            if len > 0 {
+                t.delete_range(Pos::new(row, 0, 0), Pos::new(row, len, len), false);
+            }
+            t.cursor = (row, 0);
+            t.insert_piece(next);
+        });
+        self.cursor = (row, cmp::max(col, len) - len + next_len);
+        true
+    }
+
+    /// Realign the Markdown pipe table under the cursor. The table is detected from the header row and the delimiter
+    /// row like `|---|:-:|` around the cursor line. Cells are padded to the widest cell of each column following the
+    /// column alignment, missing cells are filled, and the delimiter row is rebuilt to the column widths. The cursor
+    /// moves to the start of the same cell. Each changed row is replaced as one undo step. This method returns if the
+    /// text was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "|Name|Qty|",
+    ///     "|-|-:|",
+    ///     "|apple|3|",
+    ///     "|banana|12|",
+    /// ]);
+    ///
+    /// assert!(textarea.format_table());
+    /// assert_eq!(
+    ///     textarea.lines(),
+    ///     [
+    ///         "| Name   | Qty |",
+    ///         "| ------ | --: |",
+    ///         "| apple  |   3 |",
+    ///         "| banana |  12 |",
+    ///     ],
+    /// );
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    ///
+    /// // Already aligned
+    /// assert!(!textarea.format_table());
+    /// ```
+    pub fn format_table(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        let (start, end) = match table_range(&self.lines, row) {
+            Some(range) => range,
+            None => return false,
+        };
+        let aligned = align_table(&self.lines[start..end], self.ambiguous_width);
+        let cell = table_cell(&self.lines[row], col);
+
+        let modified = self.undo_group(|t| {
+            let mut modified = false;
+            for (r, line) in (start..end).zip(aligned) {
+                if t.lines[r] == line {
+                    continue;
+                }
+                let old = &t.lines[r];
+                let end = Pos::new(r, old.chars().count(), old.len());
+                t.cancel_selection();
+                if t.delete_range(Pos::new(r, 0, 0), end, false) {
+                    t.insert_piece(line);
+                    modified = true;
+                }
+            }
+            modified
+        });
+        self.cursor = (row, table_cell_col(&self.lines[row], cell));
+        modified
+    }
+
     /// Delete a newline from **head** of current cursor line. This method returns if a newline was deleted or not in
     /// the textarea. When some text is selected, it is deleted instead.
     /// ```
@@ -1080,6 +2409,10 @@ impl<'a> TextArea<'a> {
         if row == 0 {
             return false;
         }
+        let prev_end = (row - 1, self.lines[row - 1].chars().count());
+        if !self.protection.allows(prev_end, (row, 0)) {
+            return false;
+        }
 
         let line = self.lines.remove(row);
         let prev_line = &mut self.lines[row - 1];
@@ -1093,7 +2426,8 @@ impl<'a> TextArea<'a> {
 
     /// Delete one character before cursor. When the cursor is at head of line, the newline before the cursor will be
     /// removed. This method returns if some text was deleted or not in the textarea. When some text is selected, it is
-    /// deleted instead.
+    /// deleted instead. A character followed by combining marks is deleted with the marks as one grapheme cluster so
+    /// that the marks are not left orphaned. To delete only the last mark, use [`TextArea::delete_combining_mark`].
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -1102,19 +2436,51 @@ impl<'a> TextArea<'a> {
     /// textarea.move_cursor(CursorMove::Forward);
     /// textarea.delete_char();
     /// assert_eq!(textarea.lines(), ["bc"]);
+    ///
+    /// // `e` with the combining acute accent U+0301
+    /// let mut textarea = TextArea::from(["cafe\u{301}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["caf"]);
     /// ```
     pub fn delete_char(&mut self) -> bool {
         if self.delete_selection(false) {
             return true;
         }
 
+        // Backspace in virtual space only moves the cursor back
+        let virtual_col = self.virtual_col();
+        if virtual_col > 0 {
+            let (row, col) = self.cursor;
+            self.set_virtual_cursor((row, col + virtual_col - 1));
+            return false;
+        }
+
         let (row, col) = self.cursor;
         if col == 0 {
             return self.delete_newline();
         }
 
+        let (start, end) = grapheme_range(&self.lines[row], col - 1);
+        if end - start > 1 {
+            let line = &self.lines[row];
+            let start = Pos::new(row, start, byte_offset(line, start));
+            let end = Pos::new(row, end, byte_offset(line, end));
+            return self.delete_range(start, end, false);
+        }
+        self.delete_prev_char_in_line()
+    }
+
+    // Delete the character before the cursor. The cursor must not be at the head of line
+    fn delete_prev_char_in_line(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        if !self.protection.allows((row, col - 1), (row, col)) {
+            return false;
+        }
         let line = &mut self.lines[row];
-        if let Some((offset, c)) = line.char_indices().nth(col - 1) {
+        let offset = byte_offset(line, col - 1);
+        if let Some(c) = line[offset..].chars().next() {
             line.remove(offset);
             self.cursor.1 -= 1;
             self.push_history(
@@ -1128,12 +2494,44 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    /// Delete one character next to cursor. When the cursor is at end of line, the newline next to the cursor will be
-    /// removed. This method returns if a character was deleted or not in the textarea.
+    /// Delete the last combining mark of the character before the cursor, such as U+0301 of `e\u{301}` (é). While
+    /// [`TextArea::delete_char`] deletes the character with all its marks, this method peels off one mark at a time.
+    /// This method returns `false` when the character before the cursor has no combining mark.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["abc"]);
+    /// // `e` with the combining acute accent U+0301 and dot below U+0323
+    /// let mut textarea = TextArea::from(["e\u{301}\u{323}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert!(textarea.delete_combining_mark());
+    /// assert_eq!(textarea.lines(), ["e\u{301}"]);
+    /// assert!(textarea.delete_combining_mark());
+    /// assert_eq!(textarea.lines(), ["e"]);
+    /// assert!(!textarea.delete_combining_mark());
+    /// assert_eq!(textarea.lines(), ["e"]);
+    /// ```
+    pub fn delete_combining_mark(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        if col == 0 || self.virtual_col() > 0 {
+            return false;
+        }
+        // The character before the cursor is a mark when it doesn't start the grapheme cluster
+        let (start, _) = grapheme_range(&self.lines[row], col - 1);
+        if start == col - 1 {
+            return false;
+        }
+        self.cancel_selection();
+        self.delete_prev_char_in_line()
+    }
+
+    /// Delete one character next to cursor. When the cursor is at end of line, the newline next to the cursor will be
+    /// removed. This method returns if a character was deleted or not in the textarea. A character followed by
+    /// combining marks is deleted with the marks.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
     ///
     /// textarea.move_cursor(CursorMove::Forward);
     /// textarea.delete_next_char();
@@ -1224,7 +2622,7 @@ impl<'a> TextArea<'a> {
             return true;
         }
         let (r, c) = self.cursor;
-        if let Some(col) = find_word_start_backward(&self.lines[r], c) {
+        if let Some(col) = find_word_start_backward(&self.lines[r], c, &self.words) {
             self.delete_piece(col, c - col)
         } else if c > 0 {
             self.delete_piece(0, c)
@@ -1255,7 +2653,7 @@ impl<'a> TextArea<'a> {
         }
         let (r, c) = self.cursor;
         let line = &self.lines[r];
-        if let Some(col) = find_word_end_forward(line, c) {
+        if let Some(col) = find_word_end_forward(line, c, &self.words) {
             self.delete_piece(c, col - c)
         } else {
             let end_col = line.chars().count();
@@ -1285,988 +2683,7239 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn paste(&mut self) -> bool {
         self.delete_selection(false);
-        match self.yank.clone() {
-            YankText::Piece(s) => self.insert_piece(s),
-            YankText::Chunk(c) => self.insert_chunk(c),
-        }
+        self.paste_yank(0)
     }
 
-    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
-    /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
-    ///
-    /// textarea.start_selection();
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.copy();
-    /// assert_eq!(textarea.yank_text(), "aaa ");
-    /// ```
-    pub fn start_selection(&mut self) {
-        self.selection_start = Some(self.cursor);
+    fn paste_yank(&mut self, index: usize) -> bool {
+        let start = self.cursor;
+        let modified = match self.yank.get(index) {
+            YankText::Piece(s) => {
+                self.fits_line_limit(&[s.chars().count()]) && self.insert_piece(s)
+            }
+            YankText::Chunk(mut c) => {
+                if self.paste_reindent {
+                    let line = &self.lines[start.0];
+                    let base = &line[..line.len() - line.trim_start().len()];
+                    reindent(&mut c, base, self.tab_len);
+                }
+                let lens: Vec<_> = c.iter().map(|l| l.chars().count()).collect();
+                self.fits_line_limit(&lens)
+                    && self.fits_max_lines(c.len() - 1)
+                    && self.insert_chunk(c)
+            }
+        };
+        self.last_paste = Some(LastPaste {
+            start,
+            end: self.cursor,
+            index,
+        });
+        modified
     }
 
-    /// Stop the current text selection. This method does nothing if text selection is not ongoing.
+    /// Enable or disable re-indenting multi-line text on paste. When enabled, the lines after the first line of the
+    /// pasted text are re-indented so that the least indented line has the same indentation as the cursor line. The
+    /// relative indentation between the pasted lines is preserved. This is useful to paste a code block at a different
+    /// nesting level. It is disabled by default.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
-    ///
-    /// textarea.start_selection();
-    /// textarea.move_cursor(CursorMove::WordForward);
-    ///
-    /// // Cancel the ongoing text selection
-    /// textarea.cancel_selection();
+    /// let mut textarea = TextArea::from(["    x = "]);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.set_yank_text("[\n    1,\n]");
     ///
-    /// // As the result, this `copy` call does nothing
-    /// textarea.copy();
-    /// assert_eq!(textarea.yank_text(), "");
+    /// textarea.set_paste_reindent(true);
+    /// assert!(textarea.paste_reindent());
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["    x = [", "        1,", "    ]"]);
     /// ```
-    pub fn cancel_selection(&mut self) {
-        self.selection_start = None;
+    pub fn set_paste_reindent(&mut self, enabled: bool) {
+        self.paste_reindent = enabled;
     }
 
-    /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
-    /// it is canceled.
+    /// Return whether re-indenting on paste is enabled. See [`TextArea::set_paste_reindent`].
+    pub fn paste_reindent(&self) -> bool {
+        self.paste_reindent
+    }
+
+    /// Replace the text pasted just before with the older yanked text, like `yank-pop` in Emacs. Calling this method
+    /// repeatedly cycles through the yanked texts kept in the history. See [`TextArea::yank_history`] for the history.
+    /// This method returns `false` and does nothing when the cursor was moved or the text was modified after the last
+    /// paste.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
     ///
-    /// textarea.select_all();
+    /// // Yank "aaa", " bbb", and " ccc" in order
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word();
     ///
-    /// // Cut the entire text;
-    /// textarea.cut();
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), [" ccc"]);
+    /// textarea.paste_cycle();
+    /// assert_eq!(textarea.lines(), [" bbb"]);
+    /// textarea.paste_cycle();
+    /// assert_eq!(textarea.lines(), ["aaa"]);
+    /// textarea.paste_cycle();
+    /// assert_eq!(textarea.lines(), [" ccc"]);
     ///
-    /// assert_eq!(textarea.lines(), [""]); // Buffer is now empty
-    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\nccc");
+    /// // Cycling is not available after moving the cursor
+    /// textarea.move_cursor(CursorMove::Head);
+    /// assert!(!textarea.paste_cycle());
     /// ```
-    pub fn select_all(&mut self) {
-        self.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
-        self.selection_start = Some((0, 0));
+    pub fn paste_cycle(&mut self) -> bool {
+        let LastPaste { start, end, index } = match self.last_paste {
+            Some(p) => p,
+            None => return false,
+        };
+        let len = self.yank.entries.len();
+        if self.cursor != end
+            || self.selection_start.is_some()
+            || len < 2
+            || self.text_in_range(start, end) != self.yank.get(index).to_string()
+        {
+            self.last_paste = None;
+            return false;
+        }
+        let start_pos = Pos::new(start.0, start.1, self.line_offset(start.0, start.1));
+        let end_pos = Pos::new(end.0, end.1, self.line_offset(end.0, end.1));
+        if !self.delete_range(start_pos, end_pos, false) {
+            return false;
+        }
+        self.paste_yank((index + 1) % len);
+        true
     }
 
-    /// Return if text selection is ongoing or not.
+    /// Paste the text at the index in the yank history returned by [`TextArea::yank_history`]. The index 0 is the
+    /// newest text. This is useful to show a popup of recently yanked texts. This method returns `false` when the
+    /// index is out of the history or nothing was inserted.
     /// ```
-    /// use tui_textarea::{TextArea};
+    /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// assert!(!textarea.is_selecting());
-    /// textarea.start_selection();
-    /// assert!(textarea.is_selecting());
-    /// textarea.cancel_selection();
-    /// assert!(!textarea.is_selecting());
+    /// textarea.set_yank_text("foo");
+    /// textarea.set_yank_text("bar");
+    /// assert_eq!(textarea.yank_history(), ["bar", "foo"]);
+    ///
+    /// assert!(textarea.paste_yank_history(1));
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// assert!(!textarea.paste_yank_history(2));
     /// ```
-    pub fn is_selecting(&self) -> bool {
-        self.selection_start.is_some()
+    pub fn paste_yank_history(&mut self, index: usize) -> bool {
+        if index >= self.yank.entries.len() {
+            return false;
+        }
+        self.delete_selection(false);
+        self.paste_yank(index)
     }
 
-    fn line_offset(&self, row: usize, col: usize) -> usize {
-        let line = self
-            .lines
-            .get(row)
-            .unwrap_or(&self.lines[self.lines.len() - 1]);
-        line.char_indices()
-            .nth(col)
-            .map(|(i, _)| i)
-            .unwrap_or(line.len())
+    /// Get the recently yanked texts. The newest text comes first and it is pasted by [`TextArea::paste`].
+    pub fn yank_history(&self) -> Vec<String> {
+        self.yank
+            .entries
+            .iter()
+            .rev()
+            .map(|t| t.to_string())
+            .collect()
     }
 
-    /// Set the style used for text selection. The default style is light blue.
+    /// Set the max number of yanked texts kept in the yank history. The oldest texts are removed when the number
+    /// exceeds it. The default value is 20.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::style::{Style, Color};
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// // Change the selection color from the default to Red
-    /// textarea.set_selection_style(Style::default().bg(Color::Red));
-    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::Red));
+    /// textarea.set_max_yank_history(2);
+    /// for text in ["a", "b", "c"] {
+    ///     textarea.set_yank_text(text);
+    /// }
+    /// assert_eq!(textarea.yank_history(), ["c", "b"]);
+    /// assert_eq!(textarea.max_yank_history(), 2);
     /// ```
-    pub fn set_selection_style(&mut self, style: Style) {
-        self.select_style = style;
+    pub fn set_max_yank_history(&mut self, max: usize) {
+        self.yank.max = max;
+        self.yank.truncate();
     }
 
-    /// Get the style used for text selection.
+    /// Get the max number of yanked texts kept in the yank history set by [`TextArea::set_max_yank_history`].
+    pub fn max_yank_history(&self) -> usize {
+        self.yank.max
+    }
+
+    // Get the text between the positions. Lines are joined with newlines
+    fn text_in_range(&self, start: (usize, usize), end: (usize, usize)) -> String {
+        let (so, eo) = (
+            self.line_offset(start.0, start.1),
+            self.line_offset(end.0, end.1),
+        );
+        if start.0 == end.0 {
+            return self.lines[start.0][so..eo].to_string();
+        }
+        let mut text = self.lines[start.0][so..].to_string();
+        for line in &self.lines[start.0 + 1..end.0] {
+            text.push('\n');
+            text.push_str(line);
+        }
+        text.push('\n');
+        text.push_str(&self.lines[end.0][..eo]);
+        text
+    }
+
+    /// Paste the text in the primary selection on Linux (X11 and Wayland) at the cursor. When text is selected, it is
+    /// replaced with the pasted text. This method returns whether some text was inserted. It does nothing on platforms
+    /// which have no primary selection or when the primary selection is disabled by
+    /// [`TextArea::set_primary_selection`].
+    #[cfg(feature = "clipboard")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    pub fn paste_primary_selection(&mut self) -> bool {
+        match self.primary_selection.get() {
+            Some(text) if !text.is_empty() => self.insert_str(text),
+            _ => false,
+        }
+    }
+
+    /// Enable or disable the primary selection on Linux (X11 and Wayland). When enabled, selected text is copied to
+    /// the primary selection automatically on key inputs and clicks, and it can be pasted with
+    /// [`TextArea::paste_primary_selection`] or [`TextArea::middle_click`]. It is disabled by default.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::style::{Style, Color};
     ///
     /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.primary_selection());
     ///
-    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::LightBlue));
+    /// textarea.set_primary_selection(true);
+    /// assert!(textarea.primary_selection());
     /// ```
-    pub fn selection_style(&mut self) -> Style {
-        self.select_style
+    #[cfg(feature = "clipboard")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    pub fn set_primary_selection(&mut self, enabled: bool) {
+        self.primary_selection.enabled = enabled;
     }
 
-    fn selection_range(&self) -> Option<(Pos, Pos)> {
-        let (sr, sc) = self.selection_start?;
-        let (er, ec) = self.cursor;
-        let (so, eo) = (self.line_offset(sr, sc), self.line_offset(er, ec));
-        let s = Pos::new(sr, sc, so);
-        let e = Pos::new(er, ec, eo);
-        match (sr, so).cmp(&(er, eo)) {
-            Ordering::Less => Some((s, e)),
-            Ordering::Equal => None,
-            Ordering::Greater => Some((e, s)),
-        }
+    /// Return whether the primary selection is enabled. See [`TextArea::set_primary_selection`].
+    #[cfg(feature = "clipboard")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    pub fn primary_selection(&self) -> bool {
+        self.primary_selection.enabled
     }
 
-    fn take_selection_range(&mut self) -> Option<(Pos, Pos)> {
-        let range = self.selection_range();
-        self.cancel_selection();
-        range
+    // Copy the selected text to the primary selection
+    #[cfg(feature = "clipboard")]
+    fn sync_primary_selection(&mut self) {
+        if !self.primary_selection.enabled {
+            return;
+        }
+        let range = self
+            .selection_range()
+            .map(|(s, e)| ((s.row, s.col), (e.row, e.col)));
+        if !self.primary_selection.update_range(range) {
+            return;
+        }
+        if let Some((start, end)) = range {
+            let text = self.text_in_range(start, end);
+            self.primary_selection.set(text);
+        }
     }
 
-    /// Copy the selection text to the yank buffer. When nothing is selected, this method does nothing.
-    /// To get the yanked text, use [`TextArea::yank_text`].
-    /// ```
-    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    /// Register an abbreviation. When a word equal to `abbr` is followed by a word-terminating character such as a
+    /// space, a punctuation, a tab, or a newline typed via [`TextArea::input`], the word is replaced with `expansion`.
+    /// The expansion can contain newlines. Registering the same abbreviation again overwrites the previous expansion.
     ///
-    /// let mut textarea = TextArea::from(["Hello World"]);
+    /// To type a terminating character without expanding the abbreviation, press `Ctrl+Q` before the character.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
     ///
-    /// // Start text selection at 'W'
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.start_selection();
+    /// let mut textarea = TextArea::default();
+    /// textarea.add_abbreviation("teh", "the");
     ///
-    /// // Select the word "World" and copy the selected text
-    /// textarea.move_cursor(CursorMove::End);
-    /// textarea.copy();
+    /// for c in "teh cat".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ctrl: false, alt: false, shift: false });
+    /// }
+    /// assert_eq!(textarea.lines(), ["the cat"]);
     ///
-    /// assert_eq!(textarea.yank_text(), "World");
-    /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
+    /// // Ctrl+Q suppresses the expansion of the next character
+    /// textarea.insert_str(" teh");
+    /// textarea.input(Input { key: Key::Char('q'), ctrl: true, alt: false, shift: false });
+    /// textarea.input(Input { key: Key::Char('.'), ctrl: false, alt: false, shift: false });
+    /// assert_eq!(textarea.lines(), ["the cat teh."]);
     /// ```
-    pub fn copy(&mut self) {
-        if let Some((start, end)) = self.take_selection_range() {
-            if start.row == end.row {
-                self.yank = self.lines[start.row][start.offset..end.offset]
-                    .to_string()
-                    .into();
-                return;
-            }
-            let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
-            chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
-            chunk.push(self.lines[end.row][..end.offset].to_string());
-            self.yank = YankText::Chunk(chunk);
-        }
+    pub fn add_abbreviation(&mut self, abbr: impl Into<String>, expansion: impl Into<String>) {
+        self.abbreviations.insert(abbr.into(), expansion.into());
     }
 
-    /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
-    /// The cursor will move to the start position of the text selection.
-    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// Unregister the abbreviation previously registered by [`TextArea::add_abbreviation`]. This method returns if the
+    /// abbreviation was registered or not.
     /// ```
-    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["Hello World"]);
+    /// let mut textarea = TextArea::default();
+    /// textarea.add_abbreviation("teh", "the");
     ///
-    /// // Start text selection at 'W'
-    /// textarea.move_cursor(CursorMove::WordForward);
-    /// textarea.start_selection();
+    /// assert!(textarea.remove_abbreviation("teh"));
+    /// assert!(!textarea.remove_abbreviation("teh"));
+    /// assert_eq!(textarea.abbreviation("teh"), None);
+    /// ```
+    pub fn remove_abbreviation(&mut self, abbr: &str) -> bool {
+        self.abbreviations.remove(abbr)
+    }
+
+    /// Unregister all abbreviations.
+    /// ```
+    /// use tui_textarea::TextArea;
     ///
-    /// // Select the word "World" and copy the selected text
-    /// textarea.move_cursor(CursorMove::End);
-    /// textarea.cut();
+    /// let mut textarea = TextArea::default();
+    /// textarea.add_abbreviation("teh", "the");
+    /// textarea.add_abbreviation("adn", "and");
     ///
-    /// assert_eq!(textarea.yank_text(), "World");
-    /// assert_eq!(textarea.lines(), ["Hello "]);
+    /// textarea.clear_abbreviations();
+    /// assert_eq!(textarea.abbreviation("teh"), None);
+    /// assert_eq!(textarea.abbreviation("adn"), None);
     /// ```
-    pub fn cut(&mut self) -> bool {
-        self.delete_selection(true)
+    pub fn clear_abbreviations(&mut self) {
+        self.abbreviations.clear();
     }
 
-    fn delete_selection(&mut self, should_yank: bool) -> bool {
-        if let Some((s, e)) = self.take_selection_range() {
-            self.delete_range(s, e, should_yank);
-            return true;
-        }
-        false
+    /// Get the expansion of the abbreviation. `None` is returned when the abbreviation is not registered.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.abbreviation("teh"), None);
+    ///
+    /// textarea.add_abbreviation("teh", "the");
+    /// assert_eq!(textarea.abbreviation("teh"), Some("the"));
+    /// ```
+    pub fn abbreviation(&self, abbr: &str) -> Option<&str> {
+        self.abbreviations.get(abbr)
     }
 
-    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
-    /// the document of [`CursorMove`].
+    /// Expand the abbreviation just before the cursor. This method is useful when you define your own key mappings.
+    /// It returns if some abbreviation was expanded or not. Nothing is expanded while text selection is ongoing.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// let mut textarea = TextArea::from(["fn sig"]);
+    /// textarea.add_abbreviation("sig", "main() -> io::Result<()>");
+    /// textarea.move_cursor(CursorMove::End);
     ///
-    /// textarea.move_cursor(CursorMove::Forward);
-    /// assert_eq!(textarea.cursor(), (0, 1));
-    /// textarea.move_cursor(CursorMove::Down);
-    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// assert!(textarea.expand_abbreviation());
+    /// assert_eq!(textarea.lines(), ["fn main() -> io::Result<()>"]);
+    ///
+    /// // Nothing to expand
+    /// assert!(!textarea.expand_abbreviation());
     /// ```
-    pub fn move_cursor(&mut self, m: CursorMove) {
-        self.move_cursor_with_shift(m, self.selection_start.is_some());
-    }
+    pub fn expand_abbreviation(&mut self) -> bool {
+        if self.selection_start.is_some() {
+            return false;
+        }
 
-    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
-        if let Some(cursor) = m.next_cursor(self.cursor, &self.lines, &self.viewport) {
-            if shift {
-                if self.selection_start.is_none() {
-                    self.start_selection();
-                }
-            } else {
-                self.cancel_selection();
+        let (row, col) = self.cursor;
+        let (start_col, expansion) = match self.abbreviations.find(&self.lines[row], col) {
+            Some((c, e)) => (c, e.to_string()),
+            None => return false,
+        };
+
+        let start = Pos::new(row, start_col, self.line_offset(row, start_col));
+        let end = Pos::new(row, col, self.line_offset(row, col));
+        self.undo_group(|t| {
+            if !t.delete_range(start, end, false) {
+                return false;
             }
-            self.cursor = cursor;
-        }
+            t.insert_str(expansion);
+            true
+        })
     }
 
-    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// Enable expanding emoji shortcodes like `:tada:` into emojis like 🎉. When enabled, a shortcode followed by a
+    /// word-terminating character such as a space, a punctuation, a tab, or a newline typed via [`TextArea::input`] is
+    /// replaced with its emoji. Common shortcodes such as `:+1:`, `:smile:` and `:tada:` are registered by default and
+    /// the table can be customized with [`TextArea::add_emoji_shortcode`]. This is disabled by default.
+    ///
+    /// Like abbreviations, press `Ctrl+Q` before the terminating character to type it without expanding the shortcode.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::{TextArea, Input, Key};
     ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_emoji_expansion(true);
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
-    /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// for c in "done :tada: ".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ctrl: false, alt: false, shift: false });
+    /// }
+    /// assert_eq!(textarea.lines(), ["done 🎉 "]);
     /// ```
-    pub fn undo(&mut self) -> bool {
-        if let Some(cursor) = self.history.undo(&mut self.lines) {
-            self.cancel_selection();
-            self.cursor = cursor;
-            true
-        } else {
-            false
-        }
+    pub fn set_emoji_expansion(&mut self, enabled: bool) {
+        self.emoji_expansion = enabled;
     }
 
-    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// Get if emoji shortcodes are expanded on typing or not.
+    pub fn emoji_expansion(&self) -> bool {
+        self.emoji_expansion
+    }
+
+    /// Register an emoji shortcode. `name` is the shortcode without colons like `"tada"`. Registering a built-in
+    /// shortcode or the same shortcode again overwrites the previous emoji. The expansion is not limited to emojis.
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
-    ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// use tui_textarea::TextArea;
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
-    /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
-    /// textarea.redo();
-    /// assert_eq!(textarea.lines(), [" def"]);
+    /// let mut textarea = TextArea::default();
+    /// textarea.add_emoji_shortcode("ferris", "🦀");
+    /// assert_eq!(textarea.emoji_shortcode("ferris"), Some("🦀"));
     /// ```
-    pub fn redo(&mut self) -> bool {
-        if let Some(cursor) = self.history.redo(&mut self.lines) {
-            self.cancel_selection();
-            self.cursor = cursor;
+    pub fn add_emoji_shortcode(&mut self, name: impl Into<String>, emoji: impl Into<String>) {
+        self.emoji_shortcodes.insert(name.into(), emoji.into());
+    }
+
+    /// Unregister the emoji shortcode including the built-in one. This method returns if the shortcode was registered
+    /// or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(textarea.remove_emoji_shortcode("tada"));
+    /// assert!(!textarea.remove_emoji_shortcode("tada"));
+    /// assert_eq!(textarea.emoji_shortcode("tada"), None);
+    /// ```
+    pub fn remove_emoji_shortcode(&mut self, name: &str) -> bool {
+        self.emoji_shortcodes.remove(name)
+    }
+
+    /// Unregister all emoji shortcodes including the built-in ones. This is useful to replace the table entirely with
+    /// your own shortcodes.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.clear_emoji_shortcodes();
+    /// assert_eq!(textarea.emoji_shortcode("tada"), None);
+    ///
+    /// textarea.add_emoji_shortcode("party", "🥳");
+    /// assert_eq!(textarea.emoji_shortcode("party"), Some("🥳"));
+    /// ```
+    pub fn clear_emoji_shortcodes(&mut self) {
+        self.emoji_shortcodes.clear();
+    }
+
+    /// Get the emoji of the shortcode. `None` is returned when the shortcode is not registered.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.emoji_shortcode("tada"), Some("🎉"));
+    /// assert_eq!(textarea.emoji_shortcode("unknown"), None);
+    /// ```
+    pub fn emoji_shortcode(&self, name: &str) -> Option<&str> {
+        self.emoji_shortcodes.get(name)
+    }
+
+    /// Expand the emoji shortcode just before the cursor. This method is useful when you define your own key mappings.
+    /// It works even if [`TextArea::set_emoji_expansion`] is disabled. It returns if some shortcode was expanded or
+    /// not. Nothing is expanded while text selection is ongoing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["ship it :rocket:"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.expand_emoji_shortcode());
+    /// assert_eq!(textarea.lines(), ["ship it 🚀"]);
+    ///
+    /// // Nothing to expand
+    /// assert!(!textarea.expand_emoji_shortcode());
+    ///
+    /// // The expansion is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["ship it :rocket:"]);
+    /// ```
+    pub fn expand_emoji_shortcode(&mut self) -> bool {
+        if self.selection_start.is_some() {
+            return false;
+        }
+
+        let (row, col) = self.cursor;
+        let (start_col, emoji) = match self.emoji_shortcodes.find(&self.lines[row], col) {
+            Some((c, e)) => (c, e.to_string()),
+            None => return false,
+        };
+
+        let start = Pos::new(row, start_col, self.line_offset(row, start_col));
+        let end = Pos::new(row, col, self.line_offset(row, col));
+        self.undo_group(|t| {
+            if !t.delete_range(start, end, false) {
+                return false;
+            }
+            t.insert_str(emoji);
             true
-        } else {
-            false
+        })
+    }
+
+    /// Set the resolver of template tokens like `{{date}}`, `{{time}}` or `{{n}}`. The resolver receives the name of
+    /// the token without braces and surrounding spaces, and returns the text to replace the token with, or `None` to
+    /// keep the token as it is. When a resolver is set, typing the closing `}}` of a token via [`TextArea::input`]
+    /// replaces the token with the resolved text, and [`TextArea::insert_template`] replaces all tokens in the
+    /// inserted text. The resolver is called on each expansion so it can return the current time or a counter.
+    ///
+    /// Like abbreviations, press `Ctrl+Q` before the last `}` to type the token without expanding it.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let counter = AtomicUsize::new(0);
+    /// textarea.set_template_resolver(move |name| match name {
+    ///     "date" => Some("2024-01-02".to_string()),
+    ///     "n" => Some((counter.fetch_add(1, Ordering::Relaxed) + 1).to_string()),
+    ///     _ => None,
+    /// });
+    ///
+    /// for c in "# {{date}}".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ctrl: false, alt: false, shift: false });
+    /// }
+    /// assert_eq!(textarea.lines(), ["# 2024-01-02"]);
+    ///
+    /// textarea.insert_template("\n{{n}}. {{unknown}}\n{{n}}. ");
+    /// assert_eq!(textarea.lines(), ["# 2024-01-02", "1. {{unknown}}", "2. "]);
+    /// ```
+    pub fn set_template_resolver(
+        &mut self,
+        resolver: impl Fn(&str) -> Option<String> + Send + Sync + 'a,
+    ) {
+        self.template_resolver = Some(TemplateResolver(Arc::new(resolver)));
+    }
+
+    /// Remove the resolver set by [`TextArea::set_template_resolver`]. Template tokens are no longer expanded.
+    pub fn remove_template_resolver(&mut self) {
+        self.template_resolver = None;
+    }
+
+    /// Expand the template token like `{{date}}` just before the cursor with the resolver set by
+    /// [`TextArea::set_template_resolver`]. This method is useful when you define your own key mappings. It returns
+    /// if some token was expanded or not. Nothing is expanded while text selection is ongoing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["today is {{date}}"]);
+    /// textarea.set_template_resolver(|name| (name == "date").then(|| "Monday".to_string()));
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.expand_template());
+    /// assert_eq!(textarea.lines(), ["today is Monday"]);
+    ///
+    /// // Nothing to expand
+    /// assert!(!textarea.expand_template());
+    /// ```
+    pub fn expand_template(&mut self) -> bool {
+        if self.selection_start.is_some() {
+            return false;
         }
+        let resolver = match &self.template_resolver {
+            Some(resolver) => resolver,
+            None => return false,
+        };
+
+        let (row, col) = self.cursor;
+        let (start_col, text) = match resolver.find(&self.lines[row], col) {
+            Some(found) => found,
+            None => return false,
+        };
+
+        let start = Pos::new(row, start_col, self.line_offset(row, start_col));
+        let end = Pos::new(row, col, self.line_offset(row, col));
+        self.undo_group(|t| {
+            if !t.delete_range(start, end, false) {
+                return false;
+            }
+            t.insert_str(text);
+            true
+        })
     }
 
-    pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize, lnum_len: u8) -> Line<'b> {
-        let mut hl = LineHighlighter::new(
-            line,
-            self.cursor_style,
-            self.tab_len,
-            self.mask,
-            self.select_style,
-        );
+    /// Insert a string at current cursor position replacing template tokens like `{{date}}` in it with the resolver
+    /// set by [`TextArea::set_template_resolver`]. Tokens which are not resolved are inserted as they are. This method
+    /// is useful to insert snippets or note templates. It returns if some text was inserted or not like
+    /// [`TextArea::insert_str`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_template_resolver(|name| (name == "time").then(|| "12:34".to_string()));
+    ///
+    /// textarea.insert_template("## {{ time }}\n");
+    /// assert_eq!(textarea.lines(), ["## 12:34", ""]);
+    /// ```
+    pub fn insert_template<S: AsRef<str>>(&mut self, s: S) -> bool {
+        let s = s.as_ref();
+        let expanded = match &self.template_resolver {
+            Some(resolver) => resolver.expand(s).into_owned(),
+            None => s.to_string(),
+        };
+        self.insert_str(expanded)
+    }
 
-        if let Some(style) = self.line_number_style {
-            hl.line_number(row, lnum_len, style);
+    // Expand the abbreviation or the emoji shortcode before the word-terminating character typed via input methods
+    fn expand_at_terminator(&mut self) -> bool {
+        if self.emoji_expansion && self.expand_emoji_shortcode() {
+            return true;
         }
+        self.expand_abbreviation()
+    }
 
-        if row == self.cursor.0 {
-            hl.cursor_line(self.cursor.1, self.cursor_line_style);
+    /// Enable or disable completing words with `Ctrl+N` and `Ctrl+P` in [`TextArea::input`]. When enabled, `Ctrl+N`
+    /// and `Ctrl+P` call [`TextArea::complete_word_forward`] and [`TextArea::complete_word_back`] instead of moving the
+    /// cursor. Arrow keys still move the cursor. Word completion is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "he"]);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.set_word_completion(true);
+    /// textarea.input(Input { key: Key::Char('n'), ctrl: true, alt: false, shift: false });
+    /// assert_eq!(textarea.lines(), ["hello", "hello"]);
+    /// ```
+    pub fn set_word_completion(&mut self, enabled: bool) {
+        self.word_completion = enabled;
+    }
+
+    /// Get if completing words with `Ctrl+N` and `Ctrl+P` is enabled or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.word_completion());
+    /// textarea.set_word_completion(true);
+    /// assert!(textarea.word_completion());
+    /// ```
+    pub fn word_completion(&self) -> bool {
+        self.word_completion
+    }
+
+    /// Complete the word before the cursor with words in the buffer. Candidates are words which start with the
+    /// characters before the cursor. They are ordered from the nearest word after the cursor, wrapping around the
+    /// buffer. Calling this method repeatedly cycles through the candidates and finally goes back to the original text.
+    /// Moving the cursor or modifying the text starts a new completion. This method returns if the text was modified
+    /// or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo food fo"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.complete_word_forward());
+    /// assert_eq!(textarea.lines(), ["foo food foo"]);
+    /// assert!(textarea.complete_word_forward());
+    /// assert_eq!(textarea.lines(), ["foo food food"]);
+    ///
+    /// // Back to the original text
+    /// assert!(textarea.complete_word_forward());
+    /// assert_eq!(textarea.lines(), ["foo food fo"]);
+    ///
+    /// // No candidate
+    /// textarea.insert_str(" bar");
+    /// assert!(!textarea.complete_word_forward());
+    /// ```
+    pub fn complete_word_forward(&mut self) -> bool {
+        self.complete_word(true)
+    }
+
+    /// Complete the word before the cursor with words in the buffer cycling through the candidates backward. See
+    /// [`TextArea::complete_word_forward`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo food fo"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.complete_word_back());
+    /// assert_eq!(textarea.lines(), ["foo food food"]);
+    /// assert!(textarea.complete_word_back());
+    /// assert_eq!(textarea.lines(), ["foo food foo"]);
+    /// ```
+    pub fn complete_word_back(&mut self) -> bool {
+        self.complete_word(false)
+    }
+
+    fn complete_word(&mut self, forward: bool) -> bool {
+        if self.selection_start.is_some() {
+            return false;
         }
 
-        #[cfg(feature = "search")]
-        if let Some(matches) = self.search.matches(line) {
-            hl.search(matches, self.search.style);
+        let ongoing =
+            matches!(&self.completion, Some(c) if c.is_active_at(&self.lines, self.cursor));
+        if !ongoing {
+            self.completion = WordCompletion::start(&self.lines, self.cursor);
         }
+        let completion = match &mut self.completion {
+            Some(c) => c,
+            None => return false,
+        };
 
-        if let Some((start, end)) = self.selection_range() {
-            hl.selection(row, start.row, start.offset, end.row, end.offset);
+        let (row, start_col) = (completion.row, completion.start_col);
+        let end_col = start_col + completion.current().chars().count();
+        if forward {
+            completion.next();
+        } else {
+            completion.prev();
         }
+        let word = completion.current().to_string();
 
-        hl.into_spans()
+        let start = Pos::new(row, start_col, self.line_offset(row, start_col));
+        let end = Pos::new(row, end_col, self.line_offset(row, end_col));
+        self.undo_group(|t| {
+            if !t.delete_range(start, end, false) {
+                return false;
+            }
+            t.insert_piece(word);
+            true
+        })
     }
 
-    /// Build a ratatui (or tui-rs) widget to render the current state of the textarea. The widget instance returned
-    /// from this method can be rendered with [`ratatui::terminal::Frame::render_widget`].
-    /// ```no_run
-    /// use ratatui::backend::CrosstermBackend;
-    /// use ratatui::layout::{Constraint, Direction, Layout};
-    /// use ratatui::Terminal;
-    /// use tui_textarea::TextArea;
+    /// Open a completion popup menu with the given candidates. The popup is rendered just below the cursor by the widget
+    /// built with [`TextArea::widget`]. While the popup is open, [`TextArea::input`] handles the following keys:
     ///
-    /// let mut textarea = TextArea::default();
+    /// - `↓`, `Ctrl+N`: Select the next candidate
+    /// - `↑`, `Ctrl+P`: Select the previous candidate
+    /// - `Enter`, `Tab`: Accept the selected candidate (See [`TextArea::accept_completion`])
+    /// - `Esc`: Dismiss the popup
     ///
-    /// let layout = Layout::default()
-    ///     .direction(Direction::Vertical)
-    ///     .constraints([Constraint::Min(1)].as_ref());
-    /// let backend = CrosstermBackend::new(std::io::stdout());
-    /// let mut term = Terminal::new(backend).unwrap();
+    /// Any other key dismisses the popup and is handled as usual. When the candidates are empty, the popup is closed.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key, CursorMove};
     ///
-    /// loop {
-    ///     term.draw(|f| {
-    ///         let chunks = layout.split(f.size());
-    ///         let widget = textarea.widget();
-    ///         f.render_widget(widget, chunks[0]);
-    ///     }).unwrap();
+    /// let mut textarea = TextArea::from(["let x = std::co"]);
+    /// textarea.move_cursor(CursorMove::End);
     ///
-    ///     // ...
-    /// }
+    /// textarea.open_completion_popup(["cmp", "collections", "convert"]);
+    /// assert!(textarea.is_completion_popup_open());
+    ///
+    /// textarea.input(Input { key: Key::Down, ctrl: false, alt: false, shift: false });
+    /// assert_eq!(textarea.selected_completion(), Some("collections"));
+    ///
+    /// textarea.input(Input { key: Key::Enter, ctrl: false, alt: false, shift: false });
+    /// assert_eq!(textarea.lines(), ["let x = std::collections"]);
+    /// assert!(!textarea.is_completion_popup_open());
     /// ```
-    pub fn widget(&'a self) -> impl Widget + 'a {
-        Renderer::new(self)
+    pub fn open_completion_popup<I>(&mut self, candidates: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.completion_popup
+            .open(candidates.into_iter().map(Into::into).collect());
     }
 
-    /// Set the style of textarea. By default, textarea is not styled.
+    /// Dismiss the completion popup opened by [`TextArea::open_completion_popup`]. This method does nothing when no
+    /// popup is open.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_style(style);
-    /// assert_eq!(textarea.style(), style);
+    ///
+    /// textarea.open_completion_popup(["foo", "bar"]);
+    /// textarea.close_completion_popup();
+    /// assert!(!textarea.is_completion_popup_open());
     /// ```
-    pub fn set_style(&mut self, style: Style) {
-        self.style = style;
+    pub fn close_completion_popup(&mut self) {
+        self.completion_popup.close();
     }
 
-    /// Get the current style of textarea.
-    pub fn style(&self) -> Style {
-        self.style
+    /// Return if the completion popup is open or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.is_completion_popup_open());
+    ///
+    /// textarea.open_completion_popup(["foo"]);
+    /// assert!(textarea.is_completion_popup_open());
+    ///
+    /// // Empty candidates close the popup
+    /// textarea.open_completion_popup(Vec::<String>::new());
+    /// assert!(!textarea.is_completion_popup_open());
+    /// ```
+    pub fn is_completion_popup_open(&self) -> bool {
+        self.completion_popup.is_open()
     }
 
-    /// Set the block of textarea. By default, no block is set.
+    /// Get the candidates in the completion popup. An empty slice is returned when the popup is not open.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
     /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// assert!(textarea.block().is_some());
+    /// assert!(textarea.completion_candidates().is_empty());
+    ///
+    /// textarea.open_completion_popup(["foo", "bar"]);
+    /// assert_eq!(textarea.completion_candidates(), ["foo", "bar"]);
     /// ```
-    pub fn set_block(&mut self, block: Block<'a>) {
-        self.block = Some(block);
+    pub fn completion_candidates(&self) -> &[String] {
+        &self.completion_popup.items
     }
 
-    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// Get the currently selected candidate in the completion popup. The first candidate is selected when the popup is
+    /// opened. `None` is returned when the popup is not open.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
     /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// textarea.remove_block();
-    /// assert!(textarea.block().is_none());
+    /// assert_eq!(textarea.selected_completion(), None);
+    ///
+    /// textarea.open_completion_popup(["foo", "bar"]);
+    /// assert_eq!(textarea.selected_completion(), Some("foo"));
     /// ```
-    pub fn remove_block(&mut self) {
-        self.block = None;
+    pub fn selected_completion(&self) -> Option<&str> {
+        self.completion_popup.selected_item()
     }
 
-    /// Get the block of textarea if exists.
-    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
-        self.block.as_ref()
+    /// Select the next candidate in the completion popup. The selection wraps around the candidates.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.open_completion_popup(["foo", "bar"]);
+    /// textarea.select_next_completion();
+    /// assert_eq!(textarea.selected_completion(), Some("bar"));
+    /// textarea.select_next_completion();
+    /// assert_eq!(textarea.selected_completion(), Some("foo"));
+    /// ```
+    pub fn select_next_completion(&mut self) {
+        self.completion_popup.select_next();
     }
 
-    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// Select the previous candidate in the completion popup. The selection wraps around the candidates.
     /// ```
-    /// use tui_textarea::{TextArea, Input, Key};
+    /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
-    ///
-    /// textarea.set_tab_length(8);
-    /// textarea.input(tab_input.clone());
-    /// assert_eq!(textarea.lines(), ["        "]);
     ///
-    /// textarea.set_tab_length(2);
-    /// textarea.input(tab_input);
-    /// assert_eq!(textarea.lines(), ["          "]);
+    /// textarea.open_completion_popup(["foo", "bar"]);
+    /// textarea.select_prev_completion();
+    /// assert_eq!(textarea.selected_completion(), Some("bar"));
     /// ```
-    pub fn set_tab_length(&mut self, len: u8) {
-        self.tab_len = len;
+    pub fn select_prev_completion(&mut self) {
+        self.completion_popup.select_prev();
     }
 
-    /// Get how many spaces are used for representing tab character. The default value is 4.
-    pub fn tab_length(&self) -> u8 {
-        self.tab_len
+    /// Accept the selected candidate in the completion popup and close the popup. The word before the cursor is
+    /// replaced with the candidate. When text selection is ongoing, the selected text is replaced instead. This method
+    /// returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["pri"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.open_completion_popup(["println!", "print!"]);
+    /// assert!(textarea.accept_completion());
+    /// assert_eq!(textarea.lines(), ["println!"]);
+    ///
+    /// // Nothing happens when the popup is not open
+    /// assert!(!textarea.accept_completion());
+    ///
+    /// // The completion is undone at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["pri"]);
+    /// ```
+    pub fn accept_completion(&mut self) -> bool {
+        let candidate = match self.completion_popup.selected_item() {
+            Some(s) => s.to_string(),
+            None => return false,
+        };
+        self.completion_popup.close();
+
+        self.undo_group(|t| {
+            if t.selection_start.is_none() {
+                let (row, col) = t.cursor;
+                let start_col = find_word_head(&t.lines[row], col);
+                if start_col < col {
+                    let start = Pos::new(row, start_col, t.line_offset(row, start_col));
+                    let end = Pos::new(row, col, t.line_offset(row, col));
+                    if !t.delete_range(start, end, false) {
+                        return false;
+                    }
+                }
+            }
+            t.insert_str(candidate);
+            true
+        })
     }
 
-    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
-    /// spaces. By default, hard tab is disabled.
+    /// Set the style of the completion popup. The default style is dark gray background.
     /// ```
+    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// textarea.set_hard_tab_indent(true);
-    /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// let style = Style::default().bg(Color::Blue);
+    /// textarea.set_completion_popup_style(style);
+    /// assert_eq!(textarea.completion_popup_style(), style);
     /// ```
-    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
-        self.hard_tab_indent = enabled;
+    pub fn set_completion_popup_style(&mut self, style: Style) {
+        self.completion_popup.style = style;
     }
 
-    /// Get if a hard tab is used for indent or not.
+    /// Get the style of the completion popup.
+    pub fn completion_popup_style(&self) -> Style {
+        self.completion_popup.style
+    }
+
+    /// Set the style of the selected candidate in the completion popup. The default style is reversed dark gray
+    /// background.
     /// ```
+    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// assert!(!textarea.hard_tab_indent());
-    /// textarea.set_hard_tab_indent(true);
-    /// assert!(textarea.hard_tab_indent());
+    /// let style = Style::default().bg(Color::LightBlue);
+    /// textarea.set_completion_popup_selected_style(style);
+    /// assert_eq!(textarea.completion_popup_selected_style(), style);
+    /// ```
+    pub fn set_completion_popup_selected_style(&mut self, style: Style) {
+        self.completion_popup.selected_style = style;
+    }
+
+    /// Get the style of the selected candidate in the completion popup.
+    pub fn completion_popup_selected_style(&self) -> Style {
+        self.completion_popup.selected_style
+    }
+
+    /// Start text selection at the cursor position. If text selection is already ongoing, the start position is reset.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "aaa ");
+    /// ```
+    pub fn start_selection(&mut self) {
+        self.selection_start = Some(self.cursor);
+    }
+
+    /// Stop the current text selection. This method does nothing if text selection is not ongoing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa bbb ccc"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    ///
+    /// // Cancel the ongoing text selection
+    /// textarea.cancel_selection();
+    ///
+    /// // As the result, this `copy` call does nothing
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn cancel_selection(&mut self) {
+        self.selection_start = None;
+    }
+
+    /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
+    /// it is canceled.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["aaa", "bbb", "ccc"]);
+    ///
+    /// textarea.select_all();
+    ///
+    /// // Cut the entire text;
+    /// textarea.cut();
+    ///
+    /// assert_eq!(textarea.lines(), [""]); // Buffer is now empty
+    /// assert_eq!(textarea.yank_text(), "aaa\nbbb\nccc");
+    /// ```
+    pub fn select_all(&mut self) {
+        self.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
+        self.selection_start = Some((0, 0));
+    }
+
+    /// Return if text selection is ongoing or not.
+    /// ```
+    /// use tui_textarea::{TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.is_selecting());
+    /// textarea.start_selection();
+    /// assert!(textarea.is_selecting());
+    /// textarea.cancel_selection();
+    /// assert!(!textarea.is_selecting());
+    /// ```
+    pub fn is_selecting(&self) -> bool {
+        self.selection_start.is_some()
+    }
+
+    /// Expand the selection to the smallest enclosing structure: word, quoted string, inside of a bracket pair,
+    /// bracket pair, line, paragraph, and the entire text. When no text is selected, the structure at the cursor is
+    /// selected. The expanded selection can be shrunk back with [`TextArea::shrink_selection`]. This method returns
+    /// `false` when the selection cannot be expanded further.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["f(a, \"hello world\")"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 8));
+    ///
+    /// // Selects "hello"
+    /// assert!(textarea.expand_selection());
+    /// assert_eq!(textarea.cursor(), (0, 11));
+    /// assert_eq!(textarea.status().selected_chars, 5);
+    ///
+    /// // Selects "hello world", "\"hello world\"", and "a, \"hello world\""
+    /// assert!(textarea.expand_selection());
+    /// assert!(textarea.expand_selection());
+    /// assert!(textarea.expand_selection());
+    /// assert_eq!(textarea.status().selected_chars, 16);
+    ///
+    /// assert!(textarea.shrink_selection());
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "\"hello world\"");
+    /// ```
+    pub fn expand_selection(&mut self) -> bool {
+        let (start, end) = match self.selection_start {
+            Some(s) if s > self.cursor => (self.cursor, s),
+            Some(s) => (s, self.cursor),
+            None => (self.cursor, self.cursor),
+        };
+        let (start, end) = match expand_range(&self.lines, start, end, &self.words) {
+            Some(range) => range,
+            None => return false,
+        };
+
+        let history = &mut self.expand_history;
+        if history.expanded != self.selection_start.map(|s| (s, self.cursor)) {
+            history.entries.clear();
+        }
+        history.entries.push((self.selection_start, self.cursor));
+        history.expanded = Some((start, end));
+        self.selection_start = Some(start);
+        self.cursor = end;
+        true
+    }
+
+    /// Shrink the selection expanded by [`TextArea::expand_selection`] back to the previous selection. This method
+    /// returns `false` when the selection was not expanded or was changed after the expansion. See
+    /// [`TextArea::expand_selection`] for the example.
+    pub fn shrink_selection(&mut self) -> bool {
+        let history = &mut self.expand_history;
+        if history.expanded.is_none()
+            || history.expanded != self.selection_start.map(|s| (s, self.cursor))
+        {
+            history.entries.clear();
+            history.expanded = None;
+            return false;
+        }
+        let (selection_start, cursor) = match history.entries.pop() {
+            Some(prev) => prev,
+            None => return false,
+        };
+        history.expanded = selection_start.map(|s| (s, cursor));
+        self.selection_start = selection_start;
+        self.cursor = cursor;
+        true
+    }
+
+    fn line_offset(&self, row: usize, col: usize) -> usize {
+        let line = self
+            .lines
+            .get(row)
+            .unwrap_or(&self.lines[self.lines.len() - 1]);
+        byte_offset(line, col)
+    }
+
+    /// Set the style used for text selection. The default style is light blue.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Change the selection color from the default to Red
+    /// textarea.set_selection_style(Style::default().bg(Color::Red));
+    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::Red));
+    /// ```
+    pub fn set_selection_style(&mut self, style: Style) {
+        self.select_style = style;
+    }
+
+    /// Get the style used for text selection.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.selection_style(), Style::default().bg(Color::LightBlue));
+    /// ```
+    pub fn selection_style(&mut self) -> Style {
+        self.select_style
+    }
+
+    fn selection_range(&self) -> Option<(Pos, Pos)> {
+        let (sr, sc) = self.selection_start?;
+        let (er, ec) = self.cursor;
+        let (so, eo) = (self.line_offset(sr, sc), self.line_offset(er, ec));
+        let s = Pos::new(sr, sc, so);
+        let e = Pos::new(er, ec, eo);
+        match (sr, so).cmp(&(er, eo)) {
+            Ordering::Less => Some((s, e)),
+            Ordering::Equal => None,
+            Ordering::Greater => Some((e, s)),
+        }
+    }
+
+    /// Get the status of the textarea such as the cursor position, the number of lines, and the selection size. This
+    /// is useful to render a status bar of an editor. See [`Status`] for the details.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, Mode};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "de"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    ///
+    /// let status = textarea.status();
+    /// assert_eq!(status.mode, Mode::Select);
+    /// assert_eq!(status.selected_chars, 4); // "bc\nd"
+    /// assert_eq!(status.selected_lines, 2);
+    /// ```
+    pub fn status(&self) -> Status {
+        let (row, col) = self.cursor;
+        let lines = self.lines.len();
+        let (selected_chars, selected_lines) = match self.selection_range() {
+            Some((start, end)) if start.row == end.row => (end.col - start.col, 1),
+            Some((start, end)) => {
+                let first = self.lines[start.row].chars().count() - start.col + 1;
+                let middle: usize = self.lines[start.row + 1..end.row]
+                    .iter()
+                    .map(|l| l.chars().count() + 1)
+                    .sum();
+                (first + middle + end.col, end.row - start.row + 1)
+            }
+            None => (0, 0),
+        };
+        Status {
+            row: row + 1,
+            col: col + 1,
+            lines,
+            percent: ((row + 1) * 100 / lines) as u8,
+            mode: if self.is_selecting() {
+                Mode::Select
+            } else {
+                Mode::Insert
+            },
+            modified: self.modified,
+            selected_chars,
+            selected_lines,
+        }
+    }
+
+    /// Get the number of characters in the text. A newline between lines is counted as one character. The count is
+    /// maintained on each edit, so this method is cheap enough to call on every rendering.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// assert_eq!(textarea.char_count(), 11);
+    ///
+    /// textarea.insert_str("こんにちは, ");
+    /// assert_eq!(textarea.char_count(), 18);
+    /// ```
+    pub fn char_count(&self) -> usize {
+        self.counts.chars
+    }
+
+    /// Get the number of words in the text. A word is a run of word characters such as alphabets, digits, and `_`.
+    /// The count is maintained on each edit.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar", "baz"]);
+    /// assert_eq!(textarea.word_count(), 3);
+    ///
+    /// textarea.insert_newline();
+    /// textarea.insert_str("qux ");
+    /// assert_eq!(textarea.word_count(), 4);
+    /// textarea.undo();
+    /// assert_eq!(textarea.word_count(), 3);
+    /// ```
+    pub fn word_count(&self) -> usize {
+        self.counts.words
+    }
+
+    /// Get the number of lines in the text. This is the same as the length of [`TextArea::lines`].
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Show the number of characters and the max like `123/280` at the bottom-right of the block set by
+    /// [`TextArea::set_block`]. This is useful for length-limited message composers. The counter is rendered with the
+    /// styles set by [`TextArea::set_char_counter_styles`]. Nothing is rendered without the block. Note that this does
+    /// not prevent typing beyond the max.
+    /// ```
+    /// use ratatui::widgets::{Block, Borders};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    /// textarea.set_char_counter(280);
+    /// assert_eq!(textarea.char_counter(), Some(280));
+    ///
+    /// assert_eq!(
+    ///     textarea.render_to_string(12, 3),
+    ///     [
+    ///         "┌──────────┐",
+    ///         "│hello     │",
+    ///         "└─────5/280┘",
+    ///     ]
+    ///     .join("\n"),
+    /// );
+    /// ```
+    pub fn set_char_counter(&mut self, max: usize) {
+        self.char_counter = Some(max);
+    }
+
+    /// Stop showing the character counter set by [`TextArea::set_char_counter`].
+    pub fn remove_char_counter(&mut self) {
+        self.char_counter = None;
+    }
+
+    /// Get the max number of characters shown in the counter. See [`TextArea::set_char_counter`].
+    pub fn char_counter(&self) -> Option<usize> {
+        self.char_counter
+    }
+
+    /// Set the styles of the character counter set by [`TextArea::set_char_counter`] when the number of characters is
+    /// within the max and when it exceeds the max. The default styles are dark gray text and red text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let within = Style::default().fg(Color::Green);
+    /// let exceeded = Style::default().fg(Color::Magenta);
+    /// textarea.set_char_counter_styles(within, exceeded);
+    /// assert_eq!(textarea.char_counter_styles(), (within, exceeded));
+    /// ```
+    pub fn set_char_counter_styles(&mut self, within: Style, exceeded: Style) {
+        self.char_counter_styles = (within, exceeded);
+    }
+
+    /// Get the styles of the character counter within the max and over the max. See
+    /// [`TextArea::set_char_counter_styles`].
+    pub fn char_counter_styles(&self) -> (Style, Style) {
+        self.char_counter_styles
+    }
+
+    // Text and style of the character counter rendered on the block
+    pub(crate) fn char_counter_label(&self) -> Option<(String, Style)> {
+        let max = self.char_counter?;
+        let chars = self.counts.chars;
+        let (within, exceeded) = self.char_counter_styles;
+        let style = if chars > max { exceeded } else { within };
+        Some((format!("{}/{}", chars, max), style))
+    }
+
+    /// Return if the text was modified since the textarea was created or [`TextArea::set_modified`] was called.
+    /// Undo and redo also mark the text as modified.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.is_modified());
+    ///
+    /// textarea.insert_char('a');
+    /// assert!(textarea.is_modified());
+    /// ```
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Set the modified flag of the text. Typically this is called with `false` after the text is saved to a file.
+    /// Setting `false` also clears the lines returned by [`TextArea::modified_lines`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.set_modified(false); // Saved
+    /// assert!(!textarea.is_modified());
+    /// assert!(textarea.modified_lines().is_empty());
+    /// ```
+    pub fn set_modified(&mut self, modified: bool) {
+        self.modified = modified;
+        if !modified {
+            self.gutter.modified_lines.clear();
+        }
+    }
+
+    /// Get the rows of the lines changed since the textarea was created or [`TextArea::set_modified`] was called with
+    /// `false`, sorted in ascending order. Rows follow lines inserted or deleted before them. Note that a line is still
+    /// reported after its change is undone.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.insert_char('x');
+    /// assert_eq!(textarea.modified_lines(), [2]);
+    ///
+    /// textarea.move_cursor(CursorMove::Top);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.modified_lines(), [0, 1, 3]);
+    /// ```
+    pub fn modified_lines(&self) -> Vec<usize> {
+        self.gutter.modified_lines.iter().copied().collect()
+    }
+
+    /// Show a marker in the gutter for each line returned by [`TextArea::modified_lines`], like the familiar "changed
+    /// line" bars. Only the first cell of the symbol is rendered. The marker column is put after the change marker
+    /// column of [`TextArea::set_diff_base`].
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.modified_line_marker(), None);
+    ///
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_modified_line_marker("┃", style);
+    /// assert_eq!(textarea.modified_line_marker(), Some(("┃", style)));
+    /// ```
+    pub fn set_modified_line_marker(&mut self, symbol: impl Into<String>, style: Style) {
+        self.gutter.modified_marker = Some((symbol.into(), style));
+    }
+
+    /// Hide the marker set by [`TextArea::set_modified_line_marker`].
+    pub fn remove_modified_line_marker(&mut self) {
+        self.gutter.modified_marker = None;
+    }
+
+    /// Get the symbol and the style of the marker set by [`TextArea::set_modified_line_marker`].
+    pub fn modified_line_marker(&self) -> Option<(&str, Style)> {
+        self.gutter
+            .modified_marker
+            .as_ref()
+            .map(|(symbol, style)| (symbol.as_str(), *style))
+    }
+
+    fn take_selection_range(&mut self) -> Option<(Pos, Pos)> {
+        let range = self.selection_range();
+        self.cancel_selection();
+        range
+    }
+
+    /// Copy the selection text to the yank buffer. When nothing is selected, this method does nothing.
+    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// ```
+    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// // Start text selection at 'W'
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    ///
+    /// // Select the word "World" and copy the selected text
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.copy();
+    ///
+    /// assert_eq!(textarea.yank_text(), "World");
+    /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
+    /// ```
+    pub fn copy(&mut self) {
+        if let Some((start, end)) = self.take_selection_range() {
+            if start.row == end.row {
+                self.yank.push(
+                    self.lines[start.row][start.offset..end.offset]
+                        .to_string()
+                        .into(),
+                );
+                return;
+            }
+            let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
+            chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
+            chunk.push(self.lines[end.row][..end.offset].to_string());
+            self.yank.push(YankText::Chunk(chunk));
+        }
+    }
+
+    /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
+    /// The cursor will move to the start position of the text selection.
+    /// To get the yanked text, use [`TextArea::yank_text`].
+    /// ```
+    /// use tui_textarea::{TextArea, Key, Input, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello World"]);
+    ///
+    /// // Start text selection at 'W'
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.start_selection();
+    ///
+    /// // Select the word "World" and copy the selected text
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.cut();
+    ///
+    /// assert_eq!(textarea.yank_text(), "World");
+    /// assert_eq!(textarea.lines(), ["Hello "]);
+    /// ```
+    pub fn cut(&mut self) -> bool {
+        self.delete_selection(true)
+    }
+
+    fn delete_selection(&mut self, should_yank: bool) -> bool {
+        if let Some((s, e)) = self.take_selection_range() {
+            return self.delete_range(s, e, should_yank);
+        }
+        false
+    }
+
+    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
+    /// the document of [`CursorMove`].
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn move_cursor(&mut self, m: CursorMove) {
+        self.move_cursor_with_shift(m, self.selection_start.is_some());
+    }
+
+    fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
+        let (row, col) = self.cursor;
+        let virtual_col = self.virtual_col();
+        let len = self.lines[row].chars().count();
+        // In virtual space mode, the cursor moves beyond the end of line instead of wrapping to the next line
+        let virtual_move = match m {
+            CursorMove::Forward if self.virtual_space && col == len => Some(len + virtual_col + 1),
+            CursorMove::Back if virtual_col > 0 => Some(len + virtual_col - 1),
+            _ => None,
+        };
+        if let Some(col) = virtual_move {
+            if shift {
+                if self.selection_start.is_none() {
+                    self.start_selection();
+                }
+            } else {
+                self.cancel_selection();
+            }
+            self.set_virtual_cursor((row, col));
+            return;
+        }
+
+        if let Some(mut cursor) =
+            m.next_cursor(self.cursor, &self.lines, &self.viewport, &self.words)
+        {
+            // Skip lines hidden by folds
+            let folds = self.active_folds();
+            if let Some((start, end)) = hiding_fold(&folds, cursor.0) {
+                let row = if cursor.0 > self.cursor.0 && end + 1 < self.lines.len() {
+                    end + 1
+                } else {
+                    start
+                };
+                cursor = (row, cmp::min(cursor.1, self.lines[row].chars().count()));
+            }
+            // Keep the display column on moving vertically across lines containing tabs
+            let vertical = matches!(
+                m,
+                CursorMove::Up
+                    | CursorMove::Down
+                    | CursorMove::Top
+                    | CursorMove::Bottom
+                    | CursorMove::ParagraphForward
+                    | CursorMove::ParagraphBack
+            );
+            if vertical
+                && self.mask.is_none()
+                && (self.lines[row].contains('\t') || self.lines[cursor.0].contains('\t'))
+            {
+                cursor.1 = self.col_at_width(cursor.0, self.display_width(row, col));
+            }
+            if shift {
+                if self.selection_start.is_none() {
+                    self.start_selection();
+                }
+            } else {
+                self.cancel_selection();
+            }
+            self.cursor = cursor;
+            self.virtual_pos = None;
+            if self.virtual_space {
+                // Keep the column beyond the end of line on moving vertically
+                match m {
+                    CursorMove::Up | CursorMove::Down => {
+                        self.set_virtual_cursor((cursor.0, col + virtual_col))
+                    }
+                    CursorMove::Jump(_, c) => self.set_virtual_cursor((cursor.0, c as usize)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Display widths of the characters in the row as rendered. Tabs are expanded to the next tab stop or with the
+    // elastic tabstops
+    fn char_widths(&self, row: usize) -> Vec<usize> {
+        let mut elastic = self.elastic_tab_widths(row).into_iter();
+        let mut width = 0;
+        self.lines[row]
+            .chars()
+            .map(|c| {
+                let w = match c {
+                    '\t' if self.tab_len > 0 => elastic
+                        .next()
+                        .unwrap_or_else(|| tab_width(width, self.tab_len, &self.tab_stops)),
+                    '\t' => 0,
+                    c => control_char_width(c, self.control_char_display)
+                        .unwrap_or_else(|| char_width(c, self.ambiguous_width).unwrap_or(0)),
+                };
+                width += w;
+                w
+            })
+            .collect()
+    }
+
+    // Display width of the text before the column in the row
+    fn display_width(&self, row: usize, col: usize) -> usize {
+        self.char_widths(row).iter().take(col).sum()
+    }
+
+    // Column of the character rendered at the display width in the row. The end of line is returned when the line is
+    // narrower than the width
+    fn col_at_width(&self, row: usize, target: usize) -> usize {
+        let mut width = 0;
+        for (col, w) in self.char_widths(row).into_iter().enumerate() {
+            if target < width + w {
+                return col;
+            }
+            width += w;
+        }
+        self.lines[row].chars().count()
+    }
+
+    // Put the cursor at the position which may be beyond the end of line
+    fn set_virtual_cursor(&mut self, (row, col): (usize, usize)) {
+        let len = self.lines[row].chars().count();
+        self.cursor = (row, cmp::min(col, len));
+        self.virtual_pos = (col > len).then(|| (row, col));
+    }
+
+    // Number of columns between the end of line and the cursor in virtual space mode
+    fn virtual_col(&self) -> usize {
+        match self.virtual_pos {
+            Some((row, col)) if self.virtual_space && row == self.cursor.0 => {
+                let len = self.lines[row].chars().count();
+                if self.cursor.1 == len {
+                    col.saturating_sub(len)
+                } else {
+                    0
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        let index = self.history.index();
+        if let Some(cursor) = self.history.undo(&mut self.lines) {
+            for i in (self.history.index()..index).rev() {
+                let op = self.history.edit(i).op(true);
+                self.record_op(op);
+            }
+            // Counts were updated with the text after all edits were undone
+            self.counts.recount(&self.lines);
+            self.cancel_selection();
+            self.cursor = cursor;
+            self.modified = true;
+            self.mark_idle_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// textarea.redo();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        let index = self.history.index();
+        if let Some(cursor) = self.history.redo(&mut self.lines) {
+            for i in index..self.history.index() {
+                let op = self.history.edit(i).op(false);
+                self.record_op(op);
+            }
+            self.counts.recount(&self.lines);
+            self.cancel_selection();
+            self.cursor = cursor;
+            self.modified = true;
+            self.mark_idle_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    // `folded` is the number of lines hidden by the fold starting at the row
+    pub(crate) fn line_spans<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        folded: Option<usize>,
+    ) -> Line<'b> {
+        self.line_highlighter(line, row, lnum_len, folded)
+            .into_spans()
+    }
+
+    // Highlighter of the line with all decorations of the textarea. Callers can add their own decorations before
+    // building the spans
+    pub(crate) fn line_highlighter<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        folded: Option<usize>,
+    ) -> LineHighlighter<'b> {
+        // The hidden cursor of blinking is rendered as the plain character on the cursor line
+        let cursor_style = if self.is_cursor_visible() {
+            self.cursor_style
+        } else {
+            self.cursor_line_style
+        };
+        let mut hl = LineHighlighter::new(
+            line,
+            cursor_style,
+            self.tab_len,
+            self.mask,
+            self.select_style,
+        );
+
+        if let Some(style) = self.line_number_style {
+            hl.line_number(row, lnum_len, style);
+        }
+
+        if self.elastic_tabstops {
+            hl.elastic_tabs(self.elastic_tab_widths(row));
+        }
+        if !self.tab_stops.is_empty() {
+            hl.tab_stops(&self.tab_stops);
+        }
+        hl.control_chars(self.control_char_display, self.control_char_style);
+        hl.ambiguous_width(self.ambiguous_width);
+        if let Some(order) = self.visual_order(row) {
+            hl.visual_order(order);
+        }
+
+        if row == self.cursor.0 {
+            hl.cursor_line(self.cursor.1, self.cursor_line_style);
+            hl.virtual_space(self.virtual_col());
+            if !self.ghost_text.is_empty() && self.mask.is_none() {
+                hl.ghost_text(&self.ghost_text, self.ghost_text_style);
+            }
+        }
+
+        if let Some(style) = self.conflict_highlight.line_style(&self.lines, row) {
+            hl.line_style(style);
+        }
+
+        if self.commit_message.enabled {
+            let (style, overflow) = self.commit_message.line_styles(&self.lines, row);
+            if let Some(style) = style {
+                hl.line_style(style);
+            }
+            hl.diagnostics(overflow.into_iter());
+        }
+
+        hl.diagnostics(self.line_limit.overflow(line).into_iter());
+
+        #[cfg(feature = "search")]
+        if !self.highlight_rules.rules.is_empty() {
+            hl.highlight_rules(self.highlight_rules.matches(line).into_iter());
+        }
+
+        #[cfg(feature = "search")]
+        if let Some(matches) = self.search.matches(line, row) {
+            hl.search(matches, self.search.style);
+        }
+
+        if self.mask.is_none() {
+            let hints = self.inlay_hints_at(row);
+            if !hints.is_empty() {
+                hl.inlay_hints(hints.iter().map(|h| {
+                    let offset = byte_offset(line, h.position.1);
+                    let style = h.style.unwrap_or(self.inlay_hint_style);
+                    (offset, h.text.as_str(), style)
+                }));
+            }
+        }
+
+        if self.word_highlight && self.mask.is_none() {
+            if let Some(ident) = self.ident_at_cursor() {
+                let mut ranges = find_ident_occurrences(line, ident);
+                if row == self.cursor.0 {
+                    // Exclude the occurrence under the cursor
+                    let offset = byte_offset(line, self.cursor.1);
+                    ranges.retain(|&(start, end)| offset < start || end < offset);
+                }
+                hl.occurrences(ranges.into_iter(), self.word_highlight_style);
+            }
+        }
+
+        if self.mask.is_none() {
+            let links = self.hyperlinks_at(row);
+            if !links.is_empty() {
+                let offset = |col: usize| byte_offset(line, col);
+                hl.hyperlinks(links.iter().map(|l| {
+                    let style = l.style.unwrap_or(self.hyperlink_style);
+                    (offset(l.range.0), offset(l.range.1), style)
+                }));
+            }
+        }
+
+        if self.show_line_annotations {
+            if let Some((text, style)) = self.line_annotations.get(&row) {
+                hl.annotation(text, *style);
+            }
+        }
+
+        if !self.tracked.changes.is_empty() && self.mask.is_none() {
+            self.highlight_tracked_changes(&mut hl, line, row);
+        }
+
+        if !self.diagnostics.items.is_empty() {
+            hl.diagnostics(self.diagnostics.line_ranges(row, line).into_iter());
+        }
+
+        if self.mask.is_none() {
+            if let Some(ranges) = self.spell.misspellings(line) {
+                hl.misspellings(ranges.into_iter(), self.spell.style);
+            }
+        }
+
+        if let Some((start, end)) = self.selection_range() {
+            hl.selection(row, start.row, start.offset, end.row, end.offset);
+        }
+
+        if !self.remote_cursors.is_empty() {
+            let offset = |col: usize| byte_offset(line, col);
+            let cursors = self.remote_cursors.iter();
+            hl.remote_cursors(
+                cursors
+                    .clone()
+                    .filter(|c| c.position.0 == row)
+                    .map(|c| (offset(c.position.1), c.style)),
+                cursors.filter_map(|c| {
+                    let (start, end) = c.selection_in_row(row)?;
+                    Some((offset(start), end.map(offset), c.selection_style))
+                }),
+            );
+        }
+
+        if let Some((c, style)) = self.indent_guide {
+            hl.indent_guide(c, style);
+        }
+
+        if self.show_whitespace {
+            hl.whitespace(self.whitespace_style, self.trailing_whitespace_style);
+        }
+
+        if let Some(hidden) = folded {
+            hl.fold_summary(hidden, self.folds.style);
+        }
+
+        hl
+    }
+
+    /// Build a ratatui (or tui-rs) widget to render the current state of the textarea. The widget instance returned
+    /// from this method can be rendered with [`ratatui::terminal::Frame::render_widget`].
+    ///
+    /// `&TextArea` also implements `Widget`. Rendering the reference directly with `f.render_widget(&textarea, area)`
+    /// avoids borrowing the textarea in the intermediate widget object.
+    /// ```no_run
+    /// use ratatui::backend::CrosstermBackend;
+    /// use ratatui::layout::{Constraint, Direction, Layout};
+    /// use ratatui::Terminal;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let layout = Layout::default()
+    ///     .direction(Direction::Vertical)
+    ///     .constraints([Constraint::Min(1)].as_ref());
+    /// let backend = CrosstermBackend::new(std::io::stdout());
+    /// let mut term = Terminal::new(backend).unwrap();
+    ///
+    /// loop {
+    ///     term.draw(|f| {
+    ///         let chunks = layout.split(f.size());
+    ///         let widget = textarea.widget();
+    ///         f.render_widget(widget, chunks[0]);
+    ///     }).unwrap();
+    ///
+    ///     // ...
+    /// }
+    /// ```
+    pub fn widget(&'a self) -> impl Widget + 'a {
+        Renderer::new(self)
+    }
+
+    /// Build a widget which stores the scroll position in [`TextAreaState`] passed to
+    /// [`ratatui::terminal::Frame::render_stateful_widget`] instead of the textarea itself. This is useful to render
+    /// the same textarea in multiple areas with separate scroll positions.
+    ///
+    /// Note that [`TextArea::scroll`] and [`CursorMove::InViewport`] use the viewport of the widget returned from
+    /// [`TextArea::widget`], not the external state.
+    /// ```no_run
+    /// use ratatui::backend::CrosstermBackend;
+    /// use ratatui::layout::{Constraint, Direction, Layout};
+    /// use ratatui::Terminal;
+    /// use tui_textarea::{TextArea, TextAreaState};
+    ///
+    /// let textarea = TextArea::default();
+    /// let mut states = [TextAreaState::default(), TextAreaState::default()];
+    ///
+    /// let layout = Layout::default()
+    ///     .direction(Direction::Horizontal)
+    ///     .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref());
+    /// let backend = CrosstermBackend::new(std::io::stdout());
+    /// let mut term = Terminal::new(backend).unwrap();
+    ///
+    /// term.draw(|f| {
+    ///     let chunks = layout.split(f.size());
+    ///     // Render the same textarea side by side
+    ///     for (chunk, state) in chunks.iter().zip(states.iter_mut()) {
+    ///         f.render_stateful_widget(textarea.stateful_widget(), *chunk, state);
+    ///     }
+    /// }).unwrap();
+    /// ```
+    pub fn stateful_widget(&'a self) -> impl StatefulWidget<State = TextAreaState> + 'a {
+        Renderer::new(self)
+    }
+
+    /// Render the textarea into a new buffer of the size without a terminal. The textarea is rendered at `(0, 0)` with
+    /// the same widget as [`TextArea::widget`], so the scroll position is updated as if it was rendered on the screen.
+    /// This is useful for snapshot tests of the rendered text and styles.
+    /// ```
+    /// use ratatui::style::{Modifier, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["hello"]);
+    /// let buf = textarea.render_to_buffer(8, 1);
+    ///
+    /// // The cursor is rendered as a reversed cell
+    /// assert!(buf.get(0, 0).style().add_modifier.contains(Modifier::REVERSED));
+    /// assert_eq!(buf.get(1, 0).symbol(), "e");
+    /// ```
+    pub fn render_to_buffer(&self, width: u16, height: u16) -> Buffer {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        Widget::render(Renderer::new(self), area, &mut buf);
+        buf
+    }
+
+    /// Render the textarea into a string of the size without a terminal. Each row of the screen is one line separated
+    /// by `\n` including trailing spaces. Styles are not included. Use [`TextArea::render_to_buffer`] to check them.
+    /// Combined with [`TextArea::cursor_screen_position`], cursor placement and scrolling can be tested without a
+    /// terminal.
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea: TextArea = (0..5).map(|i| i.to_string()).collect();
+    /// textarea.set_line_number_style(Default::default());
+    /// textarea.move_cursor(CursorMove::Bottom);
+    ///
+    /// // Scrolled to show the cursor at the bottom
+    /// assert_eq!(textarea.render_to_string(6, 2), " 4 3  \n 5 4  ");
+    /// assert_eq!(textarea.cursor_screen_position(Rect::new(0, 0, 6, 2)), Some((3, 1)));
+    /// ```
+    pub fn render_to_string(&self, width: u16, height: u16) -> String {
+        buffer_to_string(&self.render_to_buffer(width, height))
+    }
+
+    /// Get the screen position of the cursor as `(x, y)` when the textarea is rendered in the area with the widget
+    /// returned from [`TextArea::widget`]. The position accounts for the block borders, the gutter, line numbers, and
+    /// the scroll position. `None` is returned when the cursor is outside the area.
+    ///
+    /// This is useful to show the terminal's cursor with [`ratatui::terminal::Frame::set_cursor`] instead of the
+    /// reversed cell. Call this method after rendering the textarea in the same frame. To hide the reversed cell, set
+    /// the default style with [`TextArea::set_cursor_style`].
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::{Block, Borders, Widget};
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// let area = Rect::new(2, 3, 20, 4);
+    /// let mut buf = Buffer::empty(area);
+    /// textarea.widget().render(area, &mut buf);
+    ///
+    /// // Inside the border at the end of the 2nd line
+    /// assert_eq!(textarea.cursor_screen_position(area), Some((8, 5)));
+    /// ```
+    pub fn cursor_screen_position(&self, area: Rect) -> Option<(u16, u16)> {
+        Renderer::new(self).cursor_position(area, &self.viewport)
+    }
+
+    /// Handle a mouse click at the screen position `(x, y)` when the textarea is rendered in the area with the widget
+    /// returned from [`TextArea::widget`]. A single click moves the cursor to the clicked character. Clicking the same
+    /// position again within the click interval selects the word under the pointer, and the third click selects the
+    /// entire line. This method returns `false` when the position is outside the text area.
+    ///
+    /// Call this method with the position of the mouse button press event reported by the backend (e.g.
+    /// `crossterm::event::MouseEventKind::Down`).
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello world", "goodbye"]);
+    ///
+    /// let area = Rect::new(0, 0, 20, 4);
+    /// let mut buf = Buffer::empty(area);
+    /// textarea.widget().render(area, &mut buf);
+    ///
+    /// // Single click moves the cursor
+    /// assert!(textarea.click(area, 7, 0));
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    ///
+    /// // Double click selects the word
+    /// textarea.click(area, 7, 0);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "world");
+    ///
+    /// // Triple click selects the line
+    /// textarea.click(area, 7, 0);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "hello world\n");
+    /// ```
+    pub fn click(&mut self, area: Rect, x: u16, y: u16) -> bool {
+        let (row, col) = match Renderer::new(self).screen_to_cursor(area, &self.viewport, x, y) {
+            Some(pos) => pos,
+            None => return false,
+        };
+
+        let now = Instant::now();
+        let count = match self.last_click {
+            Some((at, pos, count))
+                if pos == (row, col) && now.duration_since(at) <= self.click_interval =>
+            {
+                count % 3 + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some((now, (row, col), count));
+        self.virtual_pos = None;
+
+        match count {
+            1 => {
+                self.cancel_selection();
+                self.cursor = (row, col);
+            }
+            2 => {
+                let (start, end) = find_word_range(&self.lines[row], col, &self.words);
+                self.selection_start = Some((row, start));
+                self.cursor = (row, end);
+            }
+            _ => {
+                self.selection_start = Some((row, 0));
+                self.cursor = if row + 1 < self.lines.len() {
+                    (row + 1, 0)
+                } else {
+                    (row, self.lines[row].chars().count())
+                };
+            }
+        }
+        #[cfg(feature = "clipboard")]
+        self.sync_primary_selection();
+        true
+    }
+
+    /// Handle a middle click at the screen position `(x, y)` in the area where the textarea is rendered. The cursor
+    /// moves to the clicked position and the text in the primary selection is pasted there, as X11 and Wayland
+    /// applications do. This method returns whether some text was pasted. It does nothing on platforms which have no
+    /// primary selection. See [`TextArea::click`] for the area.
+    #[cfg(feature = "clipboard")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+    pub fn middle_click(&mut self, area: Rect, x: u16, y: u16) -> bool {
+        let pos = match Renderer::new(self).screen_to_cursor(area, &self.viewport, x, y) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        self.cancel_selection();
+        self.cursor = pos;
+        self.paste_primary_selection()
+    }
+
+    /// Set the maximum interval between clicks to detect double and triple clicks in [`TextArea::click`]. The default
+    /// value is 500 milliseconds.
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_click_interval(Duration::from_millis(300));
+    /// assert_eq!(textarea.click_interval(), Duration::from_millis(300));
+    /// ```
+    pub fn set_click_interval(&mut self, interval: Duration) {
+        self.click_interval = interval;
+    }
+
+    /// Get the maximum interval between clicks to detect double and triple clicks.
+    pub fn click_interval(&self) -> Duration {
+        self.click_interval
+    }
+
+    /// Set the style of textarea. By default, textarea is not styled.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_style(style);
+    /// assert_eq!(textarea.style(), style);
+    /// ```
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the current style of textarea.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Apply the styles of the theme at once. The cursor, cursor line, selection, search match, line number, and
+    /// placeholder styles are set. Line numbers are not enabled by the theme. When they are already shown, their
+    /// style is updated. Each style can be overridden by its setter after applying the theme.
+    /// ```
+    /// use tui_textarea::{TextArea, Theme};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_placeholder_text("Type here");
+    /// let theme = Theme::high_contrast();
+    ///
+    /// textarea.set_theme(&theme);
+    /// assert_eq!(textarea.cursor_style(), theme.cursor);
+    /// assert_eq!(textarea.placeholder_style(), Some(theme.placeholder));
+    /// assert_eq!(textarea.line_number_style(), None);
+    ///
+    /// // Show line numbers with the style of the theme
+    /// textarea.set_line_number_style(theme.line_number);
+    /// textarea.set_theme(&Theme::light());
+    /// assert_eq!(textarea.line_number_style(), Some(Theme::light().line_number));
+    /// ```
+    pub fn set_theme(&mut self, theme: &Theme) {
+        self.cursor_style = theme.cursor;
+        self.cursor_line_style = theme.cursor_line;
+        self.select_style = theme.selection;
+        #[cfg(feature = "search")]
+        {
+            self.search.style = theme.search;
+        }
+        if self.line_number_style.is_some() {
+            self.line_number_style = Some(theme.line_number);
+        }
+        self.placeholder_style = theme.placeholder;
+    }
+
+    /// Set the block of textarea. By default, no block is set.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// assert!(textarea.block().is_some());
+    /// ```
+    pub fn set_block(&mut self, block: Block<'a>) {
+        self.block = Some(block);
+    }
+
+    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// textarea.remove_block();
+    /// assert!(textarea.block().is_none());
+    /// ```
+    pub fn remove_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Get the block of textarea if exists.
+    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
+        self.block.as_ref()
+    }
+
+    /// Set if the textarea is focused. The border style of the block is switched on rendering by the focus when the
+    /// styles are set by [`TextArea::set_block_focus_styles`]. This is useful when multiple textareas are in a screen.
+    /// A textarea is focused by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(textarea.is_focused());
+    ///
+    /// textarea.set_focused(false);
+    /// assert!(!textarea.is_focused());
+    /// ```
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Get if the textarea is focused. See [`TextArea::set_focused`].
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Set the border styles of the block set by [`TextArea::set_block`] when the textarea is focused and unfocused.
+    /// The style is applied to the block on rendering following [`TextArea::set_focused`], so the application does
+    /// not need to rebuild the block when the focus moves. By default, the border style of the block is used as is.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use ratatui::widgets::{Block, Borders};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    ///
+    /// let focused = Style::default().fg(Color::Yellow);
+    /// let unfocused = Style::default().fg(Color::DarkGray);
+    /// textarea.set_block_focus_styles(focused, unfocused);
+    /// assert_eq!(textarea.block_focus_styles(), Some((focused, unfocused)));
+    ///
+    /// assert_eq!(textarea.render_to_buffer(4, 3).get(0, 0).fg, Color::Yellow);
+    /// textarea.set_focused(false);
+    /// assert_eq!(textarea.render_to_buffer(4, 3).get(0, 0).fg, Color::DarkGray);
+    /// ```
+    pub fn set_block_focus_styles(&mut self, focused: Style, unfocused: Style) {
+        self.focus_styles = Some((focused, unfocused));
+    }
+
+    /// Remove the border styles set by [`TextArea::set_block_focus_styles`].
+    pub fn remove_block_focus_styles(&mut self) {
+        self.focus_styles = None;
+    }
+
+    /// Get the border styles of the block when the textarea is focused and unfocused. See
+    /// [`TextArea::set_block_focus_styles`].
+    pub fn block_focus_styles(&self) -> Option<(Style, Style)> {
+        self.focus_styles
+    }
+
+    // Border style of the block following the focus
+    pub(crate) fn focus_border_style(&self) -> Option<Style> {
+        let (focused, unfocused) = self.focus_styles?;
+        Some(if self.focused { focused } else { unfocused })
+    }
+
+    /// Set the minimum number of rows of the text reported by [`TextArea::preferred_height`]. The default value is 1.
+    /// Setting the same value as [`TextArea::set_max_rows`] makes the height fixed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.min_rows(), 1);
+    ///
+    /// textarea.set_min_rows(3);
+    /// assert_eq!(textarea.min_rows(), 3);
+    /// assert_eq!(textarea.preferred_height(), 3);
+    /// ```
+    pub fn set_min_rows(&mut self, rows: u16) {
+        self.min_rows = rows;
+    }
+
+    /// Get the minimum number of rows set by [`TextArea::set_min_rows`].
+    pub fn min_rows(&self) -> u16 {
+        self.min_rows
+    }
+
+    /// Set the maximum number of rows of the text reported by [`TextArea::preferred_height`]. When the text has more
+    /// lines, the textarea rendered in the height scrolls the text. The maximum takes precedence over the minimum set
+    /// by [`TextArea::set_min_rows`]. There is no maximum by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+    /// assert_eq!(textarea.preferred_height(), 4);
+    ///
+    /// textarea.set_max_rows(2);
+    /// assert_eq!(textarea.max_rows(), Some(2));
+    /// assert_eq!(textarea.preferred_height(), 2);
+    ///
+    /// textarea.remove_max_rows();
+    /// assert_eq!(textarea.max_rows(), None);
+    /// ```
+    pub fn set_max_rows(&mut self, rows: u16) {
+        self.max_rows = Some(rows);
+    }
+
+    /// Remove the maximum number of rows set by [`TextArea::set_max_rows`].
+    pub fn remove_max_rows(&mut self) {
+        self.max_rows = None;
+    }
+
+    /// Get the maximum number of rows set by [`TextArea::set_max_rows`].
+    pub fn max_rows(&self) -> Option<u16> {
+        self.max_rows
+    }
+
+    /// Get the height preferred to render the textarea. It is the number of rows of the text on screen clamped by
+    /// [`TextArea::set_min_rows`] and [`TextArea::set_max_rows`], plus the borders and the padding of the block. Folded
+    /// lines are not counted. This is useful to allocate the space of the textarea in a layout so that the textarea
+    /// grows with its text.
+    /// ```
+    /// use ratatui::layout::Constraint;
+    /// use ratatui::widgets::{Block, Borders};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b"]);
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    /// textarea.set_min_rows(3);
+    /// textarea.set_max_rows(5);
+    /// assert_eq!(textarea.preferred_height(), 5);
+    ///
+    /// textarea.insert_str("\n\n\n\n");
+    /// assert_eq!(textarea.preferred_height(), 7);
+    ///
+    /// let _constraint = Constraint::Length(textarea.preferred_height());
+    /// ```
+    pub fn preferred_height(&self) -> u16 {
+        let rows = visual_row(&self.active_folds(), self.lines.len() - 1) + 1;
+        let rows = cmp::max(rows, self.min_rows as usize);
+        let rows = self
+            .max_rows
+            .map_or(rows, |max| cmp::min(rows, max as usize));
+        let borders = self.block.as_ref().map_or(0, |b| {
+            let area = Rect::new(0, 0, 100, 100);
+            area.height - b.inner(area).height
+        });
+        (cmp::min(rows, u16::MAX as usize) as u16).saturating_add(borders)
+    }
+
+    /// Set the length of tab character. Setting 0 disables tab inputs. Tab characters in the text are rendered up to
+    /// the next multiple of the length, and moving the cursor up or down across lines containing tabs keeps the
+    /// rendered column.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    ///
+    /// textarea.set_tab_length(8);
+    /// textarea.input(tab_input.clone());
+    /// assert_eq!(textarea.lines(), ["        "]);
+    ///
+    /// textarea.set_tab_length(2);
+    /// textarea.input(tab_input);
+    /// assert_eq!(textarea.lines(), ["          "]);
+    /// ```
+    pub fn set_tab_length(&mut self, len: u8) {
+        self.tab_len = len;
+    }
+
+    /// Get how many spaces are used for representing tab character. The default value is 4.
+    pub fn tab_length(&self) -> u8 {
+        self.tab_len
+    }
+
+    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
+    /// spaces. By default, hard tab is disabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_hard_tab_indent(true);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// ```
+    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
+        self.hard_tab_indent = enabled;
+    }
+
+    /// Get if a hard tab is used for indent or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.hard_tab_indent());
+    /// textarea.set_hard_tab_indent(true);
+    /// assert!(textarea.hard_tab_indent());
     /// ```
     pub fn hard_tab_indent(&self) -> bool {
         self.hard_tab_indent
     }
 
-    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// Enable or disable elastic tabstops. With elastic tabstops, tabs don't stop at fixed columns. Instead, the text
+    /// separated by tabs in adjacent lines is aligned as columns of a table, which is useful to edit TSV data or
+    /// aligned comments. A column is at least as wide as [`TextArea::tab_length`] and has one space after its widest
+    /// text. Only the rendering is affected and the text still contains tabs. It is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["name\tage", "alice\t30", "bob\t4"]);
+    /// textarea.set_tab_length(2);
+    /// textarea.set_elastic_tabstops(true);
+    /// assert!(textarea.elastic_tabstops());
+    ///
+    /// assert_eq!(
+    ///     textarea.render_to_string(10, 3),
+    ///     "name  age \nalice 30  \nbob   4   ",
+    /// );
+    /// ```
+    pub fn set_elastic_tabstops(&mut self, enabled: bool) {
+        self.elastic_tabstops = enabled;
+    }
+
+    /// Get if elastic tabstops are enabled. See [`TextArea::set_elastic_tabstops`].
+    pub fn elastic_tabstops(&self) -> bool {
+        self.elastic_tabstops
+    }
+
+    /// Set the columns of tab stops in display width, for example to enter data in fixed-format fields. A tab key
+    /// inserts spaces (or a hard tab with [`TextArea::set_hard_tab_indent`]) up to the next tab stop, and tab
+    /// characters are rendered up to the next tab stop. After the last tab stop, tabs stop at the multiples of
+    /// [`TextArea::tab_length`] as usual. The columns are sorted and column 0 is ignored. An empty list clears the
+    /// custom tab stops. Note that tabs are disabled when the tab length is 0 even if tab stops are set.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["ID"]);
+    /// textarea.set_tab_stops([20, 8]);
+    /// assert_eq!(textarea.tab_stops(), [8, 20]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.insert_tab();
+    /// textarea.insert_str("NAME");
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["ID      NAME        "]);
+    ///
+    /// // After the last tab stop, tabs stop at multiples of the tab length
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.cursor(), (0, 24));
+    /// ```
+    pub fn set_tab_stops(&mut self, stops: impl IntoIterator<Item = usize>) {
+        let mut stops = stops.into_iter().filter(|&s| s > 0).collect::<Vec<_>>();
+        stops.sort_unstable();
+        stops.dedup();
+        self.tab_stops = stops;
+    }
+
+    /// Get the columns of the custom tab stops. See [`TextArea::set_tab_stops`].
+    pub fn tab_stops(&self) -> &[usize] {
+        &self.tab_stops
+    }
+
+    // Character indices of the line at the row in the visual order when the line contains right-to-left text
+    #[cfg(feature = "bidi")]
+    pub(crate) fn visual_order(&self, row: usize) -> Option<Vec<usize>> {
+        if !self.bidi || self.mask.is_some() {
+            return None;
+        }
+        bidi::visual_order(&self.lines[row])
+    }
+
+    #[cfg(not(feature = "bidi"))]
+    pub(crate) fn visual_order(&self, _row: usize) -> Option<Vec<usize>> {
+        None
+    }
+
+    // Widths of the tabs in the line with elastic tabstops. Empty when elastic tabstops are not used
+    pub(crate) fn elastic_tab_widths(&self, row: usize) -> Vec<usize> {
+        if !self.elastic_tabstops || self.mask.is_some() || self.tab_len == 0 {
+            return vec![];
+        }
+        tab_widths(
+            &self.lines,
+            row,
+            self.tab_len as usize,
+            self.ambiguous_width,
+        )
+    }
+
+    /// Detect whether the text is indented with hard tabs or spaces and the width of the indentation, and set
+    /// [`TextArea::set_hard_tab_indent`] and [`TextArea::set_tab_length`] accordingly. Hard tabs are detected when
+    /// more lines are indented with tabs than with spaces. The tab length is not changed for hard tabs. It returns
+    /// `false` and changes nothing when the indentation could not be detected.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "  if x {", "    y", "  }", "}"]);
+    /// assert!(textarea.detect_indent());
+    /// assert!(!textarea.hard_tab_indent());
+    /// assert_eq!(textarea.tab_length(), 2);
+    ///
+    /// let mut textarea = TextArea::from(["f() {", "\tx", "}"]);
+    /// assert!(textarea.detect_indent());
+    /// assert!(textarea.hard_tab_indent());
+    ///
+    /// let mut textarea = TextArea::from(["not", "indented"]);
+    /// assert!(!textarea.detect_indent());
+    /// ```
+    pub fn detect_indent(&mut self) -> bool {
+        match detect_indent(&self.lines) {
+            Some(Indent::Tabs) => self.hard_tab_indent = true,
+            Some(Indent::Spaces(width)) => {
+                self.hard_tab_indent = false;
+                self.tab_len = width;
+            }
+            None => return false,
+        }
+        true
+    }
+
+    /// Enable or disable detecting the indentation with [`TextArea::detect_indent`] when the loading started by
+    /// [`TextArea::start_loading`] finishes. It is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_indent_detection(true);
+    /// assert!(textarea.indent_detection());
+    ///
+    /// textarea.start_loading(&b"a:\n  b: 1\n  c: 2\n"[..], None);
+    /// while !textarea.load_chunk(1024).unwrap() {}
+    /// assert_eq!(textarea.tab_length(), 2);
+    /// ```
+    pub fn set_indent_detection(&mut self, enabled: bool) {
+        self.indent_detection = enabled;
+    }
+
+    /// Get if the indentation is detected on loading. See [`TextArea::set_indent_detection`].
+    pub fn indent_detection(&self) -> bool {
+        self.indent_detection
+    }
+
+    /// Enable auto-indent in [`TextArea::input`]. When enabled, `Enter` calls [`TextArea::insert_newline_and_indent`]
+    /// to indent the new line following the rules set by [`TextArea::set_indent_rules`], and typing a closing pattern
+    /// such as `}` at the start of line dedents the line. Auto-indent is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, IndentRules, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_tab_length(2);
+    /// textarea.set_indent_rules(IndentRules::braces());
+    /// textarea.set_auto_indent(true);
+    ///
+    /// let mut input = |key| {
+    ///     textarea.input(Input { key, ctrl: false, alt: false, shift: false });
+    /// };
+    /// for c in "if x {".chars() {
+    ///     input(Key::Char(c));
+    /// }
+    /// input(Key::Enter);
+    /// input(Key::Char('a'));
+    /// input(Key::Enter);
+    /// input(Key::Char('}'));
+    ///
+    /// assert_eq!(textarea.lines(), ["if x {", "  a", "}"]);
+    /// ```
+    pub fn set_auto_indent(&mut self, enabled: bool) {
+        self.auto_indent = enabled;
+    }
+
+    /// Get if auto-indent is enabled or not. See [`TextArea::set_auto_indent`].
+    pub fn auto_indent(&self) -> bool {
+        self.auto_indent
+    }
+
+    /// Set the rules to decide the indentation of lines for auto-indent and [`TextArea::reindent`]. Presets such as
+    /// [`IndentRules::braces`], [`IndentRules::python`], and [`IndentRules::yaml`] are available, or you can write your
+    /// own rules. One indentation level is the tab length, and hard tabs are used when
+    /// [`TextArea::set_hard_tab_indent`] is enabled. The default rules keep the indentation of the previous line.
+    /// ```
+    /// use tui_textarea::{TextArea, IndentRules};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_indent_rules(IndentRules::python());
+    /// assert_eq!(textarea.indent_rules(), &IndentRules::python());
+    /// ```
+    pub fn set_indent_rules(&mut self, rules: IndentRules) {
+        self.indent_rules = rules;
+    }
+
+    /// Get the rules to decide the indentation of lines. See [`TextArea::set_indent_rules`].
+    pub fn indent_rules(&self) -> &IndentRules {
+        &self.indent_rules
+    }
+
+    /// Insert a newline at the cursor and indent the new line following the rules set by
+    /// [`TextArea::set_indent_rules`]. The indentation is decided from the previous non-blank line. This is useful when
+    /// you define your own key mappings. The newline and the indentation are undone at once.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, IndentRules};
+    ///
+    /// let mut textarea = TextArea::from(["def f():"]);
+    /// textarea.set_indent_rules(IndentRules::python());
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.insert_newline_and_indent();
+    /// assert_eq!(textarea.lines(), ["def f():", "    "]);
+    /// assert_eq!(textarea.cursor(), (1, 4));
+    /// ```
+    pub fn insert_newline_and_indent(&mut self) {
+        self.undo_group(|t| {
+            let len = t.lines.len();
+            t.insert_newline();
+            if t.lines.len() == len {
+                return;
+            }
+            let row = t.cursor.0;
+            let width = rule_indent(&t.lines, row, &t.indent_rules, t.tab_len);
+            t.set_line_indent(row, width);
+        });
+    }
+
+    /// Re-indent the selected lines, or the cursor line when nothing is selected, following the rules set by
+    /// [`TextArea::set_indent_rules`]. Each line is indented relative to the previous non-blank line, so the first line
+    /// of the range keeps its indentation when it is already correct relative to the line above. Blank lines are not
+    /// changed. The cursor and the selection stay on the same text. All changes are undone at once. This method returns
+    /// if some line was re-indented or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, IndentRules};
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "let x = 1;", "      if x {", "y", "}", "   }"]);
+    /// textarea.set_indent_rules(IndentRules::braces());
+    ///
+    /// textarea.select_all();
+    /// assert!(textarea.reindent());
+    /// assert_eq!(
+    ///     textarea.lines(),
+    ///     ["fn f() {", "    let x = 1;", "    if x {", "        y", "    }", "}"],
+    /// );
+    /// assert!(!textarea.reindent());
+    /// ```
+    pub fn reindent(&mut self) -> bool {
+        let (start, end) = match self.selection_range() {
+            Some((start, end)) => (start.row, end.row),
+            None => (self.cursor.0, self.cursor.0),
+        };
+        self.undo_group(|t| {
+            let mut modified = false;
+            for row in start..=end {
+                if t.lines[row].trim().is_empty() {
+                    continue;
+                }
+                let width = rule_indent(&t.lines, row, &t.indent_rules, t.tab_len);
+                modified |= t.set_line_indent(row, width);
+            }
+            modified
+        })
+    }
+
+    // Dedent the line while typing the closing pattern like `}` or `else:` at the start of line with auto-indent
+    fn indent_on_typing(&mut self) {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let head = line.trim();
+        if col != line.chars().count()
+            || head.contains(char::is_whitespace)
+            || !self.indent_rules.decreases(line)
+        {
+            return;
+        }
+        let width = rule_indent(&self.lines, row, &self.indent_rules, self.tab_len);
+        self.undo_group(|t| t.set_line_indent(row, width));
+    }
+
+    // Replace the indentation of the line with the width. The cursor and the selection start stay on the same text
+    fn set_line_indent(&mut self, row: usize, width: usize) -> bool {
+        let indent = indent_string(width, self.tab_len, self.hard_tab_indent);
+        let line = &self.lines[row];
+        let old = line.len()
+            - line
+                .trim_start_matches(|c: char| c == ' ' || c == '\t')
+                .len();
+        if line[..old] == indent || !self.protection.allows((row, 0), (row, old)) {
+            return false;
+        }
+
+        let (cursor, selection_start) = (self.cursor, self.selection_start.take());
+        if old > 0 {
+            self.delete_range(Pos::new(row, 0, 0), Pos::new(row, old, old), false);
+        }
+        self.cursor = (row, 0);
+        self.insert_piece(indent.clone());
+
+        let new = indent.len();
+        self.cursor = shift_in_row(cursor, row, old, new);
+        self.selection_start = selection_start.map(|p| shift_in_row(p, row, old, new));
+        true
+    }
+
+    /// Apply the settings in [EditorConfig](https://editorconfig.org/) files for the file at the path. `.editorconfig`
+    /// files are searched in the directory of the file and its ancestors until a file with `root = true`. The
+    /// following properties are supported.
+    ///
+    /// - `indent_style`: [`TextArea::set_hard_tab_indent`]
+    /// - `indent_size` and `tab_width`: [`TextArea::set_tab_length`]
+    /// - `insert_final_newline`: [`TextArea::set_final_newline`] with [`FinalNewline::Always`] or
+    ///   [`FinalNewline::Never`]
+    ///
+    /// It returns whether any setting was applied. The file itself does not need to exist.
+    /// ```
+    /// use std::fs;
+    /// use tui_textarea::{FinalNewline, TextArea};
+    ///
+    /// let dir = std::env::temp_dir().join("tui-textarea-editorconfig-example");
+    /// fs::create_dir_all(&dir).unwrap();
+    /// fs::write(
+    ///     dir.join(".editorconfig"),
+    ///     "root = true\n[*.yml]\nindent_style = space\nindent_size = 2\ninsert_final_newline = true\n",
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_hard_tab_indent(true);
+    /// assert!(textarea.apply_editorconfig(dir.join("config.yml")).unwrap());
+    /// assert!(!textarea.hard_tab_indent());
+    /// assert_eq!(textarea.tab_length(), 2);
+    /// assert_eq!(textarea.final_newline(), FinalNewline::Always);
+    ///
+    /// assert!(!textarea.apply_editorconfig(dir.join("main.rs")).unwrap());
+    /// ```
+    #[cfg(feature = "editorconfig")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "editorconfig")))]
+    pub fn apply_editorconfig(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<bool> {
+        let settings = editorconfig::load(path.as_ref())?;
+        if let Some(hard_tab) = settings.hard_tab {
+            self.hard_tab_indent = hard_tab;
+        }
+        if let Some(len) = settings.tab_len {
+            self.tab_len = len;
+        }
+        if let Some(policy) = settings.final_newline {
+            self.final_newline = policy;
+        }
+        Ok(!settings.is_empty())
+    }
+
+    /// Set the punctuation characters treated as a part of words by word motions ([`CursorMove::WordForward`] and
+    /// [`CursorMove::WordBack`]), word deletions ([`TextArea::delete_word`] and [`TextArea::delete_next_word`]),
+    /// selecting a word by double click, and expanding the selection. By default, no punctuation is a part of words.
+    /// For example, `"_"` fits identifiers in code and `"-./"` fits options and paths in shell commands. Only ASCII
+    /// punctuation is affected since other characters are already a part of words except for whitespaces.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["ls --all foo_bar"]);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.delete_word();
+    /// assert_eq!(textarea.lines(), ["ls --all foo_"]);
+    ///
+    /// textarea.set_word_chars("-_");
+    /// textarea.delete_word();
+    /// assert_eq!(textarea.lines(), ["ls --all "]);
+    /// textarea.move_cursor(CursorMove::WordBack);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    pub fn set_word_chars(&mut self, chars: impl Into<String>) {
+        self.words.word_chars = chars.into();
+    }
+
+    /// Get the punctuation characters treated as a part of words set by [`TextArea::set_word_chars`]. It is empty by
+    /// default.
+    pub fn word_chars(&self) -> &str {
+        &self.words.word_chars
+    }
+
+    /// Set the characters which always separate words. They are never a part of words even if they are set by
+    /// [`TextArea::set_word_chars`] or they are inside a word by [`TextArea::set_unicode_words`]. Unlike word
+    /// characters, any character can be a separator. For example, `"_"` splits identifiers such as `foo_bar` into words
+    /// with UAX #29 word boundaries. It affects the same operations as [`TextArea::set_word_chars`]. By default, it is
+    /// empty.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo_bar baz"]);
+    /// textarea.set_unicode_words(true);
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    ///
+    /// textarea.move_cursor(CursorMove::Head);
+    /// textarea.set_word_separators("_");
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    pub fn set_word_separators(&mut self, chars: impl Into<String>) {
+        self.words.separators = chars.into();
+    }
+
+    /// Get the characters which separate words set by [`TextArea::set_word_separators`]. It is empty by default.
+    pub fn word_separators(&self) -> &str {
+        &self.words.separators
+    }
+
+    /// Set if words are split at the word boundaries defined by [UAX #29][uax29] instead of at each punctuation
+    /// character. It affects the same operations as [`TextArea::set_word_chars`]. When enabled, punctuation inside a
+    /// word such as `'` in `don't` and `_` in `foo_bar` is a part of the word. Punctuation set by
+    /// [`TextArea::set_word_chars`] is still a part of words. By default, this is disabled.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["don't stop"]);
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    ///
+    /// textarea.move_cursor(CursorMove::Head);
+    /// textarea.set_unicode_words(true);
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// assert_eq!(textarea.cursor(), (0, 6));
+    /// ```
+    pub fn set_unicode_words(&mut self, enabled: bool) {
+        self.words.unicode = enabled;
+    }
+
+    /// Get if words are split at UAX #29 word boundaries set by [`TextArea::set_unicode_words`].
+    pub fn unicode_words(&self) -> bool {
+        self.words.unicode
+    }
+
+    /// Set if the default key mappings of word motions (Alt+F, Alt+B, Ctrl+Right and Ctrl+Left) move the cursor by
+    /// sub-words. When enabled, they work as [`CursorMove::SubwordForward`] and [`CursorMove::SubwordBack`], which also
+    /// stop inside identifiers such as `fooBar` and `foo_bar`. Word deletions and [`CursorMove::WordForward`] passed to
+    /// [`TextArea::move_cursor`] are not affected. By default, this is disabled.
+    /// ```
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["fooBar baz"]);
+    /// let ctrl_right = Input { key: Key::Right, ctrl: true, ..Default::default() };
+    ///
+    /// textarea.set_subword_motion(true);
+    /// textarea.input(ctrl_right.clone());
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    ///
+    /// textarea.set_subword_motion(false);
+    /// textarea.input(ctrl_right);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// ```
+    pub fn set_subword_motion(&mut self, enabled: bool) {
+        self.subword_motion = enabled;
+    }
+
+    /// Set if the cursor can be put beyond the end of line. In virtual space mode, moving forward at the end of line
+    /// moves the cursor into the virtual space instead of the next line, and moving up and down keeps the column even
+    /// on shorter lines. Typing text there fills the gap with spaces first. This is useful for block editing and
+    /// drawing ASCII diagrams. [`TextArea::cursor`] is always inside the text, and the position including the virtual
+    /// space is returned by [`TextArea::virtual_cursor`]. By default, this is disabled.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["ab", "abcdef"]);
+    /// textarea.set_virtual_space(true);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// assert_eq!(textarea.virtual_cursor(), (0, 4));
+    ///
+    /// textarea.insert_char('x');
+    /// assert_eq!(textarea.lines(), ["ab  x", "abcdef"]);
+    ///
+    /// // The column is kept beyond the end of line
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::Up);
+    /// assert_eq!(textarea.virtual_cursor(), (0, 6));
+    /// ```
+    pub fn set_virtual_space(&mut self, enabled: bool) {
+        self.virtual_space = enabled;
+        self.virtual_pos = None;
+    }
+
+    /// Get if the cursor can be put beyond the end of line. See [`TextArea::set_virtual_space`].
+    pub fn virtual_space(&self) -> bool {
+        self.virtual_space
+    }
+
+    /// Get if the default key mappings of word motions move the cursor by sub-words. See
+    /// [`TextArea::set_subword_motion`].
+    pub fn subword_motion(&self) -> bool {
+        self.subword_motion
+    }
+
+    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.indent(), "    ");
+    /// textarea.set_tab_length(2);
+    /// assert_eq!(textarea.indent(), "  ");
+    /// textarea.set_hard_tab_indent(true);
+    /// assert_eq!(textarea.indent(), "\t");
+    /// ```
+    pub fn indent(&self) -> &'static str {
+        if self.hard_tab_indent {
+            "\t"
+        } else {
+            spaces(self.tab_len)
+        }
+    }
+
+    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
+    pub fn set_max_histories(&mut self, max: usize) {
+        let grouping = self.history.grouping();
+        self.history = History::new(max);
+        self.history.set_grouping(grouping);
+    }
+
+    /// Get how many modifications are remembered for undo/redo. The default value is 50.
+    pub fn max_histories(&self) -> usize {
+        self.history.max_items()
+    }
+
+    /// Get the approximate number of bytes held by the textarea. See [`MemoryUsage`] for the details. Long-running
+    /// applications can check it periodically and call [`TextArea::compact`] to keep the memory usage bounded.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.insert_str("hello\nworld");
+    ///
+    /// let usage = textarea.memory_usage();
+    /// assert!(usage.lines >= "helloworld".len());
+    /// assert!(usage.history > 0);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        #[allow(unused_mut)]
+        let mut caches = self.yank.memory_usage()
+            + self.expand_history.entries.capacity()
+                * std::mem::size_of::<(Option<(usize, usize)>, (usize, usize))>()
+            + self.conflict_highlight.capacity() * std::mem::size_of::<Conflict>();
+        #[cfg(feature = "search")]
+        {
+            caches += strings_size(&self.search_history.entries);
+        }
+        MemoryUsage {
+            lines: strings_size(&self.lines),
+            history: self.history.memory_usage(),
+            caches,
+        }
+    }
+
+    /// Drop the undo/redo history and the data counted as caches in [`MemoryUsage`], and shrink the buffers of lines to
+    /// fit their contents. The text, the cursor, and other settings are not changed. Only the newest yanked text is
+    /// kept so that it can still be pasted.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.insert_str("hello\nworld");
+    /// let before = textarea.memory_usage();
+    ///
+    /// textarea.compact();
+    /// let after = textarea.memory_usage();
+    /// assert_eq!(after.history, 0);
+    /// assert!(after.total() < before.total());
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert!(!textarea.undo());
+    /// ```
+    pub fn compact(&mut self) {
+        self.set_max_histories(self.max_histories());
+        self.last_paste = None;
+
+        let len = self.yank.entries.len();
+        self.yank.entries.drain(..len.saturating_sub(1));
+        self.yank.entries.shrink_to_fit();
+
+        self.expand_history.entries = vec![];
+        self.expand_history.expanded = None;
+        self.conflict_highlight.shrink_to_fit();
+        #[cfg(feature = "search")]
+        {
+            self.search_history.entries = vec![];
+        }
+
+        for line in &mut self.lines {
+            line.shrink_to_fit();
+        }
+        self.lines.shrink_to_fit();
+    }
+
+    /// Check the invariants of the internal state: the text has at least one line, no line contains a newline, and the
+    /// cursor and the start of the selection are inside the text. These invariants are checked after each input in
+    /// debug builds. This method is available in release builds too, so that fuzzers and property tests can assert
+    /// the consistency after arbitrary inputs. The first broken invariant is returned as [`InvariantError`].
+    /// ```
+    /// use tui_textarea::{Input, InvariantError, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// for key in [Key::Down, Key::End, Key::Backspace, Key::Up, Key::Enter] {
+    ///     textarea.input(Input { key, ..Default::default() });
+    ///     assert_eq!(textarea.validate(), Ok(()));
+    /// }
+    ///
+    /// // Lines must not contain newlines
+    /// let textarea = TextArea::from(["hello\nworld"]);
+    /// assert_eq!(textarea.validate(), Err(InvariantError::NewlineInLine { row: 0 }));
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        validate(&self.lines, self.cursor, self.selection_start)
+    }
+
+    /// Set the policy to group modifications into undo steps. See [`UndoGrouping`] for the policies. The default policy
+    /// is [`UndoGrouping::Keystroke`], which undoes each modification separately.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key, UndoGrouping};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_undo_grouping(UndoGrouping::Word);
+    ///
+    /// for c in "hello world".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ..Default::default() });
+    /// }
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["hello "]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn set_undo_grouping(&mut self, grouping: UndoGrouping) {
+        self.history.set_grouping(grouping);
+    }
+
+    /// Get the policy to group modifications into undo steps set by [`TextArea::set_undo_grouping`].
+    pub fn undo_grouping(&self) -> UndoGrouping {
+        self.history.grouping()
+    }
+
+    /// End the current undo group. The next modification starts a new undo step regardless of the policy set by
+    /// [`TextArea::set_undo_grouping`]. With [`UndoGrouping::Explicit`], this is the only way to split undo steps.
+    /// ```
+    /// use tui_textarea::{TextArea, UndoGrouping};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_undo_grouping(UndoGrouping::Explicit);
+    ///
+    /// textarea.insert_str("foo");
+    /// textarea.insert_newline();
+    /// textarea.commit_undo_group();
+    /// textarea.insert_str("bar");
+    /// textarea.insert_newline();
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["foo", ""]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn commit_undo_group(&mut self) {
+        self.history.commit();
+    }
+
+    /// Take a snapshot of the undo/redo history. The snapshot can be restored with [`TextArea::set_undo_history`].
+    /// When `serde` feature is enabled, it can be serialized to persist the history across sessions. See
+    /// [`UndoHistory`] for the example.
+    pub fn undo_history(&self) -> UndoHistory {
+        UndoHistory::new(self.history.clone(), &self.lines)
+    }
+
+    /// Restore the undo/redo history from the snapshot taken by [`TextArea::undo_history`]. The history can be restored
+    /// only when the current text is the same as the text when the snapshot was taken. Otherwise the history is not
+    /// changed and this method returns `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.insert_str("hello");
+    /// let history = textarea.undo_history();
+    ///
+    /// // The document was modified outside
+    /// let mut textarea = TextArea::from(["hello, world"]);
+    /// assert!(!textarea.set_undo_history(history));
+    /// ```
+    pub fn set_undo_history(&mut self, history: UndoHistory) -> bool {
+        match history.restore(&self.lines) {
+            Some(history) => {
+                self.history = history;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
+    /// cursor line, set the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_cursor_line_style(style);
+    /// assert_eq!(textarea.cursor_line_style(), style);
+    ///
+    /// // Disable cursor line style
+    /// textarea.set_cursor_line_style(Style::default());
+    /// ```
+    pub fn set_cursor_line_style(&mut self, style: Style) {
+        self.cursor_line_style = style;
+    }
+
+    /// Get the style of cursor line. By default it is styled with underline.
+    pub fn cursor_line_style(&self) -> Style {
+        self.cursor_line_style
+    }
+
+    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
+    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
+    /// the default style.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Show line numbers in dark gray background
+    /// let style = Style::default().bg(Color::DarkGray);
+    /// textarea.set_line_number_style(style);
+    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// ```
+    pub fn set_line_number_style(&mut self, style: Style) {
+        self.line_number_style = Some(style);
+    }
+
+    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
+    /// method, Line numbers will no longer be shown.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
+    /// textarea.remove_line_number();
+    /// assert_eq!(textarea.line_number_style(), None);
+    /// ```
+    pub fn remove_line_number(&mut self) {
+        self.line_number_style = None;
+    }
+
+    /// Get the style of line number if set.
+    pub fn line_number_style(&self) -> Option<Style> {
+        self.line_number_style
+    }
+
+    /// Set the character and the style of indent guides. By setting them with this method, vertical guides are drawn
+    /// in the indentation of each line at every indentation level so that deeply nested text remains readable. The
+    /// width of one indentation level is the tab width set by [`TextArea::set_tab_length`]. Indent guides are
+    /// disabled by default.
+    ///
+    /// Guides are not drawn over the cursor and highlighted text such as the selection.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::DarkGray);
+    /// textarea.set_indent_guide('│', style);
+    /// assert_eq!(textarea.indent_guide(), Some(('│', style)));
+    /// ```
+    pub fn set_indent_guide(&mut self, c: char, style: Style) {
+        self.indent_guide = Some((c, style));
+    }
+
+    /// Remove indent guides set by [`TextArea::set_indent_guide`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_indent_guide('│', Style::default());
+    /// textarea.remove_indent_guide();
+    /// assert_eq!(textarea.indent_guide(), None);
+    /// ```
+    pub fn remove_indent_guide(&mut self) {
+        self.indent_guide = None;
+    }
+
+    /// Get the character and the style of indent guides if set.
+    pub fn indent_guide(&self) -> Option<(char, Style)> {
+        self.indent_guide
+    }
+
+    /// Set if whitespaces are visualized. When `true`, spaces are rendered as `·` and tabs are rendered as `→` with
+    /// the style set by [`TextArea::set_whitespace_style`]. Whitespaces at the end of lines are rendered with the
+    /// style set by [`TextArea::set_trailing_whitespace_style`]. This is useful for debugging indentation issues.
+    /// Whitespaces are not visualized by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.whitespace_visible());
+    ///
+    /// textarea.set_whitespace_visible(true);
+    /// assert!(textarea.whitespace_visible());
+    /// ```
+    pub fn set_whitespace_visible(&mut self, visible: bool) {
+        self.show_whitespace = visible;
+    }
+
+    /// Get if whitespaces are visualized.
+    pub fn whitespace_visible(&self) -> bool {
+        self.show_whitespace
+    }
+
+    /// Set the style of visualized whitespaces. The default style is a dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_whitespace_style(style);
+    /// assert_eq!(textarea.whitespace_style(), style);
+    /// ```
+    pub fn set_whitespace_style(&mut self, style: Style) {
+        self.whitespace_style = style;
+    }
+
+    /// Get the style of visualized whitespaces.
+    pub fn whitespace_style(&self) -> Style {
+        self.whitespace_style
+    }
+
+    /// Set the style of visualized whitespaces at the end of lines. The default style is a red text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_trailing_whitespace_style(style);
+    /// assert_eq!(textarea.trailing_whitespace_style(), style);
+    /// ```
+    pub fn set_trailing_whitespace_style(&mut self, style: Style) {
+        self.trailing_whitespace_style = style;
+    }
+
+    /// Get the style of visualized whitespaces at the end of lines.
+    pub fn trailing_whitespace_style(&self) -> Style {
+        self.trailing_whitespace_style
+    }
+
+    /// Set the notation to render control characters such as `\r` and `\x1b`. See [`ControlCharDisplay`] for the
+    /// notations. The default notation is [`ControlCharDisplay::Caret`].
+    /// ```
+    /// use tui_textarea::{ControlCharDisplay, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_control_char_display(ControlCharDisplay::Picture);
+    /// assert_eq!(textarea.control_char_display(), ControlCharDisplay::Picture);
+    /// ```
+    pub fn set_control_char_display(&mut self, display: ControlCharDisplay) {
+        self.control_char_display = display;
+    }
+
+    /// Get the notation to render control characters.
+    pub fn control_char_display(&self) -> ControlCharDisplay {
+        self.control_char_display
+    }
+
+    /// Set the style of rendered control characters. The default style is a blue text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Magenta);
+    /// textarea.set_control_char_style(style);
+    /// assert_eq!(textarea.control_char_style(), style);
+    /// ```
+    pub fn set_control_char_style(&mut self, style: Style) {
+        self.control_char_style = style;
+    }
+
+    /// Get the style of rendered control characters.
+    pub fn control_char_style(&self) -> Style {
+        self.control_char_style
+    }
+
+    /// Set the width of East Asian ambiguous characters such as `○` and `→` to match the terminal. See
+    /// [`AmbiguousWidth`] for more details. The default width is [`AmbiguousWidth::Narrow`]. With the wide width,
+    /// visualized whitespaces are rendered as `.` and `>` since `·` and `→` are ambiguous characters.
+    /// ```
+    /// use tui_textarea::{AmbiguousWidth, CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["○"]);
+    ///
+    /// textarea.set_ambiguous_width(AmbiguousWidth::Wide);
+    /// assert_eq!(textarea.ambiguous_width(), AmbiguousWidth::Wide);
+    ///
+    /// // Soft tab fills the spaces until the next tab stop after the wide character
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["○  "]);
+    /// ```
+    pub fn set_ambiguous_width(&mut self, width: AmbiguousWidth) {
+        self.ambiguous_width = width;
+    }
+
+    /// Get the width of East Asian ambiguous characters.
+    pub fn ambiguous_width(&self) -> AmbiguousWidth {
+        self.ambiguous_width
+    }
+
+    /// Enable rendering bidirectional text. Text is stored in the logical order and lines containing right-to-left
+    /// text such as Arabic and Hebrew are reordered visually on rendering by the Unicode bidirectional algorithm. The
+    /// base direction of lines is left-to-right. The cursor moves in the logical order and is rendered at the visual
+    /// position of the character. Mouse clicks are also mapped to the characters at the visual positions. Tab widths
+    /// are calculated in the logical order. Enable this only for terminals which don't reorder text by themselves.
+    /// This is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc אבג"]);
+    /// assert_eq!(textarea.render_to_string(8, 1), "abc אבג ");
+    ///
+    /// textarea.set_bidi(true);
+    /// assert!(textarea.bidi());
+    /// assert_eq!(textarea.render_to_string(8, 1), "abc גבא ");
+    /// ```
+    #[cfg(feature = "bidi")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bidi")))]
+    pub fn set_bidi(&mut self, enabled: bool) {
+        self.bidi = enabled;
+    }
+
+    /// Get if bidirectional text is reordered on rendering.
+    #[cfg(feature = "bidi")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bidi")))]
+    pub fn bidi(&self) -> bool {
+        self.bidi
+    }
+
+    /// Enable highlighting the other occurrences of the identifier under the cursor. The occurrences are recomputed
+    /// as the cursor moves and rendered with [`TextArea::word_highlight_style`]. This is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.word_highlight());
+    /// textarea.set_word_highlight(true);
+    /// assert!(textarea.word_highlight());
+    /// ```
+    pub fn set_word_highlight(&mut self, enabled: bool) {
+        self.word_highlight = enabled;
+    }
+
+    /// Get if the other occurrences of the identifier under the cursor are highlighted.
+    pub fn word_highlight(&self) -> bool {
+        self.word_highlight
+    }
+
+    /// Set the style of the other occurrences of the identifier under the cursor. The default style is bold and
+    /// underlined.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::DarkGray);
+    /// textarea.set_word_highlight_style(style);
+    /// assert_eq!(textarea.word_highlight_style(), style);
+    /// ```
+    pub fn set_word_highlight_style(&mut self, style: Style) {
+        self.word_highlight_style = style;
+    }
+
+    /// Get the style of the other occurrences of the identifier under the cursor.
+    pub fn word_highlight_style(&self) -> Style {
+        self.word_highlight_style
+    }
+
+    // Identifier at the cursor or just before the cursor
+    fn ident_at_cursor(&self) -> Option<&str> {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let (start, end) = find_ident_at(line, col)?;
+        Some(&line[start..end])
+    }
+
+    /// Set the annotations of lines rendered in the blame column, such as `abc123 alice 2023-04-01` from `git blame`.
+    /// The n-th annotation is for the n-th line. The blame column is rendered at the leftmost of the gutter and is
+    /// scrolled along with the text. Its width is the width of the longest annotation plus one space.
+    ///
+    /// On inserting lines, the new lines have empty annotations. On deleting lines, their annotations are removed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {", "}"]);
+    /// textarea.set_blame_column(["abc123 alice", "def456 bob"]);
+    /// assert_eq!(textarea.blame_column(), ["abc123 alice", "def456 bob"]);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.blame_column(), ["", "abc123 alice", "def456 bob"]);
+    /// ```
+    pub fn set_blame_column<I>(&mut self, annotations: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.gutter
+            .set_blame(annotations.into_iter().map(Into::into).collect());
+    }
+
+    /// Remove all annotations set by [`TextArea::set_blame_column`]. The blame column is no longer shown.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.set_blame_column(["abc123 alice"]);
+    /// textarea.clear_blame_column();
+    /// assert!(textarea.blame_column().is_empty());
+    /// ```
+    pub fn clear_blame_column(&mut self) {
+        self.gutter.set_blame(vec![]);
+    }
+
+    /// Get the annotations of lines in the blame column. See [`TextArea::set_blame_column`].
+    pub fn blame_column(&self) -> &[String] {
+        &self.gutter.blame
+    }
+
+    /// Set the style of the annotations in the blame column. The default style is dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_blame_style(style);
+    /// assert_eq!(textarea.blame_style(), style);
+    /// ```
+    pub fn set_blame_style(&mut self, style: Style) {
+        self.gutter.blame_style = style;
+    }
+
+    /// Get the style of the annotations in the blame column.
+    pub fn blame_style(&self) -> Style {
+        self.gutter.blame_style
+    }
+
+    /// Put a sign at the row in the sign column. The sign column is rendered at the left of the textarea (and line
+    /// numbers) separately from the text so that applications can mark lines with glyphs like breakpoints or errors.
+    /// The sign column is shown only while some sign is put. When a sign is already put at the row, it is replaced.
+    ///
+    /// Note that signs don't follow the lines on inserting or deleting lines.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {", "    panic!();", "}"]);
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_sign(1, "●", style);
+    /// assert_eq!(textarea.sign(1), Some(("●", style)));
+    /// assert_eq!(textarea.sign(0), None);
+    /// ```
+    pub fn set_sign(&mut self, row: usize, symbol: impl Into<String>, style: Style) {
+        self.gutter.signs.insert(row, (symbol.into(), style));
+    }
+
+    /// Remove the sign at the row put by [`TextArea::set_sign`]. It returns `true` when some sign was removed.
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.set_sign(0, "!", Style::default());
+    /// assert!(textarea.remove_sign(0));
+    /// assert!(!textarea.remove_sign(0));
+    /// ```
+    pub fn remove_sign(&mut self, row: usize) -> bool {
+        self.gutter.signs.remove(&row).is_some()
+    }
+
+    /// Remove all signs put by [`TextArea::set_sign`]. The sign column is no longer shown.
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// textarea.set_sign(0, "!", Style::default());
+    /// textarea.set_sign(1, "?", Style::default());
+    /// textarea.clear_signs();
+    /// assert_eq!(textarea.sign(0), None);
+    /// assert_eq!(textarea.sign(1), None);
+    /// ```
+    pub fn clear_signs(&mut self) {
+        self.gutter.signs.clear();
+    }
+
+    /// Get the symbol and the style of the sign at the row.
+    pub fn sign(&self, row: usize) -> Option<(&str, Style)> {
+        self.gutter
+            .signs
+            .get(&row)
+            .map(|(symbol, style)| (symbol.as_str(), *style))
+    }
+
+    /// Set the width of the sign column in cells. Signs wider than the width are truncated. The default value is 2.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.sign_column_width(), 2);
+    ///
+    /// textarea.set_sign_column_width(1);
+    /// assert_eq!(textarea.sign_column_width(), 1);
+    /// ```
+    pub fn set_sign_column_width(&mut self, width: u8) {
+        self.gutter.sign_width = width;
+    }
+
+    /// Get the width of the sign column in cells.
+    pub fn sign_column_width(&self) -> u8 {
+        self.gutter.sign_width
+    }
+
+    /// Set the base text to compare the current text with, such as the content of the file committed in VCS. When the
+    /// diff base is set, a change marker column is rendered in the gutter (next to the sign column) to show which lines
+    /// were added, modified, or removed. See [`LineChange`] for the kinds of changes.
+    /// ```
+    /// use tui_textarea::{TextArea, LineChange};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// textarea.set_diff_base(["a", "b", "c"]);
+    /// assert!(textarea.line_changes().is_empty());
+    ///
+    /// textarea.move_cursor(tui_textarea::CursorMove::Down);
+    /// textarea.insert_str("x");
+    /// assert_eq!(textarea.line_changes(), [(1, LineChange::Modified)]);
+    /// ```
+    pub fn set_diff_base<I>(&mut self, lines: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.gutter
+            .set_diff_base(Some(lines.into_iter().map(Into::into).collect()));
+    }
+
+    /// Remove the diff base set by [`TextArea::set_diff_base`]. The change marker column is no longer shown.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a"]);
+    ///
+    /// textarea.set_diff_base(["b"]);
+    /// textarea.remove_diff_base();
+    /// assert!(textarea.line_changes().is_empty());
+    /// ```
+    pub fn remove_diff_base(&mut self) {
+        self.gutter.set_diff_base(None);
+    }
+
+    /// Get the changes of lines compared with the diff base set by [`TextArea::set_diff_base`]. Each element is a pair
+    /// of the row and its change, sorted by the row. An empty vector is returned when the diff base is not set.
+    /// ```
+    /// use tui_textarea::{TextArea, LineChange};
+    ///
+    /// let mut textarea = TextArea::from(["a", "x", "c", "d"]);
+    /// textarea.set_diff_base(["a", "b", "c", "e", "d"]);
+    ///
+    /// assert_eq!(
+    ///     textarea.line_changes(),
+    ///     [(1, LineChange::Modified), (3, LineChange::Removed)],
+    /// );
+    /// ```
+    pub fn line_changes(&self) -> Vec<(usize, LineChange)> {
+        self.gutter.line_changes(&self.lines).to_vec()
+    }
+
+    // Start rows of the changed hunks
+    fn hunk_rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = vec![];
+        let mut prev = None;
+        for &(row, _) in self.gutter.line_changes(&self.lines).iter() {
+            if prev.map_or(true, |p| p + 1 < row) {
+                rows.push(row);
+            }
+            prev = Some(row);
+        }
+        rows
+    }
+
+    /// Move the cursor to the head of the next changed hunk compared with the diff base. The search wraps around the
+    /// text buffer. It returns `true` when some hunk was found. Otherwise it returns `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "x", "c", "y"]);
+    /// textarea.set_diff_base(["a", "b", "c", "d"]);
+    ///
+    /// assert!(textarea.next_hunk());
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert!(textarea.next_hunk());
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// assert!(textarea.next_hunk());
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// ```
+    pub fn next_hunk(&mut self) -> bool {
+        let rows = self.hunk_rows();
+        let row = self.cursor.0;
+        match rows.iter().find(|&&r| r > row).or_else(|| rows.first()) {
+            Some(&r) => {
+                self.cursor = (r, 0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the cursor to the head of the previous changed hunk compared with the diff base. The search wraps around
+    /// the text buffer. It returns `true` when some hunk was found. Otherwise it returns `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "x", "c", "y"]);
+    /// textarea.set_diff_base(["a", "b", "c", "d"]);
+    ///
+    /// assert!(textarea.prev_hunk());
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// assert!(textarea.prev_hunk());
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// ```
+    pub fn prev_hunk(&mut self) -> bool {
+        let rows = self.hunk_rows();
+        let row = self.cursor.0;
+        match rows
+            .iter()
+            .rev()
+            .find(|&&r| r < row)
+            .or_else(|| rows.last())
+        {
+            Some(&r) => {
+                self.cursor = (r, 0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the merge conflicts left by VCS like Git in the text, sorted by their positions. See [`Conflict`] for more
+    /// details.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from([
+    ///     "<<<<<<< HEAD",
+    ///     "ours",
+    ///     "=======",
+    ///     "theirs",
+    ///     ">>>>>>> branch",
+    /// ]);
+    ///
+    /// let conflicts = textarea.conflicts();
+    /// assert_eq!(conflicts.len(), 1);
+    /// assert_eq!((conflicts[0].start, conflicts[0].separator, conflicts[0].end), (0, 2, 4));
+    /// ```
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        find_conflicts(&self.lines)
+    }
+
+    /// Enable or disable highlighting merge conflicts. When enabled, our side, their side, and the marker lines of each
+    /// conflict are rendered with the styles set by [`TextArea::set_conflict_styles`]. Since the whole text is searched
+    /// for conflicts again on rendering after each edit, it is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.conflict_highlight());
+    ///
+    /// textarea.set_conflict_highlight(true);
+    /// assert!(textarea.conflict_highlight());
+    /// ```
+    pub fn set_conflict_highlight(&mut self, enabled: bool) {
+        self.conflict_highlight.enabled = enabled;
+    }
+
+    /// Get if merge conflicts are highlighted. See [`TextArea::set_conflict_highlight`].
+    pub fn conflict_highlight(&self) -> bool {
+        self.conflict_highlight.enabled
+    }
+
+    /// Set the styles of our side, their side, and the marker lines of merge conflicts. The styles are applied to the
+    /// entire lines. The default styles are dark green background, dark blue background, and dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let ours = Style::default().bg(Color::Green);
+    /// let theirs = Style::default().bg(Color::Blue);
+    /// let markers = Style::default().fg(Color::Gray);
+    /// textarea.set_conflict_styles(ours, theirs, markers);
+    /// assert_eq!(textarea.conflict_styles(), (ours, theirs, markers));
+    /// ```
+    pub fn set_conflict_styles(&mut self, ours: Style, theirs: Style, markers: Style) {
+        let h = &mut self.conflict_highlight;
+        h.ours_style = ours;
+        h.theirs_style = theirs;
+        h.marker_style = markers;
+    }
+
+    /// Get the styles of our side, their side, and the marker lines of merge conflicts.
+    pub fn conflict_styles(&self) -> (Style, Style, Style) {
+        let h = &self.conflict_highlight;
+        (h.ours_style, h.theirs_style, h.marker_style)
+    }
+
+    /// Enable or disable the git commit message mode for editing commit messages in TUI git clients. When enabled,
+    ///
+    /// - the subject line is rendered with the subject style, and its characters after 50 columns are rendered with
+    ///   the overflow style as a warning
+    /// - body lines are hard-wrapped at 72 columns while typing via [`TextArea::input`]. The line is broken at the last
+    ///   space which fits in the width
+    /// - lines starting with `#` are comments. They are rendered with the comment style, are not wrapped, and are
+    ///   excluded from the subject and [`TextArea::commit_message`]
+    ///
+    /// The styles can be set by [`TextArea::set_commit_message_styles`]. This mode is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Fix typo", "", ""]);
+    /// textarea.set_commit_message_mode(true);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    ///
+    /// let body = "The quick brown fox jumps over the lazy dog. The quick brown fox jumps over the lazy dog.";
+    /// for c in body.chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ctrl: false, alt: false, shift: false });
+    /// }
+    /// assert_eq!(
+    ///     textarea.lines(),
+    ///     [
+    ///         "Fix typo",
+    ///         "",
+    ///         "The quick brown fox jumps over the lazy dog. The quick brown fox jumps",
+    ///         "over the lazy dog.",
+    ///     ],
+    /// );
+    /// ```
+    pub fn set_commit_message_mode(&mut self, enabled: bool) {
+        self.commit_message.enabled = enabled;
+    }
+
+    /// Get if the git commit message mode is enabled. See [`TextArea::set_commit_message_mode`].
+    pub fn commit_message_mode(&self) -> bool {
+        self.commit_message.enabled
+    }
+
+    /// Set the styles of the subject line, the subject characters exceeding 50 columns, and the comment lines in the
+    /// git commit message mode. The default styles are bold text, red text, and dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color, Modifier};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let subject = Style::default().fg(Color::Yellow);
+    /// let overflow = Style::default().bg(Color::Red);
+    /// let comment = Style::default().add_modifier(Modifier::DIM);
+    /// textarea.set_commit_message_styles(subject, overflow, comment);
+    /// assert_eq!(textarea.commit_message_styles(), (subject, overflow, comment));
+    /// ```
+    pub fn set_commit_message_styles(&mut self, subject: Style, overflow: Style, comment: Style) {
+        let c = &mut self.commit_message;
+        c.subject_style = subject;
+        c.overflow_style = overflow;
+        c.comment_style = comment;
+    }
+
+    /// Get the styles of the subject line, the subject characters exceeding 50 columns, and the comment lines in the
+    /// git commit message mode.
+    pub fn commit_message_styles(&self) -> (Style, Style, Style) {
+        let c = &self.commit_message;
+        (c.subject_style, c.overflow_style, c.comment_style)
+    }
+
+    /// Get the text as a git commit message. Comment lines starting with `#` and trailing spaces are removed, leading
+    /// and trailing blank lines are removed, and consecutive blank lines are collapsed into one like
+    /// `git commit --cleanup=strip`. This works regardless of [`TextArea::set_commit_message_mode`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from([
+    ///     "Add feature ",
+    ///     "",
+    ///     "Details.",
+    ///     "# Please enter the commit message for your changes.",
+    ///     "",
+    /// ]);
+    /// assert_eq!(textarea.commit_message(), "Add feature\n\nDetails.");
+    /// ```
+    pub fn commit_message(&self) -> String {
+        strip_message(&self.lines)
+    }
+
+    // Break the body line of the commit message at the last space fitting in the width while typing
+    fn wrap_commit_body(&mut self) {
+        let (row, col) = self.cursor;
+        if !self.commit_message.is_body(&self.lines, row) {
+            return;
+        }
+        let ambiguous = self.ambiguous_width;
+        let line = &self.lines[row];
+        let at = match wrap_col(line, BODY_WIDTH, |c| char_width(c, ambiguous).unwrap_or(0)) {
+            Some(at) => at,
+            None => return,
+        };
+        let offset = byte_offset(line, at);
+
+        // Replace the space with a newline
+        let start = Pos::new(row, at, offset);
+        let end = Pos::new(row, at + 1, offset + 1);
+        let wrapped = self.undo_group(|t| {
+            if !t.delete_range(start, end, false) {
+                return false;
+            }
+            t.insert_newline();
+            true
+        });
+        if wrapped {
+            self.cursor = if col > at {
+                (row + 1, col - at - 1)
+            } else {
+                (row, col)
+            };
+        }
+    }
+
+    /// Set the maximum number of characters in a line. Characters of any line exceeding the limit are rendered with
+    /// the style set by [`TextArea::set_line_overflow_style`]. This is useful for protocols and forms with strict field
+    /// widths. Insertion beyond the limit can also be blocked by [`TextArea::set_block_line_overflow`]. There is no
+    /// limit by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.max_line_length(), None);
+    ///
+    /// textarea.set_max_line_length(80);
+    /// assert_eq!(textarea.max_line_length(), Some(80));
+    ///
+    /// textarea.remove_max_line_length();
+    /// assert_eq!(textarea.max_line_length(), None);
+    /// ```
+    pub fn set_max_line_length(&mut self, max: usize) {
+        self.line_limit.max = Some(max);
+    }
+
+    /// Remove the maximum number of characters in a line set by [`TextArea::set_max_line_length`].
+    pub fn remove_max_line_length(&mut self) {
+        self.line_limit.max = None;
+    }
+
+    /// Get the maximum number of characters in a line set by [`TextArea::set_max_line_length`].
+    pub fn max_line_length(&self) -> Option<usize> {
+        self.line_limit.max
+    }
+
+    /// Set the style of the characters exceeding the maximum line length set by [`TextArea::set_max_line_length`].
+    /// The default style is red background.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_line_overflow_style(style);
+    /// assert_eq!(textarea.line_overflow_style(), style);
+    /// ```
+    pub fn set_line_overflow_style(&mut self, style: Style) {
+        self.line_limit.style = style;
+    }
+
+    /// Get the style of the characters exceeding the maximum line length. See [`TextArea::set_line_overflow_style`].
+    pub fn line_overflow_style(&self) -> Style {
+        self.line_limit.style
+    }
+
+    /// Block insertion which makes a line longer than the maximum line length set by
+    /// [`TextArea::set_max_line_length`]. When enabled, typing, [`TextArea::insert_str`], [`TextArea::insert_tab`],
+    /// and pasting do nothing if some line would exceed the limit. Inserting a newline is always allowed. This is
+    /// disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_max_line_length(5);
+    /// textarea.set_block_line_overflow(true);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// assert!(textarea.insert_str("de"));
+    /// assert!(!textarea.insert_str("f"));
+    /// textarea.insert_char('g');
+    /// assert_eq!(textarea.lines(), ["abcde"]);
+    ///
+    /// // Text can be inserted after breaking the line
+    /// textarea.insert_newline();
+    /// assert!(textarea.insert_str("f"));
+    /// assert_eq!(textarea.lines(), ["abcde", "f"]);
+    /// ```
+    pub fn set_block_line_overflow(&mut self, enabled: bool) {
+        self.line_limit.block = enabled;
+    }
+
+    /// Get if insertion beyond the maximum line length is blocked. See [`TextArea::set_block_line_overflow`].
+    pub fn block_line_overflow(&self) -> bool {
+        self.line_limit.block
+    }
+
+    /// Return if some insertion was rejected by the maximum line length since the last key input. It happens only
+    /// when the insertion beyond the limit is blocked by [`TextArea::set_block_line_overflow`]. This is useful to
+    /// report the limit to users, for example by flashing the block.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.set_max_line_length(3);
+    /// textarea.set_block_line_overflow(true);
+    ///
+    /// assert!(!textarea.input(Input { key: Key::Char('d'), ..Default::default() }));
+    /// assert!(textarea.line_length_rejected());
+    /// assert_eq!(textarea.lines(), ["abc"]);
+    /// ```
+    pub fn line_length_rejected(&self) -> bool {
+        self.line_limit_rejected
+    }
+
+    // Check inserting the lines of the numbers of characters at the cursor keeps all lines within the maximum line
+    // length when the insertion beyond the limit is blocked. The rejection is remembered to report it
+    fn fits_line_limit(&mut self, inserted: &[usize]) -> bool {
+        let (row, col) = self.cursor;
+        let after = self.lines[row].chars().count() - col;
+        let fits = self
+            .line_limit
+            .allows(col + self.virtual_col(), after, inserted);
+        self.line_limit_rejected |= !fits;
+        fits
+    }
+
+    /// Set the maximum number of lines in the textarea. This is useful for a small input such as a 5-line reply box.
+    /// When the textarea has the maximum number of lines, inserting a newline, [`TextArea::insert_str`] and pasting
+    /// which would add lines do nothing, and [`TextArea::input`] returns `false` for them. The existing text is not
+    /// truncated even if it has more lines than the limit. There is no limit by default.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_max_lines(2);
+    /// assert_eq!(textarea.max_lines(), Some(2));
+    ///
+    /// assert!(textarea.input(Input { key: Key::Enter, ..Default::default() }));
+    /// // The third line is rejected
+    /// assert!(!textarea.input(Input { key: Key::Enter, ..Default::default() }));
+    /// assert!(textarea.max_lines_rejected());
+    /// assert!(!textarea.insert_str("a\nb"));
+    /// assert_eq!(textarea.lines(), ["", ""]);
+    ///
+    /// // Text without newlines can still be inserted
+    /// assert!(textarea.insert_str("a"));
+    /// assert_eq!(textarea.lines(), ["", "a"]);
+    /// ```
+    pub fn set_max_lines(&mut self, max: usize) {
+        self.max_lines = Some(max);
+    }
+
+    /// Remove the maximum number of lines set by [`TextArea::set_max_lines`].
+    pub fn remove_max_lines(&mut self) {
+        self.max_lines = None;
+    }
+
+    /// Get the maximum number of lines set by [`TextArea::set_max_lines`].
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    /// Return if some insertion was rejected by the maximum number of lines set by [`TextArea::set_max_lines`] since
+    /// the last key input. This is useful to report the limit to users, for example by flashing the block.
+    pub fn max_lines_rejected(&self) -> bool {
+        self.max_lines_rejected
+    }
+
+    // Whether some edit was rejected since the last key input by protected regions, by the maximum line length, or by
+    // the maximum number of lines
+    fn rejected(&self) -> bool {
+        self.protection.rejected || self.line_limit_rejected || self.max_lines_rejected
+    }
+
+    // Check adding the number of lines keeps the text within the maximum number of lines. The rejection is remembered
+    // to report it
+    fn fits_max_lines(&mut self, added: usize) -> bool {
+        let fits = added == 0
+            || self
+                .max_lines
+                .map_or(true, |max| self.lines.len() + added <= max);
+        self.max_lines_rejected |= !fits;
+        fits
+    }
+
+    /// Move the cursor to the head of the next merge conflict. The search wraps around the text buffer. It returns
+    /// `true` when some conflict was found. Otherwise it returns `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "<<<<<<<", "a", "=======", "b", ">>>>>>>",
+    ///     "<<<<<<<", "c", "=======", "d", ">>>>>>>",
+    /// ]);
+    ///
+    /// assert!(textarea.next_conflict());
+    /// assert_eq!(textarea.cursor(), (5, 0));
+    /// assert!(textarea.next_conflict());
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    pub fn next_conflict(&mut self) -> bool {
+        let conflicts = self.conflicts();
+        let row = self.cursor.0;
+        match conflicts
+            .iter()
+            .find(|c| c.start > row)
+            .or_else(|| conflicts.first())
+        {
+            Some(c) => {
+                self.cursor = (c.start, 0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the cursor to the head of the previous merge conflict. The search wraps around the text buffer. It returns
+    /// `true` when some conflict was found. Otherwise it returns `false`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "<<<<<<<", "a", "=======", "b", ">>>>>>>",
+    ///     "<<<<<<<", "c", "=======", "d", ">>>>>>>",
+    /// ]);
+    ///
+    /// assert!(textarea.prev_conflict());
+    /// assert_eq!(textarea.cursor(), (5, 0));
+    /// assert!(textarea.prev_conflict());
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    pub fn prev_conflict(&mut self) -> bool {
+        let conflicts = self.conflicts();
+        let row = self.cursor.0;
+        match conflicts
+            .iter()
+            .rev()
+            .find(|c| c.start < row)
+            .or_else(|| conflicts.last())
+        {
+            Some(c) => {
+                self.cursor = (c.start, 0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resolve the merge conflict at the cursor by keeping our side, their side, or both. The marker lines are removed
+    /// and the cursor is moved to the head of the kept lines. This method returns `false` when the cursor is not in
+    /// any conflict.
+    /// ```
+    /// use tui_textarea::{TextArea, ConflictResolution};
+    ///
+    /// let lines = ["x", "<<<<<<< HEAD", "ours", "=======", "theirs", ">>>>>>> branch", "y"];
+    ///
+    /// let mut textarea = TextArea::from(lines);
+    /// assert!(!textarea.resolve_conflict(ConflictResolution::Ours));
+    ///
+    /// textarea.next_conflict();
+    /// assert!(textarea.resolve_conflict(ConflictResolution::Ours));
+    /// assert_eq!(textarea.lines(), ["x", "ours", "y"]);
+    ///
+    /// let mut textarea = TextArea::from(lines);
+    /// textarea.next_conflict();
+    /// assert!(textarea.resolve_conflict(ConflictResolution::Both));
+    /// assert_eq!(textarea.lines(), ["x", "ours", "theirs", "y"]);
+    /// ```
+    pub fn resolve_conflict(&mut self, resolution: ConflictResolution) -> bool {
+        let row = self.cursor.0;
+        let conflict = match self
+            .conflicts()
+            .into_iter()
+            .find(|c| c.start <= row && row <= c.end)
+        {
+            Some(c) => c,
+            None => return false,
+        };
+        let kept: Vec<String> = match resolution {
+            ConflictResolution::Ours => self.lines[conflict.ours()].to_vec(),
+            ConflictResolution::Theirs => self.lines[conflict.theirs()].to_vec(),
+            ConflictResolution::Both => {
+                let mut lines = self.lines[conflict.ours()].to_vec();
+                lines.extend_from_slice(&self.lines[conflict.theirs()]);
+                lines
+            }
+        };
+
+        // Delete the entire lines of the conflict including the newline after them so that no empty line remains when
+        // nothing is kept
+        let (start, end) = (conflict.start, conflict.end);
+        let (start, end) = if end + 1 < self.lines.len() {
+            ((start, 0), (end + 1, 0))
+        } else if start > 0 {
+            let prev = self.lines[start - 1].chars().count();
+            ((start - 1, prev), (end, self.lines[end].chars().count()))
+        } else {
+            ((start, 0), (end, self.lines[end].chars().count()))
+        };
+        self.cancel_selection();
+        let start_pos = Pos::new(start.0, start.1, self.line_offset(start.0, start.1));
+        let end_pos = Pos::new(end.0, end.1, self.line_offset(end.0, end.1));
+        if !self.delete_range(start_pos, end_pos, false) {
+            return false;
+        }
+        if !kept.is_empty() {
+            let mut text = kept.join("\n");
+            if start.1 == 0 && end.1 == 0 {
+                text.push('\n');
+            } else if start.1 > 0 {
+                text.insert(0, '\n');
+            }
+            self.insert_str(text);
+        }
+        self.cursor = (cmp::min(conflict.start, self.lines.len() - 1), 0);
+        true
+    }
+
+    /// Set the symbol and the style of the change marker for the kind of change. Only the first cell of the symbol is
+    /// rendered. The default markers are green `▎` for added lines, yellow `▎` for modified lines, and red `▁` for
+    /// removed lines.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::{TextArea, LineChange};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_change_marker(LineChange::Added, "+", style);
+    /// assert_eq!(textarea.change_marker(LineChange::Added), ("+", style));
+    /// ```
+    pub fn set_change_marker(
+        &mut self,
+        change: LineChange,
+        symbol: impl Into<String>,
+        style: Style,
+    ) {
+        self.gutter.change_markers[change.index()] = (symbol.into(), style);
+    }
+
+    /// Get the symbol and the style of the change marker for the kind of change.
+    pub fn change_marker(&self, change: LineChange) -> (&str, Style) {
+        let (symbol, style) = &self.gutter.change_markers[change.index()];
+        (symbol.as_str(), *style)
+    }
+
+    /// Set inlay hints, which are read-only virtual text fragments rendered at arbitrary positions in the textarea (e.g.
+    /// type hints after identifiers). They don't affect the text and the cursor skips them. Previous inlay hints are
+    /// replaced. See [`InlayHint`] for more details.
+    ///
+    /// Note that the positions of the hints are not updated on editing the text. Set the new hints after the text was
+    /// modified.
+    /// ```
+    /// use tui_textarea::{TextArea, InlayHint};
+    ///
+    /// let mut textarea = TextArea::from(["let x = 42;"]);
+    ///
+    /// textarea.set_inlay_hints(vec![InlayHint::new((0, 5), ": i32")]);
+    /// assert_eq!(textarea.inlay_hints().len(), 1);
+    ///
+    /// // Inlay hints don't affect the text
+    /// assert_eq!(textarea.lines(), ["let x = 42;"]);
+    /// ```
+    pub fn set_inlay_hints(&mut self, mut hints: Vec<InlayHint>) {
+        hints.sort_by_key(|h| h.position);
+        self.inlay_hints = hints;
+    }
+
+    /// Get the inlay hints set by [`TextArea::set_inlay_hints`]. They are sorted by their positions.
+    /// ```
+    /// use tui_textarea::{TextArea, InlayHint};
+    ///
+    /// let mut textarea = TextArea::from(["foo(1, 2)"]);
+    /// textarea.set_inlay_hints(vec![
+    ///     InlayHint::new((0, 7), "y: "),
+    ///     InlayHint::new((0, 4), "x: "),
+    /// ]);
+    ///
+    /// let positions: Vec<_> = textarea.inlay_hints().iter().map(|h| h.position).collect();
+    /// assert_eq!(positions, [(0, 4), (0, 7)]);
+    /// ```
+    pub fn inlay_hints(&self) -> &[InlayHint] {
+        &self.inlay_hints
+    }
+
+    /// Remove all inlay hints set by [`TextArea::set_inlay_hints`].
+    /// ```
+    /// use tui_textarea::{TextArea, InlayHint};
+    ///
+    /// let mut textarea = TextArea::from(["let x = 42;"]);
+    ///
+    /// textarea.set_inlay_hints(vec![InlayHint::new((0, 5), ": i32")]);
+    /// textarea.clear_inlay_hints();
+    /// assert!(textarea.inlay_hints().is_empty());
+    /// ```
+    pub fn clear_inlay_hints(&mut self) {
+        self.inlay_hints.clear();
+    }
+
+    pub(crate) fn inlay_hints_at(&self, row: usize) -> &[InlayHint] {
+        let start = self.inlay_hints.partition_point(|h| h.position.0 < row);
+        let end = self.inlay_hints.partition_point(|h| h.position.0 <= row);
+        &self.inlay_hints[start..end]
+    }
+
+    /// Set the default style of inlay hints. Inlay hints which have their own styles are not affected. The default
+    /// style is a dark gray italic text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Gray);
+    /// textarea.set_inlay_hint_style(style);
+    /// assert_eq!(textarea.inlay_hint_style(), style);
+    /// ```
+    pub fn set_inlay_hint_style(&mut self, style: Style) {
+        self.inlay_hint_style = style;
+    }
+
+    /// Get the default style of inlay hints.
+    pub fn inlay_hint_style(&self) -> Style {
+        self.inlay_hint_style
+    }
+
+    /// Set hyperlinks attached to ranges of text. The link text is rendered with the link style and, when
+    /// [`TextArea::set_osc8_hyperlinks`] is enabled, it is clickable in terminals supporting OSC 8 hyperlinks. Previous
+    /// hyperlinks are replaced. See [`Hyperlink`] for more details.
+    ///
+    /// Note that the positions of the links are not updated on editing the text. Set the new links after the text was
+    /// modified.
+    /// ```
+    /// use tui_textarea::{TextArea, Hyperlink};
+    ///
+    /// let mut textarea = TextArea::from(["see the docs"]);
+    ///
+    /// textarea.set_hyperlinks(vec![Hyperlink::new(0, (8, 12), "https://docs.rs")]);
+    /// assert_eq!(textarea.hyperlinks().len(), 1);
+    /// ```
+    pub fn set_hyperlinks(&mut self, mut links: Vec<Hyperlink>) {
+        links.sort_by_key(|l| (l.row, l.range));
+        self.hyperlinks = links;
+    }
+
+    /// Get the hyperlinks set by [`TextArea::set_hyperlinks`]. They are sorted by their positions.
+    pub fn hyperlinks(&self) -> &[Hyperlink] {
+        &self.hyperlinks
+    }
+
+    /// Remove all hyperlinks set by [`TextArea::set_hyperlinks`].
+    /// ```
+    /// use tui_textarea::{TextArea, Hyperlink};
+    ///
+    /// let mut textarea = TextArea::from(["see the docs"]);
+    ///
+    /// textarea.set_hyperlinks(vec![Hyperlink::new(0, (8, 12), "https://docs.rs")]);
+    /// textarea.clear_hyperlinks();
+    /// assert!(textarea.hyperlinks().is_empty());
+    /// ```
+    pub fn clear_hyperlinks(&mut self) {
+        self.hyperlinks.clear();
+    }
+
+    // Hyperlinks in the row sorted by their ranges. URLs detected in the line are included when URL detection is
+    // enabled. Detected URLs overlapping with the links set by `set_hyperlinks` are ignored.
+    pub(crate) fn hyperlinks_at(&self, row: usize) -> Cow<'_, [Hyperlink]> {
+        let start = self.hyperlinks.partition_point(|l| l.row < row);
+        let end = self.hyperlinks.partition_point(|l| l.row <= row);
+        let links = &self.hyperlinks[start..end];
+        if !self.detect_urls {
+            return Cow::Borrowed(links);
+        }
+
+        let mut links = links.to_vec();
+        for (range, url) in find_urls(&self.lines[row]) {
+            let overlapped = links
+                .iter()
+                .any(|l| l.range.0 < range.1 && range.0 < l.range.1);
+            if !overlapped {
+                links.push(Hyperlink::new(row, range, url));
+            }
+        }
+        links.sort_by_key(|l| l.range);
+        Cow::Owned(links)
+    }
+
+    /// Get the hyperlink at the cursor position. Both links set by [`TextArea::set_hyperlinks`] and URLs detected by
+    /// [`TextArea::set_url_detection`] are considered. This is useful to implement an action to open the link.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["see https://example.com"]);
+    /// textarea.set_url_detection(true);
+    ///
+    /// assert_eq!(textarea.hyperlink_at_cursor(), None);
+    /// textarea.move_cursor(CursorMove::Jump(0, 10));
+    /// let link = textarea.hyperlink_at_cursor().unwrap();
+    /// assert_eq!(link.url, "https://example.com");
+    /// assert_eq!(link.range, (4, 23));
+    /// ```
+    pub fn hyperlink_at_cursor(&self) -> Option<Hyperlink> {
+        let (row, col) = self.cursor;
+        self.hyperlinks_at(row)
+            .iter()
+            .find(|l| l.range.0 <= col && col < l.range.1)
+            .cloned()
+    }
+
+    /// Enable detecting http(s) URLs in the text. Detected URLs are handled as hyperlinks rendered with
+    /// [`TextArea::hyperlink_style`]. They are detected on rendering so they follow the text edits. This is disabled
+    /// by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["https://example.com"]);
+    ///
+    /// assert!(!textarea.url_detection());
+    /// assert_eq!(textarea.hyperlink_at_cursor(), None);
+    ///
+    /// textarea.set_url_detection(true);
+    /// assert!(textarea.url_detection());
+    /// assert!(textarea.hyperlink_at_cursor().is_some());
+    /// ```
+    pub fn set_url_detection(&mut self, enabled: bool) {
+        self.detect_urls = enabled;
+    }
+
+    /// Get if URLs in the text are detected as hyperlinks.
+    pub fn url_detection(&self) -> bool {
+        self.detect_urls
+    }
+
+    /// Set the default style of hyperlinks. Hyperlinks which have their own styles are not affected. The default style
+    /// is a blue underlined text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Cyan);
+    /// textarea.set_hyperlink_style(style);
+    /// assert_eq!(textarea.hyperlink_style(), style);
+    /// ```
+    pub fn set_hyperlink_style(&mut self, style: Style) {
+        self.hyperlink_style = style;
+    }
+
+    /// Get the default style of hyperlinks.
+    pub fn hyperlink_style(&self) -> Style {
+        self.hyperlink_style
+    }
+
+    /// Enable emitting OSC 8 escape sequences for hyperlinks on rendering. Terminals supporting OSC 8 (e.g. WezTerm,
+    /// kitty, iTerm2) make the link text clickable. This is disabled by default since terminals which don't support it
+    /// may show the escape sequences as garbage. Links containing wide characters or tabs are only styled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.osc8_hyperlinks());
+    /// textarea.set_osc8_hyperlinks(true);
+    /// assert!(textarea.osc8_hyperlinks());
+    /// ```
+    pub fn set_osc8_hyperlinks(&mut self, enabled: bool) {
+        self.osc8_hyperlinks = enabled;
+    }
+
+    /// Get if OSC 8 escape sequences are emitted for hyperlinks.
+    pub fn osc8_hyperlinks(&self) -> bool {
+        self.osc8_hyperlinks
+    }
+
+    /// Set the annotation of the row. The annotation is virtual text rendered after the end of the line with the style,
+    /// such as an error message or blame information. It is not a part of the textarea's text. When an annotation is
+    /// already set at the row, it is replaced. Annotations can be hidden by [`TextArea::set_line_annotations_visible`].
+    ///
+    /// Note that annotations don't follow the lines on inserting or deleting lines.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["let x = 1"]);
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_line_annotation(0, "  ← error: missing semicolon", style);
+    /// assert_eq!(textarea.line_annotation(0), Some(("  ← error: missing semicolon", style)));
+    ///
+    /// // Annotations don't affect the text
+    /// assert_eq!(textarea.lines(), ["let x = 1"]);
+    /// ```
+    pub fn set_line_annotation(&mut self, row: usize, text: impl Into<String>, style: Style) {
+        self.line_annotations.insert(row, (text.into(), style));
+    }
+
+    /// Remove the annotation of the row set by [`TextArea::set_line_annotation`]. It returns `true` when some annotation
+    /// was removed.
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.set_line_annotation(0, " <- greeting", Style::default());
+    /// assert!(textarea.remove_line_annotation(0));
+    /// assert!(!textarea.remove_line_annotation(0));
+    /// ```
+    pub fn remove_line_annotation(&mut self, row: usize) -> bool {
+        self.line_annotations.remove(&row).is_some()
+    }
+
+    /// Remove all annotations set by [`TextArea::set_line_annotation`].
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// textarea.set_line_annotation(0, " <- a", Style::default());
+    /// textarea.set_line_annotation(1, " <- b", Style::default());
+    /// textarea.clear_line_annotations();
+    /// assert_eq!(textarea.line_annotation(0), None);
+    /// assert_eq!(textarea.line_annotation(1), None);
+    /// ```
+    pub fn clear_line_annotations(&mut self) {
+        self.line_annotations.clear();
+    }
+
+    /// Get the text and the style of the annotation of the row.
+    pub fn line_annotation(&self, row: usize) -> Option<(&str, Style)> {
+        self.line_annotations
+            .get(&row)
+            .map(|(text, style)| (text.as_str(), *style))
+    }
+
+    /// Show or hide the line annotations set by [`TextArea::set_line_annotation`]. Hiding annotations doesn't remove
+    /// them. Annotations are visible by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(textarea.line_annotations_visible());
+    ///
+    /// textarea.set_line_annotations_visible(false);
+    /// assert!(!textarea.line_annotations_visible());
+    /// ```
+    pub fn set_line_annotations_visible(&mut self, visible: bool) {
+        self.show_line_annotations = visible;
+    }
+
+    /// Return if the line annotations are visible or not.
+    pub fn line_annotations_visible(&self) -> bool {
+        self.show_line_annotations
+    }
+
+    /// Fold the lines from `start_row` to `end_row` (inclusive). The folded lines are collapsed into the first line with
+    /// a summary of the number of hidden lines, and a fold indicator is rendered in the gutter. The cursor skips the
+    /// hidden lines. When the cursor is in the hidden lines, it moves to the first line. Folds overlapping with the new
+    /// fold are merged. This method returns `false` when the range is invalid.
+    ///
+    /// Note that all folds are removed when lines are inserted or deleted.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    a();", "    b();", "}"]);
+    ///
+    /// assert!(textarea.fold(0, 2));
+    /// assert_eq!(textarea.folds(), [(0, 2)]);
+    ///
+    /// // The cursor skips the folded lines
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// ```
+    pub fn fold(&mut self, start_row: usize, end_row: usize) -> bool {
+        if !self.folds.add(start_row, end_row, self.lines.len()) {
+            return false;
+        }
+        let (row, col) = self.cursor;
+        if let Some((start, _)) = hiding_fold(self.folds(), row) {
+            self.cursor = (start, cmp::min(col, self.lines[start].chars().count()));
+        }
+        true
+    }
+
+    /// Fold the block of lines indented deeper than the row. The fold starts at the row and ends at the last line of
+    /// the indented block. This method returns `false` when no indented block follows the row.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["if x {", "    a();", "", "    b();", "}"]);
+    ///
+    /// assert!(textarea.fold_by_indent(0));
+    /// assert_eq!(textarea.folds(), [(0, 3)]);
+    ///
+    /// assert!(!textarea.fold_by_indent(4));
+    /// ```
+    pub fn fold_by_indent(&mut self, row: usize) -> bool {
+        if row >= self.lines.len() {
+            return false;
+        }
+        match indent_block(&self.lines, row, self.tab_len) {
+            Some((start, end)) => self.fold(start, end),
+            None => false,
+        }
+    }
+
+    /// Fold all top-level indented blocks in the textarea. See [`TextArea::fold_by_indent`] for indented blocks.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a:", "  b:", "    c", "d:", "  e"]);
+    ///
+    /// textarea.fold_all_by_indent();
+    /// assert_eq!(textarea.folds(), [(0, 2), (3, 4)]);
+    /// ```
+    pub fn fold_all_by_indent(&mut self) {
+        let mut row = 0;
+        while row < self.lines.len() {
+            match indent_block(&self.lines, row, self.tab_len) {
+                Some((start, end)) => {
+                    self.fold(start, end);
+                    row = end + 1;
+                }
+                None => row += 1,
+            }
+        }
+    }
+
+    /// Unfold the fold which contains the row. This method returns `true` when some fold was removed.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    ///
+    /// textarea.fold(0, 1);
+    /// assert!(textarea.unfold(1));
+    /// assert!(textarea.folds().is_empty());
+    /// assert!(!textarea.unfold(1));
+    /// ```
+    pub fn unfold(&mut self, row: usize) -> bool {
+        self.folds.remove_at(row, self.lines.len())
+    }
+
+    /// Remove all folds.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+    ///
+    /// textarea.fold(0, 1);
+    /// textarea.fold(2, 3);
+    /// textarea.unfold_all();
+    /// assert!(textarea.folds().is_empty());
+    /// ```
+    pub fn unfold_all(&mut self) {
+        self.folds.clear();
+    }
+
+    /// Get the folded ranges as pairs of the start row and the end row (inclusive). They are sorted by the start rows.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+    ///
+    /// textarea.fold(3, 4);
+    /// textarea.fold(0, 1);
+    /// assert_eq!(textarea.folds(), [(0, 1), (3, 4)]);
+    ///
+    /// // Folds are moved when lines are inserted above them and removed when their lines are edited
+    /// textarea.move_cursor(CursorMove::Jump(2, 1));
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.folds(), [(0, 1), (4, 5)]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 1));
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.folds(), [(5, 6)]);
+    /// ```
+    pub fn folds(&self) -> &[(usize, usize)] {
+        self.folds.ranges(self.lines.len())
+    }
+
+    // Folds applied on rendering the textarea
+    pub(crate) fn active_folds(&self) -> Vec<(usize, usize)> {
+        self.folds.active(self.lines.len(), self.cursor.0)
+    }
+
+    /// Set the style of the summary of folded lines. The default style is a dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_fold_style(style);
+    /// assert_eq!(textarea.fold_style(), style);
+    /// ```
+    pub fn set_fold_style(&mut self, style: Style) {
+        self.folds.style = style;
+    }
+
+    /// Get the style of the summary of folded lines.
+    pub fn fold_style(&self) -> Style {
+        self.folds.style
+    }
+
+    /// Set the character and the style of the fold indicator rendered in the gutter at the first line of each fold. The
+    /// default indicator is dark gray `▸`.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_fold_indicator('+', style);
+    /// assert_eq!(textarea.fold_indicator(), ('+', style));
+    /// ```
+    pub fn set_fold_indicator(&mut self, c: char, style: Style) {
+        self.folds.indicator = (c, style);
+    }
+
+    /// Get the character and the style of the fold indicator.
+    pub fn fold_indicator(&self) -> (char, Style) {
+        self.folds.indicator
+    }
+
+    /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
+    /// enables the placeholder. The default value is an empty string so the placeholder is disabled by default.
+    /// To customize the text style, see [`TextArea::set_placeholder_style`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_text(), "");
+    /// assert!(textarea.placeholder_style().is_none());
+    ///
+    /// textarea.set_placeholder_text("Hello");
+    /// assert_eq!(textarea.placeholder_text(), "Hello");
+    /// assert!(textarea.placeholder_style().is_some());
+    /// ```
+    pub fn set_placeholder_text(&mut self, placeholder: impl Into<String>) {
+        self.placeholder = placeholder.into();
+    }
+
+    /// Set the style of the placeholder text. The default style is a dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_style(), None); // When the placeholder is disabled
+    ///
+    /// textarea.set_placeholder_text("Enter your message"); // Enable placeholder by setting non-empty text
+    ///
+    /// let style = Style::default().bg(Color::Blue);
+    /// textarea.set_placeholder_style(style);
+    /// assert_eq!(textarea.placeholder_style(), Some(style));
+    /// ```
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style = style;
+    }
+
+    /// Get the placeholder text. An empty string means the placeholder is disabled. The default value is an empty string.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_text(), "");
+    /// ```
+    pub fn placeholder_text(&self) -> &'_ str {
+        self.placeholder.as_str()
+    }
+
+    /// Get the placeholder style. When the placeholder text is empty, it returns `None` since the placeholder is disabled.
+    /// The default style is a dark gray text.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.placeholder_style(), None);
+    ///
+    /// textarea.set_placeholder_text("hello");
+    /// assert!(textarea.placeholder_style().is_some());
+    /// ```
+    pub fn placeholder_style(&self) -> Option<Style> {
+        if self.placeholder.is_empty() {
+            None
+        } else {
+            Some(self.placeholder_style)
+        }
+    }
+
+    /// Set the ghost text, which is a dimmed suggestion shown just after the cursor like autosuggestions in shells. The
+    /// ghost text is virtual text which is not a part of the textarea's text. It is rendered only when the cursor is at
+    /// the end of line. Ensure that the text doesn't contain any newlines.
+    ///
+    /// While the ghost text is shown, `→` or `Tab` key accepts it in [`TextArea::input`] (See
+    /// [`TextArea::accept_ghost_text`]). Any other key clears the ghost text. An empty string means no ghost text.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["git ch"]);
+    /// textarea.move_cursor(CursorMove::End);
+    ///
+    /// textarea.set_ghost_text("eckout main");
+    /// assert_eq!(textarea.ghost_text(), "eckout main");
+    ///
+    /// textarea.input(Input { key: Key::Right, ctrl: false, alt: false, shift: false });
+    /// assert_eq!(textarea.lines(), ["git checkout main"]);
+    /// assert_eq!(textarea.ghost_text(), "");
+    /// ```
+    pub fn set_ghost_text(&mut self, text: impl Into<String>) {
+        self.ghost_text = text.into();
+    }
+
+    /// Get the ghost text. An empty string means no ghost text is set. The default value is an empty string.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.ghost_text(), "");
+    /// ```
+    pub fn ghost_text(&self) -> &'_ str {
+        self.ghost_text.as_str()
+    }
+
+    /// Clear the ghost text set by [`TextArea::set_ghost_text`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_ghost_text("hello");
+    /// textarea.clear_ghost_text();
+    /// assert_eq!(textarea.ghost_text(), "");
+    /// ```
+    pub fn clear_ghost_text(&mut self) {
+        self.ghost_text.clear();
+    }
+
+    /// Insert the ghost text at the cursor and clear it. This method returns if the text was modified or not. When the
+    /// ghost text is empty, this method does nothing.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_ghost_text("hello");
+    /// assert!(textarea.accept_ghost_text());
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    ///
+    /// // Nothing happens when the ghost text is empty
+    /// assert!(!textarea.accept_ghost_text());
+    /// ```
+    pub fn accept_ghost_text(&mut self) -> bool {
+        if self.ghost_text.is_empty() {
+            return false;
+        }
+        let text = std::mem::take(&mut self.ghost_text);
+        self.insert_str(text)
+    }
+
+    /// Set the style of the ghost text. The default style is a dark gray text.
+    /// ```
+    /// use ratatui::style::{Style, Color, Modifier};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().add_modifier(Modifier::DIM);
+    /// textarea.set_ghost_text_style(style);
+    /// assert_eq!(textarea.ghost_text_style(), style);
+    /// ```
+    pub fn set_ghost_text_style(&mut self, style: Style) {
+        self.ghost_text_style = style;
+    }
+
+    /// Get the style of the ghost text.
+    pub fn ghost_text_style(&self) -> Style {
+        self.ghost_text_style
+    }
+
+    /// Specify a character masking the text. All characters in the textarea will be replaced by this character.
+    /// This API is useful for making a kind of credentials form such as a password input.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.set_mask_char('●');
+    /// assert_eq!(textarea.mask_char(), Some('●'));
+    /// ```
+    pub fn set_mask_char(&mut self, mask: char) {
+        self.mask = Some(mask);
+    }
+
+    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// textarea.clear_mask_char();
+    /// assert_eq!(textarea.mask_char(), None);
+    /// ```
+    pub fn clear_mask_char(&mut self) {
+        self.mask = None;
+    }
+
+    /// Get the character to mask text. When no character is set, `None` is returned.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.set_mask_char('*');
+    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// ```
+    pub fn mask_char(&self) -> Option<char> {
+        self.mask
+    }
+
+    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
+    /// cursor line hides a cursor.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_cursor_style(style);
+    /// assert_eq!(textarea.cursor_style(), style);
+    /// ```
+    pub fn set_cursor_style(&mut self, style: Style) {
+        self.cursor_style = style;
+    }
+
+    /// Get the style of cursor.
+    pub fn cursor_style(&self) -> Style {
+        self.cursor_style
+    }
+
+    /// Make the cursor blink at the interval. Since the textarea has no timer, call [`TextArea::tick`] periodically
+    /// (e.g. when polling terminal events timed out) to toggle the cursor. While the cursor is hidden, the character
+    /// under the cursor is rendered with the cursor line style instead of the cursor style.
+    /// [`TextArea::cursor_blink_timeout`] tells how long to wait for the next tick. The cursor is shown again on each
+    /// key input so that it is visible while typing. The cursor does not blink by default.
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_cursor_blink(Duration::ZERO);
+    /// assert_eq!(textarea.cursor_blink(), Some(Duration::ZERO));
+    ///
+    /// assert!(textarea.is_cursor_visible());
+    /// assert!(textarea.tick());
+    /// assert!(!textarea.is_cursor_visible());
+    /// assert!(textarea.tick());
+    /// assert!(textarea.is_cursor_visible());
+    ///
+    /// textarea.remove_cursor_blink();
+    /// assert!(!textarea.tick());
+    /// assert_eq!(textarea.cursor_blink_timeout(), None);
+    /// ```
+    pub fn set_cursor_blink(&mut self, interval: Duration) {
+        self.cursor_blink = Some(CursorBlink::new(interval, Instant::now()));
+    }
+
+    /// Stop blinking the cursor set by [`TextArea::set_cursor_blink`]. The cursor is always visible after this call.
+    pub fn remove_cursor_blink(&mut self) {
+        self.cursor_blink = None;
+    }
+
+    /// Get the interval of blinking the cursor set by [`TextArea::set_cursor_blink`].
+    pub fn cursor_blink(&self) -> Option<Duration> {
+        self.cursor_blink.as_ref().map(|b| b.interval)
+    }
+
+    /// Toggle the blinking cursor when the interval set by [`TextArea::set_cursor_blink`] has passed since the last
+    /// toggle. This method returns `true` when the cursor was toggled, which means the textarea should be rendered
+    /// again.
+    pub fn tick(&mut self) -> bool {
+        match &mut self.cursor_blink {
+            Some(blink) => blink.tick(Instant::now()),
+            None => false,
+        }
+    }
+
+    /// Show the blinking cursor and restart the interval set by [`TextArea::set_cursor_blink`]. This is called on
+    /// each key input by [`TextArea::input`]. Call this method when the cursor is moved by other events like mouse
+    /// clicks. This method returns `true` when the cursor was hidden.
+    pub fn reset_cursor_blink(&mut self) -> bool {
+        match &mut self.cursor_blink {
+            Some(blink) => blink.reset(Instant::now()),
+            None => false,
+        }
+    }
+
+    /// Get if the cursor is currently visible. It is always `true` unless the cursor blinks by
+    /// [`TextArea::set_cursor_blink`].
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_blink.as_ref().map_or(true, |b| b.visible)
+    }
+
+    /// Get the time remaining until [`TextArea::tick`] should be called to toggle the blinking cursor. `None` is
+    /// returned when the cursor does not blink. This is useful as the timeout of polling terminal events.
+    pub fn cursor_blink_timeout(&self) -> Option<Duration> {
+        self.cursor_blink
+            .as_ref()
+            .map(|b| b.remaining(Instant::now()))
+    }
+
+    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
+    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
+    /// file must end with a newline.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.lines(), [""]);
+    ///
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["a", ""]);
+    ///
+    /// textarea.insert_char('b');
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// ```
+    pub fn lines(&'a self) -> &'a [String] {
+        &self.lines
+    }
+
+    /// Convert [`TextArea`] instance into line texts.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
+    ///
+    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// ```
+    pub fn into_lines(self) -> Vec<String> {
+        self.lines
+    }
+
+    /// Get the whole text joined with newlines. Whether the text ends with a newline follows the policy set by
+    /// [`TextArea::set_final_newline`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.start_loading(&b"hello\nworld\n"[..], None);
+    /// while !textarea.load_chunk(1024).unwrap() {}
+    ///
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert_eq!(textarea.text(), "hello\nworld\n");
+    /// ```
+    pub fn text(&self) -> String {
+        join_lines(&self.lines, self.final_newline, self.ends_with_newline)
+    }
+
+    /// Replace the whole text with the string by applying only the edits needed to change the current text into it.
+    /// This is useful to refresh the content by an external formatter or a file watcher. Unlike creating a new
+    /// textarea, the cursor and the selection are kept on the same text, moving along with the text edited before
+    /// them. Remote cursors and protected regions are also moved, and the edits can be undone at once. Both `\n` and
+    /// `\r\n` are recognized as newlines, and the final newline is remembered as [`TextArea::ends_with_newline`].
+    /// This method returns `false` when the text is not changed.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn f(){", "let  x=1;", "}"]);
+    /// textarea.move_cursor(CursorMove::Jump(2, 1));
+    ///
+    /// assert!(textarea.set_text("fn f() {\n    let x = 1;\n}\n"));
+    /// assert_eq!(textarea.lines(), ["fn f() {", "    let x = 1;", "}"]);
+    /// assert!(textarea.ends_with_newline());
+    /// assert_eq!(textarea.cursor(), (2, 1));
+    ///
+    /// assert!(!textarea.set_text("fn f() {\n    let x = 1;\n}\n"));
+    ///
+    /// // The edits are undone at once
+    /// assert!(textarea.undo());
+    /// assert_eq!(textarea.lines(), ["fn f(){", "let  x=1;", "}"]);
+    /// ```
+    pub fn set_text(&mut self, text: &str) -> bool {
+        let (text, ends_with_newline) = match text.strip_suffix('\n') {
+            Some(text) => (text.strip_suffix('\r').unwrap_or(text), true),
+            None => (text, false),
+        };
+        self.ends_with_newline = ends_with_newline;
+        let lines: Vec<_> = text
+            .split('\n')
+            .map(|l| l.strip_suffix('\r').unwrap_or(l).to_string())
+            .collect();
+        let edits = text_edits(&self.lines, &lines);
+        if edits.is_empty() {
+            return false;
+        }
+
+        let (mut cursor, mut selection_start) = (self.cursor, self.selection_start.take());
+        self.virtual_pos = None;
+        self.undo_group(|t| {
+            for (pos, deleted, inserted) in edits {
+                let ops = [
+                    EditOp::Delete { pos, text: deleted },
+                    EditOp::Insert {
+                        pos,
+                        text: inserted,
+                    },
+                ];
+                for op in ops {
+                    if op.text().is_empty() {
+                        continue;
+                    }
+                    t.apply_op(&op);
+                    cursor = op.shift(cursor);
+                    selection_start = selection_start.map(|p| op.shift(p));
+                }
+            }
+        });
+        self.cursor = cursor;
+        self.selection_start = selection_start;
+        true
+    }
+
+    /// Write the text returned by [`TextArea::text`] to the writer, for example to save it to a file.
+    /// ```
+    /// use tui_textarea::{FinalNewline, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.set_final_newline(FinalNewline::Always);
+    ///
+    /// let mut buf = vec![];
+    /// textarea.write_text(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello\n");
+    /// ```
+    pub fn write_text(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(self.text().as_bytes())?;
+        writer.flush()
+    }
+
+    /// Set the policy for the newline at the end of the text returned by [`TextArea::text`]. See [`FinalNewline`]
+    /// for the policies. The default policy is [`FinalNewline::Preserve`].
+    /// ```
+    /// use tui_textarea::{FinalNewline, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.set_ends_with_newline(true);
+    /// assert_eq!(textarea.text(), "hello\n");
+    ///
+    /// textarea.set_final_newline(FinalNewline::Never);
+    /// assert_eq!(textarea.final_newline(), FinalNewline::Never);
+    /// assert_eq!(textarea.text(), "hello");
+    /// ```
+    pub fn set_final_newline(&mut self, policy: FinalNewline) {
+        self.final_newline = policy;
+    }
+
+    /// Get the policy for the final newline. See [`TextArea::set_final_newline`].
+    pub fn final_newline(&self) -> FinalNewline {
+        self.final_newline
+    }
+
+    /// Set whether the original content ended with a newline. This is preserved by [`TextArea::text`] with
+    /// [`FinalNewline::Preserve`]. Lines don't keep the final newline, so call this method when creating a textarea
+    /// from lines, for example read with [`std::io::BufRead::lines`]. [`TextArea::load_chunk`] sets it automatically.
+    pub fn set_ends_with_newline(&mut self, ends_with_newline: bool) {
+        self.ends_with_newline = ends_with_newline;
+    }
+
+    /// Get whether the original content ended with a newline. It is `false` by default. See
+    /// [`TextArea::set_ends_with_newline`].
+    pub fn ends_with_newline(&self) -> bool {
+        self.ends_with_newline
+    }
+
+    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
+    ///
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// Get the current cursor position including the virtual space beyond the end of line. When virtual space mode is
+    /// disabled or the cursor is inside the text, this is the same as [`TextArea::cursor`]. See
+    /// [`TextArea::set_virtual_space`].
+    pub fn virtual_cursor(&self) -> (usize, usize) {
+        let (row, col) = self.cursor;
+        (row, col + self.virtual_col())
+    }
+
+    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
+    /// disabled because those alignments don't work well with line numbers.
+    /// ```
+    /// use ratatui::layout::Alignment;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_alignment(Alignment::Center);
+    /// assert_eq!(textarea.alignment(), Alignment::Center);
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        if let Alignment::Center | Alignment::Right = alignment {
+            self.line_number_style = None;
+        }
+        self.alignment = alignment;
+    }
+
+    /// Get current text alignment. The default alignment is [`Alignment::Left`].
+    /// ```
+    /// use ratatui::layout::Alignment;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.alignment(), Alignment::Left);
+    /// ```
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Align the text of the selected lines, or the cursor line when nothing is selected, within the width set by
+    /// [`TextArea::set_align_width`] by padding spaces before it. Whitespaces around the text of each line are
+    /// removed, and blank lines become empty. Lines wider than the width are put at the start of line. Unlike
+    /// [`TextArea::set_alignment`], this edits the text itself, so it is useful for composing banners and headers.
+    /// All changes are undone at once. This method returns if some line was changed or not.
+    /// ```
+    /// use ratatui::layout::Alignment;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["  Title  ", "subtitle"]);
+    /// textarea.set_align_width(12);
+    ///
+    /// textarea.select_all();
+    /// assert!(textarea.align_lines(Alignment::Center));
+    /// assert_eq!(textarea.lines(), ["   Title", "  subtitle"]);
+    ///
+    /// assert!(textarea.align_lines(Alignment::Right));
+    /// assert_eq!(textarea.lines(), ["       Title", "    subtitle"]);
+    ///
+    /// assert!(textarea.align_lines(Alignment::Left));
+    /// assert_eq!(textarea.lines(), ["Title", "subtitle"]);
+    /// ```
+    pub fn align_lines(&mut self, alignment: Alignment) -> bool {
+        let (start, end) = match self.selection_range() {
+            Some((start, end)) => (start.row, end.row),
+            None => (self.cursor.0, self.cursor.0),
+        };
+        let (cursor, selection_start) = (self.cursor, self.selection_start.take());
+
+        // Column offset of the text in each changed line before and after aligning, and the length of the text
+        let mut shifts = vec![];
+        let modified = self.undo_group(|t| {
+            for row in start..=end {
+                let line = &t.lines[row];
+                let aligned = align_line(line, alignment, t.align_width, t.ambiguous_width);
+                if *line == aligned {
+                    continue;
+                }
+                let text = line.trim();
+                let old = line[..line.len() - line.trim_start().len()].chars().count();
+                let new = aligned.len() - text.len();
+                let len = text.chars().count();
+                let end = Pos::new(row, line.chars().count(), line.len());
+                if t.delete_range(Pos::new(row, 0, 0), end, false) {
+                    t.insert_piece(aligned);
+                    shifts.push((row, old, new, len));
+                }
+            }
+            !shifts.is_empty()
+        });
+
+        // Keep the cursor and the selection start on the same text. A position after the text moves to its end
+        fn aligned(shifts: &[(usize, usize, usize, usize)], pos: (usize, usize)) -> (usize, usize) {
+            match shifts.iter().find(|s| s.0 == pos.0) {
+                Some(&(row, old, new, len)) => {
+                    let (r, c) = shift_in_row(pos, row, old, new);
+                    (r, cmp::min(c, new + len))
+                }
+                None => pos,
+            }
+        }
+        self.cursor = aligned(&shifts, cursor);
+        self.selection_start = selection_start.map(|p| aligned(&shifts, p));
+        modified
+    }
+
+    /// Set the width used by [`TextArea::align_lines`]. The default width is 80.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.align_width(), 80);
+    ///
+    /// textarea.set_align_width(40);
+    /// assert_eq!(textarea.align_width(), 40);
+    /// ```
+    pub fn set_align_width(&mut self, width: usize) {
+        self.align_width = width;
+    }
+
+    /// Get the width used by [`TextArea::align_lines`]. See [`TextArea::set_align_width`].
+    pub fn align_width(&self) -> usize {
+        self.align_width
+    }
+
+    /// Check if the textarea has a empty content.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// assert!(textarea.is_empty());
+    ///
+    /// let textarea = TextArea::from(["hello"]);
+    /// assert!(!textarea.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.lines == [""]
+    }
+
+    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
+    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
+    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
+    /// always joined with `\n`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.yank_text(), "abc");
+    ///
+    /// // Multiple lines are joined with \n
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.delete_str(5);
+    /// assert_eq!(textarea.yank_text(), "abc\nd");
+    /// ```
+    pub fn yank_text(&self) -> String {
+        self.yank.get(0).to_string()
+    }
+
+    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
+    /// but `\r` isn't.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_yank_text("hello\nworld");
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// ```
+    pub fn set_yank_text(&mut self, text: impl Into<String>) {
+        // `str::lines` is not available since it strips a newline at end
+        let lines: Vec<_> = text
+            .into()
+            .split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect();
+        self.yank.push(lines.into());
+    }
+
+    /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
+    /// When a valid pattern is set, all matches will be highlighted in the textarea. Note that the cursor does not
+    /// move. To move the cursor, use [`TextArea::search_forward`] and [`TextArea::search_back`].
+    ///
+    /// Grammar of regular expression follows [regex crate](https://docs.rs/regex/latest/regex). Patterns don't match
+    /// to newlines so match passes across no newline.
+    ///
+    /// When the pattern is invalid, the search pattern will not be updated and an error will be returned.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello, world", "goodbye, world"]);
+    ///
+    /// // Search "world"
+    /// textarea.set_search_pattern("world").unwrap();
+    ///
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (1, 9));
+    ///
+    /// // Stop the text search
+    /// textarea.set_search_pattern("");
+    ///
+    /// // Invalid search pattern
+    /// assert!(textarea.set_search_pattern("(hello").is_err());
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_pattern(&mut self, query: impl AsRef<str>) -> Result<(), regex::Error> {
+        let query = self.normalized(query.as_ref()).into_owned();
+        self.search.set_pattern(&query)
+    }
+
+    /// Get a regular expression which was set by [`TextArea::set_search_pattern`]. When no text search is ongoing, this
+    /// method returns `None`. The regular expression is compiled with the options set by [`TextArea::set_search_case`]
+    /// and [`TextArea::set_search_whole_word`].
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(textarea.search_pattern().is_none());
+    /// textarea.set_search_pattern("hello+").unwrap();
+    /// assert!(textarea.search_pattern().is_some());
+    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "hello+");
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_pattern(&self) -> Option<&regex::Regex> {
+        self.search.pat.as_ref()
+    }
+
+    /// Set the case sensitivity of text search. The current search pattern is also updated. The default value is
+    /// [`SearchCase::Sensitive`].
+    /// ```
+    /// use tui_textarea::{TextArea, SearchCase};
+    ///
+    /// let mut textarea = TextArea::from(["Hello", "hello"]);
+    ///
+    /// textarea.set_search_case(SearchCase::Smart);
+    /// assert_eq!(textarea.search_case(), SearchCase::Smart);
+    ///
+    /// // Lowercase pattern matches case insensitively
+    /// textarea.set_search_pattern("hello").unwrap();
+    /// textarea.search_forward(true);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// // Pattern containing uppercase matches case sensitively
+    /// textarea.set_search_pattern("Hello").unwrap();
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_case(&mut self, case: SearchCase) {
+        self.search.set_case(case);
+    }
+
+    /// Get the case sensitivity of text search.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_case(&self) -> SearchCase {
+        self.search.case
+    }
+
+    /// Set if text search only matches whole words. When enabled, matches must start and end at word boundaries. The
+    /// current search pattern is also updated. This is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["food foo"]);
+    ///
+    /// textarea.set_search_whole_word(true);
+    /// assert!(textarea.search_whole_word());
+    ///
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// textarea.search_forward(true);
+    /// assert_eq!(textarea.cursor(), (0, 5));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_whole_word(&mut self, enabled: bool) {
+        self.search.set_whole_word(enabled);
+    }
+
+    /// Get if text search only matches whole words.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_whole_word(&self) -> bool {
+        self.search.whole_word
+    }
+
+    /// Search the pattern set by [`TextArea::set_search_pattern`] forward and move the cursor to the next match
+    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
+    /// some match was found. Otherwise it returns `false`.
+    ///
+    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
+    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
+    /// move to the next match ignoring the match at the current position.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
+    ///
+    /// textarea.set_search_pattern("hello+").unwrap();
+    ///
+    /// // Move to next position
+    /// let match_found = textarea.search_forward(false);
+    /// assert!(match_found);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// // Since the cursor position matches to "hello+", it does not move
+    /// textarea.search_forward(true);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// // Text search wrap around the buffer
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// // `false` is returned when no match was found
+    /// textarea.set_search_pattern("bye+").unwrap();
+    /// let match_found = textarea.search_forward(false);
+    /// assert!(!match_found);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_forward(&mut self, match_cursor: bool) -> bool {
+        if let Some(cursor) = self.search.forward(&self.lines, self.cursor, match_cursor) {
+            self.cursor = cursor;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Search the pattern set by [`TextArea::set_search_pattern`] backward and move the cursor to the next match
+    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
+    /// some match was found. Otherwise it returns `false`.
+    ///
+    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
+    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
+    /// move to the next match ignoring the match at the current position.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
+    ///
+    /// textarea.set_search_pattern("hello+").unwrap();
+    ///
+    /// // Move to next position with wrapping around the text buffer
+    /// let match_found = textarea.search_back(false);
+    /// assert!(match_found);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// // Since the cursor position matches to "hello+", it does not move
+    /// textarea.search_back(true);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
+    /// textarea.search_back(false);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// // `false` is returned when no match was found
+    /// textarea.set_search_pattern("bye+").unwrap();
+    /// let match_found = textarea.search_back(false);
+    /// assert!(!match_found);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_back(&mut self, match_cursor: bool) -> bool {
+        if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
+            self.cursor = cursor;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Enumerate all matches of the current search pattern in the textarea. Each match is a pair of the start position
+    /// and the end position (exclusive), and each position is a pair of row and column. Empty matches are not included.
+    /// This is useful to build a custom match picker. When no search pattern is set, nothing is enumerated.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar", "baz foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
+    ///
+    /// let matches: Vec<_> = textarea.search_matches().collect();
+    /// assert_eq!(matches, [((0, 0), (0, 3)), ((1, 4), (1, 7))]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_matches(&self) -> impl Iterator<Item = ((usize, usize), (usize, usize))> + '_ {
+        self.search.all_matches(&self.lines)
+    }
+
+    /// Get the number of matches of the current search pattern in the textarea. Empty matches are not counted. With
+    /// [`TextArea::match_index`], this is useful to show the status like "3/17 matches".
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar", "baz foo", "foo"]);
+    /// assert_eq!(textarea.match_count(), 0);
+    ///
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.match_count(), 3);
+    ///
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.match_index(), Some(1)); // Cursor is at the 2nd match
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn match_count(&self) -> usize {
+        self.search_matches().count()
+    }
+
+    /// Get the 0-based index of the match at the cursor in the matches enumerated by [`TextArea::search_matches`].
+    /// `None` is returned when the cursor is not at the start of any match.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn match_index(&self) -> Option<usize> {
+        self.search_matches()
+            .take_while(|(start, _)| *start <= self.cursor)
+            .position(|(start, _)| start == self.cursor)
+    }
+
+    /// Limit text search and replace to the current selection. After calling this method, [`TextArea::search_forward`],
+    /// [`TextArea::search_back`], [`TextArea::search_matches`], [`TextArea::replace_start`], and the match highlight
+    /// only consider matches entirely inside the selected range. Searching wraps around within the range. The
+    /// selection is cancelled since searching moves the cursor, and the range moves along with text edits. This method
+    /// returns `false` and does nothing when nothing is selected.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, ReplaceAnswer};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "foo foo", "foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
+    ///
+    /// // Select from the 2nd `foo` in the 2nd line to the end of the 3rd line
+    /// textarea.move_cursor(CursorMove::Jump(1, 4));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(2, 3));
+    /// assert!(textarea.search_in_selection());
+    /// assert_eq!(textarea.search_scope(), Some(((1, 4), (2, 3))));
+    /// assert_eq!(textarea.match_count(), 2);
+    ///
+    /// assert!(textarea.replace_start("barbaz"));
+    /// assert!(textarea.replace_answer(ReplaceAnswer::All));
+    /// assert_eq!(textarea.lines(), ["foo", "foo barbaz", "barbaz"]);
+    /// assert_eq!(textarea.search_scope(), Some(((1, 4), (2, 6))));
+    ///
+    /// textarea.clear_search_scope();
+    /// assert_eq!(textarea.match_count(), 2);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_in_selection(&mut self) -> bool {
+        match self.selection_range() {
+            Some((start, end)) => {
+                self.search.scope = Some(((start.row, start.col), (end.row, end.col)));
+                self.cancel_selection();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the limit of text search and replace set by [`TextArea::search_in_selection`] so that they consider
+    /// the whole text again.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn clear_search_scope(&mut self) {
+        self.search.scope = None;
+    }
+
+    /// Get the start and end positions (exclusive) of the range where text search and replace are limited by
+    /// [`TextArea::search_in_selection`]. `None` is returned when they consider the whole text.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_scope(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.search.scope
+    }
+
+    /// Get the text style at matches of text search. The default style is colored with blue in background.
+    ///
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.search_style(), Style::default().bg(Color::Blue));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_style(&self) -> Style {
+        self.search.style
+    }
+
+    /// Set the text style at matches of text search. The default style is colored with blue in background.
+    ///
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let red_bg = Style::default().bg(Color::Red);
+    /// textarea.set_search_style(red_bg);
+    ///
+    /// assert_eq!(textarea.search_style(), red_bg);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_style(&mut self, style: Style) {
+        self.search.style = style;
+    }
+
+    /// Start an incremental search. While the search is ongoing, characters fed with [`TextArea::search_input`] (or
+    /// [`TextArea::input`]) are appended to the search query, and the cursor moves to the first match after the
+    /// position where the search started. All matches are highlighted live. Enter confirms the search and Esc cancels
+    /// it restoring the original cursor position and search pattern. This is a building block for a `/` prompt.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world", "wonder"]);
+    /// let key = |key| Input { key, ..Default::default() };
+    ///
+    /// textarea.search_start();
+    /// textarea.input(key(Key::Char('w')));
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// textarea.input(key(Key::Char('o')));
+    /// textarea.input(key(Key::Char('n')));
+    /// assert_eq!(textarea.search_query(), Some("won"));
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// // Confirm the search
+    /// textarea.input(key(Key::Enter));
+    /// assert_eq!(textarea.search_query(), None);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "won");
+    ///
+    /// // Cancel the search
+    /// textarea.search_start();
+    /// textarea.input(key(Key::Char('h')));
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// textarea.input(key(Key::Esc));
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "won");
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_start(&mut self) {
+        self.incremental_search = Some(IncrementalSearch {
+            query: String::new(),
+            origin: self.cursor,
+            prev_query: self.search.query.clone(),
+            history_index: None,
+            draft: String::new(),
+        });
+    }
+
+    /// Handle the key input for the ongoing incremental search started by [`TextArea::search_start`]. A character is
+    /// appended to the query, Backspace removes the last character (or cancels the search when the query is empty),
+    /// Up and Down recall the previous and next patterns in the search history, Enter confirms the search, and Esc
+    /// cancels it. Other keys are ignored. This method returns `false` when no
+    /// incremental search is ongoing.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_input(&mut self, input: impl Into<Input>) -> bool {
+        let search = match &mut self.incremental_search {
+            Some(search) => search,
+            None => return false,
+        };
+        let query = &mut search.query;
+        match input.into() {
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } => query.push(c),
+            Input {
+                key: Key::Backspace,
+                ..
+            } => {
+                if query.pop().is_none() {
+                    self.search_cancel();
+                    return true;
+                }
+            }
+            Input { key: Key::Up, .. } => {
+                self.search_history_prev();
+                return true;
+            }
+            Input { key: Key::Down, .. } => {
+                self.search_history_next();
+                return true;
+            }
+            Input {
+                key: Key::Enter, ..
+            } => {
+                self.search_confirm();
+                return true;
+            }
+            Input { key: Key::Esc, .. } => {
+                self.search_cancel();
+                return true;
+            }
+            _ => return true,
+        }
+        // Editing the recalled pattern stops browsing the history
+        search.history_index = None;
+        self.update_incremental_search();
+        true
+    }
+
+    #[cfg(feature = "search")]
+    fn update_incremental_search(&mut self) {
+        let (query, origin) = match &self.incremental_search {
+            Some(search) => (self.normalized(&search.query).into_owned(), search.origin),
+            None => return,
+        };
+        // Invalid pattern may appear while typing the query (e.g. "(foo"). Keep the previous matches until it is fixed
+        if self.search.set_pattern(&query).is_ok() {
+            self.cursor = origin;
+            if let Some(cursor) = self.search.forward(&self.lines, origin, true) {
+                self.cursor = cursor;
+            }
+        }
+    }
+
+    /// Confirm the ongoing incremental search. The cursor stays at the current match and the query remains as the
+    /// search pattern. The query is added to the search history. This method returns `false` when no incremental
+    /// search is ongoing.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_confirm(&mut self) -> bool {
+        if let Some(search) = self.incremental_search.take() {
+            self.search_history.push(&search.query);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Cancel the ongoing incremental search. The cursor position and the search pattern are restored to the ones
+    /// before [`TextArea::search_start`] was called. This method returns `false` when no incremental search is ongoing.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_cancel(&mut self) -> bool {
+        if let Some(search) = self.incremental_search.take() {
+            self.cursor = search.origin;
+            // The previous query was compiled successfully with the same options
+            let _ = self.search.set_pattern(&search.prev_query);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Get the query of the ongoing incremental search. `None` is returned when no incremental search is ongoing. This
+    /// is useful to render the search prompt.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_query(&self) -> Option<&str> {
+        self.incremental_search.as_ref().map(|s| s.query.as_str())
+    }
+
+    /// Replace the query of the ongoing incremental search with the previous (older) pattern in the search history, like
+    /// pressing Up in the search prompt of Vim or less. This method returns `false` when no incremental search is
+    /// ongoing or no older pattern is in the history.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
+    /// textarea.push_search_history("foo");
+    /// textarea.push_search_history("bar");
+    ///
+    /// textarea.search_start();
+    /// textarea.input(Input { key: Key::Char('b'), ..Default::default() });
+    ///
+    /// assert!(textarea.search_history_prev());
+    /// assert_eq!(textarea.search_query(), Some("bar"));
+    /// assert!(textarea.search_history_prev());
+    /// assert_eq!(textarea.search_query(), Some("foo"));
+    /// assert!(!textarea.search_history_prev());
+    ///
+    /// // Going forward past the newest pattern restores the typed query
+    /// assert!(textarea.search_history_next());
+    /// assert_eq!(textarea.search_query(), Some("bar"));
+    /// assert!(textarea.search_history_next());
+    /// assert_eq!(textarea.search_query(), Some("b"));
+    /// assert!(!textarea.search_history_next());
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_history_prev(&mut self) -> bool {
+        let search = match &mut self.incremental_search {
+            Some(search) => search,
+            None => return false,
+        };
+        let entries = &self.search_history.entries;
+        let index = match search.history_index {
+            Some(0) => return false,
+            Some(i) => i - 1,
+            None if entries.is_empty() => return false,
+            None => {
+                search.draft = search.query.clone();
+                entries.len() - 1
+            }
+        };
+        search.history_index = Some(index);
+        search.query = entries[index].clone();
+        self.update_incremental_search();
+        true
+    }
+
+    /// Replace the query of the ongoing incremental search with the next (newer) pattern in the search history. Going
+    /// forward past the newest pattern restores the query typed before browsing the history. This method returns
+    /// `false` when no incremental search is ongoing or the history is not being browsed. See
+    /// [`TextArea::search_history_prev`] for the example.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_history_next(&mut self) -> bool {
+        let search = match &mut self.incremental_search {
+            Some(search) => search,
+            None => return false,
+        };
+        let index = match search.history_index {
+            Some(i) => i + 1,
+            None => return false,
+        };
+        if let Some(pat) = self.search_history.entries.get(index) {
+            search.history_index = Some(index);
+            search.query = pat.clone();
+        } else {
+            search.history_index = None;
+            search.query = std::mem::take(&mut search.draft);
+        }
+        self.update_incremental_search();
+        true
+    }
+
+    /// Add the pattern to the search history as the newest entry. Patterns confirmed in the incremental search are
+    /// added automatically. This is useful when the application implements its own search prompt with
+    /// [`TextArea::set_search_pattern`]. An empty pattern is ignored and the same pattern already in the history is
+    /// moved to the newest entry.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.push_search_history("foo");
+    /// textarea.push_search_history("bar");
+    /// textarea.push_search_history("foo");
+    /// textarea.push_search_history("");
+    /// assert_eq!(textarea.search_history(), ["bar", "foo"]);
+    ///
+    /// textarea.clear_search_history();
+    /// assert!(textarea.search_history().is_empty());
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn push_search_history(&mut self, pattern: impl AsRef<str>) {
+        self.search_history.push(pattern.as_ref());
+    }
+
+    /// Get the patterns in the search history. Older patterns come first.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_history(&self) -> &[String] {
+        &self.search_history.entries
+    }
+
+    /// Remove all patterns in the search history.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn clear_search_history(&mut self) {
+        self.search_history.entries.clear();
+    }
+
+    /// Set the max number of patterns kept in the search history. The oldest patterns are removed when the number
+    /// exceeds it. The default value is 100.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_max_search_history(2);
+    /// for pat in ["a", "b", "c"] {
+    ///     textarea.push_search_history(pat);
+    /// }
+    /// assert_eq!(textarea.search_history(), ["b", "c"]);
+    /// assert_eq!(textarea.max_search_history(), 2);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_max_search_history(&mut self, max: usize) {
+        self.search_history.max = max;
+        self.search_history.truncate();
+    }
+
+    /// Get the max number of patterns kept in the search history set by [`TextArea::set_max_search_history`].
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn max_search_history(&self) -> usize {
+        self.search_history.max
+    }
+
+    /// Start replacing matches of the current search pattern with the replacement one by one. The cursor moves to the
+    /// first match in the textarea and the application answers whether to replace it with [`TextArea::replace_answer`].
+    /// `$1` or `${name}` in the replacement is expanded to the captured group of the match. The current match position
+    /// is available with [`TextArea::replace_match`] to highlight it.
+    ///
+    /// This method returns `false` when no search pattern is set or no match is found.
+    /// ```
+    /// use tui_textarea::{TextArea, ReplaceAnswer};
+    ///
+    /// let mut textarea = TextArea::from(["cat dog cat", "cat"]);
+    /// textarea.set_search_pattern("cat").unwrap();
+    ///
+    /// assert!(textarea.replace_start("cow"));
+    /// assert_eq!(textarea.replace_match(), Some(((0, 0), (0, 3))));
+    ///
+    /// assert!(textarea.replace_answer(ReplaceAnswer::Yes));
+    /// assert_eq!(textarea.replace_match(), Some(((0, 8), (0, 11))));
+    /// assert!(!textarea.replace_answer(ReplaceAnswer::No));
+    /// assert_eq!(textarea.replace_match(), Some(((1, 0), (1, 3))));
+    /// assert!(textarea.replace_answer(ReplaceAnswer::Yes));
+    ///
+    /// // No match remains
+    /// assert!(!textarea.is_replacing());
+    /// assert_eq!(textarea.lines(), ["cow dog cat", "cow"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_start(&mut self, replacement: impl Into<String>) -> bool {
+        self.replace = Some(InteractiveReplace {
+            replacement: replacement.into(),
+            row: 0,
+            start: 0,
+            end: 0,
+        });
+        self.update_replace((0, 0))
+    }
+
+    // Move to the next match at or after the position. The interactive replace ends when no match is found
+    #[cfg(feature = "search")]
+    fn update_replace(&mut self, pos: (usize, usize)) -> bool {
+        let found = self.search.find_from(&self.lines, pos);
+        match (&mut self.replace, found) {
+            (Some(replace), Some((row, start, end))) => {
+                replace.row = row;
+                replace.start = start;
+                replace.end = end;
+                self.cancel_selection();
+                self.cursor = (row, start);
+                true
+            }
+            _ => {
+                self.replace = None;
+                false
+            }
+        }
+    }
+
+    // Replace the current match of the interactive replace and return the position after the replaced text
+    #[cfg(feature = "search")]
+    fn replace_current(&mut self) -> (usize, usize) {
+        let (replacement, row, start, end) = match &self.replace {
+            Some(r) => (r.replacement.clone(), r.row, r.start, r.end),
+            None => return self.cursor,
+        };
+        let line = &self.lines[row];
+        let offset = byte_offset(line, start);
+        let replacement = self.search.expand(line, offset, &replacement);
+
+        // Replacing text should not overwrite the yanked text
+        let start = Pos::new(row, start, offset);
+        let end = Pos::new(row, end, self.line_offset(row, end));
+        self.cancel_selection();
+        if self.delete_range(start, end, false) {
+            self.insert_str(replacement);
+        }
+        self.cursor
+    }
+
+    /// Answer whether to replace the current match of the interactive replace started by [`TextArea::replace_start`].
+    /// The cursor moves to the next match after answering. When no match remains or [`ReplaceAnswer::Quit`] is given,
+    /// the interactive replace ends. This method returns `true` when some text was replaced.
+    /// ```
+    /// use tui_textarea::{TextArea, ReplaceAnswer};
+    ///
+    /// let mut textarea = TextArea::from(["key=1", "key=2", "key=3"]);
+    /// textarea.set_search_pattern(r"(\w+)=(\d)").unwrap();
+    ///
+    /// textarea.replace_start("$1: $2");
+    /// assert!(!textarea.replace_answer(ReplaceAnswer::No));
+    /// // Replace all remaining matches
+    /// assert!(textarea.replace_answer(ReplaceAnswer::All));
+    /// assert!(!textarea.is_replacing());
+    /// assert_eq!(textarea.lines(), ["key=1", "key: 2", "key: 3"]);
+    ///
+    /// textarea.replace_start("$1 = $2");
+    /// assert!(!textarea.replace_answer(ReplaceAnswer::Quit));
+    /// assert!(!textarea.is_replacing());
+    /// assert_eq!(textarea.lines(), ["key=1", "key: 2", "key: 3"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_answer(&mut self, answer: ReplaceAnswer) -> bool {
+        let (row, end) = match &self.replace {
+            Some(r) => (r.row, r.end),
+            None => return false,
+        };
+        match answer {
+            ReplaceAnswer::Yes => {
+                let pos = self.replace_current();
+                self.update_replace(pos);
+                true
+            }
+            ReplaceAnswer::No => {
+                self.update_replace((row, end));
+                false
+            }
+            ReplaceAnswer::All => {
+                loop {
+                    let pos = self.replace_current();
+                    if !self.update_replace(pos) {
+                        break;
+                    }
+                }
+                true
+            }
+            ReplaceAnswer::Quit => {
+                self.replace = None;
+                false
+            }
+        }
+    }
+
+    /// Get the start and end positions (exclusive) of the current match in the interactive replace started by
+    /// [`TextArea::replace_start`]. Each position is a pair of row and column. `None` is returned when no interactive
+    /// replace is ongoing.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_match(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.replace
+            .as_ref()
+            .map(|r| ((r.row, r.start), (r.row, r.end)))
+    }
+
+    /// Return `true` when the interactive replace started by [`TextArea::replace_start`] is ongoing.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn is_replacing(&self) -> bool {
+        self.replace.is_some()
+    }
+
+    /// Add a rule to highlight all matches of the regular expression with the style, such as `@mentions`, `#tags`, or
+    /// `TODO` comments. Matches are found in each line on rendering so they follow the text edits. Text search
+    /// matches, selection, and diagnostics take priority over the rules.
+    ///
+    /// When the pattern is invalid, no rule is added and an error is returned.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["TODO: ask @alice"]);
+    ///
+    /// textarea.add_highlight_rule(r"@\w+", Style::default().fg(Color::Blue)).unwrap();
+    /// textarea.add_highlight_rule(r"\b(TODO|FIXME)\b", Style::default().fg(Color::Yellow)).unwrap();
+    ///
+    /// // Invalid pattern
+    /// assert!(textarea.add_highlight_rule("(", Style::default()).is_err());
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn add_highlight_rule(
+        &mut self,
+        pattern: impl AsRef<str>,
+        style: Style,
+    ) -> Result<(), regex::Error> {
+        self.highlight_rules.add(pattern.as_ref(), style)
+    }
+
+    /// Remove the highlight rules added with the pattern by [`TextArea::add_highlight_rule`]. This method returns
+    /// `true` when some rule was removed.
+    /// ```
+    /// use ratatui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.add_highlight_rule(r"#\w+", Style::default()).unwrap();
+    /// assert!(textarea.remove_highlight_rule(r"#\w+"));
+    /// assert!(!textarea.remove_highlight_rule(r"#\w+"));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn remove_highlight_rule(&mut self, pattern: impl AsRef<str>) -> bool {
+        self.highlight_rules.remove(pattern.as_ref())
+    }
+
+    /// Remove all highlight rules added by [`TextArea::add_highlight_rule`].
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn clear_highlight_rules(&mut self) {
+        self.highlight_rules.rules.clear();
+    }
+
+    /// Register the callback invoked with the current lines after edits stop for the interval. This is useful to save a
+    /// draft automatically. Since the textarea has no timer, call [`TextArea::poll_idle_change`] periodically (e.g. when
+    /// polling terminal events timed out) to invoke the callback. [`TextArea::idle_change_timeout`] tells how long to
+    /// wait for the next poll. Registering a callback again replaces the previous one.
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let saved = Arc::new(Mutex::new(vec![]));
+    ///
+    /// let draft = saved.clone();
+    /// textarea.on_idle_change(Duration::ZERO, move |lines: &[String]| {
+    ///     *draft.lock().unwrap() = lines.to_vec();
+    /// });
+    ///
+    /// // No edit happened yet
+    /// assert!(!textarea.poll_idle_change());
+    ///
+    /// textarea.insert_str("hello");
+    /// assert_eq!(textarea.idle_change_timeout(), Some(Duration::ZERO));
+    /// assert!(textarea.poll_idle_change());
+    /// assert_eq!(*saved.lock().unwrap(), ["hello"]);
+    ///
+    /// // The callback is not invoked again until the next edit
+    /// assert!(!textarea.poll_idle_change());
+    /// assert_eq!(textarea.idle_change_timeout(), None);
+    /// ```
+    pub fn on_idle_change(
+        &mut self,
+        interval: Duration,
+        callback: impl Fn(&[String]) + Send + Sync + 'a,
+    ) {
+        self.idle_change = Some(IdleChange {
+            interval,
+            callback: Arc::new(callback),
+            changed_at: None,
+        });
+    }
+
+    /// Remove the callback registered by [`TextArea::on_idle_change`].
+    pub fn remove_idle_change(&mut self) {
+        self.idle_change = None;
+    }
+
+    /// Invoke the callback registered by [`TextArea::on_idle_change`] when the interval has passed since the last
+    /// edit. The callback is invoked once per series of edits. This method returns `true` when the callback was
+    /// invoked.
+    pub fn poll_idle_change(&mut self) -> bool {
+        let idle = match &mut self.idle_change {
+            Some(idle) => idle,
+            None => return false,
+        };
+        if idle.remaining(Instant::now()) != Some(Duration::ZERO) {
+            return false;
+        }
+        idle.changed_at = None;
+        (idle.callback)(&self.lines);
+        true
+    }
+
+    /// Get the time remaining until the callback registered by [`TextArea::on_idle_change`] should be invoked by
+    /// [`TextArea::poll_idle_change`]. `None` is returned when no edit is waiting for the callback. This is useful as
+    /// the timeout of polling terminal events.
+    pub fn idle_change_timeout(&self) -> Option<Duration> {
+        self.idle_change.as_ref()?.remaining(Instant::now())
+    }
+
+    /// Register the callback to receive semantic events of editor activity as [`Announcement`], so that accessible
+    /// frontends can announce them to assistive technology such as a screen reader. Every edit including undo, redo
+    /// and remote edits is announced as inserted or deleted text. Moves of the cursor and changes of the selection are
+    /// announced when they are caused by [`TextArea::input`]. Registering a callback again replaces the previous one.
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello world", "bye"]);
+    /// let spoken = Arc::new(Mutex::new(vec![]));
+    ///
+    /// let speech = spoken.clone();
+    /// textarea.on_announcement(move |event| speech.lock().unwrap().push(event.to_string()));
+    ///
+    /// textarea.input(Input { key: Key::Char('x'), ..Default::default() });
+    /// textarea.input(Input { key: Key::Down, ..Default::default() });
+    /// textarea.input(Input { key: Key::Up, shift: true, ..Default::default() });
+    /// textarea.input(Input { key: Key::Right, ..Default::default() });
+    ///
+    /// assert_eq!(
+    ///     *spoken.lock().unwrap(),
+    ///     [
+    ///         "inserted 'x' at line 1 col 1",
+    ///         "moved to line 2",
+    ///         "selected 3 words", // "hello world\nb"
+    ///         "selection cancelled",
+    ///     ],
+    /// );
+    /// ```
+    pub fn on_announcement(&mut self, callback: impl Fn(&Announcement) + Send + Sync + 'a) {
+        self.announcer = Some(Announcer(Arc::new(callback)));
+    }
+
+    /// Remove the callback registered by [`TextArea::on_announcement`].
+    pub fn remove_announcement(&mut self) {
+        self.announcer = None;
+    }
+
+    /// Start recording every key input passed to [`TextArea::input`] with the time elapsed since now. The recorded
+    /// [`InputLog`] can be written to a file with [`InputLog::write`] and attached to a bug report, then replayed with
+    /// [`TextArea::replay`] to reproduce the editing. Starting recording again discards the inputs recorded so far.
+    ///
+    /// Inputs passed to [`TextArea::input_without_shortcuts`] and edits by method calls are not recorded.
+    /// ```
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.start_recording();
+    /// assert!(textarea.is_recording());
+    ///
+    /// textarea.input(Input { key: Key::Char('a'), ..Default::default() });
+    /// textarea.input(Input { key: Key::Enter, ..Default::default() });
+    ///
+    /// let log = textarea.stop_recording().unwrap();
+    /// assert_eq!(log.len(), 2);
+    /// assert!(!textarea.is_recording());
+    /// ```
+    pub fn start_recording(&mut self) {
+        self.recording = Some((Instant::now(), InputLog::default()));
+    }
+
+    /// Stop recording key inputs started by [`TextArea::start_recording`] and return the recorded inputs. `None` is
+    /// returned when inputs are not being recorded.
+    pub fn stop_recording(&mut self) -> Option<InputLog> {
+        self.recording.take().map(|(_, log)| log)
+    }
+
+    /// Return if key inputs are being recorded by [`TextArea::start_recording`].
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Replay the key inputs recorded by [`TextArea::start_recording`]. Each input is passed to [`TextArea::input`]
+    /// immediately. Elapsed times are not waited for, so the application should sleep between inputs by itself when
+    /// the timing matters. To reproduce the editing, the textarea should have the same text, cursor and options as
+    /// when the recording started. This method returns if any input modified the text.
+    /// ```
+    /// use tui_textarea::{Input, InputLog, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.start_recording();
+    /// for c in "hello".chars() {
+    ///     textarea.input(Input { key: Key::Char(c), ..Default::default() });
+    /// }
+    /// textarea.input(Input { key: Key::Char('h'), ctrl: true, ..Default::default() });
+    /// let mut file = vec![];
+    /// textarea.stop_recording().unwrap().write(&mut file).unwrap();
+    ///
+    /// // Reproduce the editing from the attached log
+    /// let log = InputLog::read(&file[..]).unwrap();
+    /// let mut reproduced = TextArea::default();
+    /// assert!(reproduced.replay(&log));
+    /// assert_eq!(reproduced.lines(), ["hell"]);
+    /// assert_eq!(reproduced.cursor(), textarea.cursor());
+    /// ```
+    pub fn replay(&mut self, log: &InputLog) -> bool {
+        let mut modified = false;
+        for (_, input) in log.inputs() {
+            modified |= self.input(input.clone());
+        }
+        modified
+    }
+
+    /// Set the journal to append every edit to the writer, typically a file. Each edit is written as one line and
+    /// flushed immediately. After a crash, the text can be reconstructed by loading the last saved text and replaying
+    /// the journal with [`TextArea::replay_journal`]. The application should truncate the journal when it saves the
+    /// text. Setting a journal again replaces the previous one.
+    ///
+    /// Errors on writing the journal don't prevent editing. The first error is kept and can be taken by
+    /// [`TextArea::take_journal_error`]. The writer is owned by the textarea and shared with its clones, so it must not
+    /// borrow anything.
+    /// ```
+    /// use std::fs::{self, File};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let path = std::env::temp_dir().join("tui-textarea-journal-doctest.log");
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_journal(File::create(&path).unwrap());
+    ///
+    /// textarea.insert_str("hello");
+    /// textarea.undo();
+    /// textarea.redo();
+    /// drop(textarea);
+    ///
+    /// assert_eq!(fs::read(&path).unwrap(), b"+0:0:hello\n-0:0:hello\n+0:0:hello\n");
+    /// # fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn set_journal(&mut self, writer: impl std::io::Write + Send + 'static) {
+        self.journal = Some(Journal::new(writer));
+    }
+
+    /// Stop appending edits to the journal set by [`TextArea::set_journal`].
+    pub fn remove_journal(&mut self) {
+        self.journal = None;
+    }
+
+    /// Take the first error which happened on writing the journal set by [`TextArea::set_journal`]. `None` is
+    /// returned when no error happened.
+    pub fn take_journal_error(&mut self) -> Option<std::io::Error> {
+        self.journal.as_ref()?.take_error()
+    }
+
+    /// Replay the journal written by [`TextArea::set_journal`] to reconstruct the text after a crash. The textarea must
+    /// have the text saved when the journal was started. Replayed edits are recorded in the undo history. An
+    /// incomplete last line written at the crash is ignored. This method returns the number of replayed edits. When
+    /// the journal is malformed or doesn't match the text, an error with [`std::io::ErrorKind::InvalidData`] is
+    /// returned and the edits before the error remain applied.
+    ///
+    /// Call this method before setting a new journal, otherwise the replayed edits are appended to it again.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let journal = b"+0:5:, world\n-0:0:hello\n+0:0:goodbye\n+0:0:incomplete";
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// assert_eq!(textarea.replay_journal(&journal[..]).unwrap(), 3);
+    /// assert_eq!(textarea.lines(), ["goodbye, world"]);
+    ///
+    /// // The journal doesn't match the text
+    /// let mut textarea = TextArea::from(["foo"]);
+    /// assert!(textarea.replay_journal(&journal[..]).is_err());
+    /// ```
+    pub fn replay_journal(&mut self, mut reader: impl std::io::BufRead) -> std::io::Result<usize> {
+        use std::io::{Error, ErrorKind};
+
+        let mut count = 0;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let record = match line.strip_suffix('\n') {
+                Some(record) => record,
+                None => break, // The last record was not completely written
+            };
+            let error = |msg| {
+                let msg = format!("invalid journal at line {}: {}", count + 1, msg);
+                Error::new(ErrorKind::InvalidData, msg)
+            };
+            let op = EditOp::decode(record).ok_or_else(|| error("malformed edit"))?;
+            if !op.text().is_empty() && !self.apply_op(&op) {
+                return Err(error("edit does not match the text"));
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn apply_op(&mut self, op: &EditOp) -> bool {
+        // The edit already happened or is made by the application, so it is not rejected by protected regions
+        let suspended = std::mem::replace(&mut self.protection.suspended, true);
+        let applied = self.apply_op_unprotected(op);
+        self.protection.suspended = suspended;
+        applied
+    }
+
+    fn apply_op_unprotected(&mut self, op: &EditOp) -> bool {
+        let (row, col) = op.pos();
+        if row >= self.lines.len() || col > self.lines[row].chars().count() {
+            return false;
+        }
+        self.cancel_selection();
+        self.cursor = (row, col);
+        // The edit is made at the position, not at the column beyond the end of line
+        self.virtual_pos = None;
+
+        if op.text().is_empty() {
+            return false;
+        }
+        let mut chunk: Vec<String> = op.text().split('\n').map(|s| s.to_string()).collect();
+        if let EditOp::Insert { .. } = op {
+            if chunk.len() == 1 {
+                self.insert_piece(chunk.remove(0));
+            } else {
+                self.insert_chunk(chunk);
+            }
+            return true;
+        }
+
+        let end_row = row + chunk.len() - 1;
+        let last = chunk[chunk.len() - 1].chars().count();
+        let end_col = if chunk.len() == 1 { col + last } else { last };
+        if end_row >= self.lines.len()
+            || end_col > self.lines[end_row].chars().count()
+            || self.text_in_range((row, col), (end_row, end_col)) != op.text()
+        {
+            return false;
+        }
+        let start = Pos::new(row, col, self.line_offset(row, col));
+        let end = Pos::new(end_row, end_col, self.line_offset(end_row, end_col));
+        self.delete_range(start, end, false)
+    }
+
+    /// Start or stop recording operations of local edits for collaborative editing. Recorded operations can be taken
+    /// with [`TextArea::take_edit_ops`] and sent to other peers. Stopping recording discards operations not taken yet.
+    /// Recording is disabled by default.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, EditOp};
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.set_edit_ops_recording(true);
     ///
-    /// assert_eq!(textarea.indent(), "    ");
-    /// textarea.set_tab_length(2);
-    /// assert_eq!(textarea.indent(), "  ");
-    /// textarea.set_hard_tab_indent(true);
-    /// assert_eq!(textarea.indent(), "\t");
+    /// textarea.insert_str("hello");
+    /// textarea.delete_char();
+    /// assert_eq!(
+    ///     textarea.take_edit_ops(),
+    ///     [
+    ///         EditOp::Insert { pos: (0, 0), text: "hello".to_string() },
+    ///         EditOp::Delete { pos: (0, 4), text: "o".to_string() },
+    ///     ],
+    /// );
+    /// assert!(textarea.take_edit_ops().is_empty());
     /// ```
-    pub fn indent(&self) -> &'static str {
-        if self.hard_tab_indent {
-            "\t"
-        } else {
-            spaces(self.tab_len)
+    pub fn set_edit_ops_recording(&mut self, enabled: bool) {
+        if !enabled {
+            self.edit_ops = None;
+        } else if self.edit_ops.is_none() {
+            self.edit_ops = Some(vec![]);
         }
     }
 
-    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
-    pub fn set_max_histories(&mut self, max: usize) {
-        self.history = History::new(max);
+    /// Get if operations of local edits are recorded. See [`TextArea::set_edit_ops_recording`].
+    pub fn edit_ops_recording(&self) -> bool {
+        self.edit_ops.is_some()
     }
 
-    /// Get how many modifications are remembered for undo/redo. The default value is 50.
-    pub fn max_histories(&self) -> usize {
-        self.history.max_items()
+    /// Take the operations of local edits recorded since the last call in the order they were made, including undo and
+    /// redo. An empty vector is returned when recording is disabled. See [`TextArea::set_edit_ops_recording`].
+    pub fn take_edit_ops(&mut self) -> Vec<EditOp> {
+        self.edit_ops
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
     }
 
-    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
-    /// cursor line, set the default style.
+    /// Apply the operation made by another peer. The operation must be made on the current text, meaning that the
+    /// caller is responsible for transforming concurrent operations (e.g. with OT or CRDT) before applying them. This
+    /// method returns `false` and does nothing when the position is out of the text or the deleted text doesn't match.
+    ///
+    /// The cursor and the selection are kept at the same place in the text, moving along with the text edited before
+    /// them. A remote edit is not recorded by [`TextArea::take_edit_ops`] but is appended to the journal. It is not
+    /// undone by [`TextArea::undo`]. Instead, the local undo history is moved along with the remote edit so that undo
+    /// and redo revert only the local edits. When the remote edit touches the text edited by some local edit in the
+    /// history, the history is cleared since the local edit can no longer be reverted as it was.
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove, EditOp};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["world"]);
+    /// textarea.move_cursor(CursorMove::End);
     ///
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_cursor_line_style(style);
-    /// assert_eq!(textarea.cursor_line_style(), style);
+    /// let op = EditOp::Insert { pos: (0, 0), text: "hello\n".to_string() };
+    /// assert!(textarea.apply_remote_edit(&op));
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert_eq!(textarea.cursor(), (1, 5));
     ///
-    /// // Disable cursor line style
-    /// textarea.set_cursor_line_style(Style::default());
+    /// let op = EditOp::Delete { pos: (1, 0), text: "wor".to_string() };
+    /// assert!(textarea.apply_remote_edit(&op));
+    /// assert_eq!(textarea.lines(), ["hello", "ld"]);
+    /// assert_eq!(textarea.cursor(), (1, 2));
+    ///
+    /// // The deleted text doesn't match
+    /// let op = EditOp::Delete { pos: (0, 0), text: "foo".to_string() };
+    /// assert!(!textarea.apply_remote_edit(&op));
     /// ```
-    pub fn set_cursor_line_style(&mut self, style: Style) {
-        self.cursor_line_style = style;
-    }
+    pub fn apply_remote_edit(&mut self, op: &EditOp) -> bool {
+        let cursor = self.cursor;
+        let selection_start = self.selection_start;
+        let virtual_pos = self.virtual_pos;
+        let (row, col) = op.pos();
+        let offset = self.line_offset(row, col);
+        let recording = self.edit_ops.take();
+        let author = self.tracked.author.take();
+        // The remote edit is not undone by the local undo
+        let history = std::mem::replace(&mut self.history, History::new(0));
+        let applied = self.apply_op(op);
+        self.history = history;
+        self.edit_ops = recording;
+        self.tracked.author = author;
+        if !applied {
+            self.cursor = cursor;
+            self.selection_start = selection_start;
+            self.virtual_pos = virtual_pos;
+            return false;
+        }
 
-    /// Get the style of cursor line. By default it is styled with underline.
-    pub fn cursor_line_style(&self) -> Style {
-        self.cursor_line_style
+        self.cursor = op.shift(cursor);
+        self.selection_start = selection_start.map(|pos| op.shift(pos));
+        if !self.history.rebase(op, offset) {
+            self.set_max_histories(self.max_histories());
+        }
+        self.last_paste = None;
+        self.expand_history = ExpandHistory::default();
+        #[cfg(feature = "search")]
+        {
+            self.replace = None;
+        }
+        true
     }
 
-    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
-    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
-    /// the default style.
+    /// Register the cursor and the selection of a remote collaborator to render them alongside the local cursor. A
+    /// cursor with the same name is replaced. See [`RemoteCursor`] for more details.
+    ///
+    /// Remote cursors move along with the text edited by local edits and by [`TextArea::apply_remote_edit`]. Update
+    /// them when the collaborator moves the cursor.
     /// ```
     /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, RemoteCursor};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["hello, world"]);
+    /// let style = Style::default().bg(Color::Magenta);
     ///
-    /// // Show line numbers in dark gray background
-    /// let style = Style::default().bg(Color::DarkGray);
-    /// textarea.set_line_number_style(style);
-    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// textarea.set_remote_cursor(RemoteCursor::new("alice", (0, 7), style));
+    /// textarea.set_remote_cursor(RemoteCursor::new("bob", (0, 0), style));
+    /// textarea.set_remote_cursor(RemoteCursor::new("alice", (0, 8), style));
+    /// assert_eq!(textarea.remote_cursors().len(), 2);
+    ///
+    /// // Insert text before the cursor of alice
+    /// textarea.insert_str("oh, ");
+    /// assert_eq!(textarea.remote_cursor("alice").unwrap().position, (0, 12));
     /// ```
-    pub fn set_line_number_style(&mut self, style: Style) {
-        self.line_number_style = Some(style);
+    pub fn set_remote_cursor(&mut self, cursor: RemoteCursor) {
+        match self
+            .remote_cursors
+            .iter_mut()
+            .find(|c| c.name == cursor.name)
+        {
+            Some(c) => *c = cursor,
+            None => self.remote_cursors.push(cursor),
+        }
     }
 
-    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
-    /// method, Line numbers will no longer be shown.
+    /// Get the remote cursor of the collaborator registered by [`TextArea::set_remote_cursor`].
+    pub fn remote_cursor(&self, name: &str) -> Option<&RemoteCursor> {
+        self.remote_cursors.iter().find(|c| c.name == name)
+    }
+
+    /// Get all remote cursors registered by [`TextArea::set_remote_cursor`] in the registered order.
+    pub fn remote_cursors(&self) -> &[RemoteCursor] {
+        &self.remote_cursors
+    }
+
+    /// Remove the remote cursor of the collaborator. This method returns `true` when the cursor was registered.
     /// ```
-    /// use ratatui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use ratatui::style::Style;
+    /// use tui_textarea::{TextArea, RemoteCursor};
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.set_remote_cursor(RemoteCursor::new("alice", (0, 0), Style::default()));
     ///
-    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
-    /// textarea.remove_line_number();
-    /// assert_eq!(textarea.line_number_style(), None);
+    /// assert!(textarea.remove_remote_cursor("alice"));
+    /// assert!(!textarea.remove_remote_cursor("alice"));
+    /// assert!(textarea.remote_cursors().is_empty());
     /// ```
-    pub fn remove_line_number(&mut self) {
-        self.line_number_style = None;
+    pub fn remove_remote_cursor(&mut self, name: &str) -> bool {
+        let len = self.remote_cursors.len();
+        self.remote_cursors.retain(|c| c.name != name);
+        self.remote_cursors.len() != len
     }
 
-    /// Get the style of line number if set.
-    pub fn line_number_style(&self) -> Option<Style> {
-        self.line_number_style
+    /// Remove all remote cursors registered by [`TextArea::set_remote_cursor`].
+    pub fn clear_remote_cursors(&mut self) {
+        self.remote_cursors.clear();
     }
 
-    /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
-    /// enables the placeholder. The default value is an empty string so the placeholder is disabled by default.
-    /// To customize the text style, see [`TextArea::set_placeholder_style`].
+    /// Start tracking changes made by the author for review. While tracking, inserted text is rendered with the
+    /// author's style and deleted text is kept as struck-through virtual text until the change is accepted or rejected.
+    /// Consecutive edits by the same author are merged into one change. See [`TrackedChange`] for more details.
+    ///
+    /// Calling this method again switches the author. Edits applied by [`TextArea::apply_remote_edit`] are not tracked.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, ChangeKind};
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_text(), "");
-    /// assert!(textarea.placeholder_style().is_none());
+    /// let mut textarea = TextArea::from(["hello world"]);
+    /// textarea.start_tracking_changes("alice");
     ///
-    /// textarea.set_placeholder_text("Hello");
-    /// assert_eq!(textarea.placeholder_text(), "Hello");
-    /// assert!(textarea.placeholder_style().is_some());
+    /// textarea.delete_next_word();
+    /// textarea.insert_str("goodbye");
+    /// assert_eq!(textarea.lines(), ["goodbye world"]);
+    ///
+    /// let changes: Vec<_> = textarea
+    ///     .tracked_changes()
+    ///     .iter()
+    ///     .map(|c| (c.kind, c.text.as_str()))
+    ///     .collect();
+    /// assert_eq!(changes, [(ChangeKind::Deletion, "hello"), (ChangeKind::Insertion, "goodbye")]);
     /// ```
-    pub fn set_placeholder_text(&mut self, placeholder: impl Into<String>) {
-        self.placeholder = placeholder.into();
+    pub fn start_tracking_changes(&mut self, author: impl Into<String>) {
+        self.tracked.author = Some(author.into());
     }
 
-    /// Set the style of the placeholder text. The default style is a dark gray text.
+    /// Stop tracking changes started by [`TextArea::start_tracking_changes`]. Pending changes are kept and can still be
+    /// accepted or rejected.
+    pub fn stop_tracking_changes(&mut self) {
+        self.tracked.author = None;
+    }
+
+    /// Get the author whose changes are being tracked. `None` is returned when changes are not tracked.
+    pub fn tracking_author(&self) -> Option<&str> {
+        self.tracked.author.as_deref()
+    }
+
+    /// Get the pending tracked changes sorted by their positions.
+    pub fn tracked_changes(&self) -> &[TrackedChange] {
+        &self.tracked.changes
+    }
+
+    /// Accept the tracked change. The text is kept as it is and the change is no longer highlighted. This method returns
+    /// `false` when no pending change has the ID.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_style(), None); // When the placeholder is disabled
-    ///
-    /// textarea.set_placeholder_text("Enter your message"); // Enable placeholder by setting non-empty text
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.start_tracking_changes("alice");
+    /// textarea.delete_line_by_end();
     ///
-    /// let style = Style::default().bg(Color::Blue);
-    /// textarea.set_placeholder_style(style);
-    /// assert_eq!(textarea.placeholder_style(), Some(style));
+    /// let id = textarea.tracked_changes()[0].id;
+    /// assert!(textarea.accept_change(id));
+    /// assert!(textarea.tracked_changes().is_empty());
+    /// assert_eq!(textarea.lines(), [""]);
     /// ```
-    pub fn set_placeholder_style(&mut self, style: Style) {
-        self.placeholder_style = style;
+    pub fn accept_change(&mut self, id: usize) -> bool {
+        self.tracked.remove(id).is_some()
     }
 
-    /// Get the placeholder text. An empty string means the placeholder is disabled. The default value is an empty string.
+    /// Reject the tracked change. Inserted text is deleted and deleted text is restored. The reverting edit is not
+    /// tracked but can be undone. This method returns `false` when no pending change has the ID.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_text(), "");
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.start_tracking_changes("alice");
+    /// textarea.delete_line_by_end();
+    /// textarea.insert_str("goodbye");
+    ///
+    /// for id in textarea.tracked_changes().iter().map(|c| c.id).collect::<Vec<_>>() {
+    ///     assert!(textarea.reject_change(id));
+    /// }
+    /// assert!(textarea.tracked_changes().is_empty());
+    /// assert_eq!(textarea.lines(), ["hello"]);
     /// ```
-    pub fn placeholder_text(&self) -> &'_ str {
-        self.placeholder.as_str()
+    pub fn reject_change(&mut self, id: usize) -> bool {
+        let change = match self.tracked.remove(id) {
+            Some(change) => change,
+            None => return false,
+        };
+        let (pos, text) = (change.position, change.text);
+        let op = match change.kind {
+            ChangeKind::Insertion => EditOp::Delete { pos, text },
+            ChangeKind::Deletion => EditOp::Insert { pos, text },
+        };
+        let author = self.tracked.author.take();
+        let applied = self.apply_op(&op);
+        self.tracked.author = author;
+        applied
     }
 
-    /// Get the placeholder style. When the placeholder text is empty, it returns `None` since the placeholder is disabled.
-    /// The default style is a dark gray text.
+    /// Accept all pending tracked changes. See [`TextArea::accept_change`].
+    pub fn accept_all_changes(&mut self) {
+        self.tracked.changes.clear();
+    }
+
+    /// Reject all pending tracked changes. See [`TextArea::reject_change`].
+    pub fn reject_all_changes(&mut self) {
+        while let Some(change) = self.tracked.changes.last() {
+            let id = change.id;
+            if !self.reject_change(id) {
+                break;
+            }
+        }
+        self.tracked.changes.clear();
+    }
+
+    /// Set the style to render changes by the author. Inserted text is rendered with the style and deleted text is
+    /// rendered with the style and [`Modifier::CROSSED_OUT`]. Changes by authors without styles are rendered with green
+    /// text.
     /// ```
+    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.placeholder_style(), None);
+    /// let style = Style::default().fg(Color::Cyan);
     ///
-    /// textarea.set_placeholder_text("hello");
-    /// assert!(textarea.placeholder_style().is_some());
+    /// textarea.set_author_style("alice", style);
+    /// assert_eq!(textarea.author_style("alice"), style);
+    /// assert_eq!(textarea.author_style("bob"), Style::default().fg(Color::Green));
     /// ```
-    pub fn placeholder_style(&self) -> Option<Style> {
-        if self.placeholder.is_empty() {
-            None
-        } else {
-            Some(self.placeholder_style)
-        }
+    pub fn set_author_style(&mut self, author: impl Into<String>, style: Style) {
+        self.tracked.styles.insert(author.into(), style);
     }
 
-    /// Specify a character masking the text. All characters in the textarea will be replaced by this character.
-    /// This API is useful for making a kind of credentials form such as a password input.
+    /// Get the style to render changes by the author. See [`TextArea::set_author_style`].
+    pub fn author_style(&self, author: &str) -> Style {
+        self.tracked.style(author)
+    }
+
+    /// Protect the characters between the start and end positions from editing. Editing commands which modify the
+    /// protected text are rejected and [`TextArea::input`] returns `false` for them. Text can still be inserted just
+    /// before or after the range, which is useful for templates where only certain fields are fillable. Protected
+    /// ranges move along with edits of the text before them.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove, Input, Key};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["Name: "]);
+    /// textarea.protect_range((0, 0), (0, 6));
     ///
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
-    /// textarea.set_mask_char('●');
-    /// assert_eq!(textarea.mask_char(), Some('●'));
+    /// // Editing inside the protected range is rejected
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert!(!textarea.input(Input { key: Key::Char('x'), ..Default::default() }));
+    /// assert!(!textarea.delete_char());
+    ///
+    /// // The field after the range is fillable
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.insert_str("Alice");
+    /// assert_eq!(textarea.lines(), ["Name: Alice"]);
+    /// assert!(!textarea.delete_line_by_head());
     /// ```
-    pub fn set_mask_char(&mut self, mask: char) {
-        self.mask = Some(mask);
+    pub fn protect_range(&mut self, start: (usize, usize), end: (usize, usize)) {
+        let (start, end) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        if start < end {
+            self.protection.regions.push(Region {
+                start,
+                end,
+                lines: false,
+            });
+        }
     }
 
-    /// Clear the masking character previously set by [`TextArea::set_mask_char`].
+    /// Protect the lines from `start_row` to `end_row` (inclusive) from editing. Unlike [`TextArea::protect_range`],
+    /// text can't be inserted at the start or the end of the lines and the lines can't be joined with their neighbors.
+    /// This is useful for REPL transcripts above the input line.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["> 1 + 2", "3", ""]);
+    /// textarea.protect_lines(0, 1);
     ///
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
-    /// textarea.clear_mask_char();
-    /// assert_eq!(textarea.mask_char(), None);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// assert!(!textarea.delete_char()); // Cannot join with the line above
+    /// assert!(textarea.is_protected((1, 1)));
+    /// assert!(!textarea.is_protected((2, 0)));
+    ///
+    /// textarea.insert_str("> 4 * 5");
+    /// assert_eq!(textarea.lines(), ["> 1 + 2", "3", "> 4 * 5"]);
     /// ```
-    pub fn clear_mask_char(&mut self) {
-        self.mask = None;
+    pub fn protect_lines(&mut self, start_row: usize, end_row: usize) {
+        let last = self.lines.len() - 1;
+        let (start_row, end_row) = (
+            cmp::min(start_row, end_row),
+            cmp::min(cmp::max(start_row, end_row), last),
+        );
+        if start_row > last {
+            return;
+        }
+        self.protection.regions.push(Region {
+            start: (start_row, 0),
+            end: (end_row, self.lines[end_row].chars().count()),
+            lines: true,
+        });
     }
 
-    /// Get the character to mask text. When no character is set, `None` is returned.
+    /// Check if text can't be inserted at the position due to the ranges protected by [`TextArea::protect_range`] or
+    /// [`TextArea::protect_lines`].
+    pub fn is_protected(&self, pos: (usize, usize)) -> bool {
+        !self.protection.is_editable(pos, pos)
+    }
+
+    /// Get the ranges protected by [`TextArea::protect_range`] and [`TextArea::protect_lines`] as pairs of start and
+    /// end positions. A range of lines ends at the end of its last line.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// assert_eq!(textarea.mask_char(), None);
-    /// textarea.set_mask_char('*');
-    /// assert_eq!(textarea.mask_char(), Some('*'));
+    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// textarea.protect_range((0, 1), (0, 2));
+    /// textarea.protect_lines(1, 1);
+    /// assert_eq!(textarea.protected_ranges(), [((0, 1), (0, 2)), ((1, 0), (1, 3))]);
     /// ```
-    pub fn mask_char(&self) -> Option<char> {
-        self.mask
+    pub fn protected_ranges(&self) -> Vec<((usize, usize), (usize, usize))> {
+        self.protection
+            .regions
+            .iter()
+            .map(|r| (r.start, r.end))
+            .collect()
     }
 
-    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
-    /// cursor line hides a cursor.
+    /// Remove all ranges protected by [`TextArea::protect_range`] and [`TextArea::protect_lines`].
+    pub fn clear_protected_ranges(&mut self) {
+        self.protection.regions.clear();
+    }
+
+    fn highlight_tracked_changes<'b>(
+        &'b self,
+        hl: &mut LineHighlighter<'b>,
+        line: &'b str,
+        row: usize,
+    ) {
+        let offset = |col: usize| byte_offset(line, col);
+        let changes = self.tracked.changes.iter();
+        hl.changes(
+            changes
+                .clone()
+                .filter(|c| c.kind == ChangeKind::Insertion)
+                .filter_map(|c| {
+                    let (start, end) = (c.position, c.end());
+                    if row < start.0 || end.0 < row {
+                        return None;
+                    }
+                    let s = if row == start.0 { offset(start.1) } else { 0 };
+                    let e = if row == end.0 {
+                        offset(end.1)
+                    } else {
+                        line.len()
+                    };
+                    Some((s, e, self.tracked.style(&c.author)))
+                }),
+        );
+        hl.tracked_deletions(
+            changes
+                .filter(|c| c.kind == ChangeKind::Deletion && c.position.0 == row)
+                .map(|c| {
+                    let text = if c.text.contains('\n') {
+                        Cow::Owned(c.text.replace('\n', "↵"))
+                    } else {
+                        Cow::Borrowed(c.text.as_str())
+                    };
+                    let style = self
+                        .tracked
+                        .style(&c.author)
+                        .add_modifier(Modifier::CROSSED_OUT);
+                    (offset(c.position.1), text, style)
+                }),
+        );
+    }
+
+    /// Set the spell checker to find misspelled words in the textarea. Misspelled words are rendered with the style set
+    /// by [`TextArea::set_misspelling_style`]. See [`SpellChecker`] for more details.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["Hello, wrold!"]);
+    /// assert!(!textarea.has_spell_checker());
     ///
-    /// let style = Style::default().bg(Color::Red);
-    /// textarea.set_cursor_style(style);
-    /// assert_eq!(textarea.cursor_style(), style);
+    /// let dict = ["hello", "world"];
+    /// textarea.set_spell_checker(move |word: &str| !dict.contains(&word.to_lowercase().as_str()));
+    /// assert!(textarea.has_spell_checker());
+    ///
+    /// assert!(textarea.misspelling_forward());
+    /// assert_eq!(textarea.cursor(), (0, 7));
     /// ```
-    pub fn set_cursor_style(&mut self, style: Style) {
-        self.cursor_style = style;
-    }
-
-    /// Get the style of cursor.
-    pub fn cursor_style(&self) -> Style {
-        self.cursor_style
+    pub fn set_spell_checker(&mut self, checker: impl SpellChecker + Send + Sync + 'a) {
+        self.spell.checker = Some(Arc::new(checker));
     }
 
-    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
-    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
-    /// file must end with a newline.
+    /// Remove the spell checker set by [`TextArea::set_spell_checker`].
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.lines(), [""]);
-    ///
-    /// textarea.insert_char('a');
-    /// assert_eq!(textarea.lines(), ["a"]);
-    ///
-    /// textarea.insert_newline();
-    /// assert_eq!(textarea.lines(), ["a", ""]);
     ///
-    /// textarea.insert_char('b');
-    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// textarea.set_spell_checker(|word: &str| word == "teh");
+    /// textarea.remove_spell_checker();
+    /// assert!(!textarea.has_spell_checker());
     /// ```
-    pub fn lines(&'a self) -> &'a [String] {
-        &self.lines
+    pub fn remove_spell_checker(&mut self) {
+        self.spell.checker = None;
     }
 
-    /// Convert [`TextArea`] instance into line texts.
+    /// Return if the spell checker is set or not.
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.has_spell_checker());
     ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
-    ///
-    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// textarea.set_spell_checker(|word: &str| word == "teh");
+    /// assert!(textarea.has_spell_checker());
     /// ```
-    pub fn into_lines(self) -> Vec<String> {
-        self.lines
+    pub fn has_spell_checker(&self) -> bool {
+        self.spell.checker.is_some()
     }
 
-    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
+    /// Move the cursor to the start of the next misspelled word. The search wraps around the text buffer. It returns
+    /// `true` when some misspelled word was found. Otherwise it returns `false`. When no spell checker is set, this
+    /// method does nothing.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// let mut textarea = TextArea::from(["teh cat", "and teh dog"]);
+    /// textarea.set_spell_checker(|word: &str| word == "teh");
     ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// assert!(textarea.misspelling_forward());
+    /// assert_eq!(textarea.cursor(), (1, 4));
     ///
-    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// // Wraps around the text buffer
+    /// assert!(textarea.misspelling_forward());
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
-    pub fn cursor(&self) -> (usize, usize) {
-        self.cursor
+    pub fn misspelling_forward(&mut self) -> bool {
+        if let Some(cursor) = self.spell.forward(&self.lines, self.cursor) {
+            self.cursor = cursor;
+            true
+        } else {
+            false
+        }
     }
 
-    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
-    /// disabled because those alignments don't work well with line numbers.
+    /// Move the cursor to the start of the previous misspelled word. The search wraps around the text buffer. It returns
+    /// `true` when some misspelled word was found. Otherwise it returns `false`. When no spell checker is set, this
+    /// method does nothing.
     /// ```
-    /// use ratatui::layout::Alignment;
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["teh cat", "and teh dog"]);
+    /// textarea.set_spell_checker(|word: &str| word == "teh");
     ///
-    /// textarea.set_alignment(Alignment::Center);
-    /// assert_eq!(textarea.alignment(), Alignment::Center);
-    /// ```
-    pub fn set_alignment(&mut self, alignment: Alignment) {
-        if let Alignment::Center | Alignment::Right = alignment {
-            self.line_number_style = None;
+    /// // Wraps around the text buffer
+    /// assert!(textarea.misspelling_back());
+    /// assert_eq!(textarea.cursor(), (1, 4));
+    ///
+    /// assert!(textarea.misspelling_back());
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    pub fn misspelling_back(&mut self) -> bool {
+        if let Some(cursor) = self.spell.back(&self.lines, self.cursor) {
+            self.cursor = cursor;
+            true
+        } else {
+            false
         }
-        self.alignment = alignment;
     }
 
-    /// Get current text alignment. The default alignment is [`Alignment::Left`].
+    /// Set the style of misspelled words found by the spell checker. The default style is red underlined text.
     /// ```
-    /// use ratatui::layout::Alignment;
+    /// use ratatui::style::{Style, Color, Modifier};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// assert_eq!(textarea.alignment(), Alignment::Left);
+    /// let style = Style::default().add_modifier(Modifier::UNDERLINED);
+    /// textarea.set_misspelling_style(style);
+    /// assert_eq!(textarea.misspelling_style(), style);
     /// ```
-    pub fn alignment(&self) -> Alignment {
-        self.alignment
+    pub fn set_misspelling_style(&mut self, style: Style) {
+        self.spell.style = style;
     }
 
-    /// Check if the textarea has a empty content.
+    /// Get the style of misspelled words.
+    pub fn misspelling_style(&self) -> Style {
+        self.spell.style
+    }
+
+    /// Set diagnostics such as errors reported by linters or language servers. The text in the range of each diagnostic
+    /// is rendered with the style for its severity. See [`TextArea::set_diagnostic_style`]. Previous diagnostics are
+    /// replaced.
+    ///
+    /// Note that the positions of the diagnostics are not updated on editing the text. Set the new diagnostics after
+    /// the text was modified.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, Diagnostic, Severity};
     ///
-    /// let textarea = TextArea::default();
-    /// assert!(textarea.is_empty());
+    /// let mut textarea = TextArea::from(["let x = y;"]);
     ///
-    /// let textarea = TextArea::from(["hello"]);
-    /// assert!(!textarea.is_empty());
+    /// textarea.set_diagnostics(vec![
+    ///     Diagnostic::new((0, 4), (0, 5), Severity::Warning, "unused variable `x`"),
+    ///     Diagnostic::new((0, 8), (0, 9), Severity::Error, "cannot find value `y`"),
+    /// ]);
+    /// assert_eq!(textarea.diagnostics().len(), 2);
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.lines == [""]
+    pub fn set_diagnostics(&mut self, diagnostics: Vec<Diagnostic>) {
+        self.diagnostics.items = diagnostics;
     }
 
-    /// Get the yanked text. Text is automatically yanked when deleting strings by [`TextArea::delete_line_by_head`],
-    /// [`TextArea::delete_line_by_end`], [`TextArea::delete_word`], [`TextArea::delete_next_word`],
-    /// [`TextArea::delete_str`], [`TextArea::copy`], and [`TextArea::cut`]. When multiple lines were yanked, they are
-    /// always joined with `\n`.
+    /// Get the diagnostics set by [`TextArea::set_diagnostics`].
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc"]);
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.yank_text(), "abc");
-    ///
-    /// // Multiple lines are joined with \n
-    /// let mut textarea = TextArea::from(["abc", "def"]);
-    /// textarea.delete_str(5);
-    /// assert_eq!(textarea.yank_text(), "abc\nd");
+    /// let textarea = TextArea::default();
+    /// assert!(textarea.diagnostics().is_empty());
     /// ```
-    pub fn yank_text(&self) -> String {
-        self.yank.to_string()
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics.items
     }
 
-    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
-    /// but `\r` isn't.
+    /// Remove all diagnostics set by [`TextArea::set_diagnostics`].
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, Diagnostic, Severity};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["hello"]);
     ///
-    /// textarea.set_yank_text("hello\nworld");
-    /// textarea.paste();
-    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// textarea.set_diagnostics(vec![Diagnostic::new((0, 0), (0, 5), Severity::Info, "greeting")]);
+    /// textarea.clear_diagnostics();
+    /// assert!(textarea.diagnostics().is_empty());
     /// ```
-    pub fn set_yank_text(&mut self, text: impl Into<String>) {
-        // `str::lines` is not available since it strips a newline at end
-        let lines: Vec<_> = text
-            .into()
-            .split('\n')
-            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
-            .collect();
-        self.yank = lines.into();
+    pub fn clear_diagnostics(&mut self) {
+        self.diagnostics.items.clear();
     }
 
-    /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
-    /// When a valid pattern is set, all matches will be highlighted in the textarea. Note that the cursor does not
-    /// move. To move the cursor, use [`TextArea::search_forward`] and [`TextArea::search_back`].
-    ///
-    /// Grammar of regular expression follows [regex crate](https://docs.rs/regex/latest/regex). Patterns don't match
-    /// to newlines so match passes across no newline.
-    ///
-    /// When the pattern is invalid, the search pattern will not be updated and an error will be returned.
-    ///
+    /// Get the diagnostic at the cursor position. When multiple diagnostics overlap at the cursor, the most severe one
+    /// is returned. `None` is returned when no diagnostic is at the cursor.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, Diagnostic, Severity, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["hello, world", "goodbye, world"]);
-    ///
-    /// // Search "world"
-    /// textarea.set_search_pattern("world").unwrap();
+    /// let mut textarea = TextArea::from(["let x = y;"]);
+    /// textarea.set_diagnostics(vec![
+    ///     Diagnostic::new((0, 0), (0, 10), Severity::Warning, "unused statement"),
+    ///     Diagnostic::new((0, 8), (0, 9), Severity::Error, "cannot find value `y`"),
+    /// ]);
     ///
-    /// assert_eq!(textarea.cursor(), (0, 0));
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (0, 7));
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (1, 9));
+    /// let d = textarea.diagnostic_at_cursor().unwrap();
+    /// assert_eq!(d.message, "unused statement");
     ///
-    /// // Stop the text search
-    /// textarea.set_search_pattern("");
+    /// textarea.move_cursor(CursorMove::Jump(0, 8));
+    /// let d = textarea.diagnostic_at_cursor().unwrap();
+    /// assert_eq!(d.message, "cannot find value `y`");
     ///
-    /// // Invalid search pattern
-    /// assert!(textarea.set_search_pattern("(hello").is_err());
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert!(textarea.diagnostic_at_cursor().is_none());
     /// ```
-    #[cfg(feature = "search")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn set_search_pattern(&mut self, query: impl AsRef<str>) -> Result<(), regex::Error> {
-        self.search.set_pattern(query.as_ref())
+    pub fn diagnostic_at_cursor(&self) -> Option<&Diagnostic> {
+        self.diagnostics.at(self.cursor)
     }
 
-    /// Get a regular expression which was set by [`TextArea::set_search_pattern`]. When no text search is ongoing, this
-    /// method returns `None`.
-    ///
+    /// Set the style of diagnostics with the severity. The default styles are underlined texts colored in red for
+    /// errors, yellow for warnings, blue for information, and dark gray for hints.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::{TextArea, Severity};
     ///
     /// let mut textarea = TextArea::default();
     ///
-    /// assert!(textarea.search_pattern().is_none());
-    /// textarea.set_search_pattern("hello+").unwrap();
-    /// assert!(textarea.search_pattern().is_some());
-    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "hello+");
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_diagnostic_style(Severity::Error, style);
+    /// assert_eq!(textarea.diagnostic_style(Severity::Error), style);
     /// ```
-    #[cfg(feature = "search")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_pattern(&self) -> Option<&regex::Regex> {
-        self.search.pat.as_ref()
+    pub fn set_diagnostic_style(&mut self, severity: Severity, style: Style) {
+        self.diagnostics.set_style(severity, style);
     }
 
-    /// Search the pattern set by [`TextArea::set_search_pattern`] forward and move the cursor to the next match
-    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
-    /// some match was found. Otherwise it returns `false`.
-    ///
-    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
-    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
-    /// move to the next match ignoring the match at the current position.
+    /// Get the style of diagnostics with the severity.
+    pub fn diagnostic_style(&self, severity: Severity) -> Style {
+        self.diagnostics.style(severity)
+    }
+
+    /// Append lines at the end of the text without moving the cursor, for example to stream the output of a process
+    /// into the textarea. Lines containing newlines are split. When the textarea is empty, the lines replace the empty
+    /// line instead of being put after it.
     ///
+    /// Appending lines is not recorded in the undo history so that it is cheap even for huge streams. Since the lines
+    /// are always put after the existing text, the edits in the history can still be undone.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
-    ///
-    /// textarea.set_search_pattern("hello+").unwrap();
-    ///
-    /// // Move to next position
-    /// let match_found = textarea.search_forward(false);
-    /// assert!(match_found);
-    /// assert_eq!(textarea.cursor(), (1, 0));
-    ///
-    /// // Since the cursor position matches to "hello+", it does not move
-    /// textarea.search_forward(true);
-    /// assert_eq!(textarea.cursor(), (1, 0));
-    ///
-    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
-    /// textarea.search_forward(false);
-    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// let mut textarea = TextArea::default();
+    /// textarea.append_lines(["a", "b"]);
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
     ///
-    /// // Text search wrap around the buffer
-    /// textarea.search_forward(false);
+    /// textarea.append_lines(["c\nd"]);
+    /// assert_eq!(textarea.lines(), ["a", "b", "c", "d"]);
     /// assert_eq!(textarea.cursor(), (0, 0));
-    ///
-    /// // `false` is returned when no match was found
-    /// textarea.set_search_pattern("bye+").unwrap();
-    /// let match_found = textarea.search_forward(false);
-    /// assert!(!match_found);
     /// ```
-    #[cfg(feature = "search")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_forward(&mut self, match_cursor: bool) -> bool {
-        if let Some(cursor) = self.search.forward(&self.lines, self.cursor, match_cursor) {
-            self.cursor = cursor;
-            true
+    pub fn append_lines<I>(&mut self, lines: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut appended = vec![];
+        for line in lines {
+            let line = line.into();
+            appended.extend(
+                line.split('\n')
+                    .map(|l| l.strip_suffix('\r').unwrap_or(l).to_string()),
+            );
+        }
+        if appended.is_empty() {
+            return;
+        }
+
+        let last = self.lines.len() - 1;
+        let following = self.follow && self.cursor.0 == last;
+        let (pos, mut text) = if self.is_empty() {
+            self.lines.clear();
+            ((0, 0), String::new())
         } else {
-            false
+            let pos = (last, self.lines[last].chars().count());
+            (pos, "\n".to_string())
+        };
+        text.push_str(&appended.join("\n"));
+        self.lines.extend(appended);
+        self.record_op(EditOp::Insert { pos, text });
+        self.modified = true;
+        self.mark_idle_change();
+
+        if following {
+            self.cursor = (self.lines.len() - 1, 0);
         }
     }
 
-    /// Search the pattern set by [`TextArea::set_search_pattern`] backward and move the cursor to the next match
-    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
-    /// some match was found. Otherwise it returns `false`.
-    ///
-    /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
-    /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
-    /// move to the next match ignoring the match at the current position.
-    ///
+    /// Enable or disable the follow mode, like `tail -f`. While the cursor is at the last line, the cursor moves to
+    /// the new last line on [`TextArea::append_lines`] so that the viewport is kept pinned to the bottom. Once the
+    /// user moves the cursor or scrolls up, appended lines no longer move the viewport until the cursor goes back to the
+    /// last line. Enabling the follow mode moves the cursor to the last line.
     /// ```
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::from(["hello", "helloo", "hellooo"]);
-    ///
-    /// textarea.set_search_pattern("hello+").unwrap();
-    ///
-    /// // Move to next position with wrapping around the text buffer
-    /// let match_found = textarea.search_back(false);
-    /// assert!(match_found);
-    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// // Since the cursor position matches to "hello+", it does not move
-    /// textarea.search_back(true);
-    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_follow_mode(true);
+    /// assert!(textarea.follow_mode());
     ///
-    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
-    /// textarea.search_back(false);
+    /// textarea.append_lines(["a", "b"]);
     /// assert_eq!(textarea.cursor(), (1, 0));
     ///
-    /// // `false` is returned when no match was found
-    /// textarea.set_search_pattern("bye+").unwrap();
-    /// let match_found = textarea.search_back(false);
-    /// assert!(!match_found);
+    /// // The viewport is no longer pinned after moving up
+    /// textarea.move_cursor(CursorMove::Up);
+    /// textarea.append_lines(["c"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// // Going back to the bottom pins it again
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.append_lines(["d"]);
+    /// assert_eq!(textarea.cursor(), (3, 0));
     /// ```
-    #[cfg(feature = "search")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_back(&mut self, match_cursor: bool) -> bool {
-        if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
-            self.cursor = cursor;
-            true
-        } else {
-            false
+    pub fn set_follow_mode(&mut self, enabled: bool) {
+        self.follow = enabled;
+        if enabled {
+            self.cancel_selection();
+            self.cursor = (self.lines.len() - 1, 0);
         }
     }
 
-    /// Get the text style at matches of text search. The default style is colored with blue in background.
+    /// Get if the follow mode is enabled. See [`TextArea::set_follow_mode`].
+    pub fn follow_mode(&self) -> bool {
+        self.follow
+    }
+
+    /// Start loading text from the reader incrementally, for example to open a huge file without blocking the UI
+    /// thread. Nothing is read by this method. Call [`TextArea::load_chunk`] repeatedly (e.g. on every tick of the
+    /// event loop) to read the content chunk by chunk. The textarea can be rendered and edited while loading and it
+    /// shows the lines loaded so far. `total_bytes` is the size of the content if known, such as the file size, which
+    /// is used for [`TextArea::loading_progress`].
     ///
+    /// Loaded lines are appended at the end of the text with [`TextArea::append_lines`], so they work with the follow
+    /// mode. Starting to load again replaces the previous reader. The reader is owned by the textarea and shared with
+    /// its clones, so it must not borrow anything.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let textarea = TextArea::default();
+    /// let content = b"hello\nworld\n";
+    /// let mut textarea = TextArea::default();
+    /// textarea.start_loading(&content[..], Some(content.len() as u64));
+    /// assert!(textarea.is_loading());
     ///
-    /// assert_eq!(textarea.search_style(), Style::default().bg(Color::Blue));
+    /// while !textarea.load_chunk(4).unwrap() {
+    ///     // Render the textarea and handle inputs here
+    /// }
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert!(!textarea.is_loading());
     /// ```
-    #[cfg(feature = "search")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_style(&self) -> Style {
-        self.search.style
+    pub fn start_loading(
+        &mut self,
+        reader: impl std::io::Read + Send + 'static,
+        total_bytes: Option<u64>,
+    ) {
+        self.loader = Some(Loader::new(reader, total_bytes, self.invalid_utf8));
     }
 
-    /// Set the text style at matches of text search. The default style is colored with blue in background.
+    /// Read at most `max_bytes` bytes from the reader set by [`TextArea::start_loading`] and append the lines
+    /// completed by them. It returns `true` when the reader reached EOF and the loading finished. The last line is
+    /// appended at EOF even if it doesn't end with a newline, and whether the content ended with a newline is remembered
+    /// as [`TextArea::ends_with_newline`]. When nothing is being loaded, it returns `true`
+    /// immediately. Invalid UTF-8 sequences are decoded as set by [`TextArea::set_invalid_utf8`].
+    ///
+    /// On an error, the reader is kept so that the loading can be retried. Call [`TextArea::cancel_loading`] to give
+    /// up.
+    pub fn load_chunk(&mut self, max_bytes: usize) -> std::io::Result<bool> {
+        let (lines, eof) = match &self.loader {
+            Some(loader) => loader.read_lines(max_bytes)?,
+            None => return Ok(true),
+        };
+        self.append_lines(lines);
+        if eof {
+            if let Some(loader) = self.loader.take() {
+                self.ends_with_newline = loader.ends_with_newline();
+            }
+            if self.indent_detection {
+                self.detect_indent();
+            }
+        }
+        Ok(eof)
+    }
+
+    /// Set how invalid UTF-8 sequences are decoded by [`TextArea::start_loading`]. See [`InvalidUtf8`] for the modes.
+    /// The mode takes effect from the next call of [`TextArea::start_loading`]. The default mode is
+    /// [`InvalidUtf8::Replace`].
+    /// ```
+    /// use tui_textarea::{InvalidUtf8, TextArea};
     ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_invalid_utf8(InvalidUtf8::Hex);
+    /// assert_eq!(textarea.invalid_utf8(), InvalidUtf8::Hex);
+    /// ```
+    pub fn set_invalid_utf8(&mut self, mode: InvalidUtf8) {
+        self.invalid_utf8 = mode;
+    }
+
+    /// Get how invalid UTF-8 sequences are decoded on loading. See [`TextArea::set_invalid_utf8`].
+    pub fn invalid_utf8(&self) -> InvalidUtf8 {
+        self.invalid_utf8
+    }
+
+    /// Stop loading the content started by [`TextArea::start_loading`]. The lines already loaded are kept.
+    pub fn cancel_loading(&mut self) {
+        self.loader = None;
+    }
+
+    /// Get if the content is being loaded. See [`TextArea::start_loading`].
+    pub fn is_loading(&self) -> bool {
+        self.loader.is_some()
+    }
+
+    /// Get the progress of loading as the number of bytes loaded so far and the total number of bytes passed to
+    /// [`TextArea::start_loading`]. `None` is returned when nothing is being loaded.
     /// ```
-    /// use ratatui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.loading_progress(), None);
     ///
-    /// let red_bg = Style::default().bg(Color::Red);
-    /// textarea.set_search_style(red_bg);
-    ///
-    /// assert_eq!(textarea.search_style(), red_bg);
+    /// textarea.start_loading(&b"a\nb\nc\n"[..], Some(6));
+    /// textarea.load_chunk(4).unwrap();
+    /// assert_eq!(textarea.loading_progress(), Some((4, Some(6))));
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
     /// ```
-    #[cfg(feature = "search")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn set_search_style(&mut self, style: Style) {
-        self.search.style = style;
+    pub fn loading_progress(&self) -> Option<(u64, Option<u64>)> {
+        self.loader.as_ref().map(Loader::progress)
     }
 
     /// Scroll the textarea. See [`Scrolling`] for the argument.
@@ -2311,6 +9960,33 @@ impl<'a> TextArea<'a> {
         self.scroll_with_shift(scrolling.into(), self.selection_start.is_some());
     }
 
+    /// Enable or disable the typewriter scrolling mode. When enabled, the viewport is scrolled on rendering so that
+    /// the cursor line is kept at the vertical center of the textarea. While typing, the text scrolls up instead of
+    /// the cursor moving down. Near the start of the text, the cursor is above the center since the viewport cannot
+    /// scroll before the first line. This is disabled by default.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea: TextArea = (0..10).map(|i| i.to_string()).collect();
+    /// textarea.set_typewriter_mode(true);
+    /// assert!(textarea.typewriter_mode());
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(3, 0));
+    /// assert_eq!(textarea.render_to_string(2, 5), "1 \n2 \n3 \n4 \n5 ");
+    ///
+    /// // Empty rows are rendered after the last line
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// assert_eq!(textarea.render_to_string(2, 5), "7 \n8 \n9 \n  \n  ");
+    /// ```
+    pub fn set_typewriter_mode(&mut self, enabled: bool) {
+        self.typewriter = enabled;
+    }
+
+    /// Get if the typewriter scrolling mode is enabled. See [`TextArea::set_typewriter_mode`].
+    pub fn typewriter_mode(&self) -> bool {
+        self.typewriter
+    }
+
     fn scroll_with_shift(&mut self, scrolling: Scrolling, shift: bool) {
         if shift && self.selection_start.is_none() {
             self.selection_start = Some(self.cursor);
@@ -2348,4 +10024,164 @@ mod tests {
         textarea.scroll((-5, 0));
         assert_eq!(textarea.cursor(), (12, 0));
     }
+
+    #[test]
+    fn align_lines_keeps_cursor() {
+        let mut textarea = TextArea::from(["  foo bar", "baz  ", "   "]);
+        textarea.set_align_width(11);
+        textarea.move_cursor(CursorMove::Jump(0, 6));
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Jump(2, 2));
+
+        assert!(textarea.align_lines(Alignment::Right));
+        assert_eq!(textarea.lines(), ["    foo bar", "        baz", ""]);
+        assert_eq!(textarea.cursor(), (2, 0));
+        textarea.move_cursor(CursorMove::Jump(1, 11));
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "bar\n        baz");
+        assert!(!textarea.align_lines(Alignment::Right));
+
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), ["  foo bar", "baz  ", "   "]);
+    }
+
+    #[test]
+    fn typewriter_mode_keeps_cursor_centered() {
+        let mut textarea = TextArea::default();
+        textarea.set_typewriter_mode(true);
+        let area = Rect::new(0, 0, 10, 7);
+
+        let mut rows = vec![];
+        for _ in 0..6 {
+            textarea.insert_str("line");
+            textarea.insert_newline();
+            rows.push(textarea.cursor_screen_position(area).unwrap().1);
+        }
+        assert_eq!(rows, [1, 2, 3, 3, 3, 3]);
+
+        textarea.set_typewriter_mode(false);
+        textarea.move_cursor(CursorMove::Top);
+        assert_eq!(textarea.cursor_screen_position(area), Some((0, 0)));
+        textarea.move_cursor(CursorMove::Bottom);
+        assert_eq!(textarea.cursor_screen_position(area), Some((0, 6)));
+    }
+
+    #[test]
+    fn preferred_height_with_folds_and_block() {
+        use crate::ratatui::widgets::Borders;
+
+        let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+        assert!(textarea.fold(1, 3));
+        assert_eq!(textarea.preferred_height(), 3);
+
+        textarea.set_block(Block::default().borders(Borders::TOP | Borders::BOTTOM));
+        assert_eq!(textarea.preferred_height(), 5);
+        textarea.set_max_rows(1);
+        assert_eq!(textarea.preferred_height(), 3);
+        textarea.set_max_rows(0);
+        textarea.set_min_rows(4);
+        assert_eq!(textarea.preferred_height(), 2);
+    }
+
+    #[test]
+    fn blinking_cursor() {
+        let mut textarea = TextArea::from(["ab"]);
+        textarea.set_cursor_blink(Duration::ZERO);
+        let reversed = |t: &TextArea| {
+            t.render_to_buffer(4, 1)
+                .get(0, 0)
+                .style()
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        };
+        assert!(reversed(&textarea));
+        assert!(textarea.tick());
+        assert!(!reversed(&textarea));
+
+        // Key input shows the cursor again
+        textarea.input(Input {
+            key: Key::Null,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+        assert!(textarea.is_cursor_visible());
+        assert!(reversed(&textarea));
+    }
+
+    #[test]
+    fn remote_edit_in_virtual_space() {
+        let mut textarea = TextArea::from(["ab"]);
+        textarea.set_virtual_space(true);
+        textarea.move_cursor(CursorMove::Jump(0, 4));
+        assert_eq!(textarea.virtual_col(), 2);
+
+        // The remote edit is inserted at its position without padding the virtual columns
+        let op = EditOp::Insert {
+            pos: (0, 2),
+            text: "X".to_string(),
+        };
+        assert!(textarea.apply_remote_edit(&op));
+        assert_eq!(textarea.lines(), ["abX"]);
+        assert_eq!(textarea.virtual_col(), 0);
+
+        // Failed remote edit keeps the cursor beyond the end of line
+        textarea.move_cursor(CursorMove::Jump(0, 5));
+        let op = EditOp::Delete {
+            pos: (0, 0),
+            text: "foo".to_string(),
+        };
+        assert!(!textarea.apply_remote_edit(&op));
+        assert_eq!(textarea.virtual_col(), 2);
+
+        // Inserting empty text does nothing
+        let op = EditOp::Insert {
+            pos: (0, 0),
+            text: String::new(),
+        };
+        assert!(!textarea.apply_remote_edit(&op));
+    }
+
+    #[test]
+    fn remote_edit_keeps_undo_history() {
+        let mut t = TextArea::from(["あいう", "def"]);
+        t.move_cursor(CursorMove::Jump(1, 3));
+        t.insert_str("gh");
+        t.move_cursor(CursorMove::Jump(0, 3));
+        t.insert_char('x');
+        assert_eq!(t.lines(), ["あいうx", "defgh"]);
+
+        let op = EditOp::Insert {
+            pos: (0, 1),
+            text: "え\n12".to_string(),
+        };
+        assert!(t.apply_remote_edit(&op));
+        assert_eq!(t.lines(), ["あえ", "12いうx", "defgh"]);
+
+        // Only the local edits are undone
+        assert!(t.undo());
+        assert_eq!(t.lines(), ["あえ", "12いう", "defgh"]);
+        assert!(t.undo());
+        assert_eq!(t.lines(), ["あえ", "12いう", "def"]);
+        assert!(!t.undo());
+
+        // Undone edits are also moved along with the remote edit
+        let op = EditOp::Delete {
+            pos: (0, 0),
+            text: "あえ\n".to_string(),
+        };
+        assert!(t.apply_remote_edit(&op));
+        assert!(t.redo());
+        assert!(t.redo());
+        assert_eq!(t.lines(), ["12いうx", "defgh"]);
+
+        // The history is cleared when the remote edit touches the text of a local edit
+        let op = EditOp::Delete {
+            pos: (1, 3),
+            text: "g".to_string(),
+        };
+        assert!(t.apply_remote_edit(&op));
+        assert_eq!(t.lines(), ["12いうx", "defh"]);
+        assert!(!t.undo());
+    }
 }
@@ -0,0 +1,236 @@
+use crate::input::Input;
+use crate::ratatui::buffer::Buffer;
+use crate::ratatui::layout::Rect;
+use crate::ratatui::widgets::Widget;
+use crate::textarea::TextArea;
+
+/// Container of multiple named textareas for editor-like applications. One of them is active. Inputs are routed to the
+/// active textarea and rendering the set renders the active textarea.
+///
+/// Only the active textarea is focused by [`TextArea::set_focused`], so the focus styles of the block follow the active
+/// textarea when the textareas are rendered side by side.
+/// ```
+/// use tui_textarea::{Input, Key, TextArea, TextAreaSet};
+///
+/// let mut set = TextAreaSet::new();
+/// set.push("main.rs", TextArea::from(["fn main() {}"]));
+/// set.push("notes.txt", TextArea::default());
+/// assert_eq!(set.active_name(), Some("main.rs"));
+///
+/// assert!(set.select_by_name("notes.txt"));
+/// set.input(Input { key: Key::Char('a'), ..Default::default() });
+/// assert_eq!(set.active().unwrap().lines(), ["a"]);
+/// assert_eq!(set.get(0).unwrap().lines(), ["fn main() {}"]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TextAreaSet<'a> {
+    entries: Vec<(String, TextArea<'a>)>,
+    active: usize,
+}
+
+impl<'a> TextAreaSet<'a> {
+    /// Create a new empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the textarea with the name at the end of the set and return its index. The first textarea becomes active.
+    /// Names don't need to be unique, though [`TextAreaSet::select_by_name`] selects the first one with the name.
+    /// ```
+    /// use tui_textarea::{TextArea, TextAreaSet};
+    ///
+    /// let mut set = TextAreaSet::new();
+    /// assert_eq!(set.push("a", TextArea::default()), 0);
+    /// assert_eq!(set.push("b", TextArea::default()), 1);
+    /// assert_eq!(set.len(), 2);
+    /// assert_eq!(set.active_index(), Some(0));
+    /// ```
+    pub fn push(&mut self, name: impl Into<String>, mut textarea: TextArea<'a>) -> usize {
+        textarea.set_focused(self.entries.is_empty());
+        self.entries.push((name.into(), textarea));
+        self.entries.len() - 1
+    }
+
+    /// Remove the textarea at the index and return its name and the textarea. When the active textarea is removed, the
+    /// next one becomes active, or the previous one when it was the last. `None` is returned when the index is out of
+    /// range.
+    /// ```
+    /// use tui_textarea::{TextArea, TextAreaSet};
+    ///
+    /// let mut set = TextAreaSet::new();
+    /// set.push("a", TextArea::default());
+    /// set.push("b", TextArea::default());
+    /// set.select(1);
+    ///
+    /// let (name, _) = set.remove(1).unwrap();
+    /// assert_eq!(name, "b");
+    /// assert_eq!(set.active_name(), Some("a"));
+    /// assert!(set.remove(1).is_none());
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<(String, TextArea<'a>)> {
+        if index >= self.entries.len() {
+            return None;
+        }
+        let removed = self.entries.remove(index);
+        if index < self.active || self.active == self.entries.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        self.update_focus();
+        Some(removed)
+    }
+
+    /// Get the number of textareas in the set.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return if the set has no textarea.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the index of the active textarea. `None` is returned when the set is empty.
+    pub fn active_index(&self) -> Option<usize> {
+        (self.active < self.entries.len()).then(|| self.active)
+    }
+
+    /// Get the active textarea. `None` is returned when the set is empty.
+    pub fn active(&self) -> Option<&TextArea<'a>> {
+        self.get(self.active)
+    }
+
+    /// Get the mutable active textarea. `None` is returned when the set is empty.
+    pub fn active_mut(&mut self) -> Option<&mut TextArea<'a>> {
+        self.get_mut(self.active)
+    }
+
+    /// Get the name of the active textarea. `None` is returned when the set is empty.
+    pub fn active_name(&self) -> Option<&str> {
+        self.name(self.active)
+    }
+
+    /// Get the textarea at the index.
+    pub fn get(&self, index: usize) -> Option<&TextArea<'a>> {
+        self.entries.get(index).map(|(_, t)| t)
+    }
+
+    /// Get the mutable textarea at the index.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut TextArea<'a>> {
+        self.entries.get_mut(index).map(|(_, t)| t)
+    }
+
+    /// Get the name of the textarea at the index.
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(|(n, _)| n.as_str())
+    }
+
+    /// Rename the textarea at the index. This method returns `false` when the index is out of range.
+    /// ```
+    /// use tui_textarea::{TextArea, TextAreaSet};
+    ///
+    /// let mut set = TextAreaSet::new();
+    /// set.push("untitled", TextArea::default());
+    /// assert!(set.rename(0, "main.rs"));
+    /// assert_eq!(set.name(0), Some("main.rs"));
+    /// ```
+    pub fn rename(&mut self, index: usize, name: impl Into<String>) -> bool {
+        match self.entries.get_mut(index) {
+            Some((n, _)) => {
+                *n = name.into();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the index of the first textarea with the name.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|(n, _)| n == name)
+    }
+
+    /// Iterate the names and the textareas in the set in order.
+    /// ```
+    /// use tui_textarea::{TextArea, TextAreaSet};
+    ///
+    /// let mut set = TextAreaSet::new();
+    /// set.push("a", TextArea::from(["foo"]));
+    /// set.push("b", TextArea::from(["bar"]));
+    ///
+    /// let names: Vec<_> = set.iter().map(|(name, _)| name).collect();
+    /// assert_eq!(names, ["a", "b"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TextArea<'a>)> {
+        self.entries.iter().map(|(n, t)| (n.as_str(), t))
+    }
+
+    /// Make the textarea at the index active. This method returns `false` when the index is out of range.
+    pub fn select(&mut self, index: usize) -> bool {
+        if index >= self.entries.len() {
+            return false;
+        }
+        self.active = index;
+        self.update_focus();
+        true
+    }
+
+    /// Make the first textarea with the name active. This method returns `false` when no textarea has the name.
+    pub fn select_by_name(&mut self, name: &str) -> bool {
+        match self.position(name) {
+            Some(index) => self.select(index),
+            None => false,
+        }
+    }
+
+    /// Make the next textarea active. The selection wraps around from the last textarea to the first one.
+    /// ```
+    /// use tui_textarea::{TextArea, TextAreaSet};
+    ///
+    /// let mut set = TextAreaSet::new();
+    /// set.push("a", TextArea::default());
+    /// set.push("b", TextArea::default());
+    ///
+    /// set.select_next();
+    /// assert_eq!(set.active_name(), Some("b"));
+    /// set.select_next();
+    /// assert_eq!(set.active_name(), Some("a"));
+    /// set.select_prev();
+    /// assert_eq!(set.active_name(), Some("b"));
+    /// ```
+    pub fn select_next(&mut self) {
+        if !self.entries.is_empty() {
+            self.select((self.active + 1) % self.entries.len());
+        }
+    }
+
+    /// Make the previous textarea active. The selection wraps around from the first textarea to the last one.
+    pub fn select_prev(&mut self) {
+        if !self.entries.is_empty() {
+            let len = self.entries.len();
+            self.select((self.active + len - 1) % len);
+        }
+    }
+
+    /// Handle the input with the active textarea. See [`TextArea::input`]. This method returns `false` when the set is
+    /// empty.
+    pub fn input(&mut self, input: impl Into<Input>) -> bool {
+        match self.active_mut() {
+            Some(textarea) => textarea.input(input),
+            None => false,
+        }
+    }
+
+    fn update_focus(&mut self) {
+        for (i, (_, textarea)) in self.entries.iter_mut().enumerate() {
+            textarea.set_focused(i == self.active);
+        }
+    }
+}
+
+// Render the active textarea. Nothing is rendered when the set is empty
+impl<'a, 'b> Widget for &'b TextAreaSet<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if let Some(textarea) = self.active() {
+            Widget::render(textarea, area, buf);
+        }
+    }
+}
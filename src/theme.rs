@@ -0,0 +1,97 @@
+use crate::ratatui::style::{Color, Modifier, Style};
+
+/// Set of styles of [`crate::TextArea`] applied at once with [`crate::TextArea::set_theme`]. Presets for dark and light
+/// terminals and a high contrast preset are available. Each style can be overridden by modifying the field or by
+/// calling the setter of the style after applying the theme. The default theme has the default styles of the textarea.
+/// ```
+/// use ratatui::style::{Style, Color};
+/// use tui_textarea::{TextArea, Theme};
+///
+/// let mut textarea = TextArea::default();
+///
+/// // Override the search style of the dark theme
+/// let theme = Theme {
+///     search: Style::default().bg(Color::Magenta),
+///     ..Theme::dark()
+/// };
+/// textarea.set_theme(&theme);
+/// assert_eq!(textarea.cursor_style(), Theme::dark().cursor);
+///
+/// // Override the selection style after applying the theme
+/// textarea.set_selection_style(Style::default().bg(Color::Red));
+/// assert_eq!(textarea.selection_style(), Style::default().bg(Color::Red));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Style of the cursor. See [`crate::TextArea::set_cursor_style`]
+    pub cursor: Style,
+    /// Style of the cursor line. See [`crate::TextArea::set_cursor_line_style`]
+    pub cursor_line: Style,
+    /// Style of the selection. See [`crate::TextArea::set_selection_style`]
+    pub selection: Style,
+    /// Style of the search matches. This is used only when the `search` feature is enabled
+    pub search: Style,
+    /// Style of the line numbers. This does not enable line numbers. See [`crate::TextArea::set_line_number_style`]
+    pub line_number: Style,
+    /// Style of the placeholder text. See [`crate::TextArea::set_placeholder_style`]
+    pub placeholder: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            cursor: Style::default().add_modifier(Modifier::REVERSED),
+            cursor_line: Style::default().add_modifier(Modifier::UNDERLINED),
+            selection: Style::default().bg(Color::LightBlue),
+            search: Style::default().bg(Color::Blue),
+            line_number: Style::default().fg(Color::DarkGray),
+            placeholder: Style::default().fg(Color::DarkGray),
+        }
+    }
+}
+
+impl Theme {
+    /// Theme for terminals with a dark background.
+    pub fn dark() -> Self {
+        Self {
+            cursor: Style::default().fg(Color::Black).bg(Color::Gray),
+            cursor_line: Style::default().add_modifier(Modifier::UNDERLINED),
+            selection: Style::default().fg(Color::White).bg(Color::Blue),
+            search: Style::default().fg(Color::Black).bg(Color::Yellow),
+            line_number: Style::default().fg(Color::DarkGray),
+            placeholder: Style::default().fg(Color::DarkGray),
+        }
+    }
+
+    /// Theme for terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            cursor: Style::default().fg(Color::White).bg(Color::Black),
+            cursor_line: Style::default().add_modifier(Modifier::UNDERLINED),
+            selection: Style::default().fg(Color::Black).bg(Color::LightBlue),
+            search: Style::default().fg(Color::Black).bg(Color::LightYellow),
+            line_number: Style::default().fg(Color::Gray),
+            placeholder: Style::default().fg(Color::Gray),
+        }
+    }
+
+    /// Theme with strong contrast for accessibility. The cursor and the search matches are also rendered in bold.
+    pub fn high_contrast() -> Self {
+        Self {
+            cursor: Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            cursor_line: Style::default().add_modifier(Modifier::UNDERLINED),
+            selection: Style::default().fg(Color::Black).bg(Color::White),
+            search: Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+            line_number: Style::default().fg(Color::White),
+            placeholder: Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::ITALIC),
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use crate::op::{text_end, EditOp};
+use crate::ratatui::style::{Color, Style};
+use crate::util::byte_offset;
+use std::cmp;
+use std::collections::HashMap;
+
+/// Kind of [`TrackedChange`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The text was inserted. It is in the textarea and rendered with the author's style
+    Insertion,
+    /// The text was deleted. It is not in the textarea and rendered as struck-through virtual text at the position
+    Deletion,
+}
+
+/// A change of text recorded while tracking changes with [`crate::TextArea::start_tracking_changes`]. Pending changes
+/// are accepted or rejected by their IDs with [`crate::TextArea::accept_change`] and
+/// [`crate::TextArea::reject_change`].
+///
+/// The position is a 0-base `(row, column)` pair. It moves along with edits of the text before it.
+/// ```
+/// use tui_textarea::{TextArea, ChangeKind};
+///
+/// let mut textarea = TextArea::from(["hello"]);
+/// textarea.start_tracking_changes("alice");
+/// textarea.insert_str("oh, ");
+///
+/// let change = &textarea.tracked_changes()[0];
+/// assert_eq!(change.author, "alice");
+/// assert_eq!(change.kind, ChangeKind::Insertion);
+/// assert_eq!(change.position, (0, 0));
+/// assert_eq!(change.text, "oh, ");
+/// assert_eq!(change.end(), (0, 4));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackedChange {
+    /// ID to identify the change
+    pub id: usize,
+    /// Author who made the change
+    pub author: String,
+    /// Kind of the change
+    pub kind: ChangeKind,
+    /// Start position of the inserted text, or position where the text was deleted
+    pub position: (usize, usize),
+    /// Inserted or deleted text. It may contain newlines
+    pub text: String,
+}
+
+impl TrackedChange {
+    /// Get the end position of the inserted text. For a deletion, this is the same as the position.
+    pub fn end(&self) -> (usize, usize) {
+        match self.kind {
+            ChangeKind::Insertion => text_end(self.position, &self.text),
+            ChangeKind::Deletion => self.position,
+        }
+    }
+}
+
+// Number of characters in the text before the position. The text starts at `start`. A newline is counted as one
+// character
+fn char_offset(text: &str, start: (usize, usize), pos: (usize, usize)) -> usize {
+    if pos.0 == start.0 {
+        return pos.1 - start.1;
+    }
+    let lines: usize = text
+        .split('\n')
+        .take(pos.0 - start.0)
+        .map(|l| l.chars().count() + 1)
+        .sum();
+    lines + pos.1
+}
+
+#[derive(Clone, Debug)]
+pub struct TrackedChanges {
+    // Author of local edits. `None` when changes are not tracked
+    pub author: Option<String>,
+    // Pending changes sorted by their positions. A deletion comes before an insertion at the same position
+    pub changes: Vec<TrackedChange>,
+    pub styles: HashMap<String, Style>,
+    pub default_style: Style,
+    next_id: usize,
+}
+
+impl Default for TrackedChanges {
+    fn default() -> Self {
+        Self {
+            author: None,
+            changes: vec![],
+            styles: HashMap::new(),
+            default_style: Style::default().fg(Color::Green),
+            next_id: 0,
+        }
+    }
+}
+
+impl TrackedChanges {
+    pub fn style(&self, author: &str) -> Style {
+        self.styles
+            .get(author)
+            .copied()
+            .unwrap_or(self.default_style)
+    }
+
+    fn push(&mut self, author: String, kind: ChangeKind, position: (usize, usize), text: String) {
+        self.changes.push(TrackedChange {
+            id: self.next_id,
+            author,
+            kind,
+            position,
+            text,
+        });
+        self.next_id += 1;
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<TrackedChange> {
+        let i = self.changes.iter().position(|c| c.id == id)?;
+        Some(self.changes.remove(i))
+    }
+
+    // Update the pending changes by the edit. When the author is set, the edit is recorded as a new change or merged
+    // into the author's adjacent change
+    pub fn apply(&mut self, op: &EditOp) {
+        match op {
+            EditOp::Insert { pos, text } => self.insert(*pos, text, op),
+            EditOp::Delete { pos, text } => self.delete(*pos, op.end(), text),
+        }
+        self.changes.retain(|c| !c.text.is_empty());
+        self.changes
+            .sort_by_key(|c| (c.position, c.kind == ChangeKind::Insertion));
+    }
+
+    fn insert(&mut self, pos: (usize, usize), text: &str, op: &EditOp) {
+        use ChangeKind::*;
+
+        // Re-inserting the deleted text cancels the deletion. This makes undoing a tracked deletion work naturally
+        let mut canceled = None;
+        let mut merged = None;
+        if let Some(author) = &self.author {
+            let mine = |c: &TrackedChange, kind| &c.author == author && c.kind == kind;
+            canceled = self
+                .changes
+                .iter()
+                .position(|c| mine(c, Deletion) && c.position == pos && c.text.starts_with(text));
+            if canceled.is_none() {
+                merged = self
+                    .changes
+                    .iter()
+                    .position(|c| mine(c, Insertion) && c.position <= pos && pos <= c.end());
+            }
+        }
+
+        // Split the insertion by other author into two changes when inserting text inside it
+        if merged.is_none() {
+            let inside = self
+                .changes
+                .iter()
+                .position(|c| c.kind == Insertion && c.position < pos && pos < c.end());
+            if let Some(i) = inside {
+                let c = &mut self.changes[i];
+                let at = byte_offset(&c.text, char_offset(&c.text, c.position, pos));
+                let tail = c.text.split_off(at);
+                let author = c.author.clone();
+                self.push(author, Insertion, pos, tail);
+            }
+        }
+
+        for (i, c) in self.changes.iter_mut().enumerate() {
+            if Some(i) == canceled {
+                c.text.replace_range(..text.len(), "");
+                c.position = op.end();
+            } else if Some(i) == merged {
+                let at = byte_offset(&c.text, char_offset(&c.text, c.position, pos));
+                c.text.insert_str(at, text);
+            } else if c.kind == Insertion && c.position == pos {
+                c.position = op.end();
+            } else {
+                c.position = op.shift(c.position);
+            }
+        }
+
+        if canceled.is_none() && merged.is_none() {
+            if let Some(author) = self.author.clone() {
+                self.push(author, Insertion, pos, text.to_string());
+            }
+        }
+    }
+
+    fn delete(&mut self, pos: (usize, usize), end: (usize, usize), text: &str) {
+        use ChangeKind::*;
+
+        // Deleting text inserted by tracked insertions simply removes it from the insertions. Only the rest of the text
+        // is recorded as a deletion
+        let mut kept = vec![true; text.chars().count()];
+        for c in self.changes.iter_mut().filter(|c| c.kind == Insertion) {
+            let (from, to) = (cmp::max(c.position, pos), cmp::min(c.end(), end));
+            if from >= to {
+                continue;
+            }
+            let s = byte_offset(&c.text, char_offset(&c.text, c.position, from));
+            let e = byte_offset(&c.text, char_offset(&c.text, c.position, to));
+            c.text.replace_range(s..e, "");
+            for k in &mut kept[char_offset(text, pos, from)..char_offset(text, pos, to)] {
+                *k = false;
+            }
+        }
+        let rest: String = text
+            .chars()
+            .zip(kept)
+            .filter(|&(_, k)| k)
+            .map(|(c, _)| c)
+            .collect();
+
+        // Deleting text just before or after the author's deletion extends it
+        let merged = match &self.author {
+            Some(author) if !rest.is_empty() => self.changes.iter().position(|c| {
+                &c.author == author
+                    && c.kind == Deletion
+                    && (c.position == pos || c.position == end)
+            }),
+            _ => None,
+        };
+
+        let op = EditOp::Delete {
+            pos,
+            text: text.to_string(),
+        };
+        for (i, c) in self.changes.iter_mut().enumerate() {
+            if Some(i) == merged {
+                if c.position == end {
+                    c.text.insert_str(0, &rest);
+                } else {
+                    c.text.push_str(&rest);
+                }
+            }
+            c.position = op.shift(c.position);
+        }
+
+        if merged.is_none() && !rest.is_empty() {
+            if let Some(author) = self.author.clone() {
+                self.push(author, Deletion, pos, rest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(pos: (usize, usize), text: &str) -> EditOp {
+        let text = text.to_string();
+        EditOp::Insert { pos, text }
+    }
+
+    fn delete(pos: (usize, usize), text: &str) -> EditOp {
+        let text = text.to_string();
+        EditOp::Delete { pos, text }
+    }
+
+    fn summary(t: &TrackedChanges) -> Vec<(&str, ChangeKind, (usize, usize), &str)> {
+        t.changes
+            .iter()
+            .map(|c| (c.author.as_str(), c.kind, c.position, c.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn track_insertions() {
+        use ChangeKind::*;
+
+        let mut t = TrackedChanges {
+            author: Some("a".to_string()),
+            ..Default::default()
+        };
+        for (i, c) in "abc".chars().enumerate() {
+            t.apply(&insert((0, i), &c.to_string()));
+        }
+        t.apply(&insert((0, 3), "\nd"));
+        assert_eq!(summary(&t), [("a", Insertion, (0, 0), "abc\nd")]);
+
+        // Insertion by other author splits the change
+        t.author = Some("b".to_string());
+        t.apply(&insert((0, 1), "xy"));
+        assert_eq!(
+            summary(&t),
+            [
+                ("a", Insertion, (0, 0), "a"),
+                ("b", Insertion, (0, 1), "xy"),
+                ("a", Insertion, (0, 3), "bc\nd"),
+            ],
+        );
+
+        // Untracked insertion only moves changes
+        t.author = None;
+        t.apply(&insert((0, 0), "z\n"));
+        assert_eq!(
+            summary(&t),
+            [
+                ("a", Insertion, (1, 0), "a"),
+                ("b", Insertion, (1, 1), "xy"),
+                ("a", Insertion, (1, 3), "bc\nd"),
+            ],
+        );
+    }
+
+    #[test]
+    fn track_deletions() {
+        use ChangeKind::*;
+
+        let mut t = TrackedChanges {
+            author: Some("a".to_string()),
+            ..Default::default()
+        };
+
+        // Backspace twice and delete forward once from "hello|world"
+        t.apply(&delete((0, 4), "o"));
+        t.apply(&delete((0, 3), "l"));
+        t.apply(&delete((0, 3), "w"));
+        assert_eq!(summary(&t), [("a", Deletion, (0, 3), "low")]);
+
+        // Deleting inserted text is not recorded as a deletion
+        t.apply(&insert((1, 0), "abc"));
+        t.apply(&delete((0, 5), "x\nab"));
+        assert_eq!(
+            summary(&t),
+            [
+                ("a", Deletion, (0, 3), "low"),
+                ("a", Deletion, (0, 5), "x\n"),
+                ("a", Insertion, (0, 5), "c"),
+            ],
+        );
+
+        // Re-inserting the deleted text cancels the deletion
+        t.apply(&insert((0, 3), "lo"));
+        assert_eq!(
+            summary(&t),
+            [
+                ("a", Deletion, (0, 5), "w"),
+                ("a", Deletion, (0, 7), "x\n"),
+                ("a", Insertion, (0, 7), "c"),
+            ],
+        );
+    }
+
+    #[test]
+    fn position_offsets() {
+        assert_eq!(char_offset("abc", (1, 2), (1, 4)), 2);
+        assert_eq!(char_offset("ab\ncd\ne", (1, 2), (2, 1)), 4);
+        assert_eq!(char_offset("ab\ncd\ne", (1, 2), (3, 1)), 7);
+        assert_eq!(byte_offset("あい", 1), 3);
+        assert_eq!(byte_offset("あい", 2), 6);
+    }
+}
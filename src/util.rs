@@ -1,13 +1,74 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp;
+use unicode_segmentation::UnicodeSegmentation as _;
+
 pub fn spaces(size: u8) -> &'static str {
     const SPACES: &str = "                                                                                                                                                                                                                                                                ";
     &SPACES[..size as usize]
 }
 
+// Byte offset of the character at the column in the line. The length of the line is returned when the column is at or
+// after the end of the line. Since an ASCII character is one byte, only characters after the first non-ASCII
+// character need to be iterated. This is only a fast path for ASCII text. Offsets are not cached, so indexing a long
+// line which contains non-ASCII characters near its head still takes time proportional to the column.
+pub fn byte_offset(line: &str, col: usize) -> usize {
+    let bytes = line.as_bytes();
+    let head = &bytes[..cmp::min(col, bytes.len())];
+    let ascii = if head.is_ascii() {
+        head.len()
+    } else {
+        head.iter()
+            .position(|b| !b.is_ascii())
+            .unwrap_or(head.len())
+    };
+    line[ascii..]
+        .char_indices()
+        .nth(col - ascii)
+        .map(|(i, _)| ascii + i)
+        .unwrap_or(line.len())
+}
+
 pub fn num_digits(i: usize) -> u8 {
     f64::log10(i as f64) as u8 + 1
 }
 
+// Display width of a tab put at the display width. The tab stops at the next custom tab stop column. After the last
+// tab stop, it stops at the next multiple of the tab length. The tab length must not be 0
+pub fn tab_width(width: usize, tab_len: u8, stops: &[usize]) -> usize {
+    match stops.iter().find(|&&s| s > width) {
+        Some(s) => s - width,
+        None => tab_len as usize - width % tab_len as usize,
+    }
+}
+
+// Column range of the grapheme cluster containing the character at the column, such as `e` followed by combining
+// marks. `(col, col)` is returned when the column is at or after the end of the line
+pub fn grapheme_range(line: &str, col: usize) -> (usize, usize) {
+    let mut start = 0;
+    for g in line.graphemes(true) {
+        let end = start + g.chars().count();
+        if col < end {
+            return (start, end);
+        }
+        start = end;
+    }
+    (col, col)
+}
+
+// Position after the first `old` characters of the row are replaced with `new` characters. A position within the
+// replaced characters moves to the end of the replacement. Positions in other rows don't move
+pub fn shift_in_row(pos: (usize, usize), row: usize, old: usize, new: usize) -> (usize, usize) {
+    let (r, c) = pos;
+    if r == row {
+        (r, cmp::max(c, old) - old + new)
+    } else {
+        (r, c)
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pos {
     pub row: usize,
     pub col: usize,
@@ -19,3 +80,67 @@ impl Pos {
         Self { row, col, offset }
     }
 }
+
+// Owned lines for unit tests such as `lines(["a", "b"])` and `lines(text.lines())`
+#[cfg(test)]
+pub fn lines<T: ToString>(ls: impl IntoIterator<Item = T>) -> Vec<String> {
+    ls.into_iter().map(|l| l.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_offset_of_column() {
+        let tests = [
+            ("abc", 0, 0),
+            ("abc", 2, 2),
+            ("abc", 3, 3),
+            ("abc", 9, 3),
+            ("", 1, 0),
+            ("aあいb", 1, 1),
+            ("aあいb", 2, 4),
+            ("aあいb", 3, 7),
+            ("aあいb", 4, 8),
+            ("aあいb", 5, 8),
+            ("あa", 1, 3),
+        ];
+        for (line, col, want) in tests {
+            assert_eq!(byte_offset(line, col), want, "{:?}", (line, col));
+        }
+    }
+
+    #[test]
+    fn tab_width_at_column() {
+        let tests = [
+            (0, &[][..], 4),
+            (5, &[][..], 3),
+            (0, &[10, 20][..], 10),
+            (10, &[10, 20][..], 10),
+            (19, &[10, 20][..], 1),
+            (20, &[10, 20][..], 4),
+            (22, &[10, 20][..], 2),
+        ];
+        for (width, stops, want) in tests {
+            assert_eq!(tab_width(width, 4, stops), want, "{:?}", (width, stops));
+        }
+    }
+
+    #[test]
+    fn grapheme_range_at_column() {
+        let tests = [
+            ("abc", 1, (1, 2)),
+            ("ae\u{301}\u{323}b", 1, (1, 4)),
+            ("ae\u{301}\u{323}b", 2, (1, 4)),
+            ("ae\u{301}\u{323}b", 3, (1, 4)),
+            ("ae\u{301}\u{323}b", 4, (4, 5)),
+            ("👨\u{200d}👩", 0, (0, 3)),
+            ("abc", 3, (3, 3)),
+            ("", 0, (0, 0)),
+        ];
+        for (line, col, want) in tests {
+            assert_eq!(grapheme_range(line, col), want, "{:?}", (line, col));
+        }
+    }
+}
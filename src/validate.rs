@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt;
+
+/// Broken invariant of the internal state of [`crate::TextArea`] returned by [`crate::TextArea::validate`]. Positions
+/// are pairs of 0-base row and column.
+///
+/// This type is marked as `#[non_exhaustive]` since more invariants may be checked in the future.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvariantError {
+    /// The text has no line. The text always has at least one line even if it is empty.
+    NoLine,
+    /// The line at the row contains a newline character.
+    NewlineInLine {
+        /// Row of the line
+        row: usize,
+    },
+    /// The cursor is outside the text.
+    CursorOutOfRange {
+        /// Position of the cursor
+        cursor: (usize, usize),
+    },
+    /// The start position of the selection is outside the text.
+    SelectionOutOfRange {
+        /// Start position of the selection
+        start: (usize, usize),
+    },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoLine => write!(f, "text has no line"),
+            Self::NewlineInLine { row } => write!(f, "line {} contains newline", row),
+            Self::CursorOutOfRange { cursor } => {
+                write!(f, "cursor {:?} is out of the text", cursor)
+            }
+            Self::SelectionOutOfRange { start } => {
+                write!(f, "selection start {:?} is out of the text", start)
+            }
+        }
+    }
+}
+
+impl Error for InvariantError {}
+
+// Check the invariants of the text, the cursor, and the selection start
+pub fn validate(
+    lines: &[String],
+    cursor: (usize, usize),
+    selection_start: Option<(usize, usize)>,
+) -> Result<(), InvariantError> {
+    if lines.is_empty() {
+        return Err(InvariantError::NoLine);
+    }
+    if let Some(row) = lines.iter().position(|l| l.contains('\n')) {
+        return Err(InvariantError::NewlineInLine { row });
+    }
+    let in_text = |(row, col): (usize, usize)| {
+        lines
+            .get(row)
+            .map_or(false, |line| col <= line.chars().count())
+    };
+    if !in_text(cursor) {
+        return Err(InvariantError::CursorOutOfRange { cursor });
+    }
+    match selection_start {
+        Some(start) if !in_text(start) => Err(InvariantError::SelectionOutOfRange { start }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn validate_state() {
+        let tests = [
+            (lines(["ab", ""]), (1, 0), Some((0, 2)), Ok(())),
+            (lines(["あい"]), (0, 2), None, Ok(())),
+            (
+                lines(Vec::<&str>::new()),
+                (0, 0),
+                None,
+                Err(InvariantError::NoLine),
+            ),
+            (
+                lines(["a", "b\nc"]),
+                (0, 0),
+                None,
+                Err(InvariantError::NewlineInLine { row: 1 }),
+            ),
+            (
+                lines(["あい"]),
+                (0, 3),
+                None,
+                Err(InvariantError::CursorOutOfRange { cursor: (0, 3) }),
+            ),
+            (
+                lines(["ab"]),
+                (1, 0),
+                None,
+                Err(InvariantError::CursorOutOfRange { cursor: (1, 0) }),
+            ),
+            (
+                lines(["ab"]),
+                (0, 0),
+                Some((0, 3)),
+                Err(InvariantError::SelectionOutOfRange { start: (0, 3) }),
+            ),
+        ];
+        for (lines, cursor, selection, want) in tests {
+            assert_eq!(validate(&lines, cursor, selection), want, "{:?}", lines);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::ratatui::style::Style;
+
+/// Read-only virtual text rendered at a position in [`crate::TextArea`] like a type hint of a variable. The text is not
+/// a part of the textarea's text and the cursor skips it. Set inlay hints with [`crate::TextArea::set_inlay_hints`].
+///
+/// The position is a 0-base `(row, column)` pair. The hint is rendered just before the character at the position.
+/// When the column is at the end of line, the hint is rendered after the line.
+/// ```
+/// use ratatui::style::{Style, Color};
+/// use tui_textarea::InlayHint;
+///
+/// let hint = InlayHint::new((0, 5), ": i32");
+/// assert_eq!(hint.position, (0, 5));
+/// assert_eq!(hint.text, ": i32");
+/// assert_eq!(hint.style, None); // Rendered with `TextArea::inlay_hint_style`
+///
+/// let style = Style::default().fg(Color::Blue);
+/// let hint = InlayHint::new((0, 5), ": i32").style(style);
+/// assert_eq!(hint.style, Some(style));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlayHint {
+    /// Position where the hint is rendered
+    pub position: (usize, usize),
+    /// Text of the hint
+    pub text: String,
+    /// Style of the hint. When `None`, the style set by [`crate::TextArea::set_inlay_hint_style`] is used
+    pub style: Option<Style>,
+}
+
+impl InlayHint {
+    /// Create a new inlay hint at the position.
+    pub fn new(position: (usize, usize), text: impl Into<String>) -> Self {
+        Self {
+            position,
+            text: text.into(),
+            style: None,
+        }
+    }
+
+    /// Set the style of the hint.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
@@ -1,19 +1,25 @@
+use crate::control::control_char_width;
+use crate::fold::{buffer_row, visible_rows, visual_row};
 use crate::ratatui::buffer::Buffer;
 use crate::ratatui::layout::Rect;
 use crate::ratatui::text::Text;
-use crate::ratatui::widgets::{Paragraph, Widget};
+use crate::ratatui::widgets::{Paragraph, StatefulWidget, Widget};
 use crate::textarea::TextArea;
-use crate::util::num_digits;
+use crate::util::{num_digits, tab_width};
+use crate::width::{char_width, is_widened, pad_widened, str_width, AmbiguousWidth};
+use std::borrow::Cow;
 use std::cmp;
+use std::slice;
 use std::sync::atomic::{AtomicU64, Ordering};
+use unicode_width::UnicodeWidthStr as _;
 
 // &mut 'a (u16, u16, u16, u16) is not available since Renderer instance totally takes over the ownership of TextArea
 // instance. In the case, the TextArea instance cannot be accessed from any other objects since it is mutablly
 // borrowed.
 //
-// `tui::terminal::Frame::render_stateful_widget` would be an assumed way to render a stateful widget. But at this
-// point we stick with using `tui::terminal::Frame::render_widget` because it is simpler API. Users don't need to
-// manage states of textarea instances separately.
+// `tui::terminal::Frame::render_stateful_widget` would be an assumed way to render a stateful widget. But we primarily
+// stick with using `tui::terminal::Frame::render_widget` because it is simpler API. Users don't need to manage states
+// of textarea instances separately. `TextAreaState` is provided for users who want to manage the states by themselves.
 // https://docs.rs/tui/latest/tui/terminal/struct.Frame.html#method.render_stateful_widget
 #[derive(Default, Debug)]
 pub struct Viewport(AtomicU64);
@@ -76,6 +82,51 @@ impl Viewport {
     }
 }
 
+/// State of [`TextArea`] rendered with [`TextArea::stateful_widget`]. It holds the scroll position of the viewport
+/// separately from the textarea so that the same textarea can be rendered in multiple areas with their own scroll
+/// positions, as `ListState` for `List` widget.
+/// ```
+/// use ratatui::buffer::Buffer;
+/// use ratatui::layout::Rect;
+/// use ratatui::widgets::StatefulWidget;
+/// use tui_textarea::{TextArea, TextAreaState};
+///
+/// let textarea: TextArea = (0..10).map(|i| i.to_string()).collect();
+/// let mut state = TextAreaState::default();
+/// assert_eq!(state.scroll_top(), (0, 0));
+///
+/// let area = Rect::new(0, 0, 10, 3);
+/// let mut buf = Buffer::empty(area);
+/// textarea.stateful_widget().render(area, &mut buf, &mut state);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct TextAreaState {
+    viewport: Viewport,
+}
+
+impl TextAreaState {
+    /// Create a new state which is scrolled to the top.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the scroll top position as the 0-base `(row, column)` pair. It is updated on rendering the textarea so that
+    /// the cursor is in the viewport.
+    pub fn scroll_top(&self) -> (u16, u16) {
+        self.viewport.scroll_top()
+    }
+}
+
+// Areas and rows to render the textarea
+struct Layout {
+    text_area: Rect,
+    gutter_area: Rect,
+    folds: Vec<(usize, usize)>,
+    rows: Vec<usize>,
+    top_row: u16,
+    top_col: u16,
+}
+
 pub struct Renderer<'a>(&'a TextArea<'a>);
 
 impl<'a> Renderer<'a> {
@@ -84,25 +135,249 @@ impl<'a> Renderer<'a> {
     }
 
     #[inline]
-    fn text(&self, top_row: usize, height: usize) -> Text<'a> {
-        let lines_len = self.0.lines().len();
-        let lnum_len = num_digits(lines_len);
-        let bottom_row = cmp::min(top_row + height, lines_len);
-        let mut lines = Vec::with_capacity(bottom_row - top_row);
-        for (i, line) in self.0.lines()[top_row..bottom_row].iter().enumerate() {
-            lines.push(self.0.line_spans(line.as_str(), top_row + i, lnum_len));
+    fn text(&self, rows: &[usize], folds: &[(usize, usize)]) -> Text<'a> {
+        let lnum_len = num_digits(self.0.lines().len());
+        let mut lines = Vec::with_capacity(rows.len());
+        for &row in rows {
+            let line = self.0.lines()[row].as_str();
+            let hidden = folds
+                .binary_search_by_key(&row, |&(s, _)| s)
+                .ok()
+                .map(|i| folds[i].1 - row);
+            lines.push(self.0.line_spans(line, row, lnum_len, hidden));
         }
         Text::from(lines)
     }
+
+    // Get the screen position of the cursor cell in the text area. `None` is returned when the cursor is not visible.
+    // `rows` are the rows rendered in the area from the top.
+    fn cursor_cell(&self, area: Rect, rows: &[usize], top_col: u16) -> Option<(u16, u16)> {
+        let (row, col) = self.0.virtual_cursor();
+        let y = rows.iter().position(|&r| r == row)? as u16;
+        if y >= area.height {
+            return None;
+        }
+
+        // Inlay hints at the cursor are rendered before the cursor. Virtual space beyond the end of line is rendered
+        // as spaces
+        let len = self.0.lines()[row].chars().count();
+        let width = self.col_width(row, col, true) + col.saturating_sub(len);
+        let x = (width as u16).checked_sub(top_col)?;
+        if x >= area.width {
+            return None;
+        }
+        Some((area.x + x, area.y + y))
+    }
+
+    // Display width of the text before the column in the row including line numbers and inlay hints. Inlay hints at the
+    // column are included when `hints_at_col` is true. Hints after the end of line are rendered at the end.
+    fn col_width(&self, row: usize, col: usize, hints_at_col: bool) -> usize {
+        let widths = self.char_widths(row);
+        // With bidirectional text, the characters rendered before the column are the ones before it in visual order
+        let mut width = match self.0.visual_order(row) {
+            Some(order) if col < widths.len() => order
+                .iter()
+                .take_while(|&&c| c != col)
+                .map(|&c| widths[c])
+                .sum(),
+            _ => widths.iter().take(col).sum(),
+        };
+        width += self.line_number_width();
+        if self.0.mask_char().is_none() {
+            let len = self.0.lines()[row].chars().count();
+            for hint in self.0.inlay_hints_at(row) {
+                let pos = cmp::min(hint.position.1, len);
+                if pos < col || hints_at_col && pos == col {
+                    width += str_width(&hint.text, self.0.ambiguous_width());
+                }
+            }
+        }
+        width
+    }
+
+    // Wrap the cells of the hyperlinks with OSC 8 escape sequences. Since the width of an escape sequence is not
+    // calculated correctly on rendering the buffer, link text is split into chunks of 2 cells and each chunk is put
+    // into its first cell. The second cell is skipped by the terminal backend.
+    // https://github.com/ratatui-org/ratatui/issues/902
+    fn render_hyperlinks(&self, area: Rect, rows: &[usize], top_col: u16, buf: &mut Buffer) {
+        for (y, &row) in rows.iter().enumerate().take(area.height as usize) {
+            let line = &self.0.lines()[row];
+            for link in self.0.hyperlinks_at(row).iter() {
+                let (start_col, end_col) = link.range;
+                let plain = line
+                    .chars()
+                    .skip(start_col)
+                    .take(end_col.saturating_sub(start_col))
+                    .all(|c| c != '\t' && char_width(c, self.0.ambiguous_width()) == Some(1));
+                // Link text in bidirectional text may not be contiguous on the screen
+                if !plain || self.0.visual_order(row).is_some() {
+                    continue;
+                }
+
+                let top_col = top_col as usize;
+                let start = cmp::max(self.col_width(row, start_col, true), top_col) - top_col;
+                let end = cmp::min(
+                    self.col_width(row, end_col, false).saturating_sub(top_col),
+                    area.width as usize,
+                );
+                let y = area.y + y as u16;
+                for x in (start..end).step_by(2) {
+                    let x = area.x + x as u16;
+                    let mut text = cell_symbol(buf, x, y).to_string();
+                    if x + 1 < area.x + end as u16 {
+                        text.push_str(cell_symbol(buf, x + 1, y));
+                    }
+                    let symbol = format!("\x1b]8;;{}\x07{}\x1b]8;;\x07", link.url, text);
+                    buf.get_mut(x, y).set_symbol(&symbol);
+                }
+            }
+        }
+    }
+
+    // Get the cursor position of the character rendered at the screen position. When the position is after the end of
+    // line, the end of the line is returned. `None` is returned when the position is outside the text area.
+    fn cell_cursor(
+        &self,
+        area: Rect,
+        rows: &[usize],
+        top_col: u16,
+        x: u16,
+        y: u16,
+    ) -> Option<(usize, usize)> {
+        if x < area.x || area.right() <= x || y < area.y || area.bottom() <= y {
+            return None;
+        }
+        // Clicking under the last line points the last line
+        let row = *rows.get((y - area.y) as usize).or_else(|| rows.last())?;
+        let target =
+            ((x - area.x) as usize + top_col as usize).checked_sub(self.line_number_width())?;
+
+        let line = &self.0.lines()[row];
+        let len = line.chars().count();
+        let hints = if self.0.mask_char().is_none() {
+            self.0.inlay_hints_at(row)
+        } else {
+            &[]
+        };
+        let widths = self.char_widths(row);
+        let order = self
+            .0
+            .visual_order(row)
+            .unwrap_or_else(|| (0..len).collect());
+        let (mut width, mut hints_width) = (0, 0);
+        for col in order {
+            for hint in hints {
+                if cmp::min(hint.position.1, len) == col {
+                    hints_width += str_width(&hint.text, self.0.ambiguous_width());
+                }
+            }
+            width += widths[col];
+            if target < width + hints_width {
+                return Some((row, col));
+            }
+        }
+        Some((row, len))
+    }
+
+    // Display widths of the characters in the row in logical order
+    fn char_widths(&self, row: usize) -> Vec<usize> {
+        let elastic = self.0.elastic_tab_widths(row);
+        let mut tabs = elastic.iter();
+        let mut width = 0;
+        self.0.lines()[row]
+            .chars()
+            .map(|c| {
+                let w = self.char_width(c, width, &mut tabs);
+                width += w;
+                w
+            })
+            .collect()
+    }
+
+    // `tabs` are the widths of the following tabs with elastic tabstops
+    fn char_width(&self, c: char, width: usize, tabs: &mut slice::Iter<'_, usize>) -> usize {
+        let tab_len = self.0.tab_length();
+        let ambiguous = self.0.ambiguous_width();
+        match (self.0.mask_char(), c) {
+            (Some(m), _) => char_width(m, ambiguous).unwrap_or(0),
+            (None, '\t') if tab_len > 0 => tabs
+                .next()
+                .copied()
+                .unwrap_or_else(|| tab_width(width, tab_len, self.0.tab_stops())),
+            (None, c) => control_char_width(c, self.0.control_char_display())
+                .unwrap_or_else(|| char_width(c, ambiguous).unwrap_or(0)),
+        }
+    }
+
+    // Clear the padding cells after wide ambiguous characters so that the terminal backend doesn't draw them over the
+    // right halves of the characters. A character at the right edge is replaced with a space since it doesn't fit
+    fn clear_padding_cells(&self, area: Rect, buf: &mut Buffer) {
+        let ambiguous = self.0.ambiguous_width();
+        for y in area.top()..area.bottom() {
+            let mut x = area.left();
+            while x < area.right() {
+                let mut chars = cell_symbol(buf, x, y).chars();
+                let widened = match (chars.next(), chars.next()) {
+                    (Some(c), None) => is_widened(c, ambiguous),
+                    _ => false,
+                };
+                if !widened {
+                    x += 1;
+                    continue;
+                }
+                if x + 1 < area.right() {
+                    buf.get_mut(x + 1, y).set_symbol("");
+                } else {
+                    buf.get_mut(x, y).set_symbol(" ");
+                }
+                x += 2;
+            }
+        }
+    }
+
+    fn line_number_width(&self) -> usize {
+        if self.0.line_number_style().is_some() {
+            num_digits(self.0.lines().len()) as usize + 2
+        } else {
+            0
+        }
+    }
 }
 
 impl<'a> Widget for Renderer<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let Rect { width, height, .. } = if let Some(b) = self.0.block() {
+        let viewport = &self.0.viewport;
+        self.render_viewport(area, buf, viewport);
+    }
+}
+
+// Render the textarea without building the intermediate widget with `TextArea::widget`
+impl<'a, 'b> Widget for &'b TextArea<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Widget::render(Renderer::new(self), area, buf);
+    }
+}
+
+impl<'a> StatefulWidget for Renderer<'a> {
+    type State = TextAreaState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        self.render_viewport(area, buf, &state.viewport);
+    }
+}
+
+impl<'a> Renderer<'a> {
+    // Calculate the layout of the textarea in the area with the previous scroll position in the viewport
+    fn layout(&self, area: Rect, viewport: &Viewport) -> Layout {
+        let inner_area = if let Some(b) = self.0.block() {
             b.inner(area)
         } else {
             area
         };
+        let Rect { width, height, .. } = inner_area;
+        let folds = self.0.active_folds();
+        let gutter_width = cmp::min(self.0.gutter.width(!folds.is_empty()), width);
+        let width = width - gutter_width;
 
         fn next_scroll_top(prev_top: u16, cursor: u16, length: u16) -> u16 {
             if cursor < prev_top {
@@ -114,35 +389,451 @@ impl<'a> Widget for Renderer<'a> {
             }
         }
 
-        let cursor = self.0.cursor();
-        let (top_row, top_col) = self.0.viewport.scroll_top();
-        let top_row = next_scroll_top(top_row, cursor.0 as u16, height);
+        let cursor = self.0.virtual_cursor();
+        let (top_row, top_col) = viewport.scroll_top();
+        // Vertical scroll is calculated with rows on screen since folded lines are not rendered
+        let top_visual = visual_row(&folds, top_row as usize) as u16;
+        let cursor_visual = visual_row(&folds, cursor.0) as u16;
+        let top_visual = if self.0.typewriter_mode() {
+            // Keep the cursor line at the center of the viewport
+            cursor_visual.saturating_sub(height / 2)
+        } else {
+            next_scroll_top(top_visual, cursor_visual, height)
+        };
+        let top_row = buffer_row(&folds, top_visual as usize) as u16;
         let top_col = next_scroll_top(top_col, cursor.1 as u16, width);
+        let rows = visible_rows(
+            &folds,
+            top_row as usize,
+            self.0.lines().len(),
+            height as usize,
+        );
+
+        let gutter_area = Rect {
+            width: gutter_width,
+            ..inner_area
+        };
+        let text_area = Rect {
+            x: inner_area.x + gutter_width,
+            width,
+            ..inner_area
+        };
+        Layout {
+            text_area,
+            gutter_area,
+            folds,
+            rows,
+            top_row,
+            top_col,
+        }
+    }
+
+    // Get the screen position of the cursor when the textarea is rendered in the area
+    pub fn cursor_position(&self, area: Rect, viewport: &Viewport) -> Option<(u16, u16)> {
+        let layout = self.layout(area, viewport);
+        self.cursor_cell(layout.text_area, &layout.rows, layout.top_col)
+    }
+
+    // Get the cursor position of the character at the screen position when the textarea is rendered in the area
+    pub fn screen_to_cursor(
+        &self,
+        area: Rect,
+        viewport: &Viewport,
+        x: u16,
+        y: u16,
+    ) -> Option<(usize, usize)> {
+        let layout = self.layout(area, viewport);
+        self.cell_cursor(layout.text_area, &layout.rows, layout.top_col, x, y)
+    }
+
+    fn render_viewport(&self, area: Rect, buf: &mut Buffer, viewport: &Viewport) {
+        let Layout {
+            text_area,
+            gutter_area,
+            folds,
+            rows,
+            top_row,
+            top_col,
+        } = self.layout(area, viewport);
 
         let (text, style) = if !self.0.placeholder.is_empty() && self.0.is_empty() {
-            let text = Text::from(self.0.placeholder.as_str());
+            let placeholder = Cow::Borrowed(self.0.placeholder.as_str());
+            let text = Text::raw(pad_widened(placeholder, self.0.ambiguous_width()));
             (text, self.0.placeholder_style)
         } else {
-            (self.text(top_row as usize, height as usize), self.0.style())
+            (self.text(&rows, &folds), self.0.style())
         };
 
         // To get fine control over the text color and the surrrounding block they have to be rendered separately
         // see https://github.com/ratatui-org/ratatui/issues/144
-        let mut text_area = area;
         let mut inner = Paragraph::new(text)
             .style(style)
             .alignment(self.0.alignment());
         if let Some(b) = self.0.block() {
-            text_area = b.inner(area);
-            b.clone().render(area, buf)
+            let mut b = b.clone();
+            if let Some(style) = self.0.focus_border_style() {
+                b = b.border_style(style);
+            }
+            b.render(area, buf);
+            // The counter is put before the bottom-right corner of the block
+            if let Some((label, style)) = self.0.char_counter_label() {
+                let width = label.width() as u16;
+                if area.height > 0 && width + 2 <= area.width {
+                    buf.set_string(area.right() - 1 - width, area.bottom() - 1, label, style);
+                }
+            }
         }
         if top_col != 0 {
             inner = inner.scroll((0, top_col));
         }
 
+        // Render the gutter separately so that it is not scrolled horizontally
+        if gutter_area.width > 0 {
+            buf.set_style(gutter_area, self.0.style());
+            self.0.gutter.render(
+                gutter_area,
+                &rows,
+                self.0.lines(),
+                &folds,
+                self.0.fold_indicator(),
+                buf,
+            );
+        }
+
         // Store scroll top position for rendering on the next tick
-        self.0.viewport.store(top_row, top_col, width, height);
+        viewport.store(top_row, top_col, text_area.width, text_area.height);
 
         inner.render(text_area, buf);
+
+        if self.0.ambiguous_width() == AmbiguousWidth::Wide {
+            self.clear_padding_cells(text_area, buf);
+        }
+
+        if self.0.osc8_hyperlinks() && self.0.mask_char().is_none() {
+            self.render_hyperlinks(text_area, &rows, top_col, buf);
+        }
+
+        if self.0.completion_popup.is_open() {
+            if let Some(cell) = self.cursor_cell(text_area, &rows, top_col) {
+                self.0.completion_popup.render(cell, buf);
+            }
+        }
+    }
+}
+
+// Convert the cells in the buffer into text. Each row is a line. Cells hidden by a wide character before them are
+// skipped, as a terminal shows them
+pub fn buffer_to_string(buf: &Buffer) -> String {
+    let area = buf.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        if y > area.top() {
+            text.push('\n');
+        }
+        let mut x = area.left();
+        while x < area.right() {
+            let symbol = cell_symbol(buf, x, y);
+            text.push_str(symbol);
+            x += cmp::max(symbol.width(), 1) as u16;
+        }
+    }
+    text
+}
+
+#[cfg(feature = "ratatui")]
+fn cell_symbol(buf: &Buffer, x: u16, y: u16) -> &str {
+    buf.get(x, y).symbol()
+}
+
+#[cfg(feature = "tuirs")]
+fn cell_symbol(buf: &Buffer, x: u16, y: u16) -> &str {
+    &buf.get(x, y).symbol
+}
+
+// Tests for buffer contents don't work with tui-rs
+#[cfg(all(test, feature = "ratatui"))]
+mod tests {
+    use crate::ratatui::style::{Modifier, Style};
+    use crate::TextArea;
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+
+    fn render(textarea: &TextArea, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        textarea.widget().render(area, &mut buf);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf.get(x, y).symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn render_sign_column() {
+        let mut t = TextArea::from(["abc", "def"]);
+        assert_eq!(render(&t, 5, 2), ["abc  ", "def  "]);
+
+        t.set_sign(1, "●", Style::default());
+        assert_eq!(render(&t, 5, 2), ["  abc", "● def"]);
+
+        // Signs are not scrolled horizontally
+        t.move_cursor(crate::CursorMove::End);
+        t.insert_str("ghi");
+        assert_eq!(render(&t, 5, 2), ["  hi ", "●    "]);
+    }
+
+    #[test]
+    fn render_inlay_hints() {
+        let mut t = TextArea::from(["let x = 1;"]);
+        t.set_inlay_hints(vec![
+            crate::InlayHint::new((0, 5), ": i32"),
+            crate::InlayHint::new((0, 99), " // end"),
+        ]);
+        assert_eq!(render(&t, 24, 1), ["let x: i32 = 1; // end  "]);
+        assert_eq!(t.lines(), ["let x = 1;"]);
+    }
+
+    #[test]
+    fn render_osc8_hyperlinks() {
+        let mut t = TextArea::from(["see abc."]);
+        t.set_hyperlinks(vec![crate::Hyperlink::new(0, (4, 7), "https://x.y")]);
+        assert_eq!(render(&t, 10, 1), ["see abc.  "]);
+
+        t.set_osc8_hyperlinks(true);
+        let link = |s: &str| format!("\x1b]8;;https://x.y\x07{}\x1b]8;;\x07", s);
+        let cells = render(&t, 10, 1);
+        let want = format!("see {}b{}.  ", link("ab"), link("c"));
+        assert_eq!(cells, [want]);
+    }
+
+    #[test]
+    fn render_line_annotations() {
+        let mut t = TextArea::from(["ab", "cd"]);
+        t.set_line_annotation(1, " <- x", Style::default());
+        assert_eq!(render(&t, 8, 2), ["ab      ", "cd <- x "]);
+
+        t.set_line_annotations_visible(false);
+        assert_eq!(render(&t, 8, 2), ["ab      ", "cd      "]);
+    }
+
+    #[test]
+    fn render_textarea_ref() {
+        let t = TextArea::from(["abc"]);
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buf = Buffer::empty(area);
+        (&t).render(area, &mut buf);
+        assert_eq!(render(&t, 4, 1), ["abc "]);
+        assert_eq!(buf.get(1, 0).symbol(), "b");
+    }
+
+    #[test]
+    fn cursor_screen_position() {
+        let mut t: TextArea = (0..10).map(|i| i.to_string()).collect();
+        t.set_line_number_style(Style::default());
+        t.set_sign(8, "x", Style::default());
+        t.move_cursor(crate::CursorMove::Jump(8, 1));
+
+        // Sign column (2) + line numbers (4) + column 1, scrolled to put the cursor at the bottom
+        let area = Rect::new(1, 1, 10, 3);
+        assert_eq!(t.cursor_screen_position(area), Some((8, 3)));
+        render(&t, 10, 3);
+        assert_eq!(
+            t.cursor_screen_position(Rect::new(0, 0, 10, 3)),
+            Some((7, 2))
+        );
+
+        // Not in the area
+        assert_eq!(t.cursor_screen_position(Rect::new(0, 0, 6, 3)), None);
+    }
+
+    #[test]
+    fn click_screen_position() {
+        let mut t = TextArea::from(["ab\tc", "x y"]);
+        t.set_line_number_style(Style::default());
+        let area = Rect::new(0, 0, 10, 3);
+        render(&t, 10, 3);
+
+        // Line numbers (3) + column
+        for (x, y, want) in [
+            (3, 0, Some((0, 0))),
+            (5, 0, Some((0, 2))),
+            (6, 0, Some((0, 2))),
+            (7, 0, Some((0, 3))),
+            (9, 0, Some((0, 4))),
+            (4, 2, Some((1, 1))), // Under the last line
+            (1, 0, None),
+            (10, 0, None),
+        ] {
+            let mut t = t.clone();
+            assert_eq!(t.click(area, x, y), want.is_some(), "{:?}", (x, y));
+            if let Some(cursor) = want {
+                assert_eq!(t.cursor(), cursor, "{:?}", (x, y));
+            }
+        }
+
+        t.click(area, 3, 1);
+        t.click(area, 3, 1);
+        t.copy();
+        assert_eq!(t.yank_text(), "x");
+        t.click(area, 3, 1);
+        t.copy();
+        assert_eq!(t.yank_text(), "x y");
+
+        // Click at another position resets the count
+        t.click(area, 5, 1);
+        assert!(!t.is_selecting());
+        assert_eq!(t.cursor(), (1, 2));
+    }
+
+    #[test]
+    fn render_stateful() {
+        use crate::TextAreaState;
+        use ratatui::widgets::StatefulWidget;
+
+        let mut t: TextArea = (0..5).map(|i| i.to_string()).collect();
+        t.move_cursor(crate::CursorMove::Bottom);
+
+        let area = Rect::new(0, 0, 2, 2);
+        let mut state = TextAreaState::new();
+        let mut buf = Buffer::empty(area);
+        t.stateful_widget().render(area, &mut buf, &mut state);
+        assert_eq!(state.scroll_top(), (3, 0));
+        assert_eq!(t.viewport.scroll_top(), (0, 0)); // Viewport of the textarea is not updated
+
+        // Another state is scrolled independently
+        let mut other = TextAreaState::new();
+        let area = Rect::new(0, 0, 2, 4);
+        let mut buf = Buffer::empty(area);
+        t.stateful_widget().render(area, &mut buf, &mut other);
+        assert_eq!(other.scroll_top(), (1, 0));
+        assert_eq!(state.scroll_top(), (3, 0));
+    }
+
+    #[test]
+    fn render_indent_guides() {
+        let mut t = TextArea::from(["a:", "  b:", "    c", "d"]);
+        t.set_tab_length(2);
+        t.set_indent_guide('│', Style::default());
+        assert_eq!(render(&t, 6, 4), ["a:    ", "│ b:  ", "│ │ c ", "d     "]);
+    }
+
+    #[test]
+    fn render_whitespace() {
+        let mut t = TextArea::from(["\ta b ", "c"]);
+        t.set_tab_length(2);
+        t.move_cursor(crate::CursorMove::Down);
+        t.set_whitespace_visible(true);
+        assert_eq!(render(&t, 7, 2), ["→ a·b· ", "c      "]);
+
+        t.set_whitespace_visible(false);
+        assert_eq!(render(&t, 7, 2), ["  a b  ", "c      "]);
+    }
+
+    #[test]
+    fn render_folds() {
+        let mut t = TextArea::from(["a", "b", "c", "d", "e"]);
+        assert!(t.fold(1, 3));
+        assert_eq!(
+            render(&t, 12, 3),
+            [" a          ", "▸b ⋯ 2 lines", " e          "],
+        );
+
+        // Scroll is calculated with rows on screen
+        t.move_cursor(crate::CursorMove::Bottom);
+        assert_eq!(t.cursor(), (4, 0));
+        assert_eq!(render(&t, 4, 2), ["▸b ⋯", " e  "]);
+
+        // Multiple folds
+        t.unfold_all();
+        t.move_cursor(crate::CursorMove::Jump(2, 0));
+        t.fold(0, 1);
+        t.fold(3, 4);
+        assert_eq!(render(&t, 3, 3), ["▸a ", " c ", "▸d "]);
+    }
+
+    #[test]
+    fn render_to_string() {
+        let t = TextArea::from(["あい", "b"]);
+        assert_eq!(t.render_to_string(5, 2), "あい \nb    ");
+        assert_eq!(t.render_to_string(0, 0), "");
+    }
+
+    #[test]
+    fn render_elastic_tabstops() {
+        let mut t = TextArea::from(["a\tb", "ccccc\td"]);
+        t.set_elastic_tabstops(true);
+        assert_eq!(t.render_to_string(8, 2), "a     b \nccccc d ");
+
+        t.move_cursor(crate::CursorMove::Jump(0, 2));
+        let area = Rect::new(0, 0, 8, 2);
+        assert_eq!(t.cursor_screen_position(area), Some((6, 0)));
+        assert!(t.click(area, 4, 0));
+        assert_eq!(t.cursor(), (0, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "bidi")]
+    fn render_bidi_text() {
+        let mut t = TextArea::from(["ab אבג"]);
+        t.set_bidi(true);
+        assert_eq!(t.render_to_string(7, 1), "ab גבא ");
+
+        // The cursor is rendered at the visual position of the character
+        let area = Rect::new(0, 0, 7, 1);
+        t.move_cursor(crate::CursorMove::Jump(0, 3));
+        assert_eq!(t.cursor_screen_position(area), Some((5, 0)));
+        t.move_cursor(crate::CursorMove::End);
+        assert_eq!(t.cursor_screen_position(area), Some((6, 0)));
+
+        assert!(t.click(area, 3, 0));
+        assert_eq!(t.cursor(), (0, 5));
+        assert!(t.click(area, 1, 0));
+        assert_eq!(t.cursor(), (0, 1));
+    }
+
+    #[test]
+    fn render_ambiguous_width() {
+        let mut t = TextArea::from(["○\tb", "abc○"]);
+        t.set_ambiguous_width(crate::AmbiguousWidth::Wide);
+        assert_eq!(t.render_to_string(6, 2), "○  b \nabc○ ");
+
+        // The padding cell is covered by the wide character on the terminal
+        let buf = t.render_to_buffer(6, 2);
+        assert_eq!(buf.get(0, 0).symbol(), "○");
+        assert_eq!(buf.get(1, 0).symbol(), "");
+        assert_eq!(buf.get(4, 0).symbol(), "b");
+
+        // The wide character at the right edge doesn't fit
+        assert_eq!(t.render_to_string(4, 2), "○  \nabc ");
+
+        t.move_cursor(crate::CursorMove::Jump(0, 2));
+        let area = Rect::new(0, 0, 6, 2);
+        assert_eq!(t.cursor_screen_position(area), Some((4, 0)));
+        assert!(t.click(area, 1, 0));
+        assert_eq!(t.cursor(), (0, 0));
+        assert!(t.click(area, 2, 0));
+        assert_eq!(t.cursor(), (0, 1));
+    }
+
+    #[test]
+    fn render_virtual_space() {
+        let mut t = TextArea::from(["ab", "c"]);
+        t.set_virtual_space(true);
+        t.move_cursor(crate::CursorMove::Jump(1, 4));
+        assert_eq!(t.render_to_string(6, 2), "ab    \nc     ");
+        let buf = t.render_to_buffer(6, 2);
+        assert!(buf.get(4, 1).modifier.contains(Modifier::REVERSED));
+        assert_eq!(
+            t.cursor_screen_position(Rect::new(0, 0, 6, 2)),
+            Some((4, 1))
+        );
+
+        // Scrolled horizontally to show the cursor beyond the end of line
+        t.move_cursor(crate::CursorMove::Jump(1, 8));
+        assert_eq!(t.render_to_string(4, 2), "    \n    ");
+        assert_eq!(
+            t.cursor_screen_position(Rect::new(0, 0, 4, 2)),
+            Some((3, 1))
+        );
     }
 }
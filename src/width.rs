@@ -0,0 +1,106 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use unicode_width::UnicodeWidthChar as _;
+
+/// Width of East Asian ambiguous characters such as `○`, `→` and `×`. Terminals differ on whether they occupy one or
+/// two cells (e.g. depending on the locale), so the width should be set to match the user's terminal. It is used by
+/// all width calculations of the text in the textarea such as cursor positions, tab stops and horizontal scrolling.
+/// ```
+/// use tui_textarea::{AmbiguousWidth, TextArea};
+///
+/// let mut textarea = TextArea::from(["○\tb"]);
+/// textarea.set_tab_length(4);
+/// assert_eq!(textarea.render_to_string(6, 1), "○   b ");
+///
+/// // The cell after the wide character is covered by the character on terminals
+/// textarea.set_ambiguous_width(AmbiguousWidth::Wide);
+/// assert_eq!(textarea.render_to_string(6, 1), "○  b ");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AmbiguousWidth {
+    /// Ambiguous characters occupy one cell. This is the default width
+    Narrow,
+    /// Ambiguous characters occupy two cells like CJK characters
+    Wide,
+}
+
+impl Default for AmbiguousWidth {
+    fn default() -> Self {
+        Self::Narrow
+    }
+}
+
+pub fn char_width(c: char, ambiguous: AmbiguousWidth) -> Option<usize> {
+    match ambiguous {
+        AmbiguousWidth::Narrow => c.width(),
+        AmbiguousWidth::Wide => c.width_cjk(),
+    }
+}
+
+pub fn str_width(s: &str, ambiguous: AmbiguousWidth) -> usize {
+    s.chars()
+        .map(|c| char_width(c, ambiguous).unwrap_or(0))
+        .sum()
+}
+
+// Characters wider than the width calculated on rendering the buffer. They need a padding cell after them
+pub fn is_widened(c: char, ambiguous: AmbiguousWidth) -> bool {
+    ambiguous == AmbiguousWidth::Wide && c.width_cjk() > c.width()
+}
+
+// Insert a padding space after each widened character so that the characters after it are laid out at the cells where
+// the terminal renders them. The padding cells are cleared after rendering the buffer
+pub fn pad_widened<'a>(s: Cow<'a, str>, ambiguous: AmbiguousWidth) -> Cow<'a, str> {
+    if !s.chars().any(|c| is_widened(c, ambiguous)) {
+        return s;
+    }
+    let mut buf = String::with_capacity(s.len() + 4);
+    for c in s.chars() {
+        buf.push(c);
+        if is_widened(c, ambiguous) {
+            buf.push(' ');
+        }
+    }
+    Cow::Owned(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ambiguous_char_width() {
+        let tests = [
+            ('a', 1, 1),
+            ('○', 1, 2),
+            ('→', 1, 2),
+            ('あ', 2, 2),
+            ('\u{301}', 0, 0),
+        ];
+        for (c, narrow, wide) in tests {
+            assert_eq!(
+                char_width(c, AmbiguousWidth::Narrow),
+                Some(narrow),
+                "{:?}",
+                c
+            );
+            assert_eq!(char_width(c, AmbiguousWidth::Wide), Some(wide), "{:?}", c);
+            assert_eq!(
+                is_widened(c, AmbiguousWidth::Wide),
+                narrow < wide,
+                "{:?}",
+                c
+            );
+            assert!(!is_widened(c, AmbiguousWidth::Narrow), "{:?}", c);
+        }
+        assert_eq!(str_width("a○→あ", AmbiguousWidth::Narrow), 5);
+        assert_eq!(str_width("a○→あ", AmbiguousWidth::Wide), 7);
+
+        let pad = |s: &'static str, w| pad_widened(Cow::Borrowed(s), w).into_owned();
+        assert_eq!(pad("a○b", AmbiguousWidth::Narrow), "a○b");
+        assert_eq!(pad("a○b", AmbiguousWidth::Wide), "a○ b");
+        assert_eq!(pad("あb", AmbiguousWidth::Wide), "あb");
+    }
+}
@@ -1,27 +1,135 @@
-#[derive(PartialEq, Eq, Clone, Copy)]
+use crate::util::byte_offset;
+use std::cmp;
+use unicode_segmentation::UnicodeSegmentation as _;
+
+// Scripts which are written without spaces between words. A boundary between words of different scripts is a word
+// boundary even if no space is between them
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Script {
+    Han,
+    Hiragana,
+    Katakana,
+    Other,
+}
+
+impl Script {
+    fn new(c: char) -> Self {
+        match c {
+            '\u{3040}'..='\u{309f}' => Self::Hiragana,
+            '\u{30a0}'..='\u{30ff}' | '\u{31f0}'..='\u{31ff}' | '\u{ff66}'..='\u{ff9f}' => {
+                Self::Katakana
+            }
+            '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{f900}'..='\u{faff}'
+            | '\u{20000}'..='\u{2fa1f}' => Self::Han,
+            _ => Self::Other,
+        }
+    }
+}
+
+// Rules of word boundaries set by `TextArea::set_word_chars`, `TextArea::set_word_separators`, and
+// `TextArea::set_unicode_words`
+#[derive(Clone, Default, Debug)]
+pub struct WordBoundary {
+    // Punctuation treated as a part of words
+    pub word_chars: String,
+    // Characters which always separate words. They take precedence over `word_chars`
+    pub separators: String,
+    // Split words at UAX #29 word boundaries instead of at each punctuation character
+    pub unicode: bool,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
 enum CharKind {
     Space,
     Punct,
-    Other,
+    Word(Script),
 }
 
 impl CharKind {
-    fn new(c: char) -> Self {
+    // Punctuation in `word_chars` is treated as a part of words. Characters in `separators` are never a part of words
+    fn new(c: char, words: &WordBoundary) -> Self {
         if c.is_whitespace() {
             Self::Space
-        } else if c.is_ascii_punctuation() {
+        } else if words.separators.contains(c)
+            || c.is_ascii_punctuation() && !words.word_chars.contains(c)
+        {
             Self::Punct
         } else {
-            Self::Other
+            Self::Word(Script::new(c))
+        }
+    }
+
+    // Kind of the segment split at word boundaries. Punctuation inside a word such as an apostrophe in "don't" is a
+    // part of the word
+    fn of_segment(seg: &str, words: &WordBoundary) -> Self {
+        let mut kinds = seg.chars().map(|c| Self::new(c, words));
+        let first = kinds.next().unwrap_or(Self::Space);
+        kinds.fold(first, |acc, kind| match (acc, kind) {
+            (Self::Word(_), _) => acc,
+            (_, Self::Word(_)) => kind,
+            _ => acc,
+        })
+    }
+}
+
+pub fn is_word_char(c: char) -> bool {
+    matches!(
+        CharKind::new(c, &WordBoundary::default()),
+        CharKind::Word(_)
+    )
+}
+
+// Split the line into segments. Each segment is returned as its start column, end column (exclusive), and kind. Each
+// character is a segment by default. When UAX #29 is enabled, the line is split at the word boundaries it defines.
+// A segment containing separators is split into characters so that the separators are not merged into words
+fn segments(line: &str, words: &WordBoundary) -> Vec<(usize, usize, CharKind)> {
+    fn chars(seg: &str, start: usize, words: &WordBoundary) -> Vec<(usize, usize, CharKind)> {
+        (start..)
+            .zip(seg.chars())
+            .map(|(col, c)| (col, col + 1, CharKind::new(c, words)))
+            .collect()
+    }
+
+    if !words.unicode {
+        return chars(line, 0, words);
+    }
+    let mut col = 0;
+    let mut segs = vec![];
+    for seg in line.split_word_bounds() {
+        let start = col;
+        col += seg.chars().count();
+        if seg.contains(|c| words.separators.contains(c)) {
+            segs.extend(chars(seg, start, words));
+        } else {
+            segs.push((start, col, CharKind::of_segment(seg, words)));
         }
     }
+    segs
+}
+
+// Find the start column of the word which ends at `col`. When no word ends at `col`, `col` is returned.
+pub fn find_word_head(line: &str, col: usize) -> usize {
+    let end = byte_offset(line, col);
+    let len = line[..end]
+        .chars()
+        .rev()
+        .take_while(|c| is_word_char(*c))
+        .count();
+    col - len
 }
 
-pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
-    let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
-    for (col, c) in it {
-        let cur = CharKind::new(c);
+pub fn find_word_start_forward(
+    line: &str,
+    start_col: usize,
+    words: &WordBoundary,
+) -> Option<usize> {
+    let mut it = segments(line, words)
+        .into_iter()
+        .skip_while(|&(_, end, _)| end <= start_col);
+    let mut prev = it.next()?.2;
+    for (col, _, cur) in it {
         if cur != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -30,11 +138,12 @@ pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
     None
 }
 
-pub fn find_word_end_forward(line: &str, start_col: usize) -> Option<usize> {
-    let mut it = line.chars().enumerate().skip(start_col);
-    let mut prev = CharKind::new(it.next()?.1);
-    for (col, c) in it {
-        let cur = CharKind::new(c);
+pub fn find_word_end_forward(line: &str, start_col: usize, words: &WordBoundary) -> Option<usize> {
+    let mut it = segments(line, words)
+        .into_iter()
+        .skip_while(|&(_, end, _)| end <= start_col);
+    let mut prev = it.next()?.2;
+    for (col, _, cur) in it {
         if prev != CharKind::Space && prev != cur {
             return Some(col);
         }
@@ -43,20 +152,318 @@ pub fn find_word_end_forward(line: &str, start_col: usize) -> Option<usize> {
     None
 }
 
-pub fn find_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
-    let idx = line
-        .char_indices()
-        .nth(start_col)
-        .map(|(i, _)| i)
-        .unwrap_or(line.len());
-    let mut it = line[..idx].chars().rev().enumerate();
-    let mut cur = CharKind::new(it.next()?.1);
-    for (i, c) in it {
-        let next = CharKind::new(c);
+pub fn find_word_start_backward(
+    line: &str,
+    start_col: usize,
+    words: &WordBoundary,
+) -> Option<usize> {
+    let segs = segments(line, words);
+    let mut it = segs
+        .iter()
+        .rev()
+        .skip_while(|&&(start, _, _)| start >= start_col);
+    let &(mut start, _, mut cur) = it.next()?;
+    for &(col, _, next) in it {
         if cur != CharKind::Space && next != cur {
-            return Some(start_col - i);
+            return Some(start);
         }
         cur = next;
+        start = col;
     }
     (cur != CharKind::Space).then(|| 0)
 }
+
+// Find the columns where a sub-word starts inside an identifier, such as `Bar` in `fooBar`, `Server` in `HTTPServer`,
+// and `bar` in `foo_bar`. `_` and punctuation in `word_chars` separate sub-words
+fn subword_starts(line: &str, words: &WordBoundary) -> Vec<usize> {
+    let chars: Vec<char> = line.chars().collect();
+    let is_sep = |c: char| c == '_' || words.word_chars.contains(c);
+    (1..chars.len())
+        .filter(|&i| {
+            let (prev, cur) = (chars[i - 1], chars[i]);
+            let next = chars.get(i + 1).copied();
+            let camel = (prev.is_lowercase() || prev.is_numeric()) && cur.is_uppercase();
+            let acronym =
+                prev.is_uppercase() && cur.is_uppercase() && next.map_or(false, char::is_lowercase);
+            cur.is_alphanumeric() && (is_sep(prev) || camel || acronym)
+        })
+        .collect()
+}
+
+pub fn find_subword_start_forward(
+    line: &str,
+    start_col: usize,
+    words: &WordBoundary,
+) -> Option<usize> {
+    let word = find_word_start_forward(line, start_col, words);
+    let sub = subword_starts(line, words)
+        .into_iter()
+        .find(|&col| col > start_col);
+    match (word, sub) {
+        (Some(w), Some(s)) => Some(cmp::min(w, s)),
+        (w, s) => w.or(s),
+    }
+}
+
+pub fn find_subword_start_backward(
+    line: &str,
+    start_col: usize,
+    words: &WordBoundary,
+) -> Option<usize> {
+    let word = find_word_start_backward(line, start_col, words);
+    let sub = subword_starts(line, words)
+        .into_iter()
+        .rev()
+        .find(|&col| col < start_col);
+    match (word, sub) {
+        (Some(w), Some(s)) => Some(cmp::max(w, s)),
+        (w, s) => w.or(s),
+    }
+}
+
+// Find the range of the segments of the same kind as the segment at `col`. The range is returned as the start column
+// and the end column (exclusive). When the line is empty, `(0, 0)` is returned.
+pub fn find_word_range(line: &str, col: usize, words: &WordBoundary) -> (usize, usize) {
+    let segs = segments(line, words);
+    let last = match segs.last() {
+        Some(&(_, end, _)) => end,
+        None => return (0, 0),
+    };
+    let col = cmp::min(col, last - 1);
+    let i = segs.iter().position(|&(_, end, _)| col < end).unwrap_or(0);
+    let kind = segs[i].2;
+    let start = segs[..i]
+        .iter()
+        .rev()
+        .take_while(|&&(_, _, k)| k == kind)
+        .last()
+        .map_or(segs[i].0, |&(s, _, _)| s);
+    let end = segs[i..]
+        .iter()
+        .take_while(|&&(_, _, k)| k == kind)
+        .last()
+        .map_or(segs[i].1, |&(_, e, _)| e);
+    (start, end)
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Find the identifier at `col` in the line. When no identifier is at `col`, the identifier which ends at `col` is
+// returned. Returns the byte offset range of the identifier.
+pub fn find_ident_at(line: &str, col: usize) -> Option<(usize, usize)> {
+    let ident_at = |col: usize| {
+        let i = byte_offset(line, col);
+        line[i..]
+            .chars()
+            .next()
+            .filter(|c| is_ident_char(*c))
+            .map(|_| i)
+    };
+    let offset = ident_at(col).or_else(|| ident_at(col.checked_sub(1)?))?;
+    let start = line[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()
+        .map_or(offset, |(i, _)| i);
+    let end = line[offset..]
+        .find(|c: char| !is_ident_char(c))
+        .map_or(line.len(), |i| offset + i);
+    Some((start, end))
+}
+
+// Find the occurrences of the identifier in the line. Only matches at identifier boundaries are returned as byte
+// offset ranges.
+pub fn find_ident_occurrences(line: &str, ident: &str) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    if ident.is_empty() {
+        return ranges;
+    }
+    for (start, _) in line.match_indices(ident) {
+        let end = start + ident.len();
+        let before = line[..start]
+            .chars()
+            .next_back()
+            .map_or(false, is_ident_char);
+        let after = line[end..].chars().next().map_or(false, is_ident_char);
+        if !before && !after {
+            ranges.push((start, end));
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ident_at_column() {
+        let tests = [
+            ("foo_bar baz", 0, Some((0, 7))),
+            ("foo_bar baz", 3, Some((0, 7))),
+            ("foo_bar baz", 7, Some((0, 7))),
+            ("foo_bar baz", 8, Some((8, 11))),
+            ("foo_bar baz", 11, Some((8, 11))),
+            ("a + b", 2, None),
+            ("", 0, None),
+            ("(x)", 0, None),
+            ("あい うえ", 4, Some((7, 13))),
+        ];
+        for test in tests {
+            let (line, col, want) = test;
+            assert_eq!(find_ident_at(line, col), want, "{:?}", test);
+        }
+    }
+
+    #[test]
+    fn ident_occurrences() {
+        let tests = [
+            ("x = x + xs + _x", "x", &[(0, 1), (4, 5)][..]),
+            ("foo(foo)", "foo", &[(0, 3), (4, 7)][..]),
+            ("abc", "", &[][..]),
+        ];
+        for test in tests {
+            let (line, ident, want) = test;
+            assert_eq!(find_ident_occurrences(line, ident), want, "{:?}", test);
+        }
+    }
+
+    fn words(word_chars: &str, unicode: bool) -> WordBoundary {
+        WordBoundary {
+            word_chars: word_chars.to_string(),
+            unicode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn word_boundaries_with_word_chars() {
+        assert_eq!(
+            find_word_start_forward("foo-bar baz", 0, &words("", false)),
+            Some(3)
+        );
+        assert_eq!(
+            find_word_start_forward("foo-bar baz", 0, &words("-", false)),
+            Some(8)
+        );
+        assert_eq!(
+            find_word_start_forward("foo_bar baz", 0, &words("", false)),
+            Some(3)
+        );
+        assert_eq!(
+            find_word_end_forward("foo_bar baz", 0, &words("_", false)),
+            Some(7)
+        );
+        assert_eq!(
+            find_word_start_backward("ab cd.ef", 8, &words(".", false)),
+            Some(3)
+        );
+        assert_eq!(find_word_range("a.b/c d", 2, &words("", false)), (2, 3));
+        assert_eq!(find_word_range("a.b/c d", 2, &words("./", false)), (0, 5));
+    }
+
+    #[test]
+    fn word_boundaries_with_separators() {
+        for unicode in [false, true] {
+            let words = WordBoundary {
+                word_chars: "_".to_string(),
+                separators: "_".to_string(),
+                unicode,
+            };
+            assert_eq!(find_word_start_forward("foo_bar baz", 0, &words), Some(3));
+            assert_eq!(find_word_end_forward("foo_bar baz", 4, &words), Some(7));
+            assert_eq!(find_word_start_backward("foo_bar baz", 7, &words), Some(4));
+            assert_eq!(find_word_range("foo_bar baz", 1, &words), (0, 3));
+        }
+
+        let words = WordBoundary {
+            separators: "'".to_string(),
+            unicode: true,
+            ..Default::default()
+        };
+        assert_eq!(find_word_start_forward("don't stop", 0, &words), Some(3));
+        assert_eq!(find_word_start_forward("foo_bar baz", 0, &words), Some(8));
+    }
+
+    #[test]
+    fn word_boundaries_at_each_character() {
+        let tests = [
+            ("don't stop", 0, Some(3)),
+            ("3.14 + x", 0, Some(1)),
+            ("foo_bar(x)", 0, Some(3)),
+            ("これはペンです", 0, Some(3)),
+            ("日本語のテキスト", 1, Some(3)),
+        ];
+        for test in tests {
+            let (line, col, want) = test;
+            let got = find_word_start_forward(line, col, &words("", false));
+            assert_eq!(got, want, "{:?}", test);
+        }
+    }
+
+    #[test]
+    fn word_boundaries_by_uax29() {
+        let uax29 = words("", true);
+        let tests = [
+            ("don't stop", 0, Some(6)),
+            ("3.14 + x", 0, Some(5)),
+            ("foo_bar(x)", 0, Some(7)),
+            ("これはペンです", 0, Some(3)),
+            ("これはペンです", 3, Some(5)),
+            ("日本語のテキスト", 1, Some(3)),
+            ("word  ことば 🐶", 6, Some(10)),
+        ];
+        for test in tests {
+            let (line, col, want) = test;
+            assert_eq!(
+                find_word_start_forward(line, col, &uax29),
+                want,
+                "{:?}",
+                test
+            );
+        }
+
+        assert_eq!(
+            find_word_start_backward("これはペンです", 7, &uax29),
+            Some(5)
+        );
+        assert_eq!(find_word_end_forward("これはペンです", 0, &uax29), Some(3));
+        assert_eq!(find_word_range("日本語のテキスト", 5, &uax29), (4, 8));
+        assert_eq!(find_word_range("", 0, &uax29), (0, 0));
+    }
+
+    #[test]
+    fn subword_boundaries() {
+        let tests = [
+            ("fooBarBaz", &[3, 6][..]),
+            ("foo_bar__baz", &[4, 9][..]),
+            ("HTTPServer", &[4][..]),
+            ("utf8Decode x_y", &[4, 13][..]),
+            ("_foo BAR", &[1][..]),
+            ("foo-bar", &[][..]),
+        ];
+        for test in tests {
+            let (line, want) = test;
+            assert_eq!(subword_starts(line, &words("", false)), want, "{:?}", test);
+        }
+        assert_eq!(subword_starts("foo-bar", &words("-", false)), [4]);
+
+        let line = "let fooBar = foo_bar;";
+        let uax29 = words("", true);
+        assert_eq!(find_subword_start_forward(line, 4, &uax29), Some(7));
+        assert_eq!(find_subword_start_forward(line, 7, &uax29), Some(11));
+        assert_eq!(find_subword_start_forward(line, 17, &uax29), Some(20));
+        assert_eq!(find_subword_start_forward(line, 20, &uax29), None);
+        assert_eq!(find_subword_start_backward(line, 20, &uax29), Some(17));
+        assert_eq!(find_subword_start_backward(line, 17, &uax29), Some(13));
+        assert_eq!(find_subword_start_backward(line, 13, &uax29), Some(11));
+        assert_eq!(find_subword_start_backward(line, 7, &uax29), Some(4));
+        assert_eq!(
+            find_subword_start_forward(line, 13, &words("", false)),
+            Some(16)
+        );
+    }
+}
@@ -18,6 +18,8 @@ fn empty_textarea() {
         Bottom,
         WordForward,
         WordBack,
+        SubwordForward,
+        SubwordBack,
         ParagraphForward,
         ParagraphBack,
         Jump(0, 0),
@@ -166,6 +168,27 @@ fn down() {
     }
 }
 
+#[test]
+fn up_down_keep_display_column_with_tabs() {
+    let mut t = TextArea::from(["abcdefgh", "\tx", "ab\tcd", "abcdefgh"]);
+    t.move_cursor(CursorMove::Jump(0, 5));
+
+    // The tab is rendered in the columns 0..4 and `x` is at the column 4
+    for expected in [(1, 2), (2, 4), (3, 5)] {
+        t.move_cursor(CursorMove::Down);
+        assert_eq!(t.cursor(), expected);
+    }
+    for expected in [(2, 4), (1, 2), (0, 5)] {
+        t.move_cursor(CursorMove::Up);
+        assert_eq!(t.cursor(), expected);
+    }
+
+    // On a tab
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (1, 0));
+}
+
 #[test]
 fn down_trim() {
     for text in [["abcd", "efg", "h", ""], ["あ?い!", "🐶!🐱", "👪", ""]] {
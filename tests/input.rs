@@ -1,4 +1,11 @@
-use tui_textarea::{Input, Key, TextArea};
+use tui_textarea::{CursorMove, Input, Key, TextArea};
+
+fn key(key: Key) -> Input {
+    Input {
+        key,
+        ..Default::default()
+    }
+}
 
 // Sanity test for checking textarea does not crash against all combination of inputs
 #[test]
@@ -80,3 +87,385 @@ fn test_insert_multi_code_unit_emoji() {
     }
     assert_eq!(t.lines(), ["👨‍👩‍👧‍👦"]);
 }
+
+#[test]
+fn test_codepoint_entry() {
+    let ctrl_shift_u = Input {
+        key: Key::Char('U'),
+        ctrl: true,
+        alt: false,
+        shift: true,
+    };
+
+    let mut t = TextArea::default();
+    assert!(!t.input(ctrl_shift_u.clone()));
+    for c in "e9".chars() {
+        t.input(key(Key::Char(c)));
+    }
+    t.input(key(Key::Backspace));
+    assert_eq!(t.codepoint_entry(), Some("e"));
+    t.input(key(Key::Char('9')));
+    assert!(t.input(key(Key::Char(' '))));
+    assert_eq!(t.lines(), ["é"]);
+    assert_eq!(t.codepoint_entry(), None);
+
+    // Esc cancels the entry
+    t.input(ctrl_shift_u.clone());
+    t.input(key(Key::Char('4')));
+    t.input(key(Key::Esc));
+    assert_eq!(t.codepoint_entry(), None);
+    assert_eq!(t.lines(), ["é"]);
+
+    // Other keys cancel the entry and are handled as usual
+    t.input(ctrl_shift_u);
+    t.input(key(Key::Char('4')));
+    assert!(t.input(key(Key::Char('z'))));
+    assert_eq!(t.lines(), ["éz"]);
+    assert_eq!(t.codepoint_entry(), None);
+}
+
+#[test]
+fn test_digraph_input() {
+    let ctrl_k = Input {
+        key: Key::Char('k'),
+        ctrl: true,
+        alt: false,
+        shift: false,
+    };
+
+    // Ctrl+K deletes the text until the end of line by default
+    let mut t = TextArea::from(["abc"]);
+    assert!(t.input(ctrl_k.clone()));
+    assert_eq!(t.lines(), [""]);
+
+    t.set_digraph_input(true);
+    assert!(!t.input(ctrl_k.clone()));
+    assert!(!t.input(key(Key::Char('e'))));
+    assert!(t.is_entering_digraph());
+    assert!(t.input(key(Key::Char('\''))));
+    assert!(!t.is_entering_digraph());
+    assert_eq!(t.lines(), ["é"]);
+
+    // Characters in reverse order
+    t.input(ctrl_k.clone());
+    t.input(key(Key::Char('>')));
+    t.input(key(Key::Char('-')));
+    assert_eq!(t.lines(), ["é→"]);
+
+    // The second character is inserted when the characters are not a digraph
+    t.input(ctrl_k.clone());
+    t.input(key(Key::Char('q')));
+    t.input(key(Key::Char('z')));
+    assert_eq!(t.lines(), ["é→z"]);
+
+    // Esc cancels the entry
+    t.input(ctrl_k.clone());
+    t.input(key(Key::Char('a')));
+    assert!(!t.input(key(Key::Esc)));
+    assert!(!t.is_entering_digraph());
+    assert_eq!(t.lines(), ["é→z"]);
+
+    // Other keys cancel the entry and are handled as usual
+    t.input(ctrl_k);
+    assert!(t.input(key(Key::Backspace)));
+    assert!(!t.is_entering_digraph());
+    assert_eq!(t.lines(), ["é→"]);
+}
+
+#[test]
+fn test_template_expansion() {
+    fn char_input(c: char) -> Input {
+        Input {
+            key: Key::Char(c),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    let mut t = TextArea::default();
+    for c in "{{date}} ".chars() {
+        t.input(char_input(c));
+    }
+    assert_eq!(t.lines(), ["{{date}} "]);
+
+    t.set_template_resolver(|name| (name == "date").then(|| "2024-01-02".to_string()));
+    for c in "{{date}} {{x}}".chars() {
+        t.input(char_input(c));
+    }
+    assert_eq!(t.lines(), ["{{date}} 2024-01-02 {{x}}"]);
+
+    // Expansion is undone at once
+    let mut u = TextArea::default();
+    u.set_template_resolver(|name| (name == "date").then(|| "2024-01-02".to_string()));
+    for c in "{{date}}".chars() {
+        u.input(char_input(c));
+    }
+    assert_eq!(u.lines(), ["2024-01-02"]);
+    assert!(u.undo());
+    assert_eq!(u.lines(), ["{{date}}"]);
+
+    // Ctrl+Q prevents the expansion
+    for c in " {{date}".chars() {
+        t.input(char_input(c));
+    }
+    t.input(Input {
+        key: Key::Char('q'),
+        ctrl: true,
+        alt: false,
+        shift: false,
+    });
+    t.input(char_input('}'));
+    assert_eq!(t.lines(), ["{{date}} 2024-01-02 {{x}} {{date}}"]);
+
+    t.remove_template_resolver();
+    assert!(!t.expand_template());
+}
+
+#[test]
+fn test_list_continuation() {
+    let enter = Input {
+        key: Key::Enter,
+        ctrl: false,
+        alt: false,
+        shift: false,
+    };
+
+    let mut t = TextArea::from(["- foo"]);
+    t.move_cursor(CursorMove::End);
+    t.input(enter.clone());
+    assert_eq!(t.lines(), ["- foo", ""]);
+
+    let mut t = TextArea::from(["* foo", "9. bar", "plain"]);
+    t.set_list_continuation(true);
+    t.move_cursor(CursorMove::End);
+    t.input(enter.clone());
+    t.insert_str("baz");
+    assert_eq!(t.lines(), ["* foo", "* baz", "9. bar", "plain"]);
+
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::End);
+    t.input(enter.clone());
+    assert_eq!(t.lines(), ["* foo", "* baz", "9. bar", "10. ", "plain"]);
+
+    // Enter on the empty item terminates the list
+    t.input(enter.clone());
+    assert_eq!(t.lines(), ["* foo", "* baz", "9. bar", "", "plain"]);
+    assert_eq!(t.cursor(), (3, 0));
+
+    // The text after the cursor is moved to the next item. Undo reverts the whole continuation
+    t.move_cursor(CursorMove::Jump(0, 3));
+    t.input(enter.clone());
+    assert_eq!(t.lines(), ["* f", "* oo", "* baz", "9. bar", "", "plain"]);
+    assert_eq!(t.cursor(), (1, 2));
+    t.undo();
+    assert_eq!(t.lines(), ["* foo", "* baz", "9. bar", "", "plain"]);
+
+    // Enter before the marker inserts a newline as usual
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.input(enter.clone());
+    assert_eq!(t.lines(), ["", "* foo", "* baz", "9. bar", "", "plain"]);
+
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    t.input(enter);
+    assert_eq!(t.lines(), ["", "* foo", "* baz", "9. bar", "", "plain", ""]);
+}
+
+#[test]
+fn test_commit_message_wrap() {
+    fn type_str(t: &mut TextArea, s: &str) {
+        for c in s.chars() {
+            t.input(Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                shift: false,
+            });
+        }
+    }
+    let long = format!("{} word", "x".repeat(70));
+
+    // Disabled by default
+    let mut t = TextArea::from(["Subject", "", ""]);
+    t.move_cursor(CursorMove::Bottom);
+    type_str(&mut t, &long);
+    assert_eq!(t.lines()[2], long);
+
+    // The subject and comment lines are not wrapped
+    let mut t = TextArea::from(["# comment", "Subject", "", ""]);
+    t.set_commit_message_mode(true);
+    t.move_cursor(CursorMove::Jump(0, 9));
+    type_str(&mut t, &format!(" {}", long));
+    t.move_cursor(CursorMove::Jump(1, 7));
+    type_str(&mut t, &format!(" {}", long));
+    assert_eq!(t.lines()[0], format!("# comment {}", long));
+    assert_eq!(t.lines()[1], format!("Subject {}", long));
+
+    // Body line is wrapped and the cursor follows the text
+    t.move_cursor(CursorMove::Bottom);
+    type_str(&mut t, &long);
+    type_str(&mut t, " next");
+    assert_eq!(t.lines()[2..], ["", "x".repeat(70).as_str(), "word next"]);
+    assert_eq!(t.cursor(), (4, 9));
+}
+
+#[test]
+fn test_abbreviation_expansion() {
+    fn char_input(c: char) -> Input {
+        Input {
+            key: Key::Char(c),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    for (typed, want) in [
+        ("teh ", "the "),
+        ("teh.", "the."),
+        ("(teh)", "(the)"),
+        ("tehe ", "tehe "),
+        ("steh ", "steh "),
+        ("teh", "teh"),
+    ] {
+        let mut t = TextArea::default();
+        t.add_abbreviation("teh", "the");
+        for c in typed.chars() {
+            t.input(char_input(c));
+        }
+        assert_eq!(t.lines(), [want], "{typed:?}");
+    }
+
+    // Expansion is undone at once
+    let mut t = TextArea::default();
+    t.add_abbreviation("teh", "the");
+    for c in "x teh ".chars() {
+        t.input(char_input(c));
+    }
+    assert_eq!(t.lines(), ["x the "]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x the"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x teh"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x te"]);
+
+    // Enter and Tab terminate a word as well
+    let mut t = TextArea::default();
+    t.add_abbreviation("sig", "fn main() {\n}");
+    for c in "sig".chars() {
+        t.input(char_input(c));
+    }
+    t.input(Input {
+        key: Key::Enter,
+        ..Default::default()
+    });
+    assert_eq!(t.lines(), ["fn main() {", "}", ""]);
+    assert_eq!(t.cursor(), (2, 0));
+
+    // Quoted insert suppresses the expansion
+    let mut t = TextArea::from(["teh"]);
+    t.add_abbreviation("teh", "the");
+    t.move_cursor(tui_textarea::CursorMove::End);
+    assert!(!t.input(Input {
+        key: Key::Char('q'),
+        ctrl: true,
+        ..Default::default()
+    }));
+    assert!(t.input(char_input(' ')));
+    assert_eq!(t.lines(), ["teh "]);
+    t.input(char_input('x'));
+    assert_eq!(t.lines(), ["teh x"]);
+}
+
+#[test]
+fn test_completion_popup_input() {
+    let mut t = TextArea::from(["fo"]);
+    t.input(key(Key::End));
+
+    t.open_completion_popup(["foo", "food", "fool"]);
+    assert!(!t.input(key(Key::Down)));
+    assert!(!t.input(key(Key::Down)));
+    assert!(!t.input(key(Key::Up)));
+    assert_eq!(t.selected_completion(), Some("food"));
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(t.input(key(Key::Tab)));
+    assert_eq!(t.lines(), ["food"]);
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(!t.is_completion_popup_open());
+
+    // Esc dismisses the popup without modifying the text
+    t.open_completion_popup(["foo"]);
+    assert!(!t.input(key(Key::Esc)));
+    assert!(!t.is_completion_popup_open());
+    assert_eq!(t.lines(), ["food"]);
+
+    // Other keys dismiss the popup and are handled as usual
+    t.open_completion_popup(["foo"]);
+    assert!(t.input(key(Key::Char('!'))));
+    assert!(!t.is_completion_popup_open());
+    assert_eq!(t.lines(), ["food!"]);
+}
+
+#[test]
+fn test_ghost_text_input() {
+    let mut t = TextArea::from(["hel"]);
+    t.input(key(Key::End));
+
+    // Accepted at end of line
+    t.set_ghost_text("lo");
+    assert!(t.input(key(Key::Tab)));
+    assert_eq!(t.lines(), ["hello"]);
+    assert_eq!(t.cursor(), (0, 5));
+    assert_eq!(t.ghost_text(), "");
+
+    // Cleared on typing
+    t.set_ghost_text(" world");
+    assert!(t.input(key(Key::Char('!'))));
+    assert_eq!(t.lines(), ["hello!"]);
+    assert_eq!(t.ghost_text(), "");
+
+    // Right key moves the cursor as usual when the cursor is not at end of line
+    t.input(key(Key::Home));
+    t.set_ghost_text("foo");
+    assert!(!t.input(key(Key::Right)));
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.lines(), ["hello!"]);
+    assert_eq!(t.ghost_text(), "");
+}
+
+#[test]
+fn test_ctrl_i_inserts_tab() {
+    // Ctrl+I is distinguished from Tab with the kitty keyboard protocol
+    let mut t = TextArea::default();
+    t.set_hard_tab_indent(true);
+    let input = Input {
+        key: Key::Char('i'),
+        ctrl: true,
+        alt: false,
+        shift: false,
+    };
+    assert!(t.input(input.clone()));
+    assert_eq!(t.lines(), ["\t"]);
+
+    // Not handled without default key mappings
+    assert!(!t.input_without_shortcuts(input));
+    assert_eq!(t.lines(), ["\t"]);
+}
+
+#[test]
+fn test_shift_tab_does_not_insert_tab() {
+    // Shift+Tab is converted into `Key::Tab` with `shift` from `BackTab`
+    let mut t = TextArea::from(["abc"]);
+    let input = Input {
+        key: Key::Tab,
+        ctrl: false,
+        alt: false,
+        shift: true,
+    };
+    assert!(!t.input(input.clone()));
+    assert_eq!(t.lines(), ["abc"]);
+    assert!(!t.input_without_shortcuts(input));
+    assert_eq!(t.lines(), ["abc"]);
+}
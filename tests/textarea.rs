@@ -1,6 +1,6 @@
 use std::cmp;
 use std::fmt::Debug;
-use tui_textarea::{CursorMove, TextArea};
+use tui_textarea::{CursorMove, IndentRules, Input, Key, Mode, TextArea, TextAreaSet, Theme};
 
 fn assert_undo_redo<T: Debug>(
     before_pos: (usize, usize),
@@ -44,6 +44,8 @@ fn test_insert_soft_tab() {
         ("🐶", 1, "🐶  ", 2),
         ("あ", 0, "    あ", 4),
         ("あい", 1, "あ  い", 2),
+        ("\ta", 2, "\ta   ", 3),
+        ("a\tb", 3, "a\tb   ", 3),
     ] {
         let (input, col, expected, width) = test;
         let mut t = TextArea::from([input.to_string()]);
@@ -1582,6 +1584,27 @@ fn test_delete_next_char() {
     t.test((1, 1), (1, 1, t.0, ""));
 }
 
+#[test]
+fn test_delete_char_with_combining_marks() {
+    let t = DeleteTester(&["ae\u{301}b"], |t| t.delete_char());
+    t.test((0, 3), (0, 1, &["ab"], ""));
+    t.test((0, 2), (0, 1, &["ab"], ""));
+    t.test((0, 4), (0, 3, &["ae\u{301}"], ""));
+
+    let t = DeleteTester(&["ae\u{301}b"], |t| t.delete_next_char());
+    t.test((0, 0), (0, 0, &["e\u{301}b"], ""));
+    t.test((0, 1), (0, 1, &["ab"], ""));
+}
+
+#[test]
+fn test_delete_combining_mark() {
+    let t = DeleteTester(&["ae\u{301}b"], |t| t.delete_combining_mark());
+    t.test((0, 3), (0, 2, &["aeb"], ""));
+    t.test((0, 2), (0, 2, t.0, ""));
+    t.test((0, 1), (0, 1, t.0, ""));
+    t.test((0, 0), (0, 0, t.0, ""));
+}
+
 #[test]
 fn test_delete_line_by_end() {
     let t = DeleteTester(&["aaa bbb", "d"], |t| t.delete_line_by_end());
@@ -1632,3 +1655,433 @@ fn test_delete_next_word() {
     t.test((1, 0), (1, 0, &["word  ことば 🐶", ""], " x"));
     t.test((1, 2), (1, 2, t.0, ""));
 }
+
+#[test]
+fn test_complete_word() {
+    let mut t = TextArea::from(["apple apply", "ap"]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+
+    assert!(t.complete_word_forward());
+    assert_eq!(t.lines(), ["apple apply", "apple"]);
+    assert_eq!(t.cursor(), (1, 5));
+    assert!(t.complete_word_forward());
+    assert_eq!(t.lines(), ["apple apply", "apply"]);
+
+    // Modifying the text starts a new completion with the new prefix
+    t.delete_char();
+    assert_eq!(t.lines(), ["apple apply", "appl"]);
+    assert!(t.complete_word_back());
+    assert_eq!(t.lines(), ["apple apply", "apply"]);
+    assert!(t.complete_word_back());
+    assert_eq!(t.lines(), ["apple apply", "apple"]);
+
+    // Completion while selecting does nothing
+    t.start_selection();
+    assert!(!t.complete_word_forward());
+
+    // Completion is undone at once
+    let mut t = TextArea::from(["apple", "ap"]);
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    assert!(t.complete_word_forward());
+    assert_eq!(t.lines(), ["apple", "apple"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["apple", "ap"]);
+
+    // No word before cursor
+    let mut t = TextArea::from(["apple", ""]);
+    t.move_cursor(CursorMove::Bottom);
+    assert!(!t.complete_word_forward());
+    assert_eq!(t.lines(), ["apple", ""]);
+}
+
+#[test]
+fn test_spell_checker() {
+    let mut t = TextArea::from(["Ths is", "a tset", ""]);
+    assert!(!t.misspelling_forward());
+
+    t.set_spell_checker(|w: &str| {
+        !["this", "is", "a", "test"].contains(&w.to_lowercase().as_str())
+    });
+    assert!(t.misspelling_forward());
+    assert_eq!(t.cursor(), (1, 2));
+    assert!(t.misspelling_forward());
+    assert_eq!(t.cursor(), (0, 0)); // Wrapped around
+    assert!(t.misspelling_back());
+    assert_eq!(t.cursor(), (1, 2));
+    assert!(t.misspelling_back());
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Cursor on empty line
+    t.move_cursor(CursorMove::Bottom);
+    assert!(t.misspelling_back());
+    assert_eq!(t.cursor(), (1, 2));
+
+    t.remove_spell_checker();
+    assert!(!t.misspelling_back());
+    assert_eq!(t.cursor(), (1, 2));
+}
+
+#[test]
+fn test_fold_cursor_movement() {
+    let mut t = TextArea::from(["fn f() {", "    aaaaaa", "    b", "}", "x"]);
+    t.move_cursor(CursorMove::Jump(2, 3));
+    assert!(t.fold_by_indent(0));
+    assert_eq!(t.folds(), [(0, 2)]);
+    assert_eq!(t.cursor(), (0, 3)); // Moved out of the hidden lines
+
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (3, 1));
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.cursor(), (0, 1));
+
+    // Modifying a line keeps folds
+    t.insert_char('x');
+    assert_eq!(t.folds(), [(0, 2)]);
+    t.insert_newline();
+    assert!(t.folds().is_empty());
+
+    assert!(!t.fold(2, 2));
+    assert!(!t.fold(0, 99));
+    assert!(t.fold(3, 5));
+    assert!(t.unfold(5));
+    assert!(t.folds().is_empty());
+}
+
+#[test]
+fn test_fold_edit_lines() {
+    let mut t = TextArea::from(["a", "b", "c", "d", "e", "f", "g"]);
+    assert!(t.fold(3, 5));
+
+    // Lines inserted or deleted above the fold move it
+    t.insert_newline();
+    assert_eq!(t.folds(), [(4, 6)]);
+    assert!(t.delete_char());
+    assert_eq!(t.folds(), [(3, 5)]);
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(t.delete_line_by_end());
+    assert!(t.delete_next_char());
+    assert_eq!(t.folds(), [(2, 4)]);
+    assert!(t.undo());
+    assert_eq!(t.folds(), [(3, 5)]);
+
+    // Lines inserted or deleted below the fold don't move it
+    t.move_cursor(CursorMove::Jump(6, 1));
+    t.insert_newline();
+    assert_eq!(t.folds(), [(3, 5)]);
+    assert!(t.delete_char());
+    assert_eq!(t.folds(), [(3, 5)]);
+
+    // Lines inserted or deleted inside the fold remove it
+    t.move_cursor(CursorMove::Jump(4, 1));
+    t.insert_newline();
+    assert!(t.folds().is_empty());
+    assert!(t.fold(3, 6));
+    assert!(t.delete_char());
+    assert!(t.folds().is_empty());
+
+    // Folds don't come back when the number of lines returns to the original
+    t.insert_newline();
+    assert_eq!(t.lines().len(), 8);
+    assert!(t.folds().is_empty());
+}
+
+#[test]
+fn test_status() {
+    let mut t = TextArea::from(["ab", "cde", "", "f"]);
+    let s = t.status();
+    assert_eq!((s.row, s.col, s.lines, s.percent), (1, 1, 4, 25));
+    assert_eq!((s.selected_chars, s.selected_lines), (0, 0));
+
+    t.move_cursor(CursorMove::Forward);
+    t.start_selection();
+    t.move_cursor(CursorMove::Bottom);
+    let s = t.status();
+    assert_eq!((s.row, s.col, s.percent), (4, 2, 100));
+    assert_eq!(s.mode, Mode::Select);
+    assert_eq!((s.selected_chars, s.selected_lines), (8, 4)); // "b\ncde\n\nf"
+
+    t.cut();
+    assert!(t.status().modified);
+    assert_eq!(t.status().mode, Mode::Insert);
+    t.set_modified(false);
+    assert!(!t.is_modified());
+    t.undo();
+    assert!(t.is_modified());
+}
+
+#[test]
+fn test_toggle_markdown_markup() {
+    // Without selection, an empty pair is inserted or removed
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.toggle_code());
+    assert_eq!(t.lines(), ["a``b"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(t.toggle_code());
+    assert_eq!(t.lines(), ["ab"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    // Markers at both ends of the selected text are removed. The text inside the markers stays selected
+    let mut t = TextArea::from(["a **bold** b"]);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 10));
+    assert!(t.toggle_bold());
+    assert_eq!(t.lines(), ["a bold b"]);
+
+    // Bold is not removed by toggling italic
+    assert!(t.toggle_bold());
+    assert!(t.toggle_italic());
+    assert_eq!(t.lines(), ["a ***bold*** b"]);
+    assert!(t.toggle_bold());
+    assert_eq!(t.lines(), ["a *bold* b"]);
+    t.copy();
+    assert_eq!(t.yank_text(), "bold");
+
+    // Selection over multiple lines. Toggling is undone at once
+    let mut t = TextArea::from(["foo bar", "baz qux"]);
+    t.move_cursor(CursorMove::Jump(1, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 4));
+    assert!(t.toggle_italic());
+    assert_eq!(t.lines(), ["foo *bar", "baz* qux"]);
+    t.copy();
+    assert_eq!(t.yank_text(), "bar\nbaz");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo bar", "baz qux"]);
+}
+
+#[test]
+fn test_cycle_heading() {
+    let mut t = TextArea::from(["## Title"]);
+    t.move_cursor(CursorMove::End);
+    for want in [
+        "### Title",
+        "#### Title",
+        "##### Title",
+        "###### Title",
+        "Title",
+        "# Title",
+    ] {
+        assert!(t.cycle_heading());
+        assert_eq!(t.lines(), [want]);
+        assert_eq!(t.cursor(), (0, want.len()));
+    }
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["Title"]);
+
+    // The cursor in the old prefix moves to the end of the new prefix
+    let mut t = TextArea::from(["# a"]);
+    assert!(t.cycle_heading());
+    assert_eq!(t.cursor(), (0, 3));
+}
+
+#[test]
+fn test_auto_indent_python() {
+    let mut t = TextArea::from(["if x:", "    a"]);
+    t.set_indent_rules(IndentRules::python());
+    t.set_auto_indent(true);
+    t.move_cursor(CursorMove::Bottom);
+    t.move_cursor(CursorMove::End);
+    t.insert_newline_and_indent();
+    assert_eq!(t.lines(), ["if x:", "    a", "    "]);
+
+    // `else:` is dedented when typing `:`
+    for c in "else:".chars() {
+        t.input(Input {
+            key: Key::Char(c),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+    }
+    assert_eq!(t.lines(), ["if x:", "    a", "else:"]);
+    assert_eq!(t.cursor(), (2, 5));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["if x:", "    a", "    else:"]);
+}
+
+#[test]
+fn test_reindent_with_hard_tabs() {
+    let mut t = TextArea::from(["a {", "  b", "c {", "d", "}", "}"]);
+    t.set_indent_rules(IndentRules::braces());
+    t.set_hard_tab_indent(true);
+    t.move_cursor(CursorMove::Jump(1, 3));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(5, 1));
+    assert!(t.reindent());
+    assert_eq!(t.lines(), ["a {", "\tb", "\tc {", "\t\td", "\t}", "}"]);
+    assert_eq!(t.cursor(), (5, 1));
+    t.copy();
+    assert_eq!(t.yank_text(), "\n\tc {\n\t\td\n\t}\n}");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a {", "  b", "c {", "d", "}", "}"]);
+}
+
+#[test]
+fn test_block_line_overflow() {
+    let mut t = TextArea::from(["abcd", "ef"]);
+    t.set_max_line_length(6);
+    t.set_block_line_overflow(true);
+
+    // Multi-line text is blocked when any resulting line exceeds the limit
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(!t.insert_str("xyz\n1234567\nw"));
+    assert!(!t.insert_str("xyzwv\n1"));
+    assert!(t.insert_str("xyzw\n1"));
+    assert_eq!(t.lines(), ["abxyzw", "1cd", "ef"]);
+
+    t.set_yank_text("12345");
+    t.move_cursor(CursorMove::Jump(2, 0));
+    assert!(!t.paste());
+    t.set_yank_text("1234");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["abxyzw", "1cd", "1234ef"]);
+
+    t.set_tab_length(4);
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert!(!t.insert_tab());
+    assert_eq!(t.lines(), ["abxyzw", "1cd", "1234ef"]);
+
+    // Existing long lines are not changed but insertion into them is blocked
+    t.set_max_line_length(3);
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.insert_char('x');
+    assert_eq!(t.lines(), ["abxyzw", "1cd", "1234ef"]);
+    assert!(!t.input(Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    }));
+    assert!(t.line_length_rejected());
+    assert_eq!(t.lines(), ["abxyzw", "1cd", "1234ef"]);
+    t.set_block_line_overflow(false);
+    assert!(t.input(Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    }));
+    assert!(!t.line_length_rejected());
+    assert_eq!(t.lines(), ["xabxyzw", "1cd", "1234ef"]);
+}
+
+#[test]
+fn test_max_lines() {
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.set_max_lines(3);
+
+    t.set_yank_text("x\ny");
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(!t.paste());
+    t.insert_newline();
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert!(t.max_lines_rejected());
+
+    // Lines removed by replacing the selection are taken into account
+    t.move_cursor(CursorMove::Jump(0, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.insert_newline();
+    assert_eq!(t.lines(), ["", "", "c"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // The rejection is reset on the next key input
+    let input = Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    };
+    assert!(t.input(input));
+    assert!(!t.max_lines_rejected());
+    assert_eq!(t.lines(), ["", "x", "c"]);
+
+    t.remove_max_lines();
+    assert_eq!(t.max_lines(), None);
+    assert!(t.insert_str("\n"));
+    assert_eq!(t.lines(), ["", "x", "", "c"]);
+}
+
+#[test]
+fn test_set_text_keeps_cursor() {
+    let mut t = TextArea::from(["foo", "bar", "baz"]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+
+    // The cursor and the selection move along with the line inserted before them
+    assert!(t.set_text("new\r\nfoo\r\nbar\r\nbaz"));
+    assert_eq!(t.lines(), ["new", "foo", "bar", "baz"]);
+    assert!(!t.ends_with_newline());
+    assert_eq!(t.cursor(), (3, 1));
+    t.copy();
+    assert_eq!(t.yank_text(), "r\nb");
+    t.cancel_selection();
+
+    // Only the changed characters are edited
+    assert!(t.set_text("new\nfoo\nbar\nxbaz"));
+    assert_eq!(t.cursor(), (3, 2));
+
+    assert!(t.set_text(""));
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["new", "foo", "bar", "xbaz"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["new", "foo", "bar", "baz"]);
+}
+
+#[test]
+fn test_textarea_set() {
+    let mut set = TextAreaSet::new();
+    assert_eq!(set.active_index(), None);
+    assert!(!set.input(Input::default()));
+    set.select_next();
+
+    for name in ["a", "b", "c"] {
+        set.push(name, TextArea::from([name]));
+    }
+    let focused =
+        |set: &TextAreaSet| -> Vec<bool> { set.iter().map(|(_, t)| t.is_focused()).collect() };
+    assert_eq!(focused(&set), [true, false, false]);
+
+    assert!(set.select_by_name("b"));
+    assert!(!set.select_by_name("x"));
+    assert!(!set.select(3));
+    assert_eq!(focused(&set), [false, true, false]);
+
+    let input = Input {
+        key: Key::Char('x'),
+        ..Default::default()
+    };
+    assert!(set.input(input));
+    assert_eq!(set.active().unwrap().lines(), ["xb"]);
+
+    // Removing the textarea before the active one keeps the active one
+    set.remove(0);
+    assert_eq!(set.active_name(), Some("b"));
+    // Removing the active one makes the next one active
+    set.remove(0);
+    assert_eq!(set.active_name(), Some("c"));
+    assert_eq!(focused(&set), [true]);
+    set.remove(0);
+    assert!(set.is_empty());
+    assert!(set.active().is_none());
+}
+
+#[test]
+fn test_default_theme() {
+    let mut t = TextArea::default();
+    t.set_placeholder_text("placeholder");
+    let theme = Theme::default();
+    assert_eq!(t.cursor_style(), theme.cursor);
+    assert_eq!(t.cursor_line_style(), theme.cursor_line);
+    assert_eq!(t.selection_style(), theme.selection);
+    assert_eq!(t.placeholder_style(), Some(theme.placeholder));
+
+    for theme in [Theme::dark(), Theme::light(), Theme::high_contrast()] {
+        t.set_theme(&theme);
+        assert_eq!(t.cursor_style(), theme.cursor);
+        assert_eq!(t.selection_style(), theme.selection);
+    }
+    t.set_theme(&Theme::default());
+    assert_eq!(t.cursor_style(), theme.cursor);
+}